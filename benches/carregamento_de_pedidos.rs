@@ -0,0 +1,114 @@
+// Compara o carregamento dos produtos de uma lista de pedidos com uma consulta por item
+// (como era feito antes) e com a consulta em lote de `carrega_pedidos`. Cada ida ao
+// repositório de produtos custa LATENCIA, simulando o round trip até o Postgres.
+//
+//   cargo bench --bench carregamento_de_pedidos
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::time::Duration;
+
+use tech_challenge::entities::ingredientes::Ingredientes;
+use tech_challenge::entities::pedido::Status;
+use tech_challenge::entities::produto::Produto;
+use tech_challenge::external::postgres::pedido::{DadosDoPedido, ProxyPedido};
+use tech_challenge::gateways::postgres_pedido_gateway::carrega_pedidos;
+use tech_challenge::traits::produto_gateway::{MockProdutoGateway, ProdutoGateway};
+
+const LATENCIA: Duration = Duration::from_micros(200);
+
+fn produto(id: usize) -> Produto {
+    Produto::new(
+        id,
+        format!("Produto {}", id),
+        "produto.png".to_string(),
+        "Descrição".to_string(),
+        1,
+        9.99,
+        Ingredientes::new(vec!["Pão".to_string()]).unwrap(),
+        "2024-01-17".to_string(),
+        "2024-01-17".to_string(),
+    )
+}
+
+fn proxies(quantidade: usize) -> Vec<ProxyPedido> {
+    (0..quantidade)
+        .map(|id| {
+            let dados = DadosDoPedido {
+                cliente: None,
+                lanche_id: Some(id % 10),
+                acompanhamento_id: Some(10 + id % 5),
+                bebida_id: Some(15 + id % 5),
+                lanche_preco: None,
+                acompanhamento_preco: None,
+                bebida_preco: None,
+                desconto: 0.0,
+                pagamento: None,
+                status: Status::Pendente,
+                loja_id: 1,
+                versao: 1,
+            };
+            ProxyPedido::new(
+                id,
+                dados,
+                "2024-01-17".to_string(),
+                "2024-01-17".to_string(),
+            )
+        })
+        .collect()
+}
+
+fn repositorio_com_latencia() -> MockProdutoGateway {
+    let mut mock = MockProdutoGateway::new();
+    mock.expect_get_produto_by_id().returning(|id| {
+        std::thread::sleep(LATENCIA);
+        Ok(produto(id))
+    });
+    mock.expect_get_produtos_by_ids().returning(|ids| {
+        std::thread::sleep(LATENCIA);
+        Ok(ids.into_iter().map(produto).collect())
+    });
+    mock
+}
+
+async fn uma_consulta_por_item(proxies: &[ProxyPedido], repositorio: &MockProdutoGateway) -> usize {
+    let mut produtos = 0;
+    for proxy in proxies {
+        for id in [proxy.lanche_id(), proxy.acompanhamento_id(), proxy.bebida_id()]
+            .into_iter()
+            .flatten()
+        {
+            if repositorio.get_produto_by_id(*id).await.is_ok() {
+                produtos += 1;
+            }
+        }
+    }
+    produtos
+}
+
+fn carregamento_de_pedidos(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let repositorio = repositorio_com_latencia();
+    let mut grupo = c.benchmark_group("carregamento_de_pedidos");
+    grupo.sample_size(10);
+
+    for quantidade in [10, 100] {
+        let pedidos = proxies(quantidade);
+        grupo.bench_with_input(
+            BenchmarkId::new("uma_consulta_por_item", quantidade),
+            &pedidos,
+            |b, pedidos| b.to_async(&runtime).iter(|| uma_consulta_por_item(pedidos, &repositorio)),
+        );
+        grupo.bench_with_input(
+            BenchmarkId::new("consulta_em_lote", quantidade),
+            &pedidos,
+            |b, pedidos| {
+                b.to_async(&runtime)
+                    .iter(|| carrega_pedidos(pedidos.clone(), &repositorio))
+            },
+        );
+    }
+    grupo.finish();
+}
+
+criterion_group!(benches, carregamento_de_pedidos);
+criterion_main!(benches);
@@ -0,0 +1,5 @@
+-- Desfaz 0001_create_table.sql
+DROP TABLE IF EXISTS pedido;
+DROP TYPE IF EXISTS STATUS_PEDIDO_ENUM;
+DROP TABLE IF EXISTS produto;
+DROP TYPE IF EXISTS CATEGORIA_ENUM;
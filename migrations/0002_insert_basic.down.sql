@@ -0,0 +1,22 @@
+-- Desfaz 0002_insert_basic.sql: remove os produtos de exemplo e os pedidos que os usam
+DELETE FROM pedido
+USING produto
+WHERE produto.id IN (pedido.lanche_id, pedido.acompanhamento_id, pedido.bebida_id)
+    AND produto.nome IN (
+        'Hamburguer',
+        'Cheeseburguer',
+        'Hamburguer de Frango',
+        'Refrigerante',
+        'Batata Frita',
+        'Sorvete'
+    );
+
+DELETE FROM produto
+WHERE nome IN (
+        'Hamburguer',
+        'Cheeseburguer',
+        'Hamburguer de Frango',
+        'Refrigerante',
+        'Batata Frita',
+        'Sorvete'
+    );
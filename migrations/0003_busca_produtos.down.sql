@@ -0,0 +1,3 @@
+-- Desfaz 0003_busca_produtos.sql
+DROP EXTENSION IF EXISTS pg_trgm;
+DROP EXTENSION IF EXISTS unaccent;
@@ -0,0 +1,4 @@
+-- Extensoes usadas pela busca de produtos (GET /produtos/busca)
+-- unaccent: ignora acentos | pg_trgm: tolera erros de digitacao
+CREATE EXTENSION IF NOT EXISTS unaccent;
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
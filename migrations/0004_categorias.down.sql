@@ -0,0 +1,21 @@
+-- Desfaz 0004_categorias.sql: volta produto.categoria_id para o CATEGORIA_ENUM
+-- Falha se algum produto estiver numa categoria que nao existia no enum
+CREATE TYPE CATEGORIA_ENUM AS ENUM (
+    'Lanche',
+    'Bebida',
+    'Acompanhamento',
+    'Sobremesa'
+);
+
+ALTER TABLE produto ADD COLUMN categoria CATEGORIA_ENUM;
+
+UPDATE produto
+SET categoria = CAST(categoria.nome AS CATEGORIA_ENUM)
+FROM categoria
+WHERE categoria.id = produto.categoria_id;
+
+ALTER TABLE produto ALTER COLUMN categoria SET NOT NULL;
+ALTER TABLE produto DROP CONSTRAINT fk_categoria;
+ALTER TABLE produto DROP COLUMN categoria_id;
+
+DROP TABLE IF EXISTS categoria;
@@ -0,0 +1,34 @@
+-- Criacao da tabela de categoria
+CREATE TABLE IF NOT EXISTS categoria (
+    id SERIAL PRIMARY KEY,
+    nome TEXT NOT NULL UNIQUE,
+    icone TEXT,
+    ordem INT NOT NULL DEFAULT 0,
+    ativa BOOLEAN NOT NULL DEFAULT TRUE,
+    item_pedido TEXT,
+    data_criacao TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    data_atualizacao TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Categorias que antes eram o CATEGORIA_ENUM
+INSERT INTO categoria (nome, ordem, item_pedido)
+VALUES
+    ('Lanche', 1, 'Lanche'),
+    ('Bebida', 2, 'Bebida'),
+    ('Acompanhamento', 3, 'Acompanhamento'),
+    ('Sobremesa', 4, NULL)
+ON CONFLICT (nome) DO NOTHING;
+
+-- Migracao de produto.categoria (enum) para produto.categoria_id
+ALTER TABLE produto ADD COLUMN IF NOT EXISTS categoria_id INT;
+
+UPDATE produto
+SET categoria_id = categoria.id
+FROM categoria
+WHERE categoria.nome = CAST(produto.categoria AS VARCHAR);
+
+ALTER TABLE produto ALTER COLUMN categoria_id SET NOT NULL;
+ALTER TABLE produto ADD CONSTRAINT fk_categoria FOREIGN KEY (categoria_id) REFERENCES categoria(id);
+ALTER TABLE produto DROP COLUMN categoria;
+
+DROP TYPE IF EXISTS CATEGORIA_ENUM;
@@ -0,0 +1,6 @@
+-- Desfaz 0005_agendas.sql
+ALTER TABLE pedido DROP COLUMN IF EXISTS bebida_preco;
+ALTER TABLE pedido DROP COLUMN IF EXISTS acompanhamento_preco;
+ALTER TABLE pedido DROP COLUMN IF EXISTS lanche_preco;
+
+DROP TABLE IF EXISTS agenda;
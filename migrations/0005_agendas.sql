@@ -0,0 +1,17 @@
+-- Criacao da tabela de agenda (janelas de disponibilidade e precos programados)
+CREATE TABLE IF NOT EXISTS agenda (
+    id SERIAL PRIMARY KEY,
+    alvo TEXT NOT NULL,
+    alvo_id INT NOT NULL,
+    dias_semana INT[] NOT NULL,
+    hora_inicio TEXT NOT NULL,
+    hora_fim TEXT NOT NULL,
+    preco FLOAT,
+    data_criacao TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    data_atualizacao TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Preco de cada item no momento do pedido
+ALTER TABLE pedido ADD COLUMN IF NOT EXISTS lanche_preco FLOAT;
+ALTER TABLE pedido ADD COLUMN IF NOT EXISTS acompanhamento_preco FLOAT;
+ALTER TABLE pedido ADD COLUMN IF NOT EXISTS bebida_preco FLOAT;
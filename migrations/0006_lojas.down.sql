@@ -0,0 +1,6 @@
+-- Desfaz 0006_lojas.sql
+ALTER TABLE pedido DROP COLUMN IF EXISTS loja_id;
+ALTER TABLE produto DROP COLUMN IF EXISTS loja_id;
+
+DROP TABLE IF EXISTS produto_loja;
+DROP TABLE IF EXISTS loja;
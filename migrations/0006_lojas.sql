@@ -0,0 +1,30 @@
+-- Criacao da tabela de loja
+CREATE TABLE IF NOT EXISTS loja (
+    id SERIAL PRIMARY KEY,
+    nome TEXT NOT NULL,
+    ativa BOOLEAN NOT NULL DEFAULT TRUE,
+    data_criacao TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    data_atualizacao TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Loja que atende os produtos e pedidos ja existentes
+INSERT INTO loja (id, nome)
+VALUES (1, 'Loja Principal')
+ON CONFLICT (id) DO NOTHING;
+
+SELECT setval('loja_id_seq', (SELECT MAX(id) FROM loja));
+
+-- Ajustes de preco e disponibilidade por loja
+CREATE TABLE IF NOT EXISTS produto_loja (
+    id SERIAL PRIMARY KEY,
+    loja_id INT NOT NULL REFERENCES loja(id),
+    produto_id INT NOT NULL REFERENCES produto(id) ON DELETE CASCADE,
+    preco FLOAT,
+    disponivel BOOLEAN NOT NULL DEFAULT TRUE,
+    UNIQUE (loja_id, produto_id)
+);
+
+-- Produto sem loja faz parte do catalogo de todas as lojas
+ALTER TABLE produto ADD COLUMN IF NOT EXISTS loja_id INT REFERENCES loja(id);
+
+ALTER TABLE pedido ADD COLUMN IF NOT EXISTS loja_id INT NOT NULL DEFAULT 1 REFERENCES loja(id);
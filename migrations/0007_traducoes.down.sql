@@ -0,0 +1,2 @@
+-- Desfaz 0007_traducoes.sql
+DROP TABLE IF EXISTS produto_traducao;
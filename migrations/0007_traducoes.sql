@@ -0,0 +1,9 @@
+-- Traducoes de nome e descricao dos produtos (pt-BR fica na propria tabela produto)
+CREATE TABLE IF NOT EXISTS produto_traducao (
+    id SERIAL PRIMARY KEY,
+    produto_id INT NOT NULL REFERENCES produto(id) ON DELETE CASCADE,
+    idioma TEXT NOT NULL,
+    nome TEXT,
+    descricao TEXT,
+    UNIQUE (produto_id, idioma)
+);
@@ -0,0 +1,3 @@
+-- Desfaz 0008_versionamento.sql
+ALTER TABLE produto DROP COLUMN IF EXISTS versao;
+ALTER TABLE pedido DROP COLUMN IF EXISTS versao;
@@ -0,0 +1,3 @@
+-- Versao dos pedidos e produtos para o controle de concorrencia otimista (ETag/If-Match)
+ALTER TABLE pedido ADD COLUMN IF NOT EXISTS versao INT NOT NULL DEFAULT 1;
+ALTER TABLE produto ADD COLUMN IF NOT EXISTS versao INT NOT NULL DEFAULT 1;
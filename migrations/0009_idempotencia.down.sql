@@ -0,0 +1,2 @@
+-- Desfaz 0009_idempotencia.sql
+DROP TABLE IF EXISTS idempotencia;
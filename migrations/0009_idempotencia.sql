@@ -0,0 +1,9 @@
+-- Chaves Idempotency-Key do POST /pedidos, com a resposta devolvida as repeticoes
+CREATE TABLE IF NOT EXISTS idempotencia (
+    chave TEXT PRIMARY KEY,
+    impressao_digital TEXT NOT NULL,
+    pedido JSONB,
+    criada_em TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idempotencia_criada_em_idx ON idempotencia (criada_em);
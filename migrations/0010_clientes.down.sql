@@ -0,0 +1,2 @@
+-- Desfaz 0010_clientes.sql
+DROP TABLE IF EXISTS cliente;
@@ -0,0 +1,13 @@
+-- Cadastro de clientes, identificados pelo CPF (somente digitos)
+CREATE TABLE IF NOT EXISTS cliente (
+    cpf TEXT PRIMARY KEY,
+    nome TEXT NOT NULL,
+    email TEXT,
+    aceita_marketing BOOLEAN NOT NULL DEFAULT FALSE,
+    aceita_compartilhamento BOOLEAN NOT NULL DEFAULT FALSE,
+    data_criacao TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    data_atualizacao TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Pedido do cliente anonimo fica sem cliente, e nao com CPF vazio
+UPDATE pedido SET cliente = NULL WHERE cliente = '';
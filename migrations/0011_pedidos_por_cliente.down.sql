@@ -0,0 +1,2 @@
+-- Desfaz 0011_pedidos_por_cliente.sql
+DROP INDEX IF EXISTS pedido_cliente_idx;
@@ -0,0 +1,2 @@
+-- Historico de pedidos do cliente, do mais recente ao mais antigo
+CREATE INDEX IF NOT EXISTS pedido_cliente_idx ON pedido (cliente, data_criacao DESC, id DESC);
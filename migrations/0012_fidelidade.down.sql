@@ -0,0 +1,3 @@
+-- Desfaz 0012_fidelidade.sql
+DROP TABLE IF EXISTS movimento_de_pontos;
+ALTER TABLE pedido DROP COLUMN IF EXISTS desconto;
@@ -0,0 +1,16 @@
+-- Programa de fidelidade: extrato de pontos dos clientes e desconto do resgate no pedido
+ALTER TABLE pedido ADD COLUMN IF NOT EXISTS desconto FLOAT NOT NULL DEFAULT 0;
+
+CREATE TABLE IF NOT EXISTS movimento_de_pontos (
+    id SERIAL PRIMARY KEY,
+    cliente TEXT NOT NULL REFERENCES cliente (cpf),
+    pedido_id INT NOT NULL REFERENCES pedido (id),
+    tipo TEXT NOT NULL,
+    pontos BIGINT NOT NULL,
+    validade TIMESTAMP,
+    data_criacao TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    -- Cada pedido acumula, resgata e estorna uma unica vez
+    UNIQUE (pedido_id, tipo)
+);
+
+CREATE INDEX IF NOT EXISTS movimento_de_pontos_cliente_idx ON movimento_de_pontos (cliente, data_criacao);
@@ -0,0 +1,3 @@
+-- Desfaz 0013_lgpd.sql
+DROP TABLE IF EXISTS auditoria;
+ALTER TABLE pedido DROP COLUMN IF EXISTS cliente_pseudonimo;
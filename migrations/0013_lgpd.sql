@@ -0,0 +1,13 @@
+-- LGPD: pedidos anonimizados guardam o pseudonimo do CPF em vez do CPF
+ALTER TABLE pedido ADD COLUMN IF NOT EXISTS cliente_pseudonimo TEXT;
+
+-- Registro das exportacoes e anonimizacoes de dados pessoais
+CREATE TABLE IF NOT EXISTS auditoria (
+    id SERIAL PRIMARY KEY,
+    acao TEXT NOT NULL,
+    titular TEXT NOT NULL,
+    request_id TEXT NOT NULL,
+    data_criacao TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS auditoria_titular_idx ON auditoria (titular, data_criacao);
@@ -0,0 +1,22 @@
+-- Desfaz 0014_cpf_cifrado.sql. Os CPFs precisam ter sido decifrados antes (api migrate down
+-- faz isso); do contrario o texto cifrado ficaria no lugar do CPF.
+DO $$
+BEGIN
+    IF EXISTS (SELECT 1 FROM cliente WHERE cpf_hash IS NOT NULL)
+        OR EXISTS (SELECT 1 FROM pedido WHERE cliente_hash IS NOT NULL)
+        OR EXISTS (SELECT 1 FROM movimento_de_pontos WHERE cliente_hash IS NOT NULL) THEN
+        RAISE EXCEPTION 'Ainda ha CPFs cifrados';
+    END IF;
+END $$;
+
+DROP INDEX IF EXISTS movimento_de_pontos_cliente_hash_idx;
+ALTER TABLE movimento_de_pontos DROP COLUMN IF EXISTS cliente_hash;
+ALTER TABLE movimento_de_pontos ADD CONSTRAINT movimento_de_pontos_cliente_fkey FOREIGN KEY (cliente) REFERENCES cliente (cpf);
+CREATE INDEX IF NOT EXISTS movimento_de_pontos_cliente_idx ON movimento_de_pontos (cliente, data_criacao);
+
+DROP INDEX IF EXISTS cliente_cpf_hash_idx;
+ALTER TABLE cliente DROP COLUMN IF EXISTS cpf_hash;
+
+DROP INDEX IF EXISTS pedido_cliente_hash_idx;
+ALTER TABLE pedido DROP COLUMN IF EXISTS cliente_hash;
+CREATE INDEX IF NOT EXISTS pedido_cliente_idx ON pedido (cliente, data_criacao DESC, id DESC);
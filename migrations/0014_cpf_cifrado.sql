@@ -0,0 +1,15 @@
+-- CPFs cifrados: as colunas de CPF passam a guardar o texto cifrado e as colunas *_hash
+-- (HMAC do CPF) servem para as buscas por igualdade. A chave fica na configuracao, entao
+-- quem cifra as linhas existentes e a aplicacao, logo depois das migracoes.
+ALTER TABLE pedido ADD COLUMN IF NOT EXISTS cliente_hash TEXT;
+DROP INDEX IF EXISTS pedido_cliente_idx;
+CREATE INDEX IF NOT EXISTS pedido_cliente_hash_idx ON pedido (cliente_hash, data_criacao DESC, id DESC);
+
+ALTER TABLE cliente ADD COLUMN IF NOT EXISTS cpf_hash TEXT;
+CREATE UNIQUE INDEX IF NOT EXISTS cliente_cpf_hash_idx ON cliente (cpf_hash);
+
+-- O mesmo CPF cifrado duas vezes da textos diferentes: a referencia ao cliente vai pelo hash
+ALTER TABLE movimento_de_pontos DROP CONSTRAINT IF EXISTS movimento_de_pontos_cliente_fkey;
+ALTER TABLE movimento_de_pontos ADD COLUMN IF NOT EXISTS cliente_hash TEXT REFERENCES cliente (cpf_hash);
+DROP INDEX IF EXISTS movimento_de_pontos_cliente_idx;
+CREATE INDEX IF NOT EXISTS movimento_de_pontos_cliente_hash_idx ON movimento_de_pontos (cliente_hash, data_criacao);
@@ -0,0 +1,4 @@
+-- Desfaz 0015_documento_fiscal.sql
+DROP INDEX IF EXISTS pedido_documento_fiscal_hash_idx;
+ALTER TABLE pedido DROP COLUMN IF EXISTS documento_fiscal_hash;
+ALTER TABLE pedido DROP COLUMN IF EXISTS documento_fiscal;
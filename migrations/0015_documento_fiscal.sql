@@ -0,0 +1,5 @@
+-- CPF ou CNPJ da nota fiscal do pedido, cifrado como os CPFs. O hash serve para achar, na
+-- anonimizacao, os pedidos com o CPF do titular na nota.
+ALTER TABLE pedido ADD COLUMN IF NOT EXISTS documento_fiscal TEXT;
+ALTER TABLE pedido ADD COLUMN IF NOT EXISTS documento_fiscal_hash TEXT;
+CREATE INDEX IF NOT EXISTS pedido_documento_fiscal_hash_idx ON pedido (documento_fiscal_hash);
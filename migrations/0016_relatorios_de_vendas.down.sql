@@ -0,0 +1,2 @@
+-- Desfaz 0016_relatorios_de_vendas.sql
+DROP INDEX IF EXISTS pedido_loja_data_criacao_idx;
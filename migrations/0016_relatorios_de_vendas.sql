@@ -0,0 +1,2 @@
+-- Relatorios de vendas: pedidos de uma loja num intervalo de datas
+CREATE INDEX IF NOT EXISTS pedido_loja_data_criacao_idx ON pedido (loja_id, data_criacao);
@@ -0,0 +1,2 @@
+-- Desfaz 0017_transicoes_de_status.sql
+DROP TABLE IF EXISTS transicao_de_status;
@@ -0,0 +1,13 @@
+-- Desempenho da cozinha: momento em que cada pedido entrou em cada status
+CREATE TABLE IF NOT EXISTS transicao_de_status (
+    id SERIAL PRIMARY KEY,
+    pedido_id INT NOT NULL REFERENCES pedido (id),
+    status TEXT NOT NULL,
+    data_criacao TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS transicao_de_status_pedido_idx ON transicao_de_status (pedido_id, data_criacao);
+
+-- Dos pedidos anteriores so se sabe o status atual e quando ele foi gravado
+INSERT INTO transicao_de_status (pedido_id, status, data_criacao)
+SELECT id, CAST(status AS VARCHAR), data_atualizacao FROM pedido;
@@ -1,2 +1,6 @@
 pub mod api_key_validator;
-pub mod user_group_validator;
\ No newline at end of file
+pub mod user_group_validator;
+pub mod local_foto_storage;
+pub mod jwt_token_validator;
+pub mod postgres_dependencia;
+pub mod rabbitmq_dependencia;
@@ -0,0 +1,115 @@
+use jsonwebtoken::{decode, DecodingKey, Validation};
+use serde::Deserialize;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::cpf::Cpf;
+use crate::traits::token_validator_adapter::TokenValidatorAdapter;
+
+#[derive(Debug, Deserialize)]
+struct Claims {
+    loja_id: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClaimsDoCliente {
+    cpf: String,
+}
+
+pub struct JwtTokenValidator {
+    chave: DecodingKey,
+}
+
+impl JwtTokenValidator {
+    pub fn new(segredo: String) -> Self {
+        Self {
+            chave: DecodingKey::from_secret(segredo.as_bytes()),
+        }
+    }
+}
+
+impl TokenValidatorAdapter for JwtTokenValidator {
+    fn loja_id(&self, token: String) -> Result<Option<usize>, DomainError> {
+        match decode::<Claims>(&token, &self.chave, &Validation::default()) {
+            Ok(dados) => Ok(dados.claims.loja_id),
+            Err(_) => Err(DomainError::Unauthorized),
+        }
+    }
+
+    fn cpf(&self, token: String) -> Result<Cpf, DomainError> {
+        match decode::<ClaimsDoCliente>(&token, &self.chave, &Validation::default()) {
+            Ok(dados) => Cpf::new(dados.claims.cpf).map_err(|_| DomainError::Unauthorized),
+            Err(_) => Err(DomainError::Unauthorized),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct ClaimsDeTeste {
+        loja_id: usize,
+        exp: usize,
+    }
+
+    fn gera_token(segredo: &str, exp: usize) -> String {
+        encode(
+            &Header::default(),
+            &ClaimsDeTeste { loja_id: 2, exp },
+            &EncodingKey::from_secret(segredo.as_bytes()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_loja_id() {
+        let validator = JwtTokenValidator::new("segredo".to_string());
+
+        assert_eq!(validator.loja_id(gera_token("segredo", 4_102_444_800)).unwrap(), Some(2));
+        assert_eq!(validator.loja_id(gera_token_do_cliente("123.456.789-09")).unwrap(), None);
+        assert!(matches!(
+            validator.loja_id(gera_token("outro", 4_102_444_800)),
+            Err(DomainError::Unauthorized)
+        ));
+        assert!(matches!(
+            validator.loja_id(gera_token("segredo", 1)),
+            Err(DomainError::Unauthorized)
+        ));
+        assert!(validator.loja_id("invalido".to_string()).is_err());
+    }
+
+    #[derive(Serialize)]
+    struct ClaimsDoClienteDeTeste {
+        cpf: String,
+        exp: usize,
+    }
+
+    fn gera_token_do_cliente(cpf: &str) -> String {
+        encode(
+            &Header::default(),
+            &ClaimsDoClienteDeTeste { cpf: cpf.to_string(), exp: 4_102_444_800 },
+            &EncodingKey::from_secret("segredo".as_bytes()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_cpf() {
+        let validator = JwtTokenValidator::new("segredo".to_string());
+
+        let cpf = validator.cpf(gera_token_do_cliente("123.456.789-09")).unwrap();
+        assert_eq!(cpf.get_string(), "12345678909");
+        assert!(matches!(
+            validator.cpf(gera_token_do_cliente("000.000.000-00")),
+            Err(DomainError::Unauthorized)
+        ));
+        assert!(matches!(
+            validator.cpf(gera_token("segredo", 4_102_444_800)),
+            Err(DomainError::Unauthorized)
+        ));
+    }
+}
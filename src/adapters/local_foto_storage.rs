@@ -0,0 +1,148 @@
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use crate::base::domain_error::DomainError;
+use crate::traits::foto_storage_adapter::FotoStorageAdapter;
+
+pub const DIRETORIO_THUMBNAILS: &str = "thumbs";
+const TAMANHO_THUMBNAIL: u32 = 320;
+
+pub struct LocalFotoStorage {
+    diretorio: PathBuf,
+    url_base: String,
+}
+
+impl LocalFotoStorage {
+    pub fn new(diretorio: String, url_base: String) -> Self {
+        Self {
+            diretorio: PathBuf::from(diretorio),
+            url_base: url_base.trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn gera_thumbnail(conteudo: &[u8]) -> Result<Vec<u8>, DomainError> {
+        let formato = image::guess_format(conteudo)
+            .map_err(|_| DomainError::Invalid("Foto".to_string()))?;
+        let imagem = image::load_from_memory_with_format(conteudo, formato)
+            .map_err(|_| DomainError::Invalid("Foto".to_string()))?;
+        let mut thumbnail = Cursor::new(Vec::new());
+        imagem
+            .thumbnail(TAMANHO_THUMBNAIL, TAMANHO_THUMBNAIL)
+            .write_to(&mut thumbnail, formato)
+            .map_err(|e| DomainError::Invalid(format!("Thumbnail: {}", e)))?;
+        Ok(thumbnail.into_inner())
+    }
+}
+
+#[async_trait]
+impl FotoStorageAdapter for LocalFotoStorage {
+    async fn salva_foto(&self, nome_arquivo: String, conteudo: Vec<u8>) -> Result<String, DomainError> {
+        let thumbnail = LocalFotoStorage::gera_thumbnail(&conteudo)?;
+        let diretorio_thumbnails = self.diretorio.join(DIRETORIO_THUMBNAILS);
+
+        tokio::fs::create_dir_all(&diretorio_thumbnails)
+            .await
+            .map_err(|e| DomainError::Invalid(e.to_string()))?;
+        tokio::fs::write(self.diretorio.join(&nome_arquivo), conteudo)
+            .await
+            .map_err(|e| DomainError::Invalid(e.to_string()))?;
+        tokio::fs::write(diretorio_thumbnails.join(&nome_arquivo), thumbnail)
+            .await
+            .map_err(|e| DomainError::Invalid(e.to_string()))?;
+
+        Ok(format!("{}/{}", self.url_base, nome_arquivo))
+    }
+
+    async fn remove_foto(&self, url: &str) -> Result<(), DomainError> {
+        let Some(nome_arquivo) = url
+            .strip_prefix(&self.url_base)
+            .and_then(|resto| resto.strip_prefix('/'))
+        else {
+            return Ok(());
+        };
+        // Só arquivos do próprio diretório, nunca um caminho vindo da URL.
+        if nome_arquivo.is_empty() || nome_arquivo.contains('/') || nome_arquivo.contains("..") {
+            return Ok(());
+        }
+        for arquivo in [
+            self.diretorio.join(nome_arquivo),
+            self.diretorio.join(DIRETORIO_THUMBNAILS).join(nome_arquivo),
+        ] {
+            match tokio::fs::remove_file(&arquivo).await {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                    return Err(DomainError::Invalid(e.to_string()))
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, RgbImage};
+
+    fn create_png(largura: u32, altura: u32) -> Vec<u8> {
+        let mut png = Cursor::new(Vec::new());
+        RgbImage::new(largura, altura)
+            .write_to(&mut png, ImageFormat::Png)
+            .unwrap();
+        png.into_inner()
+    }
+
+    #[tokio::test]
+    async fn test_salva_foto_e_thumbnail() {
+        let diretorio = std::env::temp_dir().join("tech_challenge_fotos_test");
+        let storage = LocalFotoStorage::new(
+            diretorio.to_string_lossy().to_string(),
+            "/fotos/".to_string(),
+        );
+
+        let url = storage
+            .salva_foto("produto-1.png".to_string(), create_png(800, 400))
+            .await
+            .unwrap();
+
+        assert_eq!(url, "/fotos/produto-1.png");
+        assert!(diretorio.join("produto-1.png").exists());
+
+        let thumbnail = std::fs::read(diretorio.join(DIRETORIO_THUMBNAILS).join("produto-1.png")).unwrap();
+        let thumbnail = image::load_from_memory(&thumbnail).unwrap();
+        assert_eq!(thumbnail.width(), TAMANHO_THUMBNAIL);
+        assert_eq!(thumbnail.height(), TAMANHO_THUMBNAIL / 2);
+    }
+
+    #[tokio::test]
+    async fn test_salva_foto_invalida() {
+        let diretorio = std::env::temp_dir().join("tech_challenge_fotos_test");
+        let storage = LocalFotoStorage::new(diretorio.to_string_lossy().to_string(), "/fotos".to_string());
+
+        let result = storage
+            .salva_foto("produto-2.png".to_string(), b"nao e uma imagem".to_vec())
+            .await;
+
+        assert!(matches!(result, Err(DomainError::Invalid(_))));
+        assert!(!diretorio.join("produto-2.png").exists());
+    }
+
+    #[tokio::test]
+    async fn test_remove_foto_e_thumbnail() {
+        let diretorio = std::env::temp_dir().join("tech_challenge_fotos_remove_test");
+        let storage = LocalFotoStorage::new(diretorio.to_string_lossy().to_string(), "/fotos".to_string());
+        let url = storage
+            .salva_foto("produto-3.png".to_string(), create_png(10, 10))
+            .await
+            .unwrap();
+
+        storage.remove_foto(&url).await.unwrap();
+        assert!(!diretorio.join("produto-3.png").exists());
+        assert!(!diretorio.join(DIRETORIO_THUMBNAILS).join("produto-3.png").exists());
+
+        // Já apagada, ou de fora deste armazenamento: nada a fazer.
+        storage.remove_foto(&url).await.unwrap();
+        storage.remove_foto("cheeseburger.png").await.unwrap();
+        storage.remove_foto("/fotos/../segredo").await.unwrap();
+    }
+}
@@ -0,0 +1,58 @@
+use deadpool_postgres::Pool;
+use std::time::Duration;
+use tokio::time::timeout;
+
+use crate::traits::dependencia_adapter::DependenciaAdapter;
+
+const TEMPO_LIMITE: Duration = Duration::from_secs(2);
+
+pub struct PostgresDependencia {
+    pool: Result<Pool, String>,
+}
+
+impl PostgresDependencia {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool: Ok(pool) }
+    }
+
+    // Banco que não conectou na inicialização: a prontidão reporta o erro da conexão.
+    pub fn indisponivel(erro: String) -> Self {
+        Self { pool: Err(erro) }
+    }
+}
+
+#[async_trait]
+impl DependenciaAdapter for PostgresDependencia {
+    fn nome(&self) -> String {
+        "postgres".to_string()
+    }
+
+    async fn verifica(&self) -> Result<(), String> {
+        let pool = self.pool.as_ref().map_err(|erro| erro.clone())?;
+        let verificacao = async {
+            let client = pool.get().await.map_err(|erro| erro.to_string())?;
+            client
+                .query_one("SELECT 1", &[])
+                .await
+                .map_err(|erro| erro.to_string())
+        };
+        match timeout(TEMPO_LIMITE, verificacao).await {
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err(erro)) => Err(erro),
+            Err(_) => Err("Tempo limite excedido".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_indisponivel() {
+        let dependencia = PostgresDependencia::indisponivel("connection refused".to_string());
+
+        assert_eq!(dependencia.nome(), "postgres");
+        assert_eq!(dependencia.verifica().await, Err("connection refused".to_string()));
+    }
+}
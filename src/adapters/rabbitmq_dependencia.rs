@@ -0,0 +1,43 @@
+use lapin::Channel;
+use std::sync::{Arc, RwLock};
+
+use crate::traits::dependencia_adapter::DependenciaAdapter;
+
+// Acompanha o canal aberto pelo consumidor da fila; vazio até a primeira conexão.
+pub struct RabbitMQDependencia {
+    canal: Arc<RwLock<Option<Channel>>>,
+}
+
+impl RabbitMQDependencia {
+    pub fn new(canal: Arc<RwLock<Option<Channel>>>) -> Self {
+        Self { canal }
+    }
+}
+
+#[async_trait]
+impl DependenciaAdapter for RabbitMQDependencia {
+    fn nome(&self) -> String {
+        "rabbitmq".to_string()
+    }
+
+    async fn verifica(&self) -> Result<(), String> {
+        match self.canal.read().unwrap().as_ref() {
+            Some(canal) if canal.status().connected() => Ok(()),
+            Some(canal) => Err(format!("Canal {:?}", canal.status().state())),
+            None => Err("Canal não conectado".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sem_canal() {
+        let dependencia = RabbitMQDependencia::new(Arc::new(RwLock::new(None)));
+
+        assert_eq!(dependencia.nome(), "rabbitmq");
+        assert_eq!(dependencia.verifica().await, Err("Canal não conectado".to_string()));
+    }
+}
@@ -1,5 +1,10 @@
 mod routes;
+mod cpf_mascarado;
 mod error_handling;
+mod etag;
+mod mensagens;
+mod metricas;
+pub mod request_id;
 pub mod config;
 mod route_guards;
 pub mod server;
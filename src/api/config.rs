@@ -1,6 +1,12 @@
-use std::{env, str::FromStr};
+use std::{env, str::FromStr, time::Duration};
+use chrono_tz::Tz;
 use dotenv::dotenv;
 
+use crate::base::cifrador_de_cpf::CifradorDeCpf;
+use crate::base::pseudonimo::Pseudonimizador;
+use crate::entities::pontos::RegrasDeFidelidade;
+use crate::use_cases::relatorios_de_vendas_use_case::FUSO_PADRAO;
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum Env {
     Dev,
@@ -38,9 +44,48 @@ pub struct Config {
     pub api_key: String,
     pub rabbitmq_addr: String,
     pub queue_name: String,
+    pub fotos_dir: String,
+    pub fotos_url: String,
+    pub jwt_secret: String,
+    pub metricas_porta: u16,
+    pub db_pool_tamanho: usize,
+    pub db_tempo_limite: Duration,
+    pub idempotencia_janela: Duration,
+    pub fidelidade_pontos_por_real: f64,
+    pub fidelidade_valor_do_ponto: f64,
+    pub fidelidade_validade_dias: i64,
+    pub pseudonimo_chave: String,
+    pub cpf_chave: String,
+    pub fuso_horario: Tz,
+}
+
+// Segredos não têm valor padrão fora dos testes: subir assinando com uma chave conhecida é
+// pior do que não subir.
+fn segredo(nome: &str, padrao_de_teste: &str) -> String {
+    match env::var(nome) {
+        Ok(valor) if !valor.is_empty() => valor,
+        _ if cfg!(test) => padrao_de_teste.to_string(),
+        _ => panic!("a variável de ambiente {} é obrigatória", nome),
+    }
 }
 
 impl Config {
+    pub fn regras_de_fidelidade(&self) -> RegrasDeFidelidade {
+        RegrasDeFidelidade::new(
+            self.fidelidade_pontos_por_real,
+            self.fidelidade_valor_do_ponto,
+            self.fidelidade_validade_dias,
+        )
+    }
+
+    pub fn pseudonimizador(&self) -> Pseudonimizador {
+        Pseudonimizador::new(self.pseudonimo_chave.clone())
+    }
+
+    pub fn cifrador_de_cpf(&self) -> CifradorDeCpf {
+        CifradorDeCpf::new(&self.cpf_chave)
+    }
+
     pub fn build() -> Config {
         dotenv().ok();
         let env = env::var("ENV").unwrap_or("test".to_string());
@@ -51,12 +96,76 @@ impl Config {
 
         let queue_name = env::var("QUEUE_NAME").unwrap_or("status-pagamentos".to_string());
 
+        let fotos_dir = env::var("FOTOS_DIR").unwrap_or("fotos".to_string());
+        let fotos_url = env::var("FOTOS_URL").unwrap_or("/fotos".to_string());
+
+        let jwt_secret = segredo("JWT_SECRET", "jwt_secret");
+
+        let metricas_porta = env::var("METRICAS_PORTA")
+            .ok()
+            .and_then(|porta| porta.parse().ok())
+            .unwrap_or(3000);
+
+        let db_pool_tamanho = env::var("DB_POOL_TAMANHO")
+            .ok()
+            .and_then(|tamanho| tamanho.parse().ok())
+            .unwrap_or(16);
+        let db_tempo_limite = env::var("DB_TEMPO_LIMITE_SEGUNDOS")
+            .ok()
+            .and_then(|segundos| segundos.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(Duration::from_secs(5));
+        // Por quanto tempo uma Idempotency-Key do POST /pedidos é lembrada.
+        let idempotencia_janela = env::var("IDEMPOTENCIA_JANELA_SEGUNDOS")
+            .ok()
+            .and_then(|segundos| segundos.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(Duration::from_secs(24 * 60 * 60));
+        // Programa de fidelidade: pontos por real gasto, desconto por ponto resgatado (R$)
+        // e dias até os pontos vencerem.
+        let fidelidade_pontos_por_real = env::var("FIDELIDADE_PONTOS_POR_REAL")
+            .ok()
+            .and_then(|pontos| pontos.parse().ok())
+            .unwrap_or(1.0);
+        let fidelidade_valor_do_ponto = env::var("FIDELIDADE_VALOR_DO_PONTO")
+            .ok()
+            .and_then(|valor| valor.parse().ok())
+            .unwrap_or(0.05);
+        let fidelidade_validade_dias = env::var("FIDELIDADE_VALIDADE_DIAS")
+            .ok()
+            .and_then(|dias| dias.parse().ok())
+            .unwrap_or(365);
+        // Chave do HMAC que gera o pseudônimo do CPF nos pedidos anonimizados (LGPD).
+        let pseudonimo_chave = segredo("PSEUDONIMO_CHAVE", "pseudonimo_chave");
+        // Chave de onde saem a cifra e o hash dos CPFs gravados no banco; a api e o listener
+        // cifram com ela, ao subir, os CPFs ainda em claro. Trocá-la torna os CPFs já gravados
+        // ilegíveis.
+        let cpf_chave = segredo("CPF_CHAVE", "cpf_chave");
+        // Fuso horário da loja (nome IANA), em que valem as agendas e as datas dos relatórios.
+        let fuso_horario = env::var("FUSO_HORARIO")
+            .ok()
+            .and_then(|fuso| fuso.parse().ok())
+            .unwrap_or(FUSO_PADRAO);
+
         Config {
             env,
             db_url,
             api_key,
             rabbitmq_addr,
-            queue_name
+            queue_name,
+            fotos_dir,
+            fotos_url,
+            jwt_secret,
+            metricas_porta,
+            db_pool_tamanho,
+            db_tempo_limite,
+            idempotencia_janela,
+            fidelidade_pontos_por_real,
+            fidelidade_valor_do_ponto,
+            fidelidade_validade_dias,
+            pseudonimo_chave,
+            cpf_chave,
+            fuso_horario,
         }
     }
 }
@@ -0,0 +1,142 @@
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::ContentType;
+use rocket::{Request, Response};
+use serde_json::Value;
+use std::io::Cursor;
+use std::sync::Arc;
+
+use crate::entities::cpf::Cpf;
+use crate::traits::user_group_validator_adapter::{UserGroup, UserGroupValidatorAdapter};
+
+// Campos das respostas que podem trazer um CPF (Cliente, Pedido, MovimentoDePontos e
+// DadosDoTitular). No documento fiscal, só o CPF é mascarado; o CNPJ é público.
+const CAMPOS_COM_CPF: [&str; 3] = ["cpf", "cliente", "documento_fiscal"];
+
+fn administrador(req: &Request<'_>) -> bool {
+    let validator = req.rocket().state::<Arc<dyn UserGroupValidatorAdapter + Sync + Send>>();
+    match (req.headers().get_one("UserGroup"), validator) {
+        (Some(user_group), Some(validator)) => {
+            validator.validate_user_group(user_group.to_string(), UserGroup::Admin)
+        }
+        _ => false,
+    }
+}
+
+fn e_cpf(valor: &str) -> bool {
+    valor.len() == 11 && valor.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+fn mascara(valor: &mut Value) {
+    match valor {
+        Value::Object(campos) => {
+            for (campo, valor) in campos.iter_mut() {
+                match valor {
+                    Value::String(texto) if CAMPOS_COM_CPF.contains(&campo.as_str()) && e_cpf(texto) => {
+                        *texto = Cpf(texto.clone()).mascarado();
+                    }
+                    _ => mascara(valor),
+                }
+            }
+        }
+        Value::Array(itens) => itens.iter_mut().for_each(mascara),
+        _ => (),
+    }
+}
+
+// Os CPFs saem completos só para o administrador; para os demais perfis, os das respostas
+// JSON saem mascarados (***.456.789-**).
+pub struct MascaraDeCpfs;
+
+#[rocket::async_trait]
+impl Fairing for MascaraDeCpfs {
+    fn info(&self) -> Info {
+        Info {
+            name: "Máscara de CPFs",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        if res.content_type() != Some(ContentType::JSON) || administrador(req) {
+            return;
+        }
+        let corpo = match res.body_mut().to_bytes().await {
+            Ok(corpo) => corpo,
+            Err(erro) => {
+                tracing::error!(erro = %erro, "Erro ao ler o corpo da resposta");
+                return;
+            }
+        };
+        let corpo = match serde_json::from_slice::<Value>(&corpo) {
+            Ok(mut valor) => {
+                mascara(&mut valor);
+                serde_json::to_vec(&valor).unwrap_or(corpo)
+            }
+            Err(_) => corpo,
+        };
+        res.set_sized_body(corpo.len(), Cursor::new(corpo));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::user_group_validator::UserGroupValidator;
+    use rocket::http::Header;
+    use rocket::local::blocking::Client;
+    use rocket::serde::json::{json, Json};
+
+    #[get("/")]
+    fn route() -> Json<Value> {
+        Json(json!({
+            "cpf": "12345678909",
+            "cliente": {"cpf": "12345678909", "nome": "Maria"},
+            "pedidos": [{"cliente": "12345678909", "documento_fiscal": "11222333000181"}],
+            "pagamento": "12345678909",
+        }))
+    }
+
+    fn client() -> Client {
+        let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> =
+            Arc::new(UserGroupValidator::new());
+        let rocket = rocket::build()
+            .mount("/", routes![route])
+            .attach(MascaraDeCpfs)
+            .manage(user_group_validator);
+        Client::tracked(rocket).expect("valid rocket instance")
+    }
+
+    #[test]
+    fn test_mascara_os_cpfs() {
+        let client = client();
+        let corpo: Value = client.get("/").dispatch().into_json().unwrap();
+
+        assert_eq!(corpo["cpf"], "***.456.789-**");
+        assert_eq!(corpo["cliente"]["cpf"], "***.456.789-**");
+        assert_eq!(corpo["cliente"]["nome"], "Maria");
+        assert_eq!(corpo["pedidos"][0]["cliente"], "***.456.789-**");
+        assert_eq!(corpo["pedidos"][0]["documento_fiscal"], "11222333000181");
+        assert_eq!(corpo["pagamento"], "12345678909");
+
+        let corpo: Value = client
+            .get("/")
+            .header(Header::new("UserGroup", "Kitchen"))
+            .dispatch()
+            .into_json()
+            .unwrap();
+        assert_eq!(corpo["cpf"], "***.456.789-**");
+    }
+
+    #[test]
+    fn test_administrador_ve_o_cpf_completo() {
+        let corpo: Value = client()
+            .get("/")
+            .header(Header::new("UserGroup", "Admin"))
+            .dispatch()
+            .into_json()
+            .unwrap();
+
+        assert_eq!(corpo["cpf"], "12345678909");
+        assert_eq!(corpo["pedidos"][0]["cliente"], "12345678909");
+    }
+}
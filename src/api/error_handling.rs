@@ -1,74 +1,259 @@
+use crate::api::mensagens::Mensagem;
+use crate::api::request_id::{request_id, HEADER_REQUEST_ID};
+use crate::api::route_guards::idioma_route_guard::idioma_da_requisicao;
+use crate::api::route_guards::loja_route_guard::loja_inexistente;
 use crate::base::domain_error::DomainError;
-use rocket::http::Status;
-use rocket::serde::json::Json;
+use rocket::http::{ContentType, Header, Status};
+use rocket::response::{self, Responder, Response};
+use rocket::Request;
+use rocket_okapi::gen::OpenApiGenerator;
+use rocket_okapi::okapi::openapi3::Responses;
+use rocket_okapi::response::OpenApiResponderInner;
+use rocket_okapi::util::add_schema_response;
 use schemars::JsonSchema;
 use serde::Serialize;
+use std::io::Cursor;
+
+const PROBLEM_JSON: &str = "application/problem+json";
 
 impl From<DomainError> for Status {
     fn from(error: DomainError) -> Self {
         match error {
             DomainError::AlreadyExists => Status::Conflict,
+            DomainError::Conflict => Status::Conflict,
             DomainError::NotFound => Status::NotFound,
             DomainError::Empty => Status::BadRequest,
             DomainError::Unauthorized => Status::Unauthorized,
+            DomainError::Forbidden => Status::Forbidden,
             DomainError::Invalid(_) => Status::BadRequest,
+            DomainError::InvalidField { .. } => Status::BadRequest,
+            DomainError::NonPositive => Status::BadRequest,
             _ => Status::InternalServerError,
         }
     }
 }
 
+// Corpo application/problem+json (RFC 7807) com as extensões code, field e request_id.
 #[derive(Debug, Serialize, JsonSchema)]
 pub struct ErrorResponse {
-    pub msg: String,
-    pub status: usize,
+    #[serde(rename = "type")]
+    pub tipo: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    pub code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    pub request_id: String,
 }
 
-#[catch(400)]
-fn bad_request() -> Json<ErrorResponse> {
-    let error = ErrorResponse {
-        msg: "Input inválido".to_string(),
-        status: 400,
+// Erro devolvido pelas rotas; o título é traduzido para o idioma da requisição ao responder.
+#[derive(Debug)]
+pub struct Problema {
+    status: Status,
+    code: &'static str,
+    mensagem: Mensagem,
+    detail: Option<String>,
+    field: Option<String>,
+    // Sem detalhe próprio: o catcher da rota monta a resposta (ex.: "Produto não encontrado!").
+    usa_catcher: bool,
+}
+
+impl Problema {
+    pub fn new(status: Status, code: &'static str, mensagem: Mensagem) -> Self {
+        Problema {
+            status,
+            code,
+            mensagem,
+            detail: None,
+            field: None,
+            usa_catcher: false,
+        }
+    }
+
+    fn com_detalhe(mut self, detail: String) -> Self {
+        self.detail = Some(detail);
+        self
+    }
+
+    pub fn com_status(mut self, status: Status) -> Self {
+        self.status = status;
+        self
+    }
+
+    fn usando_catcher(status: Status) -> Self {
+        Problema {
+            usa_catcher: true,
+            ..Problema::new(status, "erro_interno", Mensagem::ErroInesperado)
+        }
+    }
+}
+
+impl From<DomainError> for Problema {
+    fn from(error: DomainError) -> Self {
+        match error {
+            DomainError::AlreadyExists => {
+                Problema::new(Status::Conflict, "ja_existe", Mensagem::RecursoJaExiste)
+            }
+            DomainError::Empty => Problema::new(Status::BadRequest, "campo_vazio", Mensagem::InputInvalido)
+                .com_detalhe("Campo obrigatório".to_string()),
+            DomainError::Unauthorized => Problema::usando_catcher(Status::Unauthorized),
+            DomainError::Forbidden => {
+                Problema::new(Status::Forbidden, "acesso_negado", Mensagem::AcessoNegado)
+            }
+            DomainError::NotFound => Problema::usando_catcher(Status::NotFound),
+            DomainError::Invalid(detail) => {
+                Problema::new(Status::BadRequest, "invalido", Mensagem::InputInvalido).com_detalhe(detail)
+            }
+            DomainError::InvalidField { field, message } => Problema {
+                field: Some(field),
+                ..Problema::new(Status::BadRequest, "campo_invalido", Mensagem::InputInvalido)
+                    .com_detalhe(message)
+            },
+            DomainError::NonPositive => {
+                Problema::new(Status::BadRequest, "valor_negativo", Mensagem::InputInvalido)
+                    .com_detalhe("Valor não pode ser negativo".to_string())
+            }
+            DomainError::Database(_) => {
+                Problema::new(Status::InternalServerError, "erro_interno", Mensagem::ErroInesperado)
+            }
+            DomainError::Conflict => {
+                Problema::new(Status::Conflict, "conflito_de_versao", Mensagem::VersaoDesatualizada)
+            }
+        }
+    }
+}
+
+impl From<Status> for Problema {
+    fn from(status: Status) -> Self {
+        Problema::usando_catcher(status)
+    }
+}
+
+impl<'r> Responder<'r, 'static> for Problema {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        if self.usa_catcher {
+            return Err(self.status);
+        }
+        let title = self.mensagem.traduz(idioma_da_requisicao(req)).to_string();
+        let request_id = request_id(req);
+        let corpo = ErrorResponse {
+            tipo: format!("/erros/{}", self.code),
+            detail: self.detail.unwrap_or_else(|| title.clone()),
+            title,
+            status: self.status.code,
+            code: self.code.to_string(),
+            field: self.field,
+            request_id: request_id.clone(),
+        };
+        let corpo = serde_json::to_string(&corpo).map_err(|_| Status::InternalServerError)?;
+        Response::build()
+            .status(self.status)
+            .header(ContentType::new("application", "problem+json"))
+            .header(Header::new(HEADER_REQUEST_ID, request_id))
+            .sized_body(corpo.len(), Cursor::new(corpo))
+            .ok()
+    }
+}
+
+impl OpenApiResponderInner for Problema {
+    fn responses(gen: &mut OpenApiGenerator) -> rocket_okapi::Result<Responses> {
+        let mut responses = Responses::default();
+        let schema = gen.json_schema::<ErrorResponse>();
+        for status in [400, 401, 403, 404, 409, 412, 500] {
+            add_schema_response(&mut responses, status, PROBLEM_JSON, schema.clone())?;
+        }
+        Ok(responses)
+    }
+}
+
+// 404 dos catchers: se quem faltou foi a loja da requisição (LojaGuard), e não o recurso
+// da rota, a mensagem é a da loja.
+pub fn nao_encontrado(req: &Request<'_>, mensagem: Mensagem) -> Problema {
+    let mensagem = match loja_inexistente(req) {
+        true => Mensagem::LojaNaoEncontrada,
+        false => mensagem,
     };
-    Json(error)
+    Problema::new(Status::NotFound, "nao_encontrado", mensagem)
+}
+
+#[catch(400)]
+fn bad_request() -> Problema {
+    Problema::new(Status::BadRequest, "requisicao_invalida", Mensagem::InputInvalido)
 }
 
 #[catch(401)]
-fn unauthorized() -> Json<ErrorResponse> {
-    let error = ErrorResponse {
-        msg: "Credenciais invalidas".to_string(),
-        status: 401,
-    };
-    Json(error)
+fn unauthorized() -> Problema {
+    Problema::new(Status::Unauthorized, "nao_autorizado", Mensagem::CredenciaisInvalidas)
+}
+
+#[catch(403)]
+fn forbidden() -> Problema {
+    Problema::new(Status::Forbidden, "acesso_negado", Mensagem::AcessoNegado)
+}
+
+#[catch(404)]
+fn not_found(req: &Request<'_>) -> Problema {
+    nao_encontrado(req, Mensagem::RecursoNaoEncontrado)
+}
+
+#[catch(412)]
+fn precondition_failed() -> Problema {
+    Problema::new(Status::PreconditionFailed, "conflito_de_versao", Mensagem::VersaoDesatualizada)
+}
+
+#[catch(413)]
+fn payload_too_large() -> Problema {
+    Problema::new(Status::PayloadTooLarge, "requisicao_muito_grande", Mensagem::RequisicaoMuitoGrande)
+}
+
+#[catch(422)]
+fn unprocessable_entity() -> Problema {
+    Problema::new(Status::UnprocessableEntity, "requisicao_invalida", Mensagem::InputInvalido)
 }
 
 #[catch(500)]
-fn internal() -> Json<ErrorResponse> {
-    let error = ErrorResponse {
-        msg: "Erro inesperado. Tente novamente mais tarde".to_string(),
-        status: 500,
-    };
-    Json(error)
+fn internal() -> Problema {
+    Problema::new(Status::InternalServerError, "erro_interno", Mensagem::ErroInesperado)
 }
 
 pub fn generic_catchers() -> Vec<rocket::Catcher> {
-    catchers![bad_request, unauthorized, internal]
+    catchers![
+        bad_request,
+        unauthorized,
+        forbidden,
+        not_found,
+        precondition_failed,
+        payload_too_large,
+        unprocessable_entity,
+        internal
+    ]
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rocket::local::blocking::Client;
-    use rocket::http::Status;
+    use rocket::local::blocking::{Client, LocalResponse};
+    use rocket::http::{Header, Status};
+    use serde_json::Value;
+
+    fn problema(response: LocalResponse) -> Value {
+        assert_eq!(
+            response.content_type().map(|content_type| content_type.to_string()),
+            Some(PROBLEM_JSON.to_string())
+        );
+        serde_json::from_str(&response.into_string().unwrap()).unwrap()
+    }
+
+    #[get("/")]
+    async fn route_empty() -> Result<(), Status> {
+        Err(DomainError::Empty.into())
+    }
 
     #[test]
     fn test_empty() {
-        #[get("/")]
-        async fn route() -> Result<(), Status> {
-            Err(DomainError::Empty.into())
-        }
-
         let rocket = rocket::build()
-            .mount("/", routes![route])
+            .mount("/", routes![route_empty])
             .register("/", generic_catchers());
 
         let client = Client::tracked(rocket).expect("valid rocket instance");
@@ -76,19 +261,74 @@ mod tests {
 
         assert_eq!(response.status(), Status::BadRequest);
 
-        let response = response.into_string().unwrap();
-        assert_eq!(r##"{"msg":"Input inválido","status":400}"##, response);
+        let response = problema(response);
+        assert_eq!(response["title"], "Input inválido");
+        assert_eq!(response["status"], 400);
+    }
+
+    #[get("/")]
+    async fn route_invalid() -> Result<(), Problema> {
+        Err(DomainError::Invalid("Status inválido".to_string()).into())
     }
 
     #[test]
     fn test_invalid() {
-        #[get("/")]
-        async fn route() -> Result<(), Status> {
-            Err(DomainError::Invalid("Entity".to_string()).into())
+        let rocket = rocket::build()
+            .mount("/", routes![route_invalid])
+            .register("/", generic_catchers());
+
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+        let response = client.get("/").dispatch();
+
+        assert_eq!(response.status(), Status::BadRequest);
+
+        let response = problema(response);
+        assert_eq!(response["code"], "invalido");
+        assert_eq!(response["title"], "Input inválido");
+        assert_eq!(response["detail"], "Status inválido");
+        assert_eq!(response["type"], "/erros/invalido");
+    }
+
+    #[get("/")]
+    async fn route_invalid_field() -> Result<(), Problema> {
+        Err(DomainError::InvalidField {
+            field: "cpf".to_string(),
+            message: "CPF inválido".to_string(),
         }
+        .into())
+    }
 
+    #[test]
+    fn test_invalid_field() {
         let rocket = rocket::build()
-            .mount("/", routes![route])
+            .mount("/", routes![route_invalid_field])
+            .register("/", generic_catchers());
+
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+        let response = client
+            .get("/")
+            .header(Header::new("X-Request-Id", "abc-123"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::BadRequest);
+        assert_eq!(response.headers().get_one("X-Request-Id"), Some("abc-123"));
+
+        let response = problema(response);
+        assert_eq!(response["code"], "campo_invalido");
+        assert_eq!(response["field"], "cpf");
+        assert_eq!(response["detail"], "CPF inválido");
+        assert_eq!(response["request_id"], "abc-123");
+    }
+
+    #[get("/")]
+    async fn route_non_positive() -> Result<(), Problema> {
+        Err(DomainError::NonPositive.into())
+    }
+
+    #[test]
+    fn test_non_positive() {
+        let rocket = rocket::build()
+            .mount("/", routes![route_non_positive])
             .register("/", generic_catchers());
 
         let client = Client::tracked(rocket).expect("valid rocket instance");
@@ -96,19 +336,84 @@ mod tests {
 
         assert_eq!(response.status(), Status::BadRequest);
 
-        let response = response.into_string().unwrap();
-        assert_eq!(r##"{"msg":"Input inválido","status":400}"##, response);
+        let response = problema(response);
+        assert_eq!(response["code"], "valor_negativo");
+        assert!(response.get("field").is_none());
+        assert!(!response["request_id"].as_str().unwrap().is_empty());
+    }
+
+    #[get("/")]
+    async fn route_database() -> Result<(), Problema> {
+        Err(DomainError::Database("relation \"pedido\" does not exist".to_string()).into())
     }
 
     #[test]
-    fn test_unauthorized() {
-        #[get("/")]
-        async fn route() -> Result<(), Status> {
-            Err(DomainError::Unauthorized.into())
-        }
+    fn test_database() {
+        let rocket = rocket::build()
+            .mount("/", routes![route_database])
+            .register("/", generic_catchers());
+
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+        let response = client.get("/").dispatch();
+
+        assert_eq!(response.status(), Status::InternalServerError);
+
+        let response = problema(response);
+        assert_eq!(response["code"], "erro_interno");
+        assert_eq!(response["detail"], "Erro inesperado. Tente novamente mais tarde");
+    }
+
+    #[get("/")]
+    async fn route_conflict() -> Result<(), Problema> {
+        Err(DomainError::Conflict.into())
+    }
+
+    #[test]
+    fn test_conflict() {
+        let rocket = rocket::build()
+            .mount("/", routes![route_conflict])
+            .register("/", generic_catchers());
+
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+        let response = client.get("/").dispatch();
+
+        assert_eq!(response.status(), Status::Conflict);
+
+        let response = problema(response);
+        assert_eq!(response["code"], "conflito_de_versao");
+        assert_eq!(response["title"], "O recurso foi alterado por outra requisição");
+    }
+
+    #[get("/")]
+    async fn route_forbidden() -> Result<(), Problema> {
+        Err(DomainError::Forbidden.into())
+    }
+
+    #[test]
+    fn test_forbidden() {
+        let rocket = rocket::build()
+            .mount("/", routes![route_forbidden])
+            .register("/", generic_catchers());
+
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+        let response = client.get("/").dispatch();
+
+        assert_eq!(response.status(), Status::Forbidden);
+
+        let response = problema(response);
+        assert_eq!(response["code"], "acesso_negado");
+        assert_eq!(response["title"], "Acesso negado");
+    }
+
+    #[get("/")]
+    async fn route_unauthorized() -> Result<(), Problema> {
+        Err(DomainError::Unauthorized.into())
+    }
 
+    #[test]
+    fn test_unauthorized() {
         let rocket = rocket::build()
-            .mount("/", routes![route])
+            .mount("/", routes![route_unauthorized])
             .register("/", generic_catchers());
 
         let client = Client::tracked(rocket).expect("valid rocket instance");
@@ -116,19 +421,20 @@ mod tests {
 
         assert_eq!(response.status(), Status::Unauthorized);
 
-        let response = response.into_string().unwrap();
-        assert_eq!(r##"{"msg":"Credenciais invalidas","status":401}"##, response);
+        let response = problema(response);
+        assert_eq!(response["code"], "nao_autorizado");
+        assert_eq!(response["title"], "Credenciais invalidas");
+    }
+
+    #[get("/")]
+    async fn route_internal() -> Result<(), Status> {
+        Err(Status::InternalServerError)
     }
 
     #[test]
     fn test_internal() {
-        #[get("/")]
-        async fn route() -> Result<(), Status> {
-            Err(Status::InternalServerError)
-        }
-
         let rocket = rocket::build()
-            .mount("/", routes![route])
+            .mount("/", routes![route_internal])
             .register("/", generic_catchers());
 
         let client = Client::tracked(rocket).expect("valid rocket instance");
@@ -136,17 +442,83 @@ mod tests {
 
         assert_eq!(response.status(), Status::InternalServerError);
 
-        let response = response.into_string().unwrap();
-        assert_eq!(r##"{"msg":"Erro inesperado. Tente novamente mais tarde","status":500}"##, response);
+        let response = problema(response);
+        assert_eq!(response["title"], "Erro inesperado. Tente novamente mais tarde");
+    }
+
+    #[get("/")]
+    async fn route_mensagem_traduzida() -> Result<(), Problema> {
+        Err(DomainError::Unauthorized.into())
+    }
+
+    #[test]
+    fn test_mensagem_traduzida() {
+        let rocket = rocket::build()
+            .mount("/", routes![route_mensagem_traduzida])
+            .register("/", generic_catchers());
+
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+        let response = client
+            .get("/")
+            .header(Header::new("Accept-Language", "en-US,en;q=0.9"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Unauthorized);
+        assert_eq!(problema(response)["title"], "Invalid credentials");
+
+        let response = client
+            .get("/")
+            .header(Header::new("Accept-Language", "es"))
+            .dispatch();
+        assert_eq!(problema(response)["title"], "Credenciales inválidas");
+
+        let response = client
+            .get("/")
+            .header(Header::new("Accept-Language", "fr"))
+            .dispatch();
+        assert_eq!(problema(response)["title"], "Credenciais invalidas");
+    }
+
+    #[get("/<codigo>")]
+    async fn route_status(codigo: u16) -> Result<(), Status> {
+        Err(Status::from_code(codigo).unwrap())
+    }
+
+    #[test]
+    fn test_catchers_genericos() {
+        let rocket = rocket::build()
+            .mount("/", routes![route_status])
+            .register("/", generic_catchers());
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+
+        for (status, code, title) in [
+            (Status::Forbidden, "acesso_negado", "Acesso negado"),
+            (Status::NotFound, "nao_encontrado", "Recurso não encontrado!"),
+            (Status::PayloadTooLarge, "requisicao_muito_grande", "Requisição maior que o permitido"),
+        ] {
+            let response = client.get(format!("/{}", status.code)).dispatch();
+            assert_eq!(response.status(), status);
+
+            let response = problema(response);
+            assert_eq!(response["code"], code);
+            assert_eq!(response["title"], title);
+        }
+
+        let response = client.get("/rota/inexistente").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(problema(response)["code"], "nao_encontrado");
     }
 
     #[test]
     fn test_status_from_error() {
         assert_eq!(Status::from(DomainError::AlreadyExists), Status::Conflict);
+        assert_eq!(Status::from(DomainError::Conflict), Status::Conflict);
         assert_eq!(Status::from(DomainError::NotFound), Status::NotFound);
         assert_eq!(Status::from(DomainError::Empty), Status::BadRequest);
         assert_eq!(Status::from(DomainError::Unauthorized), Status::Unauthorized);
+        assert_eq!(Status::from(DomainError::Forbidden), Status::Forbidden);
         assert_eq!(Status::from(DomainError::Invalid("Entity".to_string())), Status::BadRequest);
-        assert_eq!(Status::from(DomainError::NonPositive), Status::InternalServerError);
+        assert_eq!(Status::from(DomainError::NonPositive), Status::BadRequest);
+        assert_eq!(Status::from(DomainError::Database("erro".to_string())), Status::InternalServerError);
     }
 }
@@ -0,0 +1,67 @@
+use rocket::http::Header;
+use rocket::response::{self, Responder};
+use rocket::serde::json::Json;
+use rocket::Request;
+use rocket_okapi::gen::OpenApiGenerator;
+use rocket_okapi::okapi::openapi3::Responses;
+use rocket_okapi::response::OpenApiResponderInner;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+pub const HEADER_ETAG: &str = "ETag";
+
+// A ETag é a versão da entidade entre aspas: "3".
+pub fn etag(versao: usize) -> String {
+    format!("\"{}\"", versao)
+}
+
+// Versão contida numa ETag forte ("3") ou fraca (W/"3").
+pub fn versao_da_etag(etag: &str) -> Option<usize> {
+    let etag = etag.trim();
+    let etag = etag.strip_prefix("W/").unwrap_or(etag);
+    etag.strip_prefix('"')?.strip_suffix('"')?.parse().ok()
+}
+
+// Corpo JSON acompanhado do header ETag com a versão da entidade.
+pub struct ComEtag<T> {
+    versao: usize,
+    corpo: Json<T>,
+}
+
+impl<T> ComEtag<T> {
+    pub fn new(versao: usize, corpo: T) -> Self {
+        ComEtag {
+            versao,
+            corpo: Json(corpo),
+        }
+    }
+}
+
+impl<'r, T: Serialize> Responder<'r, 'static> for ComEtag<T> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let mut response = self.corpo.respond_to(req)?;
+        response.set_header(Header::new(HEADER_ETAG, etag(self.versao)));
+        Ok(response)
+    }
+}
+
+impl<T: Serialize + JsonSchema + Send> OpenApiResponderInner for ComEtag<T> {
+    fn responses(gen: &mut OpenApiGenerator) -> rocket_okapi::Result<Responses> {
+        Json::<T>::responses(gen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versao_da_etag() {
+        assert_eq!(versao_da_etag("\"3\""), Some(3));
+        assert_eq!(versao_da_etag("W/\"12\""), Some(12));
+        assert_eq!(versao_da_etag(" \"7\" "), Some(7));
+        assert_eq!(versao_da_etag("3"), None);
+        assert_eq!(versao_da_etag("\"abc\""), None);
+        assert_eq!(versao_da_etag(&etag(5)), Some(5));
+    }
+}
@@ -0,0 +1,69 @@
+use crate::entities::idioma::Idioma;
+
+// Catálogo das mensagens de erro devolvidas pela API.
+#[derive(Clone, Copy, Debug)]
+pub enum Mensagem {
+    InputInvalido,
+    RecursoJaExiste,
+    VersaoDesatualizada,
+    CredenciaisInvalidas,
+    AcessoNegado,
+    RecursoNaoEncontrado,
+    RequisicaoMuitoGrande,
+    ErroInesperado,
+    AgendaNaoEncontrada,
+    CategoriaNaoEncontrada,
+    ClienteNaoEncontrado,
+    LojaNaoEncontrada,
+    PedidoNaoEncontrado,
+    ProdutoNaoEncontrado,
+}
+
+impl Mensagem {
+    pub fn traduz(&self, idioma: Idioma) -> &'static str {
+        match (self, idioma) {
+            (Mensagem::InputInvalido, Idioma::PtBr) => "Input inválido",
+            (Mensagem::InputInvalido, Idioma::En) => "Invalid input",
+            (Mensagem::InputInvalido, Idioma::Es) => "Entrada inválida",
+            (Mensagem::RecursoJaExiste, Idioma::PtBr) => "Recurso já existe",
+            (Mensagem::RecursoJaExiste, Idioma::En) => "Resource already exists",
+            (Mensagem::RecursoJaExiste, Idioma::Es) => "El recurso ya existe",
+            (Mensagem::VersaoDesatualizada, Idioma::PtBr) => "O recurso foi alterado por outra requisição",
+            (Mensagem::VersaoDesatualizada, Idioma::En) => "The resource was modified by another request",
+            (Mensagem::VersaoDesatualizada, Idioma::Es) => "El recurso fue modificado por otra solicitud",
+            (Mensagem::CredenciaisInvalidas, Idioma::PtBr) => "Credenciais invalidas",
+            (Mensagem::CredenciaisInvalidas, Idioma::En) => "Invalid credentials",
+            (Mensagem::CredenciaisInvalidas, Idioma::Es) => "Credenciales inválidas",
+            (Mensagem::AcessoNegado, Idioma::PtBr) => "Acesso negado",
+            (Mensagem::AcessoNegado, Idioma::En) => "Access denied",
+            (Mensagem::AcessoNegado, Idioma::Es) => "Acceso denegado",
+            (Mensagem::RecursoNaoEncontrado, Idioma::PtBr) => "Recurso não encontrado!",
+            (Mensagem::RecursoNaoEncontrado, Idioma::En) => "Resource not found!",
+            (Mensagem::RecursoNaoEncontrado, Idioma::Es) => "¡Recurso no encontrado!",
+            (Mensagem::RequisicaoMuitoGrande, Idioma::PtBr) => "Requisição maior que o permitido",
+            (Mensagem::RequisicaoMuitoGrande, Idioma::En) => "Request larger than allowed",
+            (Mensagem::RequisicaoMuitoGrande, Idioma::Es) => "Solicitud mayor que lo permitido",
+            (Mensagem::ErroInesperado, Idioma::PtBr) => "Erro inesperado. Tente novamente mais tarde",
+            (Mensagem::ErroInesperado, Idioma::En) => "Unexpected error. Please try again later",
+            (Mensagem::ErroInesperado, Idioma::Es) => "Error inesperado. Inténtelo de nuevo más tarde",
+            (Mensagem::AgendaNaoEncontrada, Idioma::PtBr) => "Agenda não encontrada!",
+            (Mensagem::AgendaNaoEncontrada, Idioma::En) => "Schedule not found!",
+            (Mensagem::AgendaNaoEncontrada, Idioma::Es) => "¡Horario no encontrado!",
+            (Mensagem::CategoriaNaoEncontrada, Idioma::PtBr) => "Categoria não encontrada!",
+            (Mensagem::CategoriaNaoEncontrada, Idioma::En) => "Category not found!",
+            (Mensagem::CategoriaNaoEncontrada, Idioma::Es) => "¡Categoría no encontrada!",
+            (Mensagem::ClienteNaoEncontrado, Idioma::PtBr) => "Cliente não encontrado!",
+            (Mensagem::ClienteNaoEncontrado, Idioma::En) => "Customer not found!",
+            (Mensagem::ClienteNaoEncontrado, Idioma::Es) => "¡Cliente no encontrado!",
+            (Mensagem::LojaNaoEncontrada, Idioma::PtBr) => "Loja não encontrada!",
+            (Mensagem::LojaNaoEncontrada, Idioma::En) => "Store not found!",
+            (Mensagem::LojaNaoEncontrada, Idioma::Es) => "¡Tienda no encontrada!",
+            (Mensagem::PedidoNaoEncontrado, Idioma::PtBr) => "Pedido não encontrado!",
+            (Mensagem::PedidoNaoEncontrado, Idioma::En) => "Order not found!",
+            (Mensagem::PedidoNaoEncontrado, Idioma::Es) => "¡Pedido no encontrado!",
+            (Mensagem::ProdutoNaoEncontrado, Idioma::PtBr) => "Produto não encontrado!",
+            (Mensagem::ProdutoNaoEncontrado, Idioma::En) => "Product not found!",
+            (Mensagem::ProdutoNaoEncontrado, Idioma::Es) => "¡Producto no encontrado!",
+        }
+    }
+}
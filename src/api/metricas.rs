@@ -0,0 +1,93 @@
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::ContentType;
+use rocket::{Data, Request, Response, Route, State};
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::base::metricas;
+use crate::traits::pedido_gateway::PedidoGateway;
+
+struct InicioDaRequisicao(Option<Instant>);
+
+// Conta as requisições e mede a latência por método, rota e status.
+// A rota é o template montado (ex.: /produtos/<id>), para não explodir a cardinalidade.
+pub struct MetricasFairing;
+
+#[rocket::async_trait]
+impl Fairing for MetricasFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "Métricas HTTP",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _: &mut Data<'_>) {
+        req.local_cache(|| InicioDaRequisicao(Some(Instant::now())));
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let duracao = match req.local_cache(|| InicioDaRequisicao(None)).0 {
+            Some(inicio) => inicio.elapsed().as_secs_f64(),
+            None => 0.0,
+        };
+        let rota = req
+            .route()
+            .map(|rota| rota.uri.to_string())
+            .unwrap_or_else(|| "desconhecida".to_string());
+        metricas::registra_requisicao(req.method().as_str(), &rota, res.status().code, duracao);
+    }
+}
+
+#[get("/metrics")]
+async fn exporta_metricas(
+    pedido_gateway: &State<Arc<dyn PedidoGateway + Sync + Send>>,
+) -> (ContentType, String) {
+    match pedido_gateway.conta_pedidos_por_status().await {
+        Ok(contagem) => {
+            let contagem: Vec<(String, usize)> = contagem
+                .into_iter()
+                .map(|(status, quantidade)| (status.to_string(), quantidade))
+                .collect();
+            metricas::atualiza_pedidos_por_status(&contagem);
+        }
+        Err(erro) => tracing::warn!(erro = ?erro, "Erro ao contar pedidos por status"),
+    }
+    (
+        ContentType::new("text", "plain").with_params(("version", "0.0.4")),
+        metricas::exporta(),
+    )
+}
+
+pub fn routes() -> Vec<Route> {
+    routes![exporta_metricas]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gateways::in_memory_pedido_gateway::InMemoryPedidoRepository;
+    use rocket::http::Status;
+    use rocket::local::asynchronous::Client;
+
+    #[rocket::async_test]
+    async fn test_exporta_metricas() {
+        let pedido_gateway: Arc<dyn PedidoGateway + Sync + Send> =
+            Arc::new(InMemoryPedidoRepository::new());
+        let rocket = rocket::build()
+            .mount("/", routes())
+            .manage(pedido_gateway)
+            .attach(MetricasFairing);
+        let client = Client::tracked(rocket).await.expect("valid rocket instance");
+
+        client.get("/metrics").dispatch().await;
+        let response = client.get("/metrics").dispatch().await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let corpo = response.into_string().await.unwrap();
+        assert!(corpo.contains("pedidos_por_status{status=\"Pendente\"} 1"));
+        assert!(corpo.contains(
+            "http_requests_total{method=\"GET\",route=\"/metrics\",status=\"200\"}"
+        ));
+    }
+}
@@ -0,0 +1,126 @@
+use chrono::Utc;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::Header;
+use rocket::route::{Handler, Outcome};
+use rocket::{Data, Request, Response, Route};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::Instrument;
+
+pub const HEADER_REQUEST_ID: &str = "X-Request-Id";
+
+static SEQUENCIA: AtomicU64 = AtomicU64::new(0);
+
+struct RequestId(String);
+
+pub fn novo_request_id() -> String {
+    let sequencia = SEQUENCIA.fetch_add(1, Ordering::Relaxed);
+    format!("{:x}-{:x}", Utc::now().timestamp_millis(), sequencia)
+}
+
+// Identificador da requisição: o X-Request-Id recebido ou um gerado na primeira consulta.
+pub fn request_id(req: &Request<'_>) -> String {
+    req.local_cache(|| {
+        let id = req
+            .headers()
+            .get_one(HEADER_REQUEST_ID)
+            .map(|id| id.trim().to_string())
+            .filter(|id| !id.is_empty())
+            .unwrap_or_else(novo_request_id);
+        RequestId(id)
+    })
+    .0
+    .clone()
+}
+
+// Devolve o X-Request-Id em todas as respostas, inclusive as dos catchers.
+pub struct RequestIdFairing;
+
+#[rocket::async_trait]
+impl Fairing for RequestIdFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "X-Request-Id",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _: &mut Data<'_>) {
+        request_id(req);
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        res.set_header(Header::new(HEADER_REQUEST_ID, request_id(req)));
+    }
+}
+
+// Executa o handler (guards, controllers, use cases e gateways) dentro de um span
+// com o request_id, para que todos os logs da requisição possam ser correlacionados.
+#[derive(Clone)]
+struct Rastreado(Box<dyn Handler>);
+
+#[rocket::async_trait]
+impl Handler for Rastreado {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        let span = tracing::info_span!(
+            "requisicao",
+            request_id = %request_id(req),
+            method = %req.method(),
+            uri = %req.uri(),
+        );
+        self.0.handle(req, data).instrument(span).await
+    }
+}
+
+pub fn rastreadas(routes: Vec<Route>) -> Vec<Route> {
+    routes
+        .into_iter()
+        .map(|mut route| {
+            route.handler = Box::new(Rastreado(route.handler));
+            route
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::http::Status;
+    use rocket::local::blocking::Client;
+
+    #[get("/")]
+    fn rota() -> &'static str {
+        "ok"
+    }
+
+    fn client() -> Client {
+        let rocket = rocket::build()
+            .mount("/", rastreadas(routes![rota]))
+            .attach(RequestIdFairing);
+        Client::tracked(rocket).expect("valid rocket instance")
+    }
+
+    #[test]
+    fn test_propaga_request_id_recebido() {
+        let client = client();
+        let response = client
+            .get("/")
+            .header(Header::new(HEADER_REQUEST_ID, "abc-123"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.headers().get_one(HEADER_REQUEST_ID), Some("abc-123"));
+    }
+
+    #[test]
+    fn test_gera_request_id() {
+        let client = client();
+        let primeira = client.get("/").dispatch();
+        let segunda = client.get("/nao-existe").dispatch();
+
+        let primeiro_id = primeira.headers().get_one(HEADER_REQUEST_ID).unwrap().to_string();
+        let segundo_id = segunda.headers().get_one(HEADER_REQUEST_ID).unwrap().to_string();
+        assert_eq!(segunda.status(), Status::NotFound);
+        assert!(!primeiro_id.is_empty());
+        assert_ne!(primeiro_id, segundo_id);
+    }
+}
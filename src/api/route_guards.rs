@@ -1,3 +1,9 @@
 pub mod admin_route_guard;
 pub mod api_key_route_guard;
-pub mod kitchen_route_guard;
\ No newline at end of file
+pub mod cliente_route_guard;
+pub mod idempotency_key_route_guard;
+pub mod idioma_route_guard;
+pub mod if_match_route_guard;
+pub mod kitchen_route_guard;
+pub mod loja_route_guard;
+pub mod request_id_route_guard;
\ No newline at end of file
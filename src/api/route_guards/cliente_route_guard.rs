@@ -0,0 +1,66 @@
+use rocket::{
+  http::Status,
+  request::{self, FromRequest, Outcome, Request},
+};
+use rocket_okapi::{
+  gen::OpenApiGenerator,
+  okapi::openapi3::{Object, SecurityRequirement, SecurityScheme, SecuritySchemeData},
+  request::{OpenApiFromRequest, RequestHeaderInput},
+  OpenApiError,
+};
+
+use crate::base::domain_error::DomainError;
+use crate::entities::cpf::Cpf;
+use crate::traits::token_validator_adapter::TokenValidatorAdapter;
+
+use std::sync::Arc;
+
+// Cliente autenticado: claim `cpf` do token Bearer de cliente.
+pub struct ClienteGuard(pub Cpf);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ClienteGuard {
+  type Error = DomainError;
+
+  async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+      match req.headers().get_one("Authorization").and_then(|valor| valor.strip_prefix("Bearer ")) {
+          Some(token) => {
+              let token_validator = req
+                  .rocket()
+                  .state::<Arc<dyn TokenValidatorAdapter + Sync + Send>>()
+                  .unwrap();
+              match token_validator.cpf(token.trim().to_string()) {
+                  Ok(cpf) => Outcome::Success(ClienteGuard(cpf)),
+                  Err(erro) => Outcome::Error((Status::Unauthorized, erro)),
+              }
+          }
+          None => Outcome::Error((Status::Unauthorized, DomainError::Unauthorized)),
+      }
+  }
+}
+
+impl<'a> OpenApiFromRequest<'a> for ClienteGuard {
+  fn from_request_input(
+      _gen: &mut OpenApiGenerator,
+      _name: String,
+      _required: bool,
+  ) -> Result<RequestHeaderInput, OpenApiError> {
+      let security_scheme = SecurityScheme {
+          description: Some("Token de cliente, com o CPF na claim cpf.".to_owned()),
+
+          data: SecuritySchemeData::Http {
+              scheme: "bearer".to_owned(),
+              bearer_format: Some("JWT".to_owned()),
+          },
+          extensions: Object::default(),
+      };
+
+      let mut security_req = SecurityRequirement::new();
+      security_req.insert("Cliente".to_owned(), Vec::new());
+      Ok(RequestHeaderInput::Security(
+          "Cliente".to_owned(),
+          security_scheme,
+          security_req,
+      ))
+  }
+}
@@ -0,0 +1,61 @@
+use rocket::{
+  http::Status,
+  request::{self, FromRequest, Outcome, Request},
+};
+use rocket_okapi::{
+  gen::OpenApiGenerator,
+  okapi::openapi3::{Object, Parameter, ParameterValue},
+  request::{OpenApiFromRequest, RequestHeaderInput},
+  OpenApiError,
+};
+
+use crate::base::domain_error::DomainError;
+
+pub const HEADER_IDEMPOTENCY_KEY: &str = "Idempotency-Key";
+const TAMANHO_MAXIMO_CHAVE: usize = 255;
+
+// Chave opcional enviada pelo cliente para que repetições da mesma requisição não
+// criem recursos duplicados.
+pub struct IdempotencyKeyGuard(pub Option<String>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for IdempotencyKeyGuard {
+  type Error = DomainError;
+
+  async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+      match req.headers().get_one(HEADER_IDEMPOTENCY_KEY).map(str::trim) {
+          None => Outcome::Success(IdempotencyKeyGuard(None)),
+          Some(chave) if chave.is_empty() || chave.len() > TAMANHO_MAXIMO_CHAVE => Outcome::Error((
+              Status::BadRequest,
+              DomainError::Invalid(HEADER_IDEMPOTENCY_KEY.to_string()),
+          )),
+          Some(chave) => Outcome::Success(IdempotencyKeyGuard(Some(chave.to_string()))),
+      }
+  }
+}
+
+impl<'a> OpenApiFromRequest<'a> for IdempotencyKeyGuard {
+  fn from_request_input(
+      gen: &mut OpenApiGenerator,
+      _name: String,
+      _required: bool,
+  ) -> Result<RequestHeaderInput, OpenApiError> {
+      Ok(RequestHeaderInput::Parameter(Parameter {
+          name: HEADER_IDEMPOTENCY_KEY.to_owned(),
+          location: "header".to_owned(),
+          description: Some("Identificador único da requisição (até 255 caracteres). Repetições com a mesma chave devolvem o pedido original.".to_owned()),
+          required: false,
+          deprecated: false,
+          allow_empty_value: false,
+          value: ParameterValue::Schema {
+              style: None,
+              explode: None,
+              allow_reserved: false,
+              schema: gen.json_schema::<String>(),
+              example: None,
+              examples: None,
+          },
+          extensions: Object::default(),
+      }))
+  }
+}
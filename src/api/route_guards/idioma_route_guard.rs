@@ -0,0 +1,58 @@
+use rocket::{
+  request::{self, FromRequest, Outcome, Request},
+};
+use rocket_okapi::{
+  gen::OpenApiGenerator,
+  okapi::openapi3::{Object, Parameter, ParameterValue},
+  request::{OpenApiFromRequest, RequestHeaderInput},
+  OpenApiError,
+};
+use std::convert::Infallible;
+
+use crate::entities::idioma::Idioma;
+
+// Idioma da resposta, negociado pelo header Accept-Language; sem correspondência, pt-BR.
+pub struct IdiomaGuard(pub Idioma);
+
+pub fn idioma_da_requisicao(req: &Request<'_>) -> Idioma {
+  req
+      .headers()
+      .get_one("Accept-Language")
+      .map(Idioma::from_accept_language)
+      .unwrap_or_default()
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for IdiomaGuard {
+  type Error = Infallible;
+
+  async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+      Outcome::Success(IdiomaGuard(idioma_da_requisicao(req)))
+  }
+}
+
+impl<'a> OpenApiFromRequest<'a> for IdiomaGuard {
+  fn from_request_input(
+      gen: &mut OpenApiGenerator,
+      _name: String,
+      _required: bool,
+  ) -> Result<RequestHeaderInput, OpenApiError> {
+      Ok(RequestHeaderInput::Parameter(Parameter {
+          name: "Accept-Language".to_owned(),
+          location: "header".to_owned(),
+          description: Some("Idioma dos textos: pt-BR (padrão), en ou es.".to_owned()),
+          required: false,
+          deprecated: false,
+          allow_empty_value: false,
+          value: ParameterValue::Schema {
+              style: None,
+              explode: None,
+              allow_reserved: false,
+              schema: gen.json_schema::<String>(),
+              example: None,
+              examples: None,
+          },
+          extensions: Object::default(),
+      }))
+  }
+}
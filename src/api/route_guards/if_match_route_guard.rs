@@ -0,0 +1,74 @@
+use rocket::{
+  http::Status,
+  request::{self, FromRequest, Outcome, Request},
+};
+use rocket_okapi::{
+  gen::OpenApiGenerator,
+  okapi::openapi3::{Object, Parameter, ParameterValue},
+  request::{OpenApiFromRequest, RequestHeaderInput},
+  OpenApiError,
+};
+
+use crate::api::error_handling::Problema;
+use crate::api::etag::versao_da_etag;
+use crate::base::domain_error::DomainError;
+
+pub const HEADER_IF_MATCH: &str = "If-Match";
+
+// Versão que o cliente leu, enviada no header If-Match. Sem o header (ou com "*") a
+// atualização vale para a versão atual.
+pub struct IfMatchGuard(pub Option<usize>);
+
+impl IfMatchGuard {
+  // Com If-Match, uma versão desatualizada é 412 Precondition Failed; sem ele, 409 Conflict.
+  pub fn problema(&self, erro: DomainError) -> Problema {
+      match erro {
+          DomainError::Conflict if self.0.is_some() => {
+              Problema::from(erro).com_status(Status::PreconditionFailed)
+          }
+          erro => erro.into(),
+      }
+  }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for IfMatchGuard {
+  type Error = DomainError;
+
+  async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+      match req.headers().get_one(HEADER_IF_MATCH).map(str::trim) {
+          None | Some("*") => Outcome::Success(IfMatchGuard(None)),
+          // Uma ETag que não é versão de nenhuma entidade nunca confere.
+          Some(etag) => match versao_da_etag(etag) {
+              Some(versao) => Outcome::Success(IfMatchGuard(Some(versao))),
+              None => Outcome::Error((Status::PreconditionFailed, DomainError::Conflict)),
+          },
+      }
+  }
+}
+
+impl<'a> OpenApiFromRequest<'a> for IfMatchGuard {
+  fn from_request_input(
+      gen: &mut OpenApiGenerator,
+      _name: String,
+      _required: bool,
+  ) -> Result<RequestHeaderInput, OpenApiError> {
+      Ok(RequestHeaderInput::Parameter(Parameter {
+          name: HEADER_IF_MATCH.to_owned(),
+          location: "header".to_owned(),
+          description: Some("ETag lida no GET; se o recurso tiver mudado desde então, a atualização é recusada com 412.".to_owned()),
+          required: false,
+          deprecated: false,
+          allow_empty_value: false,
+          value: ParameterValue::Schema {
+              style: None,
+              explode: None,
+              allow_reserved: false,
+              schema: gen.json_schema::<String>(),
+              example: None,
+              examples: None,
+          },
+          extensions: Object::default(),
+      }))
+  }
+}
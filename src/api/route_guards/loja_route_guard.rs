@@ -0,0 +1,107 @@
+use rocket::{
+  http::Status,
+  request::{self, FromRequest, Outcome, Request},
+};
+use rocket_okapi::{
+  gen::OpenApiGenerator,
+  okapi::openapi3::{Object, SecurityRequirement, SecurityScheme, SecuritySchemeData},
+  request::{OpenApiFromRequest, RequestHeaderInput},
+  OpenApiError,
+};
+
+use crate::base::domain_error::DomainError;
+use crate::entities::loja::LOJA_PADRAO;
+use crate::traits::{loja_gateway::LojaGateway, token_validator_adapter::TokenValidatorAdapter};
+
+use std::sync::Arc;
+
+// Loja da requisição: claim `loja_id` do token Bearer, header X-Loja-Id ou a loja padrão.
+// O header só escolhe a loja quando o token não traz uma; se divergir da claim, é 403.
+pub struct LojaGuard(pub usize);
+
+// Marca, no cache da requisição, que a loja pedida não existe ou está inativa.
+struct LojaInexistente(bool);
+
+// Se o 404 da requisição veio do LojaGuard, e não do recurso da rota.
+pub fn loja_inexistente(req: &Request<'_>) -> bool {
+  req.local_cache(|| LojaInexistente(false)).0
+}
+
+fn resolve_loja_id(req: &Request<'_>) -> Result<usize, (Status, DomainError)> {
+  let do_header = match req.headers().get_one("X-Loja-Id") {
+      Some(loja_id) => Some(loja_id.trim().parse::<usize>().map_err(|_| {
+          (Status::BadRequest, DomainError::Invalid("X-Loja-Id".to_string()))
+      })?),
+      None => None,
+  };
+  let do_token = match req.headers().get_one("Authorization").and_then(|valor| valor.strip_prefix("Bearer ")) {
+      Some(token) => {
+          let token_validator = req
+              .rocket()
+              .state::<Arc<dyn TokenValidatorAdapter + Sync + Send>>()
+              .unwrap();
+          token_validator
+              .loja_id(token.trim().to_string())
+              .map_err(|erro| (Status::Unauthorized, erro))?
+      }
+      None => None,
+  };
+  match (do_token, do_header) {
+      (Some(do_token), Some(do_header)) if do_token != do_header => {
+          Err((Status::Forbidden, DomainError::Forbidden))
+      }
+      (Some(loja_id), _) | (None, Some(loja_id)) => Ok(loja_id),
+      (None, None) => Ok(LOJA_PADRAO),
+  }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for LojaGuard {
+  type Error = DomainError;
+
+  async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+      let loja_id = match resolve_loja_id(req) {
+          Ok(loja_id) => loja_id,
+          Err(erro) => return Outcome::Error(erro),
+      };
+      let loja_repository = req
+          .rocket()
+          .state::<Arc<dyn LojaGateway + Sync + Send>>()
+          .unwrap();
+      let loja = loja_repository.get_loja_by_id(loja_id).await;
+      match loja {
+          Ok(loja) if loja.ativa() => Outcome::Success(LojaGuard(loja_id)),
+          Ok(_) | Err(DomainError::NotFound) => {
+              req.local_cache(|| LojaInexistente(true));
+              Outcome::Error((Status::NotFound, DomainError::NotFound))
+          }
+          Err(erro) => Outcome::Error((Status::InternalServerError, erro)),
+      }
+  }
+}
+
+impl<'a> OpenApiFromRequest<'a> for LojaGuard {
+  fn from_request_input(
+      _gen: &mut OpenApiGenerator,
+      _name: String,
+      _required: bool,
+  ) -> Result<RequestHeaderInput, OpenApiError> {
+      let security_scheme = SecurityScheme {
+          description: Some("Loja da requisição, para tokens sem a claim loja_id. Com a claim, o header deve coincidir com ela.".to_owned()),
+
+          data: SecuritySchemeData::ApiKey {
+              name: "X-Loja-Id".to_owned(),
+              location: "header".to_owned(),
+          },
+          extensions: Object::default(),
+      };
+
+      let mut security_req = SecurityRequirement::new();
+      security_req.insert("X-Loja-Id".to_owned(), Vec::new());
+      Ok(RequestHeaderInput::Security(
+          "X-Loja-Id".to_owned(),
+          security_scheme,
+          security_req,
+      ))
+  }
+}
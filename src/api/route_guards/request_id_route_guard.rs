@@ -0,0 +1,33 @@
+use rocket::{
+  request::{self, FromRequest, Outcome, Request},
+};
+use rocket_okapi::{
+  gen::OpenApiGenerator,
+  request::{OpenApiFromRequest, RequestHeaderInput},
+  OpenApiError,
+};
+use std::convert::Infallible;
+
+use crate::api::request_id::request_id;
+
+// X-Request-Id da requisição, para quem precisa guardá-lo além dos logs (a auditoria, por exemplo).
+pub struct RequestIdGuard(pub String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RequestIdGuard {
+  type Error = Infallible;
+
+  async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+      Outcome::Success(RequestIdGuard(request_id(req)))
+  }
+}
+
+impl<'a> OpenApiFromRequest<'a> for RequestIdGuard {
+  fn from_request_input(
+      _gen: &mut OpenApiGenerator,
+      _name: String,
+      _required: bool,
+  ) -> Result<RequestHeaderInput, OpenApiError> {
+      Ok(RequestHeaderInput::None)
+  }
+}
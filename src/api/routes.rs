@@ -1,2 +1,8 @@
 pub mod pedido_route;
 pub mod produto_route;
+pub mod categoria_route;
+pub mod agenda_route;
+pub mod loja_route;
+pub mod cliente_route;
+pub mod saude_route;
+pub mod relatorio_route;
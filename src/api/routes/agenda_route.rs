@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::State;
+use rocket_okapi::{openapi, openapi_get_routes};
+
+use crate::api::error_handling::Problema;
+use crate::api::mensagens::Mensagem;
+use crate::api::route_guards::admin_route_guard::AdminGuard;
+use crate::controllers::agenda_controller::AgendaController;
+use crate::entities::agenda::Agenda;
+use crate::traits::{agenda_gateway, categoria_gateway, produto_gateway};
+use crate::use_cases::gerenciamento_de_agendas_use_case::CreateAgendaInput;
+
+#[openapi(tag = "Agendas")]
+#[get("/")]
+async fn get_agendas(
+    agenda_repository: &State<Arc<dyn agenda_gateway::AgendaGateway + Sync + Send>>,
+    produto_repository: &State<Arc<dyn produto_gateway::ProdutoGateway + Sync + Send>>,
+    categoria_repository: &State<Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>>,
+) -> Result<Json<Vec<Agenda>>, Problema> {
+    let agenda_controller = AgendaController::new(agenda_repository.inner().clone(), produto_repository.inner().clone(), categoria_repository.inner().clone());
+    let agendas = agenda_controller.get_agendas().await?;
+    Ok(Json(agendas))
+}
+
+#[openapi(tag = "Agendas")]
+#[post("/", data = "<agenda_input>")]
+async fn create_agenda(
+    agenda_repository: &State<Arc<dyn agenda_gateway::AgendaGateway + Sync + Send>>,
+    produto_repository: &State<Arc<dyn produto_gateway::ProdutoGateway + Sync + Send>>,
+    categoria_repository: &State<Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>>,
+    agenda_input: Json<CreateAgendaInput>,
+    _usuario_admin: AdminGuard,
+) -> Result<Json<Agenda>, Problema> {
+    let agenda_controller = AgendaController::new(agenda_repository.inner().clone(), produto_repository.inner().clone(), categoria_repository.inner().clone());
+    let agenda = agenda_controller.create_agenda(agenda_input.into_inner()).await?;
+    Ok(Json(agenda))
+}
+
+#[openapi(tag = "Agendas")]
+#[delete("/<id>")]
+async fn delete_agenda(
+    agenda_repository: &State<Arc<dyn agenda_gateway::AgendaGateway + Sync + Send>>,
+    produto_repository: &State<Arc<dyn produto_gateway::ProdutoGateway + Sync + Send>>,
+    categoria_repository: &State<Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>>,
+    id: usize,
+    _usuario_admin: AdminGuard,
+) -> Result<Json<String>, Problema> {
+    let agenda_controller = AgendaController::new(agenda_repository.inner().clone(), produto_repository.inner().clone(), categoria_repository.inner().clone());
+    agenda_controller.delete_agenda(id).await?;
+    Ok(Json("success".to_string()))
+}
+
+pub fn routes() -> Vec<rocket::Route> {
+    openapi_get_routes![get_agendas, create_agenda, delete_agenda]
+}
+
+#[catch(404)]
+fn agenda_not_found() -> Problema {
+    Problema::new(Status::NotFound, "nao_encontrado", Mensagem::AgendaNaoEncontrada)
+}
+
+pub fn catchers() -> Vec<rocket::Catcher> {
+    catchers![agenda_not_found]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::{http::{ContentType, Header}, local::blocking::Client};
+    use crate::{adapters::user_group_validator::UserGroupValidator, gateways::{in_memory_agenda_gateway::InMemoryAgendaRepository, in_memory_categoria_gateway::InMemoryCategoriaRepository}, traits::user_group_validator_adapter::UserGroupValidatorAdapter};
+
+    fn build_rocket() -> rocket::Rocket<rocket::Build> {
+        let agenda_gateway: Arc<dyn agenda_gateway::AgendaGateway + Sync + Send> = Arc::new(InMemoryAgendaRepository::new());
+        let categoria_gateway: Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send> = Arc::new(InMemoryCategoriaRepository::new());
+        let produto_gateway: Arc<dyn produto_gateway::ProdutoGateway + Sync + Send> = Arc::new(produto_gateway::MockProdutoGateway::new());
+        let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> =
+            Arc::new(UserGroupValidator::new());
+
+        rocket::build()
+            .mount("/", routes())
+            .register("/", catchers())
+            .manage(agenda_gateway)
+            .manage(categoria_gateway)
+            .manage(produto_gateway)
+            .manage(user_group_validator)
+    }
+
+    #[test]
+    fn test_create_and_list_agendas() {
+        let client = Client::tracked(build_rocket()).expect("valid rocket instance");
+        let response = client.post("/")
+            .header(ContentType::JSON)
+            .header(Header::new("UserGroup", "Admin"))
+            .body(r##"{
+                "alvo": "Categoria",
+                "alvo_id": 1,
+                "dias_semana": [1, 2, 3, 4, 5],
+                "hora_inicio": "06:00",
+                "hora_fim": "11:00"
+            }"##).dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = response.into_string().unwrap();
+        assert!(response.contains(r##""id":1"##));
+        assert!(response.contains(r##""preco":null"##));
+
+        let response = client.get("/").dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        assert!(response.into_string().unwrap().contains(r##""hora_fim":"11:00""##));
+    }
+
+    #[test]
+    fn test_create_agenda_invalida() {
+        let client = Client::tracked(build_rocket()).expect("valid rocket instance");
+        let response = client.post("/")
+            .header(ContentType::JSON)
+            .header(Header::new("UserGroup", "Admin"))
+            .body(r##"{
+                "alvo": "Categoria",
+                "alvo_id": 1,
+                "dias_semana": [9],
+                "hora_inicio": "06:00",
+                "hora_fim": "11:00"
+            }"##).dispatch();
+
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    #[test]
+    fn test_delete_agenda_not_found() {
+        let client = Client::tracked(build_rocket()).expect("valid rocket instance");
+        let response = client.delete("/1")
+            .header(Header::new("UserGroup", "Admin"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
@@ -0,0 +1,195 @@
+use std::sync::Arc;
+
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::State;
+use rocket_okapi::{openapi, openapi_get_routes};
+
+use crate::api::error_handling::Problema;
+use crate::api::mensagens::Mensagem;
+use crate::api::route_guards::admin_route_guard::AdminGuard;
+use crate::controllers::categoria_controller::CategoriaController;
+use crate::entities::categoria::Categoria;
+use crate::traits::{categoria_gateway, produto_gateway};
+use crate::use_cases::gerenciamento_de_categorias_use_case::{CreateCategoriaInput, UpdateCategoriaInput};
+
+#[openapi(tag = "Categorias")]
+#[get("/")]
+async fn get_categorias(
+    categoria_repository: &State<Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>>,
+    produto_repository: &State<Arc<dyn produto_gateway::ProdutoGateway + Sync + Send>>,
+) -> Result<Json<Vec<Categoria>>, Problema> {
+    let categoria_controller = CategoriaController::new(categoria_repository.inner().clone(), produto_repository.inner().clone());
+    let categorias = categoria_controller.get_categorias().await?;
+    Ok(Json(categorias))
+}
+
+#[openapi(tag = "Categorias")]
+#[get("/<id>")]
+async fn get_categoria_by_id(
+    categoria_repository: &State<Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>>,
+    produto_repository: &State<Arc<dyn produto_gateway::ProdutoGateway + Sync + Send>>,
+    id: usize,
+) -> Result<Json<Categoria>, Problema> {
+    let categoria_controller = CategoriaController::new(categoria_repository.inner().clone(), produto_repository.inner().clone());
+    let categoria = categoria_controller.get_categoria_by_id(id).await?;
+    Ok(Json(categoria))
+}
+
+#[openapi(tag = "Categorias")]
+#[post("/", data = "<categoria_input>")]
+async fn create_categoria(
+    categoria_repository: &State<Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>>,
+    produto_repository: &State<Arc<dyn produto_gateway::ProdutoGateway + Sync + Send>>,
+    categoria_input: Json<CreateCategoriaInput>,
+    _usuario_admin: AdminGuard,
+) -> Result<Json<Categoria>, Problema> {
+    let categoria_controller = CategoriaController::new(categoria_repository.inner().clone(), produto_repository.inner().clone());
+    let categoria = categoria_controller.create_categoria(categoria_input.into_inner()).await?;
+    Ok(Json(categoria))
+}
+
+#[openapi(tag = "Categorias")]
+#[put("/<id>", data = "<categoria_input>")]
+async fn update_categoria(
+    categoria_repository: &State<Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>>,
+    produto_repository: &State<Arc<dyn produto_gateway::ProdutoGateway + Sync + Send>>,
+    categoria_input: Json<UpdateCategoriaInput>,
+    id: usize,
+    _usuario_admin: AdminGuard,
+) -> Result<Json<Categoria>, Problema> {
+    let categoria_controller = CategoriaController::new(categoria_repository.inner().clone(), produto_repository.inner().clone());
+    let categoria = categoria_controller.update_categoria(id, categoria_input.into_inner()).await?;
+    Ok(Json(categoria))
+}
+
+#[openapi(tag = "Categorias")]
+#[delete("/<id>")]
+async fn delete_categoria(
+    categoria_repository: &State<Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>>,
+    produto_repository: &State<Arc<dyn produto_gateway::ProdutoGateway + Sync + Send>>,
+    id: usize,
+    _usuario_admin: AdminGuard,
+) -> Result<Json<String>, Problema> {
+    let categoria_controller = CategoriaController::new(categoria_repository.inner().clone(), produto_repository.inner().clone());
+    categoria_controller.delete_categoria(id).await?;
+    Ok(Json("success".to_string()))
+}
+
+pub fn routes() -> Vec<rocket::Route> {
+    openapi_get_routes![get_categorias, get_categoria_by_id, create_categoria, update_categoria, delete_categoria]
+}
+
+#[catch(404)]
+fn categoria_not_found() -> Problema {
+    Problema::new(Status::NotFound, "nao_encontrado", Mensagem::CategoriaNaoEncontrada)
+}
+
+pub fn catchers() -> Vec<rocket::Catcher> {
+    catchers![categoria_not_found]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::{http::{ContentType, Header}, local::blocking::Client};
+    use crate::{adapters::user_group_validator::UserGroupValidator, gateways::in_memory_categoria_gateway::InMemoryCategoriaRepository, traits::user_group_validator_adapter::UserGroupValidatorAdapter};
+
+    fn build_rocket(mock_produto_gateway: produto_gateway::MockProdutoGateway) -> rocket::Rocket<rocket::Build> {
+        let categoria_gateway: Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send> = Arc::new(InMemoryCategoriaRepository::new());
+        let produto_gateway: Arc<dyn produto_gateway::ProdutoGateway + Sync + Send> = Arc::new(mock_produto_gateway);
+        let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> =
+            Arc::new(UserGroupValidator::new());
+
+        rocket::build()
+            .mount("/", routes())
+            .register("/", catchers())
+            .manage(categoria_gateway)
+            .manage(produto_gateway)
+            .manage(user_group_validator)
+    }
+
+    #[test]
+    fn test_get_categorias() {
+        let client = Client::tracked(build_rocket(produto_gateway::MockProdutoGateway::new())).expect("valid rocket instance");
+        let response = client.get("/").dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = response.into_string().unwrap();
+        assert!(response.contains(r##""nome":"Sobremesa""##));
+    }
+
+    #[test]
+    fn test_post_create_categoria() {
+        let client = Client::tracked(build_rocket(produto_gateway::MockProdutoGateway::new())).expect("valid rocket instance");
+        let response = client.post("/")
+            .header(ContentType::JSON)
+            .header(Header::new("UserGroup", "Admin"))
+            .body(r##"{
+                "nome": "Combos",
+                "icone": "combo.svg",
+                "ordem": 0,
+                "item_pedido": "Lanche"
+            }"##).dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = response.into_string().unwrap();
+        assert!(response.contains(r##""id":5"##));
+        assert!(response.contains(r##""ativa":true"##));
+        assert!(response.contains(r##""item_pedido":"Lanche""##));
+    }
+
+    #[test]
+    fn test_post_create_categoria_sem_permissao() {
+        let client = Client::tracked(build_rocket(produto_gateway::MockProdutoGateway::new())).expect("valid rocket instance");
+        let response = client.post("/")
+            .header(ContentType::JSON)
+            .header(Header::new("UserGroup", "Kitchen"))
+            .body(r##"{ "nome": "Combos", "ordem": 0 }"##)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+
+    #[test]
+    fn test_update_categoria() {
+        let client = Client::tracked(build_rocket(produto_gateway::MockProdutoGateway::new())).expect("valid rocket instance");
+        let response = client.put("/4")
+            .header(ContentType::JSON)
+            .header(Header::new("UserGroup", "Admin"))
+            .body(r##"{ "ativa": false }"##)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = response.into_string().unwrap();
+        assert!(response.contains(r##""ativa":false"##));
+    }
+
+    #[test]
+    fn test_delete_categoria() {
+        let mut mock_produto_gateway = produto_gateway::MockProdutoGateway::new();
+        mock_produto_gateway.expect_get_produtos().returning(|| Ok(vec![]));
+
+        let client = Client::tracked(build_rocket(mock_produto_gateway)).expect("valid rocket instance");
+        let response = client.delete("/4")
+            .header(Header::new("UserGroup", "Admin"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    #[test]
+    fn test_handle_not_found() {
+        let client = Client::tracked(build_rocket(produto_gateway::MockProdutoGateway::new())).expect("valid rocket instance");
+        let response = client.get("/42").dispatch();
+
+        assert_eq!(response.status(), Status::NotFound);
+
+        let response = response.into_string().unwrap();
+        assert!(response.contains(r##""title":"Categoria não encontrada!""##));
+        assert!(response.contains(r##""code":"nao_encontrado""##));
+    }
+}
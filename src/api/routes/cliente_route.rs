@@ -0,0 +1,436 @@
+use std::sync::Arc;
+
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::State;
+use rocket_okapi::{openapi, openapi_get_routes};
+
+use crate::api::error_handling::Problema;
+use crate::api::mensagens::Mensagem;
+use crate::api::route_guards::admin_route_guard::AdminGuard;
+use crate::api::route_guards::cliente_route_guard::ClienteGuard;
+use crate::api::route_guards::request_id_route_guard::RequestIdGuard;
+use crate::base::paginacao::Pagina;
+use crate::base::pseudonimo::Pseudonimizador;
+use crate::controllers::{
+    cliente_controller::ClienteController, fidelidade_controller::FidelidadeController,
+    protecao_de_dados_controller::ProtecaoDeDadosController,
+};
+use crate::entities::{
+    cliente::Cliente,
+    pedido::Pedido,
+    pontos::{MovimentoDePontos, RegrasDeFidelidade, SaldoDePontos},
+};
+use crate::traits::{auditoria_gateway, cliente_gateway, pedido_gateway, pontos_gateway};
+use crate::use_cases::gerenciamento_de_clientes_use_case::{CreateClienteInput, UpdateClienteInput};
+use crate::use_cases::programa_de_fidelidade_use_case::ResgateInput;
+use crate::use_cases::protecao_de_dados_use_case::{Anonimizacao, DadosDoTitular};
+
+// Dependências das rotas de LGPD, gerenciadas juntas pelo Rocket.
+pub struct EstadoDaProtecaoDeDados {
+    pub cliente_repository: Arc<dyn cliente_gateway::ClienteGateway + Sync + Send>,
+    pub pedido_repository: Arc<dyn pedido_gateway::PedidoGateway + Sync + Send>,
+    pub pontos_repository: Arc<dyn pontos_gateway::PontosGateway + Sync + Send>,
+    pub auditoria_repository: Arc<dyn auditoria_gateway::AuditoriaGateway + Sync + Send>,
+    pub pseudonimizador: Pseudonimizador,
+}
+
+impl EstadoDaProtecaoDeDados {
+    fn protecao_de_dados_controller(&self) -> ProtecaoDeDadosController {
+        ProtecaoDeDadosController::new(
+            self.cliente_repository.clone(),
+            self.pedido_repository.clone(),
+            self.pontos_repository.clone(),
+            self.auditoria_repository.clone(),
+            self.pseudonimizador.clone(),
+        )
+    }
+}
+
+#[openapi(tag = "Clientes")]
+#[get("/<cpf>")]
+async fn get_cliente_by_cpf(
+    cliente_repository: &State<Arc<dyn cliente_gateway::ClienteGateway + Sync + Send>>,
+    pedido_repository: &State<Arc<dyn pedido_gateway::PedidoGateway + Sync + Send>>,
+    cpf: String,
+) -> Result<Json<Cliente>, Problema> {
+    let cliente_controller = ClienteController::new(
+        cliente_repository.inner().clone(),
+        pedido_repository.inner().clone(),
+    );
+    let cliente = cliente_controller.get_cliente_by_cpf(cpf).await?;
+    Ok(Json(cliente))
+}
+
+#[openapi(tag = "Clientes")]
+#[post("/", data = "<cliente_input>")]
+async fn create_cliente(
+    cliente_repository: &State<Arc<dyn cliente_gateway::ClienteGateway + Sync + Send>>,
+    pedido_repository: &State<Arc<dyn pedido_gateway::PedidoGateway + Sync + Send>>,
+    cliente_input: Json<CreateClienteInput>,
+) -> Result<Json<Cliente>, Problema> {
+    let cliente_controller = ClienteController::new(
+        cliente_repository.inner().clone(),
+        pedido_repository.inner().clone(),
+    );
+    let cliente = cliente_controller.create_cliente(cliente_input.into_inner()).await?;
+    Ok(Json(cliente))
+}
+
+#[openapi(tag = "Clientes")]
+#[put("/<cpf>", data = "<cliente_input>")]
+async fn update_cliente(
+    cliente_repository: &State<Arc<dyn cliente_gateway::ClienteGateway + Sync + Send>>,
+    pedido_repository: &State<Arc<dyn pedido_gateway::PedidoGateway + Sync + Send>>,
+    cliente_input: Json<UpdateClienteInput>,
+    cpf: String,
+) -> Result<Json<Cliente>, Problema> {
+    let cliente_controller = ClienteController::new(
+        cliente_repository.inner().clone(),
+        pedido_repository.inner().clone(),
+    );
+    let cliente = cliente_controller.update_cliente(cpf, cliente_input.into_inner()).await?;
+    Ok(Json(cliente))
+}
+
+#[openapi(tag = "Clientes")]
+#[get("/<cpf>/pedidos?<pagina>&<tamanho>")]
+async fn get_historico_de_pedidos(
+    cliente_repository: &State<Arc<dyn cliente_gateway::ClienteGateway + Sync + Send>>,
+    pedido_repository: &State<Arc<dyn pedido_gateway::PedidoGateway + Sync + Send>>,
+    cliente: ClienteGuard,
+    cpf: String,
+    pagina: Option<usize>,
+    tamanho: Option<usize>,
+) -> Result<Json<Pagina<Pedido>>, Problema> {
+    let cliente_controller = ClienteController::new(
+        cliente_repository.inner().clone(),
+        pedido_repository.inner().clone(),
+    );
+    let pedidos = cliente_controller
+        .historico_de_pedidos(cpf, &cliente.0, pagina, tamanho)
+        .await?;
+    Ok(Json(pedidos))
+}
+
+#[openapi(tag = "Clientes")]
+#[get("/<cpf>/pontos")]
+async fn get_saldo_de_pontos(
+    pontos_repository: &State<Arc<dyn pontos_gateway::PontosGateway + Sync + Send>>,
+    pedido_repository: &State<Arc<dyn pedido_gateway::PedidoGateway + Sync + Send>>,
+    regras_de_fidelidade: &State<RegrasDeFidelidade>,
+    cliente: ClienteGuard,
+    cpf: String,
+) -> Result<Json<SaldoDePontos>, Problema> {
+    let fidelidade_controller = FidelidadeController::new(
+        pontos_repository.inner().clone(),
+        pedido_repository.inner().clone(),
+        regras_de_fidelidade.inner().clone(),
+    );
+    let saldo = fidelidade_controller.saldo(cpf, &cliente.0).await?;
+    Ok(Json(saldo))
+}
+
+#[openapi(tag = "Clientes")]
+#[get("/<cpf>/pontos/extrato?<pagina>&<tamanho>")]
+async fn get_extrato_de_pontos(
+    pontos_repository: &State<Arc<dyn pontos_gateway::PontosGateway + Sync + Send>>,
+    pedido_repository: &State<Arc<dyn pedido_gateway::PedidoGateway + Sync + Send>>,
+    regras_de_fidelidade: &State<RegrasDeFidelidade>,
+    cliente: ClienteGuard,
+    cpf: String,
+    pagina: Option<usize>,
+    tamanho: Option<usize>,
+) -> Result<Json<Pagina<MovimentoDePontos>>, Problema> {
+    let fidelidade_controller = FidelidadeController::new(
+        pontos_repository.inner().clone(),
+        pedido_repository.inner().clone(),
+        regras_de_fidelidade.inner().clone(),
+    );
+    let extrato = fidelidade_controller
+        .extrato(cpf, &cliente.0, pagina, tamanho)
+        .await?;
+    Ok(Json(extrato))
+}
+
+#[openapi(tag = "Clientes")]
+#[post("/<cpf>/pontos/resgates", data = "<resgate_input>")]
+async fn post_resgate_de_pontos(
+    pontos_repository: &State<Arc<dyn pontos_gateway::PontosGateway + Sync + Send>>,
+    pedido_repository: &State<Arc<dyn pedido_gateway::PedidoGateway + Sync + Send>>,
+    regras_de_fidelidade: &State<RegrasDeFidelidade>,
+    cliente: ClienteGuard,
+    cpf: String,
+    resgate_input: Json<ResgateInput>,
+) -> Result<Json<Pedido>, Problema> {
+    let fidelidade_controller = FidelidadeController::new(
+        pontos_repository.inner().clone(),
+        pedido_repository.inner().clone(),
+        regras_de_fidelidade.inner().clone(),
+    );
+    let pedido = fidelidade_controller
+        .resgata(cpf, &cliente.0, resgate_input.into_inner())
+        .await?;
+    Ok(Json(pedido))
+}
+
+// LGPD: exporta tudo o que está ligado ao CPF. Fica registrado na auditoria.
+#[openapi(tag = "Clientes")]
+#[get("/<cpf>/dados")]
+async fn get_dados_do_titular(
+    estado: &State<EstadoDaProtecaoDeDados>,
+    _usuario_admin: AdminGuard,
+    request_id: RequestIdGuard,
+    cpf: String,
+) -> Result<Json<DadosDoTitular>, Problema> {
+    let protecao_de_dados_controller = estado.protecao_de_dados_controller();
+    let dados = protecao_de_dados_controller
+        .exporta_dados(cpf, request_id.0)
+        .await?;
+    Ok(Json(dados))
+}
+
+// LGPD: troca o CPF dos pedidos por um pseudônimo e apaga cadastro e pontos do cliente.
+#[openapi(tag = "Clientes")]
+#[post("/<cpf>/anonimizacao")]
+async fn post_anonimizacao(
+    estado: &State<EstadoDaProtecaoDeDados>,
+    _usuario_admin: AdminGuard,
+    request_id: RequestIdGuard,
+    cpf: String,
+) -> Result<Json<Anonimizacao>, Problema> {
+    let protecao_de_dados_controller = estado.protecao_de_dados_controller();
+    let anonimizacao = protecao_de_dados_controller
+        .anonimiza(cpf, request_id.0)
+        .await?;
+    Ok(Json(anonimizacao))
+}
+
+pub fn routes() -> Vec<rocket::Route> {
+    openapi_get_routes![
+        get_cliente_by_cpf,
+        create_cliente,
+        update_cliente,
+        get_historico_de_pedidos,
+        get_saldo_de_pontos,
+        get_extrato_de_pontos,
+        post_resgate_de_pontos,
+        get_dados_do_titular,
+        post_anonimizacao
+    ]
+}
+
+#[catch(404)]
+fn cliente_not_found() -> Problema {
+    Problema::new(Status::NotFound, "nao_encontrado", Mensagem::ClienteNaoEncontrado)
+}
+
+pub fn catchers() -> Vec<rocket::Catcher> {
+    catchers![cliente_not_found]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::{http::{ContentType, Header}, local::blocking::Client};
+    use crate::api::cpf_mascarado::MascaraDeCpfs;
+    use crate::base::domain_error::DomainError;
+    use crate::entities::cpf::Cpf;
+    use crate::adapters::user_group_validator::UserGroupValidator;
+    use crate::gateways::{
+        in_memory_auditoria_gateway::InMemoryAuditoriaRepository,
+        in_memory_cliente_gateway::InMemoryClienteRepository,
+        in_memory_pedido_gateway::InMemoryPedidoRepository,
+        in_memory_pontos_gateway::InMemoryPontosRepository,
+    };
+    use crate::traits::token_validator_adapter::{MockTokenValidatorAdapter, TokenValidatorAdapter};
+    use crate::traits::user_group_validator_adapter::UserGroupValidatorAdapter;
+
+    fn build_rocket() -> rocket::Rocket<rocket::Build> {
+        let cliente_gateway: Arc<dyn cliente_gateway::ClienteGateway + Sync + Send> = Arc::new(InMemoryClienteRepository::new());
+        let pedido_gateway: Arc<dyn pedido_gateway::PedidoGateway + Sync + Send> = Arc::new(InMemoryPedidoRepository::new());
+        let pontos_gateway: Arc<dyn pontos_gateway::PontosGateway + Sync + Send> = Arc::new(InMemoryPontosRepository::new());
+        let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> = Arc::new(UserGroupValidator::new());
+
+        // O token "maria" é o da cliente 123.456.789-09; qualquer outro é inválido.
+        let mut mock_token_validator = MockTokenValidatorAdapter::new();
+        mock_token_validator.expect_cpf().returning(|token| match token.as_str() {
+            "maria" => Cpf::new("123.456.789-09".to_string()),
+            _ => Err(DomainError::Unauthorized),
+        });
+        let token_validator: Arc<dyn TokenValidatorAdapter + Sync + Send> = Arc::new(mock_token_validator);
+
+        rocket::build()
+            .mount("/", routes())
+            .register("/", catchers())
+            .attach(MascaraDeCpfs)
+            .manage(EstadoDaProtecaoDeDados {
+                cliente_repository: cliente_gateway.clone(),
+                pedido_repository: pedido_gateway.clone(),
+                pontos_repository: pontos_gateway.clone(),
+                auditoria_repository: Arc::new(InMemoryAuditoriaRepository::new()),
+                pseudonimizador: Pseudonimizador::new("chave".to_string()),
+            })
+            .manage(cliente_gateway)
+            .manage(pedido_gateway)
+            .manage(pontos_gateway)
+            .manage(RegrasDeFidelidade::new(1.0, 0.05, 365))
+            .manage(user_group_validator)
+            .manage(token_validator)
+    }
+
+    #[test]
+    fn test_create_and_update_cliente() {
+        let client = Client::tracked(build_rocket()).expect("valid rocket instance");
+        let response = client.post("/")
+            .header(ContentType::JSON)
+            .body(r##"{ "cpf": "123.456.789-09", "nome": "Maria", "email": "maria@email.com" }"##)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = response.into_string().unwrap();
+        assert!(response.contains(r##""cpf":"***.456.789-**""##));
+        assert!(response.contains(r##""aceita_marketing":false"##));
+
+        let response = client.post("/")
+            .header(ContentType::JSON)
+            .body(r##"{ "cpf": "12345678909", "nome": "Outra Maria" }"##)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Conflict);
+
+        let response = client.put("/123.456.789-09")
+            .header(ContentType::JSON)
+            .body(r##"{ "aceita_marketing": true }"##)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        assert!(response.into_string().unwrap().contains(r##""aceita_marketing":true"##));
+
+        let response = client.get("/12345678909").dispatch();
+        assert!(response.into_string().unwrap().contains(r##""nome":"Maria""##));
+
+        // O administrador vê o CPF completo.
+        let response = client.get("/12345678909")
+            .header(Header::new("UserGroup", "Admin"))
+            .dispatch();
+        assert!(response.into_string().unwrap().contains(r##""cpf":"12345678909""##));
+    }
+
+    #[test]
+    fn test_create_cliente_cpf_invalido() {
+        let client = Client::tracked(build_rocket()).expect("valid rocket instance");
+        let response = client.post("/")
+            .header(ContentType::JSON)
+            .body(r##"{ "cpf": "000.000.000-00", "nome": "Admin" }"##)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::BadRequest);
+        assert!(response.into_string().unwrap().contains(r##""field":"cpf""##));
+    }
+
+    #[test]
+    fn test_get_historico_de_pedidos() {
+        let client = Client::tracked(build_rocket()).expect("valid rocket instance");
+        let response = client.get("/123.456.789-09/pedidos?pagina=1&tamanho=5")
+            .header(Header::new("Authorization", "Bearer maria"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        let response = response.into_string().unwrap();
+        assert!(response.contains(r##""pagina":1"##));
+        assert!(response.contains(r##""tamanho":5"##));
+
+        let response = client.get("/097.855.456-60/pedidos")
+            .header(Header::new("Authorization", "Bearer maria"))
+            .dispatch();
+        assert_eq!(response.status(), Status::Forbidden);
+
+        let response = client.get("/123.456.789-09/pedidos?tamanho=1000")
+            .header(Header::new("Authorization", "Bearer maria"))
+            .dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+
+        let response = client.get("/123.456.789-09/pedidos").dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+
+        let response = client.get("/123.456.789-09/pedidos")
+            .header(Header::new("Authorization", "Bearer outro"))
+            .dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+
+    #[test]
+    fn test_pontos() {
+        let client = Client::tracked(build_rocket()).expect("valid rocket instance");
+        let response = client.get("/123.456.789-09/pontos")
+            .header(Header::new("Authorization", "Bearer maria"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        assert!(response.into_string().unwrap().contains(r##""saldo":0"##));
+
+        let response = client.get("/123.456.789-09/pontos/extrato?tamanho=10")
+            .header(Header::new("Authorization", "Bearer maria"))
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert!(response.into_string().unwrap().contains(r##""total":0"##));
+
+        // Sem saldo, não há o que resgatar.
+        let response = client.post("/123.456.789-09/pontos/resgates")
+            .header(Header::new("Authorization", "Bearer maria"))
+            .header(ContentType::JSON)
+            .body(r##"{ "pedido_id": 1, "pontos": 0 }"##)
+            .dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+
+        let response = client.get("/097.855.456-60/pontos")
+            .header(Header::new("Authorization", "Bearer maria"))
+            .dispatch();
+        assert_eq!(response.status(), Status::Forbidden);
+    }
+
+    #[test]
+    fn test_exporta_e_anonimiza_dados() {
+        let client = Client::tracked(build_rocket()).expect("valid rocket instance");
+        client.post("/")
+            .header(ContentType::JSON)
+            .body(r##"{ "cpf": "123.456.789-09", "nome": "Maria" }"##)
+            .dispatch();
+
+        let response = client.get("/123.456.789-09/dados").dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+
+        let response = client.get("/123.456.789-09/dados")
+            .header(Header::new("UserGroup", "Admin"))
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let corpo = response.into_string().unwrap();
+        assert!(corpo.contains(r##""nome":"Maria""##));
+        assert!(corpo.contains(r##""cpf":"12345678909""##));
+
+        let response = client.post("/123.456.789-09/anonimizacao")
+            .header(Header::new("UserGroup", "Admin"))
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let corpo = response.into_string().unwrap();
+        assert!(corpo.contains(r##""cadastro_removido":true"##));
+        assert!(!corpo.contains("12345678909"));
+
+        let response = client.get("/123.456.789-09").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn test_handle_not_found() {
+        let client = Client::tracked(build_rocket()).expect("valid rocket instance");
+        let response = client.get("/12345678909").dispatch();
+
+        assert_eq!(response.status(), Status::NotFound);
+
+        let response = response.into_string().unwrap();
+        assert!(response.contains(r##""title":"Cliente não encontrado!""##));
+        assert!(response.contains(r##""code":"nao_encontrado""##));
+    }
+}
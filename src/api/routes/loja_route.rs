@@ -0,0 +1,144 @@
+use std::sync::Arc;
+
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::State;
+use rocket_okapi::{openapi, openapi_get_routes};
+
+use crate::api::error_handling::Problema;
+use crate::api::mensagens::Mensagem;
+use crate::api::route_guards::admin_route_guard::AdminGuard;
+use crate::controllers::loja_controller::LojaController;
+use crate::entities::loja::Loja;
+use crate::traits::loja_gateway;
+use crate::use_cases::gerenciamento_de_lojas_use_case::{CreateLojaInput, UpdateLojaInput};
+
+#[openapi(tag = "Lojas")]
+#[get("/")]
+async fn get_lojas(
+    loja_repository: &State<Arc<dyn loja_gateway::LojaGateway + Sync + Send>>,
+) -> Result<Json<Vec<Loja>>, Problema> {
+    let loja_controller = LojaController::new(loja_repository.inner().clone());
+    let lojas = loja_controller.get_lojas().await?;
+    Ok(Json(lojas))
+}
+
+#[openapi(tag = "Lojas")]
+#[get("/<id>")]
+async fn get_loja_by_id(
+    loja_repository: &State<Arc<dyn loja_gateway::LojaGateway + Sync + Send>>,
+    id: usize,
+) -> Result<Json<Loja>, Problema> {
+    let loja_controller = LojaController::new(loja_repository.inner().clone());
+    let loja = loja_controller.get_loja_by_id(id).await?;
+    Ok(Json(loja))
+}
+
+#[openapi(tag = "Lojas")]
+#[post("/", data = "<loja_input>")]
+async fn create_loja(
+    loja_repository: &State<Arc<dyn loja_gateway::LojaGateway + Sync + Send>>,
+    loja_input: Json<CreateLojaInput>,
+    _usuario_admin: AdminGuard,
+) -> Result<Json<Loja>, Problema> {
+    let loja_controller = LojaController::new(loja_repository.inner().clone());
+    let loja = loja_controller.create_loja(loja_input.into_inner()).await?;
+    Ok(Json(loja))
+}
+
+#[openapi(tag = "Lojas")]
+#[put("/<id>", data = "<loja_input>")]
+async fn update_loja(
+    loja_repository: &State<Arc<dyn loja_gateway::LojaGateway + Sync + Send>>,
+    loja_input: Json<UpdateLojaInput>,
+    id: usize,
+    _usuario_admin: AdminGuard,
+) -> Result<Json<Loja>, Problema> {
+    let loja_controller = LojaController::new(loja_repository.inner().clone());
+    let loja = loja_controller.update_loja(id, loja_input.into_inner()).await?;
+    Ok(Json(loja))
+}
+
+pub fn routes() -> Vec<rocket::Route> {
+    openapi_get_routes![get_lojas, get_loja_by_id, create_loja, update_loja]
+}
+
+#[catch(404)]
+fn loja_not_found() -> Problema {
+    Problema::new(Status::NotFound, "nao_encontrado", Mensagem::LojaNaoEncontrada)
+}
+
+pub fn catchers() -> Vec<rocket::Catcher> {
+    catchers![loja_not_found]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::{http::{ContentType, Header}, local::blocking::Client};
+    use crate::{adapters::user_group_validator::UserGroupValidator, gateways::in_memory_loja_gateway::InMemoryLojaRepository, traits::user_group_validator_adapter::UserGroupValidatorAdapter};
+
+    fn build_rocket() -> rocket::Rocket<rocket::Build> {
+        let loja_gateway: Arc<dyn loja_gateway::LojaGateway + Sync + Send> = Arc::new(InMemoryLojaRepository::new());
+        let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> =
+            Arc::new(UserGroupValidator::new());
+
+        rocket::build()
+            .mount("/", routes())
+            .register("/", catchers())
+            .manage(loja_gateway)
+            .manage(user_group_validator)
+    }
+
+    #[test]
+    fn test_create_and_update_loja() {
+        let client = Client::tracked(build_rocket()).expect("valid rocket instance");
+        let response = client.post("/")
+            .header(ContentType::JSON)
+            .header(Header::new("UserGroup", "Admin"))
+            .body(r##"{ "nome": "Shopping" }"##)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = response.into_string().unwrap();
+        assert!(response.contains(r##""id":2"##));
+        assert!(response.contains(r##""ativa":true"##));
+
+        let response = client.put("/2")
+            .header(ContentType::JSON)
+            .header(Header::new("UserGroup", "Admin"))
+            .body(r##"{ "ativa": false }"##)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        assert!(response.into_string().unwrap().contains(r##""ativa":false"##));
+
+        let response = client.get("/").dispatch();
+        assert!(response.into_string().unwrap().contains(r##""nome":"Loja Principal""##));
+    }
+
+    #[test]
+    fn test_create_loja_sem_permissao() {
+        let client = Client::tracked(build_rocket()).expect("valid rocket instance");
+        let response = client.post("/")
+            .header(ContentType::JSON)
+            .header(Header::new("UserGroup", "Kitchen"))
+            .body(r##"{ "nome": "Shopping" }"##)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+
+    #[test]
+    fn test_handle_not_found() {
+        let client = Client::tracked(build_rocket()).expect("valid rocket instance");
+        let response = client.get("/42").dispatch();
+
+        assert_eq!(response.status(), Status::NotFound);
+
+        let response = response.into_string().unwrap();
+        assert!(response.contains(r##""title":"Loja não encontrada!""##));
+        assert!(response.contains(r##""code":"nao_encontrado""##));
+    }
+}
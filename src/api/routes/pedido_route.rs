@@ -1,115 +1,173 @@
 use std::sync::Arc;
 
-use rocket::http::Status;
+use chrono_tz::Tz;
 use rocket::serde::json::Json;
-use rocket::State;
+use rocket::{Request, State};
 use rocket_okapi::{openapi, openapi_get_routes};
-use tokio::sync::Mutex;
 
-use crate::api::error_handling::ErrorResponse;
+use crate::api::error_handling::{nao_encontrado, Problema};
+use crate::api::etag::ComEtag;
+use crate::api::mensagens::Mensagem;
+use crate::api::route_guards::cliente_route_guard::ClienteGuard;
+use crate::api::route_guards::idempotency_key_route_guard::IdempotencyKeyGuard;
+use crate::api::route_guards::if_match_route_guard::IfMatchGuard;
 use crate::api::route_guards::kitchen_route_guard::KitchenGuard;
+use crate::api::route_guards::loja_route_guard::LojaGuard;
 use crate::controllers::pedido_controller::PedidoController;
 use crate::entities::pedido::Pedido;
+use crate::use_cases::programa_de_fidelidade_use_case::ProgramaDeFidelidadeUseCase;
+
+use crate::traits::{agenda_gateway, categoria_gateway, cliente_gateway, idempotencia_gateway, pedido_gateway, produto_gateway};
+
+// Dependências das rotas de pedidos, gerenciadas juntas pelo Rocket.
+pub struct EstadoDosPedidos {
+    pub pedido_repository: Arc<dyn pedido_gateway::PedidoGateway + Sync + Send>,
+    pub produto_repository: Arc<dyn produto_gateway::ProdutoGateway + Sync + Send>,
+    pub categoria_repository: Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>,
+    pub agenda_repository: Arc<dyn agenda_gateway::AgendaGateway + Sync + Send>,
+    pub cliente_repository: Arc<dyn cliente_gateway::ClienteGateway + Sync + Send>,
+    pub idempotencia_repository: Arc<dyn idempotencia_gateway::IdempotenciaGateway + Sync + Send>,
+    pub programa_de_fidelidade_use_case: Arc<ProgramaDeFidelidadeUseCase>,
+    pub fuso: Tz,
+}
 
-use crate::traits::{pedido_gateway, produto_gateway};
-use crate::use_cases::pedidos_e_pagamentos_use_case::CreatePedidoInput;
+impl EstadoDosPedidos {
+    fn pedido_controller(&self) -> PedidoController {
+        PedidoController::new(
+            self.pedido_repository.clone(),
+            self.produto_repository.clone(),
+            self.categoria_repository.clone(),
+            self.agenda_repository.clone(),
+            self.cliente_repository.clone(),
+            self.idempotencia_repository.clone(),
+            self.programa_de_fidelidade_use_case.clone(),
+        )
+    }
+}
+use crate::use_cases::desempenho_da_cozinha_use_case::PrevisaoDePreparo;
+use crate::use_cases::pedidos_e_pagamentos_use_case::{CreatePedidoInput, PedidoRepetido};
 
 #[openapi(tag = "Pedidos")]
 #[get("/")]
 async fn get_pedidos(
-    pedido_repository: &State<Arc<Mutex<dyn pedido_gateway::PedidoGateway + Sync + Send>>>,
-    produto_repository: &State<Arc<Mutex<dyn produto_gateway::ProdutoGateway + Sync + Send>>>,
+    estado: &State<EstadoDosPedidos>,
     _usuario_cozinha: KitchenGuard,
-) -> Result<Json<Vec<Pedido>>, Status> {
-    let pedido_controller = PedidoController::new(
-        pedido_repository.inner().clone(),
-        produto_repository.inner().clone(),
-    );
-    let pedidos = pedido_controller.get_pedidos().await?;
+    loja: LojaGuard,
+) -> Result<Json<Vec<Pedido>>, Problema> {
+    let pedido_controller = estado.pedido_controller();
+    let pedidos = pedido_controller.get_pedidos(loja.0).await?;
     Ok(Json(pedidos))
 }
 
 #[openapi(tag = "Pedidos")]
 #[get("/<id>")]
 async fn get_pedido_by_id(
-    pedido_repository: &State<Arc<Mutex<dyn pedido_gateway::PedidoGateway + Sync + Send>>>,
-    produto_repository: &State<Arc<Mutex<dyn produto_gateway::ProdutoGateway + Sync + Send>>>,
+    estado: &State<EstadoDosPedidos>,
     id: usize,
     _usuario_cozinha: KitchenGuard,
-) -> Result<Json<Pedido>, Status> {
-    let pedido_controller = PedidoController::new(
-        pedido_repository.inner().clone(),
-        produto_repository.inner().clone(),
-    );
-    let pedido = pedido_controller.get_pedido_by_id(id).await?;
-    Ok(Json(pedido))
+    loja: LojaGuard,
+) -> Result<ComEtag<Pedido>, Problema> {
+    let pedido_controller = estado.pedido_controller();
+    let pedido = pedido_controller.get_pedido_by_id(id, loja.0).await?;
+    Ok(ComEtag::new(pedido.versao(), pedido))
 }
 
 #[openapi(tag = "Pedidos")]
 #[post("/", data = "<pedido_input>")]
 async fn post_novo_pedido(
-    pedido_repository: &State<Arc<Mutex<dyn pedido_gateway::PedidoGateway + Sync + Send>>>,
-    produto_repository: &State<Arc<Mutex<dyn produto_gateway::ProdutoGateway + Sync + Send>>>,
+    estado: &State<EstadoDosPedidos>,
     pedido_input: Json<CreatePedidoInput>,
-) -> Result<Json<Pedido>, Status> {
-    let pedido_controller = PedidoController::new(
-        pedido_repository.inner().clone(),
-        produto_repository.inner().clone(),
-    );
+    loja: LojaGuard,
+    idempotency_key: IdempotencyKeyGuard,
+) -> Result<Json<Pedido>, Problema> {
+    let pedido_controller = estado.pedido_controller();
     let pedido_input = pedido_input.into_inner();
-    let novo_pedido = pedido_controller.novo_pedido(pedido_input).await?;
+    let novo_pedido = match idempotency_key.0 {
+        Some(chave) => {
+            pedido_controller
+                .novo_pedido_idempotente(pedido_input, loja.0, chave, estado.fuso)
+                .await?
+        }
+        None => {
+            pedido_controller
+                .novo_pedido(pedido_input, loja.0, estado.fuso)
+                .await?
+        }
+    };
     Ok(Json(novo_pedido))
 }
 
+#[openapi(tag = "Pedidos")]
+#[post("/<id>/repetir")]
+async fn post_repete_pedido(
+    estado: &State<EstadoDosPedidos>,
+    id: usize,
+    cliente: ClienteGuard,
+) -> Result<Json<PedidoRepetido>, Problema> {
+    let pedido_controller = estado.pedido_controller();
+    let pedido_repetido = pedido_controller.repete_pedido(id, &cliente.0, estado.fuso).await?;
+    Ok(Json(pedido_repetido))
+}
+
 #[openapi(tag = "Pedidos")]
 #[get("/novos")]
 async fn get_pedidos_novos(
-    pedido_repository: &State<Arc<Mutex<dyn pedido_gateway::PedidoGateway + Sync + Send>>>,
-    produto_repository: &State<Arc<Mutex<dyn produto_gateway::ProdutoGateway + Sync + Send>>>,
+    estado: &State<EstadoDosPedidos>,
     _usuario_cozinha: KitchenGuard,
-) -> Result<Json<Vec<Pedido>>, Status> {
-    let pedido_controller = PedidoController::new(
-        pedido_repository.inner().clone(),
-        produto_repository.inner().clone(),
-    );
-    let pedidos_novos = pedido_controller.get_pedidos_novos().await?;
+    loja: LojaGuard,
+) -> Result<Json<Vec<Pedido>>, Problema> {
+    let pedido_controller = estado.pedido_controller();
+    let pedidos_novos = pedido_controller.get_pedidos_novos(loja.0).await?;
     Ok(Json(pedidos_novos))
 }
 
 #[openapi(tag = "Pedidos")]
 #[put("/<id>/status/<status>")]
 async fn put_status_pedido(
-    pedido_repository: &State<Arc<Mutex<dyn pedido_gateway::PedidoGateway + Sync + Send>>>,
-    produto_repository: &State<Arc<Mutex<dyn produto_gateway::ProdutoGateway + Sync + Send>>>,
+    estado: &State<EstadoDosPedidos>,
     id: usize,
     status: &str,
     _usuario_cozinha: KitchenGuard,
-) -> Result<Json<Pedido>, Status> {
-    let pedido_controller = PedidoController::new(
-        pedido_repository.inner().clone(),
-        produto_repository.inner().clone(),
-    );
-    let pedido = pedido_controller.atualiza_status_pedido(id, status).await?;
-    Ok(Json(pedido))
+    loja: LojaGuard,
+    if_match: IfMatchGuard,
+) -> Result<ComEtag<Pedido>, Problema> {
+    let pedido_controller = estado.pedido_controller();
+    let pedido = pedido_controller
+        .atualiza_status_pedido(id, status, loja.0, if_match.0)
+        .await
+        .map_err(|erro| if_match.problema(erro))?;
+    Ok(ComEtag::new(pedido.versao(), pedido))
+}
+
+// Quando o pedido deve ficar pronto, estimado a partir do tempo que a cozinha da loja tem
+// levado em cada etapa. Sem guarda de cozinha: é o que o totem mostra ao cliente.
+#[openapi(tag = "Pedidos")]
+#[get("/<id>/previsao")]
+async fn get_previsao_de_preparo(
+    estado: &State<EstadoDosPedidos>,
+    id: usize,
+    loja: LojaGuard,
+) -> Result<Json<PrevisaoDePreparo>, Problema> {
+    let pedido_controller = estado.pedido_controller();
+    let previsao = pedido_controller.previsao_de_preparo(id, loja.0, estado.fuso).await?;
+    Ok(Json(previsao))
 }
 
 pub fn routes() -> Vec<rocket::Route> {
     openapi_get_routes![
         get_pedidos,
         get_pedido_by_id,
+        get_previsao_de_preparo,
         post_novo_pedido,
+        post_repete_pedido,
         get_pedidos_novos,
         put_status_pedido,
     ]
 }
 
 #[catch(404)]
-fn pedido_not_found() -> Json<ErrorResponse> {
-    let error = ErrorResponse {
-        msg: "Pedido não encontrado!".to_string(),
-        status: 404,
-    };
-    Json(error)
+fn pedido_not_found(req: &Request<'_>) -> Problema {
+    nao_encontrado(req, Mensagem::PedidoNaoEncontrado)
 }
 
 pub fn catchers() -> Vec<rocket::Catcher> {
@@ -119,18 +177,29 @@ pub fn catchers() -> Vec<rocket::Catcher> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::{DateTime, Utc};
+    use mockall::predicate::eq;
     use crate::{
         adapters::user_group_validator::UserGroupValidator,
-        base::domain_error::DomainError,
+        base::{domain_error::DomainError, paginacao::{Pagina, Paginacao}},
+        gateways::{in_memory_agenda_gateway::InMemoryAgendaRepository, in_memory_categoria_gateway::InMemoryCategoriaRepository, in_memory_cliente_gateway::InMemoryClienteRepository, in_memory_idempotencia_gateway::InMemoryIdempotenciaRepository, in_memory_loja_gateway::InMemoryLojaRepository, in_memory_pontos_gateway::InMemoryPontosRepository},
         entities::{
+            cpf::Cpf,
             ingredientes::Ingredientes,
             pedido,
-            produto::{Categoria, Produto},
+            pontos::RegrasDeFidelidade,
+            produto::Produto,
+            transicao_de_status::TransicaoDeStatus,
+        },
+        traits::{
+            loja_gateway::LojaGateway,
+            token_validator_adapter::{MockTokenValidatorAdapter, TokenValidatorAdapter},
+            user_group_validator_adapter::UserGroupValidatorAdapter,
         },
-        traits::user_group_validator_adapter::UserGroupValidatorAdapter,
     };
+    use crate::use_cases::relatorios_de_vendas_use_case::FUSO_PADRAO;
     use rocket::{
-        http::{ContentType, Header},
+        http::{ContentType, Header, Status},
         local::blocking::Client,
     };
 
@@ -149,27 +218,49 @@ mod tests {
         )
     }
 
+    // Rotas de pedidos com os gateways informados e os demais em memória.
+    fn build_rocket(
+        pedido_gateway: impl pedido_gateway::PedidoGateway + Sync + Send + 'static,
+        produto_gateway: impl produto_gateway::ProdutoGateway + Sync + Send + 'static,
+    ) -> rocket::Rocket<rocket::Build> {
+        let pedido_gateway: Arc<dyn pedido_gateway::PedidoGateway + Sync + Send> = Arc::new(pedido_gateway);
+        let programa_de_fidelidade_use_case = ProgramaDeFidelidadeUseCase::new(
+            Arc::new(InMemoryPontosRepository::new()),
+            pedido_gateway.clone(),
+            RegrasDeFidelidade::new(1.0, 0.05, 365),
+        );
+        let estado = EstadoDosPedidos {
+            pedido_repository: pedido_gateway,
+            produto_repository: Arc::new(produto_gateway),
+            categoria_repository: Arc::new(InMemoryCategoriaRepository::new()),
+            agenda_repository: Arc::new(InMemoryAgendaRepository::new()),
+            cliente_repository: Arc::new(InMemoryClienteRepository::new()),
+            idempotencia_repository: Arc::new(InMemoryIdempotenciaRepository::new(
+                std::time::Duration::from_secs(60),
+            )),
+            programa_de_fidelidade_use_case: Arc::new(programa_de_fidelidade_use_case),
+            fuso: FUSO_PADRAO,
+        };
+        let loja_gateway: Arc<dyn LojaGateway + Sync + Send> = Arc::new(InMemoryLojaRepository::new());
+        let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> =
+            Arc::new(UserGroupValidator::new());
+
+        rocket::build()
+            .mount("/", routes())
+            .manage(estado)
+            .manage(loja_gateway)
+            .manage(user_group_validator)
+    }
+
     #[test]
     fn test_get_pedidos() {
         let mut mock_pedido_gateway = pedido_gateway::MockPedidoGateway::new();
         mock_pedido_gateway
             .expect_lista_pedidos()
             .times(1)
-            .returning(|| Ok(vec![]));
+            .returning(|_| Ok(vec![]));
 
-        let pedido_gateway: Arc<Mutex<dyn pedido_gateway::PedidoGateway + Sync + Send>> =
-            Arc::new(Mutex::new(mock_pedido_gateway));
-        let produto_gateway: Arc<Mutex<dyn produto_gateway::ProdutoGateway + Sync + Send>> =
-            Arc::new(Mutex::new(produto_gateway::MockProdutoGateway::new()));
-        let user_group_validator = UserGroupValidator::new();
-        let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> =
-            Arc::new(user_group_validator);
-
-        let rocket = rocket::build()
-            .mount("/", routes())
-            .manage(pedido_gateway)
-            .manage(produto_gateway)
-            .manage(user_group_validator);
+        let rocket = build_rocket(mock_pedido_gateway, produto_gateway::MockProdutoGateway::new());
 
         let client = Client::tracked(rocket).expect("valid rocket instance");
         let response = client
@@ -184,30 +275,65 @@ mod tests {
     fn test_get_pedido_by_id() {
         let mut mock_pedido_gateway = pedido_gateway::MockPedidoGateway::new();
         mock_pedido_gateway
-            .expect_get_pedido_by_id()
+            .expect_get_pedido_da_loja()
             .times(1)
-            .returning(|_| Ok(create_valid_pedido()));
+            .returning(|_, _| Ok(create_valid_pedido()));
 
-        let pedido_gateway: Arc<Mutex<dyn pedido_gateway::PedidoGateway + Sync + Send>> =
-            Arc::new(Mutex::new(mock_pedido_gateway));
-        let produto_gateway: Arc<Mutex<dyn produto_gateway::ProdutoGateway + Sync + Send>> =
-            Arc::new(Mutex::new(produto_gateway::MockProdutoGateway::new()));
-        let user_group_validator = UserGroupValidator::new();
-        let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> =
-            Arc::new(user_group_validator);
+        let rocket = build_rocket(mock_pedido_gateway, produto_gateway::MockProdutoGateway::new());
 
-        let rocket = rocket::build()
-            .mount("/", routes())
-            .manage(pedido_gateway)
-            .manage(produto_gateway)
-            .manage(user_group_validator);
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+        let response = client
+            .get("/1")
+            .header(Header::new("UserGroup", "Kitchen"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    #[test]
+    fn test_loja_do_token_prevalece_sobre_o_header() {
+        let mut mock_pedido_gateway = pedido_gateway::MockPedidoGateway::new();
+        mock_pedido_gateway
+            .expect_get_pedido_da_loja()
+            .times(2)
+            .with(eq(1), eq(1))
+            .returning(|_, _| Ok(create_valid_pedido()));
+        let mut mock_token_validator = MockTokenValidatorAdapter::new();
+        mock_token_validator
+            .expect_loja_id()
+            .returning(|token| match token.as_str() {
+                "cozinha" => Ok(Some(1)),
+                _ => Ok(None),
+            });
+
+        let token_validator: Arc<dyn TokenValidatorAdapter + Sync + Send> = Arc::new(mock_token_validator);
+
+        let rocket = build_rocket(mock_pedido_gateway, produto_gateway::MockProdutoGateway::new())
+            .manage(token_validator);
 
         let client = Client::tracked(rocket).expect("valid rocket instance");
         let response = client
             .get("/1")
             .header(Header::new("UserGroup", "Kitchen"))
+            .header(Header::new("Authorization", "Bearer cozinha"))
+            .header(Header::new("X-Loja-Id", "2"))
             .dispatch();
+        assert_eq!(response.status(), Status::Forbidden);
 
+        let response = client
+            .get("/1")
+            .header(Header::new("UserGroup", "Kitchen"))
+            .header(Header::new("Authorization", "Bearer cozinha"))
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+
+        // Token sem loja (ex.: de cliente): vale o header.
+        let response = client
+            .get("/1")
+            .header(Header::new("UserGroup", "Kitchen"))
+            .header(Header::new("Authorization", "Bearer cliente"))
+            .header(Header::new("X-Loja-Id", "1"))
+            .dispatch();
         assert_eq!(response.status(), Status::Ok);
     }
 
@@ -216,19 +342,19 @@ mod tests {
         let mut mock_pedido_gateway = pedido_gateway::MockPedidoGateway::new();
         mock_pedido_gateway
             .expect_create_pedido()
-            .times(1)
+            .times(2)
             .returning(|_| Ok(create_valid_pedido()));
 
         let mut mock_produto_gateway = produto_gateway::MockProdutoGateway::new();
         mock_produto_gateway
-            .expect_get_produto_by_id()
+            .expect_get_produtos_da_loja()
             .returning(|_| {
-                Ok(Produto::new(
+                Ok(vec![Produto::new(
                     1,
                     "Nome".to_string(),
                     "Foto".to_string(),
                     "Descricao".to_string(),
-                    Categoria::Lanche,
+                    1,
                     1.0,
                     Ingredientes::new(vec![
                         "Pão".to_string(),
@@ -238,18 +364,10 @@ mod tests {
                     .unwrap(),
                     "2021-10-10".to_string(),
                     "2021-10-10".to_string(),
-                ))
+                )])
             });
 
-        let pedido_gateway: Arc<Mutex<dyn pedido_gateway::PedidoGateway + Sync + Send>> =
-            Arc::new(Mutex::new(mock_pedido_gateway));
-        let produto_gateway: Arc<Mutex<dyn produto_gateway::ProdutoGateway + Sync + Send>> =
-            Arc::new(Mutex::new(mock_produto_gateway));
-
-        let rocket = rocket::build()
-            .mount("/", routes())
-            .manage(pedido_gateway)
-            .manage(produto_gateway);
+        let rocket = build_rocket(mock_pedido_gateway, mock_produto_gateway);
 
         let client = Client::tracked(rocket).expect("valid rocket instance");
         let response = client
@@ -263,6 +381,33 @@ mod tests {
             .dispatch();
 
         assert_eq!(response.status(), Status::Ok);
+
+        // Com Idempotency-Key, a repetição devolve o pedido sem criar outro.
+        for _ in 0..2 {
+            let response = client
+                .post("/")
+                .header(ContentType::JSON)
+                .header(Header::new("Idempotency-Key", "quiosque-7-0001"))
+                .body(r##"{"lanche_id": 1}"##)
+                .dispatch();
+            assert_eq!(response.status(), Status::Ok);
+        }
+
+        let response = client
+            .post("/")
+            .header(ContentType::JSON)
+            .header(Header::new("Idempotency-Key", "quiosque-7-0001"))
+            .body(r##"{"lanche_id": 2}"##)
+            .dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+
+        let response = client
+            .post("/")
+            .header(ContentType::JSON)
+            .header(Header::new("Idempotency-Key", " "))
+            .body(r##"{"lanche_id": 1}"##)
+            .dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
     }
 
     #[test]
@@ -271,21 +416,9 @@ mod tests {
         mock_pedido_gateway
             .expect_get_pedidos_novos()
             .times(1)
-            .returning(|| Ok(vec![create_valid_pedido()]));
+            .returning(|_| Ok(vec![create_valid_pedido()]));
 
-        let pedido_gateway: Arc<Mutex<dyn pedido_gateway::PedidoGateway + Sync + Send>> =
-            Arc::new(Mutex::new(mock_pedido_gateway));
-        let produto_gateway: Arc<Mutex<dyn produto_gateway::ProdutoGateway + Sync + Send>> =
-            Arc::new(Mutex::new(produto_gateway::MockProdutoGateway::new()));
-        let user_group_validator = UserGroupValidator::new();
-        let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> =
-            Arc::new(user_group_validator);
-
-        let rocket = rocket::build()
-            .mount("/", routes())
-            .manage(pedido_gateway)
-            .manage(produto_gateway)
-            .manage(user_group_validator);
+        let rocket = build_rocket(mock_pedido_gateway, produto_gateway::MockProdutoGateway::new());
 
         let client = Client::tracked(rocket).expect("valid rocket instance");
         let response = client
@@ -294,29 +427,37 @@ mod tests {
             .dispatch();
 
         assert_eq!(response.status(), Status::Ok);
+
+        let response = client
+            .get("/novos")
+            .header(Header::new("UserGroup", "Kitchen"))
+            .header(Header::new("X-Loja-Id", "2"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::NotFound);
+
+        let response = client
+            .get("/novos")
+            .header(Header::new("UserGroup", "Kitchen"))
+            .header(Header::new("X-Loja-Id", "centro"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::BadRequest);
     }
 
     #[test]
     fn test_put_status_pedido() {
         let mut mock_pedido_gateway = pedido_gateway::MockPedidoGateway::new();
         mock_pedido_gateway
-            .expect_atualiza_status()
+            .expect_get_pedido_da_loja()
             .times(1)
             .returning(|_, _| Ok(create_valid_pedido()));
+        mock_pedido_gateway
+            .expect_atualiza_status()
+            .times(1)
+            .returning(|_, _, _| Ok(create_valid_pedido()));
 
-        let pedido_gateway: Arc<Mutex<dyn pedido_gateway::PedidoGateway + Sync + Send>> =
-            Arc::new(Mutex::new(mock_pedido_gateway));
-        let produto_gateway: Arc<Mutex<dyn produto_gateway::ProdutoGateway + Sync + Send>> =
-            Arc::new(Mutex::new(produto_gateway::MockProdutoGateway::new()));
-        let user_group_validator = UserGroupValidator::new();
-        let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> =
-            Arc::new(user_group_validator);
-
-        let rocket = rocket::build()
-            .mount("/", routes())
-            .manage(pedido_gateway)
-            .manage(produto_gateway)
-            .manage(user_group_validator);
+        let rocket = build_rocket(mock_pedido_gateway, produto_gateway::MockProdutoGateway::new());
 
         let client = Client::tracked(rocket).expect("valid rocket instance");
         let response = client
@@ -325,30 +466,145 @@ mod tests {
             .dispatch();
 
         assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.headers().get_one("ETag"), Some("\"1\""));
     }
 
     #[test]
-    fn test_handle_not_found() {
+    fn test_put_status_pedido_com_if_match() {
+        use crate::api::error_handling::generic_catchers;
+        use crate::gateways::in_memory_pedido_gateway::InMemoryPedidoRepository;
+
+        let rocket = build_rocket(InMemoryPedidoRepository::new(), produto_gateway::MockProdutoGateway::new())
+            .register("/", generic_catchers());
+
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+        let response = client
+            .get("/1")
+            .header(Header::new("UserGroup", "Kitchen"))
+            .dispatch();
+        assert_eq!(response.headers().get_one("ETag"), Some("\"1\""));
+
+        let response = client
+            .put("/1/status/EmPreparacao")
+            .header(Header::new("UserGroup", "Kitchen"))
+            .header(Header::new("If-Match", "\"1\""))
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.headers().get_one("ETag"), Some("\"2\""));
+
+        // A outra cozinha ainda tem a versão 1.
+        let response = client
+            .put("/1/status/Cancelado")
+            .header(Header::new("UserGroup", "Kitchen"))
+            .header(Header::new("If-Match", "\"1\""))
+            .dispatch();
+        assert_eq!(response.status(), Status::PreconditionFailed);
+        let corpo: serde_json::Value = serde_json::from_str(&response.into_string().unwrap()).unwrap();
+        assert_eq!(corpo["code"], "conflito_de_versao");
+
+        let response = client
+            .put("/1/status/Cancelado")
+            .header(Header::new("UserGroup", "Kitchen"))
+            .header(Header::new("If-Match", "desconhecida"))
+            .dispatch();
+        assert_eq!(response.status(), Status::PreconditionFailed);
+
+        let response = client
+            .put("/1/status/Pronto")
+            .header(Header::new("UserGroup", "Kitchen"))
+            .header(Header::new("If-Match", "W/\"2\""))
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.headers().get_one("ETag"), Some("\"3\""));
+    }
+
+    #[test]
+    fn test_post_repete_pedido() {
+        let lanche = Produto::new(
+            1,
+            "X-Salada".to_string(),
+            "x-salada.png".to_string(),
+            "Pão, hambúrguer e salada".to_string(),
+            1,
+            18.0,
+            Ingredientes::new(vec!["Pão".to_string(), "Hambúrguer".to_string()]).unwrap(),
+            "2021-10-10".to_string(),
+            "2021-10-10".to_string(),
+        );
+
         let mut mock_pedido_gateway = pedido_gateway::MockPedidoGateway::new();
+        let original = lanche.clone();
         mock_pedido_gateway
             .expect_get_pedido_by_id()
+            .returning(move |_| {
+                let mut pedido = Pedido::new(
+                    4,
+                    Some(Cpf::new("123.456.789-09".to_string()).unwrap()),
+                    Some(original.clone()),
+                    None,
+                    None,
+                    None,
+                    pedido::Status::Finalizado,
+                    "2021-10-10".to_string(),
+                    "2021-10-10".to_string(),
+                );
+                pedido.set_loja_id(1);
+                Ok(pedido)
+            });
+        mock_pedido_gateway
+            .expect_create_pedido()
             .times(1)
-            .returning(|_| Err(DomainError::NotFound));
+            .returning(Ok);
 
-        let pedido_gateway: Arc<Mutex<dyn pedido_gateway::PedidoGateway + Sync + Send>> =
-            Arc::new(Mutex::new(mock_pedido_gateway));
-        let produto_gateway: Arc<Mutex<dyn produto_gateway::ProdutoGateway + Sync + Send>> =
-            Arc::new(Mutex::new(produto_gateway::MockProdutoGateway::new()));
-        let user_group_validator = UserGroupValidator::new();
-        let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> =
-            Arc::new(user_group_validator);
+        let mut mock_produto_gateway = produto_gateway::MockProdutoGateway::new();
+        mock_produto_gateway
+            .expect_get_produtos_da_loja()
+            .returning(move |_| Ok(vec![lanche.clone()]));
 
-        let rocket = rocket::build()
-            .mount("/", routes())
+        let mut mock_token_validator = MockTokenValidatorAdapter::new();
+        mock_token_validator.expect_cpf().returning(|token| match token.as_str() {
+            "maria" => Cpf::new("123.456.789-09".to_string()),
+            _ => Cpf::new("097.855.456-60".to_string()),
+        });
+
+        let token_validator: Arc<dyn TokenValidatorAdapter + Sync + Send> = Arc::new(mock_token_validator);
+
+        let rocket = build_rocket(mock_pedido_gateway, mock_produto_gateway)
             .register("/", catchers())
-            .manage(pedido_gateway)
-            .manage(produto_gateway)
-            .manage(user_group_validator);
+            .manage(token_validator);
+
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+        let response = client
+            .post("/4/repetir")
+            .header(Header::new("Authorization", "Bearer maria"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        let response = response.into_string().unwrap();
+        assert!(response.contains(r##""status":"Pendente""##));
+        assert!(response.contains(r##""indisponiveis":[]"##));
+
+        // O pedido de outro cliente não aparece para quem não é o dono.
+        let response = client
+            .post("/4/repetir")
+            .header(Header::new("Authorization", "Bearer joao"))
+            .dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+
+        let response = client.post("/4/repetir").dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+
+    #[test]
+    fn test_handle_not_found() {
+        let mut mock_pedido_gateway = pedido_gateway::MockPedidoGateway::new();
+        mock_pedido_gateway
+            .expect_get_pedido_da_loja()
+            .times(1)
+            .returning(|_, _| Err(DomainError::NotFound));
+
+        let rocket = build_rocket(mock_pedido_gateway, produto_gateway::MockProdutoGateway::new())
+            .register("/", catchers());
 
         let client = Client::tracked(rocket).expect("valid rocket instance");
         let response = client
@@ -359,9 +615,80 @@ mod tests {
         assert_eq!(response.status(), Status::NotFound);
 
         let response = response.into_string().unwrap();
-        assert_eq!(
-            r##"{"msg":"Pedido não encontrado!","status":404}"##,
-            response
-        );
+        assert!(response.contains(r##""title":"Pedido não encontrado!""##));
+        assert!(response.contains(r##""code":"nao_encontrado""##));
+
+        // Quem não existe é a loja, não o pedido.
+        let response = client
+            .get("/1")
+            .header(Header::new("UserGroup", "Kitchen"))
+            .header(Header::new("X-Loja-Id", "99"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::NotFound);
+        let response = response.into_string().unwrap();
+        assert!(response.contains(r##""title":"Loja não encontrada!""##));
+    }
+
+    // Gateway em que cada consulta de pedidos novos espera as demais chegarem à barreira.
+    struct PedidosNovosNaBarreira(tokio::sync::Barrier);
+
+    #[async_trait]
+    impl pedido_gateway::PedidoGateway for PedidosNovosNaBarreira {
+        async fn get_pedidos_novos(&self, _loja_id: usize) -> Result<Vec<Pedido>, DomainError> {
+            self.0.wait().await;
+            Ok(vec![])
+        }
+
+        async fn create_pedido(&self, _: Pedido) -> Result<Pedido, DomainError> { unreachable!() }
+        async fn lista_pedidos(&self, _: usize) -> Result<Vec<Pedido>, DomainError> { unreachable!() }
+        async fn get_pedido_by_id(&self, _: usize) -> Result<Pedido, DomainError> { unreachable!() }
+        async fn get_pedido_da_loja(&self, _: usize, _: usize) -> Result<Pedido, DomainError> { unreachable!() }
+        async fn lista_pedidos_do_cliente(&self, _: &Cpf, _: Paginacao) -> Result<Pagina<Pedido>, DomainError> { unreachable!() }
+        async fn pedidos_do_titular(&self, _: &Cpf) -> Result<Vec<Pedido>, DomainError> { unreachable!() }
+        async fn atualiza_status(&self, _: usize, _: pedido::Status, _: usize) -> Result<Pedido, DomainError> { unreachable!() }
+        async fn atualiza_pagamento_status(&self, _: usize, _: String, _: pedido::Status) -> Result<Pedido, DomainError> { unreachable!() }
+        async fn aplica_desconto(&self, _: usize, _: f64, _: usize) -> Result<Pedido, DomainError> { unreachable!() }
+        async fn anonimiza_pedidos_do_cliente(&self, _: &Cpf, _: &str) -> Result<usize, DomainError> { unreachable!() }
+        async fn conta_pedidos_por_status(&self) -> Result<Vec<(pedido::Status, usize)>, DomainError> { unreachable!() }
+        async fn pedidos_do_periodo(&self, _: usize, _: DateTime<Utc>, _: DateTime<Utc>) -> Result<Vec<Pedido>, DomainError> { unreachable!() }
+        async fn transicoes_do_pedido(&self, _: usize) -> Result<Vec<TransicaoDeStatus>, DomainError> { unreachable!() }
+        async fn transicoes_do_periodo(&self, _: usize, _: DateTime<Utc>, _: DateTime<Utc>) -> Result<Vec<TransicaoDeStatus>, DomainError> { unreachable!() }
+    }
+
+    #[rocket::async_test]
+    async fn test_requisicoes_concorrentes_nao_sao_serializadas() {
+        use rocket::local::asynchronous::Client;
+        use std::time::Duration;
+
+        // A barreira só abre com as REQUISICOES dentro do gateway ao mesmo tempo; com elas em
+        // fila, a primeira nunca volta e o teste estoura o ESPERA.
+        const REQUISICOES: usize = 10;
+        const ESPERA: Duration = Duration::from_secs(30);
+
+        let rocket = build_rocket(PedidosNovosNaBarreira(tokio::sync::Barrier::new(REQUISICOES)), produto_gateway::MockProdutoGateway::new());
+
+        let client = Arc::new(Client::tracked(rocket).await.expect("valid rocket instance"));
+
+        let mut requisicoes = tokio::task::JoinSet::new();
+        for _ in 0..REQUISICOES {
+            let client = client.clone();
+            requisicoes.spawn(async move {
+                client
+                    .get("/novos")
+                    .header(Header::new("UserGroup", "Kitchen"))
+                    .dispatch()
+                    .await
+                    .status()
+            });
+        }
+        let respostas = async {
+            while let Some(status) = requisicoes.join_next().await {
+                assert_eq!(status.unwrap(), Status::Ok);
+            }
+        };
+        tokio::time::timeout(ESPERA, respostas)
+            .await
+            .expect("as requisições foram atendidas uma de cada vez");
     }
 }
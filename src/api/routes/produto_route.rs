@@ -1,48 +1,150 @@
 use std::sync::Arc;
 
-use rocket::http::Status;
+use chrono_tz::Tz;
+
+use rocket::form::Form;
+use rocket::fs::TempFile;
+use rocket::data::{Data, ToByteUnit};
+use rocket::http::{ContentType, Status};
 use rocket::serde::json::Json;
-use rocket::State;
+use rocket::tokio::io::AsyncReadExt;
+use rocket::{Request, State};
 use rocket_okapi::{openapi, openapi_get_routes};
-use tokio::sync::Mutex;
+use schemars::JsonSchema;
 
-use crate::api::error_handling::ErrorResponse;
+use crate::api::error_handling::{nao_encontrado, Problema};
+use crate::api::etag::ComEtag;
+use crate::api::mensagens::Mensagem;
 use crate::api::route_guards::admin_route_guard::AdminGuard;
+use crate::api::route_guards::idioma_route_guard::IdiomaGuard;
+use crate::api::route_guards::if_match_route_guard::IfMatchGuard;
 use crate::api::route_guards::kitchen_route_guard::KitchenGuard;
+use crate::api::route_guards::loja_route_guard::LojaGuard;
+use crate::base::domain_error::DomainError;
 use crate::controllers::produto_controller::ProdutoController;
-use crate::traits::produto_gateway;
-use crate::use_cases::gerenciamento_de_produtos_use_case::{CreateProdutoInput, UpdateProdutoInput};
-use crate::entities::produto::Produto;
+use crate::traits::{agenda_gateway, categoria_gateway, foto_storage_adapter::FotoStorageAdapter, produto_gateway};
+use crate::use_cases::gerenciamento_de_produtos_use_case::{CreateProdutoInput, FotoInput, ProdutoLojaInput, SecaoCardapio, TraducaoInput, UpdateProdutoInput};
+use crate::use_cases::importacao_de_catalogo_use_case::{RelatorioImportacao, TAMANHO_MAXIMO_CATALOGO};
+use crate::entities::{idioma::TraducaoProduto, loja::ProdutoLoja, produto::Produto};
 
 #[openapi(tag = "Produtos")]
-#[get("/")]
+#[get("/?<em>")]
 async fn get_produtos(
-    produto_repository: &State<Arc<Mutex<dyn produto_gateway::ProdutoGateway + Sync + Send>>>,
-) -> Result<Json<Vec<Produto>>, Status> {
-    let produto_controller = ProdutoController::new(produto_repository.inner().clone());
-    let produtos = produto_controller.get_produto().await?;
+    produto_repository: &State<Arc<dyn produto_gateway::ProdutoGateway + Sync + Send>>,
+    categoria_repository: &State<Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>>,
+    agenda_repository: &State<Arc<dyn agenda_gateway::AgendaGateway + Sync + Send>>,
+    fuso: &State<Tz>,
+    em: Option<String>,
+    loja: LojaGuard,
+    idioma: IdiomaGuard,
+) -> Result<Json<Vec<Produto>>, Problema> {
+    let produto_controller = ProdutoController::new(produto_repository.inner().clone(), categoria_repository.inner().clone(), agenda_repository.inner().clone());
+    let produtos = produto_controller.get_produto(loja.0, idioma.0, em, *fuso.inner()).await?;
+    Ok(Json(produtos))
+}
+
+#[openapi(tag = "Produtos")]
+#[get("/cardapio?<em>")]
+async fn get_cardapio(
+    produto_repository: &State<Arc<dyn produto_gateway::ProdutoGateway + Sync + Send>>,
+    categoria_repository: &State<Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>>,
+    agenda_repository: &State<Arc<dyn agenda_gateway::AgendaGateway + Sync + Send>>,
+    fuso: &State<Tz>,
+    em: Option<String>,
+    loja: LojaGuard,
+    idioma: IdiomaGuard,
+) -> Result<Json<Vec<SecaoCardapio>>, Problema> {
+    let produto_controller = ProdutoController::new(produto_repository.inner().clone(), categoria_repository.inner().clone(), agenda_repository.inner().clone());
+    let cardapio = produto_controller.get_cardapio(loja.0, idioma.0, em, *fuso.inner()).await?;
+    Ok(Json(cardapio))
+}
+
+#[openapi(tag = "Produtos")]
+#[get("/busca?<q>")]
+async fn busca_produtos(
+    produto_repository: &State<Arc<dyn produto_gateway::ProdutoGateway + Sync + Send>>,
+    categoria_repository: &State<Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>>,
+    agenda_repository: &State<Arc<dyn agenda_gateway::AgendaGateway + Sync + Send>>,
+    q: String,
+    loja: LojaGuard,
+    idioma: IdiomaGuard,
+) -> Result<Json<Vec<Produto>>, Problema> {
+    let produto_controller = ProdutoController::new(produto_repository.inner().clone(), categoria_repository.inner().clone(), agenda_repository.inner().clone());
+    let produtos = produto_controller.busca_produtos(q, loja.0, idioma.0).await?;
     Ok(Json(produtos))
 }
 
+#[openapi(tag = "Produtos")]
+#[get("/catalogo?<formato>")]
+async fn exporta_catalogo(
+    produto_repository: &State<Arc<dyn produto_gateway::ProdutoGateway + Sync + Send>>,
+    categoria_repository: &State<Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>>,
+    agenda_repository: &State<Arc<dyn agenda_gateway::AgendaGateway + Sync + Send>>,
+    formato: Option<String>,
+    _usuario_admin: AdminGuard,
+) -> Result<(ContentType, String), Problema> {
+    let formato = formato.unwrap_or("json".to_string());
+    let produto_controller = ProdutoController::new(produto_repository.inner().clone(), categoria_repository.inner().clone(), agenda_repository.inner().clone());
+    let catalogo = produto_controller.exporta_catalogo(formato.clone()).await?;
+    let content_type = match formato.to_lowercase().as_str() {
+        "csv" => ContentType::CSV,
+        _ => ContentType::JSON,
+    };
+    Ok((content_type, catalogo))
+}
+
+#[openapi(tag = "Produtos")]
+#[post("/catalogo?<formato>&<dry_run>", data = "<catalogo>")]
+async fn importa_catalogo(
+    produto_repository: &State<Arc<dyn produto_gateway::ProdutoGateway + Sync + Send>>,
+    categoria_repository: &State<Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>>,
+    agenda_repository: &State<Arc<dyn agenda_gateway::AgendaGateway + Sync + Send>>,
+    formato: Option<String>,
+    dry_run: Option<bool>,
+    catalogo: Data<'_>,
+    _usuario_admin: AdminGuard,
+) -> Result<Json<RelatorioImportacao>, Problema> {
+    let conteudo = catalogo
+        .open(TAMANHO_MAXIMO_CATALOGO.bytes())
+        .into_string()
+        .await
+        .map_err(|_| Status::BadRequest)?;
+    if !conteudo.is_complete() {
+        return Err(Status::PayloadTooLarge.into());
+    }
+
+    let produto_controller = ProdutoController::new(produto_repository.inner().clone(), categoria_repository.inner().clone(), agenda_repository.inner().clone());
+    let relatorio = produto_controller
+        .importa_catalogo(conteudo.into_inner(), formato.unwrap_or("json".to_string()), dry_run.unwrap_or(false))
+        .await?;
+    Ok(Json(relatorio))
+}
+
 #[openapi(tag = "Produtos")]
 #[get("/<id>")]
 async fn get_produto_by_id(
-    produto_repository: &State<Arc<Mutex<dyn produto_gateway::ProdutoGateway + Sync + Send>>>,
+    produto_repository: &State<Arc<dyn produto_gateway::ProdutoGateway + Sync + Send>>,
+    categoria_repository: &State<Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>>,
+    agenda_repository: &State<Arc<dyn agenda_gateway::AgendaGateway + Sync + Send>>,
     id: usize,
-) -> Result<Json<Produto>, Status> {
-    let produto_controller = ProdutoController::new(produto_repository.inner().clone());
-    let produto = produto_controller.get_produto_by_id(id).await?;
-    Ok(Json(produto))
+    loja: LojaGuard,
+    idioma: IdiomaGuard,
+) -> Result<ComEtag<Produto>, Problema> {
+    let produto_controller = ProdutoController::new(produto_repository.inner().clone(), categoria_repository.inner().clone(), agenda_repository.inner().clone());
+    let produto = produto_controller.get_produto_by_id(id, loja.0, idioma.0).await?;
+    Ok(ComEtag::new(produto.versao(), produto))
 }
 
 #[openapi(tag = "Produtos")]
 #[post("/", data = "<produto_input>")]
 async fn create_produto(
-    produto_repository: &State<Arc<Mutex<dyn produto_gateway::ProdutoGateway + Sync + Send>>>,
+    produto_repository: &State<Arc<dyn produto_gateway::ProdutoGateway + Sync + Send>>,
+    categoria_repository: &State<Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>>,
+    agenda_repository: &State<Arc<dyn agenda_gateway::AgendaGateway + Sync + Send>>,
     produto_input: Json<CreateProdutoInput>,
     _usuario_cozinha: KitchenGuard,
-) -> Result<Json<Produto>, Status> {
-    let produto_controller = ProdutoController::new(produto_repository.inner().clone());
+) -> Result<Json<Produto>, Problema> {
+    let produto_controller = ProdutoController::new(produto_repository.inner().clone(), categoria_repository.inner().clone(), agenda_repository.inner().clone());
     let produto_input = produto_input.into_inner();
     let produto = produto_controller.create_produto(produto_input).await?;
     Ok(Json(produto))
@@ -51,40 +153,134 @@ async fn create_produto(
 #[openapi(tag = "Produtos")]
 #[put("/<id>", data = "<produto_input>")]
 async fn update_produto(
-    produto_repository: &State<Arc<Mutex<dyn produto_gateway::ProdutoGateway + Sync + Send>>>,
+    produto_repository: &State<Arc<dyn produto_gateway::ProdutoGateway + Sync + Send>>,
+    categoria_repository: &State<Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>>,
+    agenda_repository: &State<Arc<dyn agenda_gateway::AgendaGateway + Sync + Send>>,
     produto_input: Json<UpdateProdutoInput>,
     id: usize,
     _usuario_cozinha: KitchenGuard,
-) -> Result<Json<Produto>, Status> {
-    let produto_controller = ProdutoController::new(produto_repository.inner().clone());
+    if_match: IfMatchGuard,
+) -> Result<ComEtag<Produto>, Problema> {
+    let produto_controller = ProdutoController::new(produto_repository.inner().clone(), categoria_repository.inner().clone(), agenda_repository.inner().clone());
     let produto_input = produto_input.into_inner();
-    let produto = produto_controller.update_produto(id, produto_input).await?;
+    let produto = produto_controller
+        .update_produto(id, produto_input, if_match.0)
+        .await
+        .map_err(|erro| if_match.problema(erro))?;
+    Ok(ComEtag::new(produto.versao(), produto))
+}
+
+#[openapi(tag = "Produtos")]
+#[put("/<id>/loja", data = "<ajuste>")]
+async fn define_produto_loja(
+    produto_repository: &State<Arc<dyn produto_gateway::ProdutoGateway + Sync + Send>>,
+    categoria_repository: &State<Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>>,
+    agenda_repository: &State<Arc<dyn agenda_gateway::AgendaGateway + Sync + Send>>,
+    ajuste: Json<ProdutoLojaInput>,
+    id: usize,
+    _usuario_admin: AdminGuard,
+    loja: LojaGuard,
+) -> Result<Json<ProdutoLoja>, Problema> {
+    let produto_controller = ProdutoController::new(produto_repository.inner().clone(), categoria_repository.inner().clone(), agenda_repository.inner().clone());
+    let produto_loja = produto_controller.define_produto_loja(loja.0, id, ajuste.into_inner()).await?;
+    Ok(Json(produto_loja))
+}
+
+#[openapi(tag = "Produtos")]
+#[get("/<id>/traducoes")]
+async fn get_traducoes(
+    produto_repository: &State<Arc<dyn produto_gateway::ProdutoGateway + Sync + Send>>,
+    categoria_repository: &State<Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>>,
+    agenda_repository: &State<Arc<dyn agenda_gateway::AgendaGateway + Sync + Send>>,
+    id: usize,
+    _usuario_admin: AdminGuard,
+) -> Result<Json<Vec<TraducaoProduto>>, Problema> {
+    let produto_controller = ProdutoController::new(produto_repository.inner().clone(), categoria_repository.inner().clone(), agenda_repository.inner().clone());
+    let traducoes = produto_controller.get_traducoes(id).await?;
+    Ok(Json(traducoes))
+}
+
+#[openapi(tag = "Produtos")]
+#[put("/<id>/traducoes/<idioma>", data = "<traducao>")]
+async fn define_traducao(
+    produto_repository: &State<Arc<dyn produto_gateway::ProdutoGateway + Sync + Send>>,
+    categoria_repository: &State<Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>>,
+    agenda_repository: &State<Arc<dyn agenda_gateway::AgendaGateway + Sync + Send>>,
+    traducao: Json<TraducaoInput>,
+    id: usize,
+    idioma: String,
+    _usuario_admin: AdminGuard,
+) -> Result<Json<TraducaoProduto>, Problema> {
+    let produto_controller = ProdutoController::new(produto_repository.inner().clone(), categoria_repository.inner().clone(), agenda_repository.inner().clone());
+    let traducao = produto_controller.define_traducao(id, idioma, traducao.into_inner()).await?;
+    Ok(Json(traducao))
+}
+
+#[derive(FromForm, JsonSchema)]
+pub struct FotoUpload<'r> {
+    #[schemars(with = "Vec<u8>")]
+    foto: TempFile<'r>,
+}
+
+#[openapi(tag = "Produtos")]
+#[post("/<id>/foto", data = "<upload>")]
+async fn upload_foto(
+    produto_repository: &State<Arc<dyn produto_gateway::ProdutoGateway + Sync + Send>>,
+    categoria_repository: &State<Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>>,
+    agenda_repository: &State<Arc<dyn agenda_gateway::AgendaGateway + Sync + Send>>,
+    foto_storage: &State<Arc<dyn FotoStorageAdapter + Sync + Send>>,
+    id: usize,
+    upload: Form<FotoUpload<'_>>,
+    _usuario_cozinha: KitchenGuard,
+) -> Result<Json<Produto>, Problema> {
+    let content_type = match upload.foto.content_type() {
+        Some(content_type) => format!("{}/{}", content_type.top(), content_type.sub()),
+        None => {
+            return Err(DomainError::InvalidField {
+                field: "foto".to_string(),
+                message: "Tipo da foto não informado".to_string(),
+            }
+            .into())
+        }
+    };
+    let mut conteudo = Vec::new();
+    upload
+        .foto
+        .open()
+        .await
+        .map_err(|_| Status::BadRequest)?
+        .read_to_end(&mut conteudo)
+        .await
+        .map_err(|_| Status::BadRequest)?;
+
+    let produto_controller = ProdutoController::new(produto_repository.inner().clone(), categoria_repository.inner().clone(), agenda_repository.inner().clone());
+    let produto = produto_controller
+        .atualiza_foto(id, FotoInput { content_type, conteudo }, foto_storage.inner().clone())
+        .await?;
     Ok(Json(produto))
 }
 
 #[openapi(tag = "Produtos")]
 #[delete("/<id>")]
 async fn delete_produto(
-    produto_repository: &State<Arc<Mutex<dyn produto_gateway::ProdutoGateway + Sync + Send>>>,
+    produto_repository: &State<Arc<dyn produto_gateway::ProdutoGateway + Sync + Send>>,
+    categoria_repository: &State<Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>>,
+    agenda_repository: &State<Arc<dyn agenda_gateway::AgendaGateway + Sync + Send>>,
     id: usize,
     _usuario_cozinha: AdminGuard,
-) -> Result<Json<String>, Status> {
-    let produto_controller = ProdutoController::new(produto_repository.inner().clone());
+) -> Result<Json<String>, Problema> {
+    let produto_controller = ProdutoController::new(produto_repository.inner().clone(), categoria_repository.inner().clone(), agenda_repository.inner().clone());
     produto_controller.delete_produto(id).await?;
     Ok(Json("success".to_string()))
 }
 
 pub fn routes() -> Vec<rocket::Route> {
-    openapi_get_routes![get_produtos, get_produto_by_id, create_produto, update_produto, delete_produto]
+    openapi_get_routes![get_produtos, get_cardapio, busca_produtos, exporta_catalogo, importa_catalogo, get_produto_by_id, create_produto, update_produto, define_produto_loja, get_traducoes, define_traducao, upload_foto, delete_produto]
 }
 
 #[catch(404)]
-fn produto_not_found() -> Json<ErrorResponse> {
-    let error = ErrorResponse {
-        msg: "Produto não encontrado!".to_string(),
-        status: 404,
-    };
-    Json(error)
+fn produto_not_found(req: &Request<'_>) -> Problema {
+    nao_encontrado(req, Mensagem::ProdutoNaoEncontrado)
 }
 
 pub fn catchers() -> Vec<rocket::Catcher> {
@@ -95,7 +291,8 @@ pub fn catchers() -> Vec<rocket::Catcher> {
 mod tests {
     use super::*;
     use rocket::{http::{ContentType, Header}, local::blocking::Client};
-    use crate::{adapters::user_group_validator::UserGroupValidator, base::domain_error::DomainError, entities::{ingredientes::Ingredientes, produto::{Categoria, Produto}}, traits::user_group_validator_adapter::UserGroupValidatorAdapter};
+    use crate::{adapters::user_group_validator::UserGroupValidator, base::domain_error::DomainError, entities::{ingredientes::Ingredientes, produto::Produto}, gateways::{in_memory_agenda_gateway::InMemoryAgendaRepository, in_memory_categoria_gateway::InMemoryCategoriaRepository, in_memory_loja_gateway::InMemoryLojaRepository, in_memory_produto_gateway::InMemoryProdutoRepository}, traits::{foto_storage_adapter::MockFotoStorageAdapter, loja_gateway::LojaGateway, user_group_validator_adapter::UserGroupValidatorAdapter}};
+    use crate::use_cases::relatorios_de_vendas_use_case::FUSO_PADRAO;
 
     fn create_valid_produto() -> Produto {
         Produto::new(
@@ -103,7 +300,7 @@ mod tests {
             "Nome".to_string(),
             "Foto".to_string(),
             "Descricao".to_string(),
-            Categoria::Lanche,
+            1,
             1.0,
             Ingredientes::new(vec![
                 "Pão".to_string(),
@@ -119,11 +316,15 @@ mod tests {
     fn test_get_produtos() {
         let mut mock_produto_gateway = produto_gateway::MockProdutoGateway::new();
         mock_produto_gateway
-            .expect_get_produtos()
+            .expect_get_produtos_da_loja()
             .times(1)
-            .returning(|| Ok(vec![create_valid_produto()]));
+            .returning(|_| Ok(vec![create_valid_produto()]));
 
-        let produto_gateway: Arc<Mutex<dyn produto_gateway::ProdutoGateway + Sync + Send>> = Arc::new(Mutex::new(mock_produto_gateway));
+        let produto_gateway: Arc<dyn produto_gateway::ProdutoGateway + Sync + Send> = Arc::new(mock_produto_gateway);
+        let foto_storage: Arc<dyn FotoStorageAdapter + Sync + Send> = Arc::new(MockFotoStorageAdapter::new());
+        let categoria_gateway: Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send> = Arc::new(InMemoryCategoriaRepository::new());
+        let agenda_gateway: Arc<dyn agenda_gateway::AgendaGateway + Sync + Send> = Arc::new(InMemoryAgendaRepository::new());
+        let loja_gateway: Arc<dyn LojaGateway + Sync + Send> = Arc::new(InMemoryLojaRepository::new());
         let user_group_validator = UserGroupValidator::new();
         let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> =
             Arc::new(user_group_validator);
@@ -131,6 +332,11 @@ mod tests {
         let rocket = rocket::build()
             .mount("/", routes())
             .manage(produto_gateway)
+            .manage(foto_storage)
+            .manage(categoria_gateway)
+            .manage(agenda_gateway)
+            .manage(FUSO_PADRAO)
+            .manage(loja_gateway)
             .manage(user_group_validator);
 
         let client = Client::tracked(rocket).expect("valid rocket instance");
@@ -141,6 +347,45 @@ mod tests {
         assert_eq!(response.status(), Status::Ok);
     }
 
+    #[test]
+    fn test_get_produtos_em() {
+        let mut mock_produto_gateway = produto_gateway::MockProdutoGateway::new();
+        mock_produto_gateway
+            .expect_get_produtos_da_loja()
+            .times(1)
+            .returning(|_| Ok(vec![create_valid_produto()]));
+
+        let produto_gateway: Arc<dyn produto_gateway::ProdutoGateway + Sync + Send> = Arc::new(mock_produto_gateway);
+        let foto_storage: Arc<dyn FotoStorageAdapter + Sync + Send> = Arc::new(MockFotoStorageAdapter::new());
+        let categoria_gateway: Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send> = Arc::new(InMemoryCategoriaRepository::new());
+        let agenda_gateway: Arc<dyn agenda_gateway::AgendaGateway + Sync + Send> = Arc::new(InMemoryAgendaRepository::new());
+        let loja_gateway: Arc<dyn LojaGateway + Sync + Send> = Arc::new(InMemoryLojaRepository::new());
+
+        let rocket = rocket::build()
+            .mount("/", routes())
+            .manage(produto_gateway)
+            .manage(foto_storage)
+            .manage(categoria_gateway)
+            .manage(agenda_gateway)
+            .manage(FUSO_PADRAO)
+            .manage(loja_gateway);
+
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+        let response = client.get("/?em=2024-05-06T08:00:00-03:00").dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = client.get("/?em=ontem").dispatch();
+
+        assert_eq!(response.status(), Status::BadRequest);
+        assert_eq!(response.content_type().unwrap().to_string(), "application/problem+json");
+
+        let response = response.into_string().unwrap();
+        assert!(response.contains(r##""code":"campo_invalido""##));
+        assert!(response.contains(r##""field":"em""##));
+        assert!(response.contains(r##""detail":"Data inválida: ontem""##));
+    }
+
     #[test]
     fn test_get_produto_by_id() {
         let mut mock_produto_gateway = produto_gateway::MockProdutoGateway::new();
@@ -148,8 +393,16 @@ mod tests {
             .expect_get_produto_by_id()
             .times(1)
             .returning(|_| Ok(create_valid_produto()));
+        mock_produto_gateway
+            .expect_get_produtos_loja()
+            .times(1)
+            .returning(|_| Ok(vec![]));
 
-        let produto_gateway: Arc<Mutex<dyn produto_gateway::ProdutoGateway + Sync + Send>> = Arc::new(Mutex::new(mock_produto_gateway));
+        let produto_gateway: Arc<dyn produto_gateway::ProdutoGateway + Sync + Send> = Arc::new(mock_produto_gateway);
+        let foto_storage: Arc<dyn FotoStorageAdapter + Sync + Send> = Arc::new(MockFotoStorageAdapter::new());
+        let categoria_gateway: Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send> = Arc::new(InMemoryCategoriaRepository::new());
+        let agenda_gateway: Arc<dyn agenda_gateway::AgendaGateway + Sync + Send> = Arc::new(InMemoryAgendaRepository::new());
+        let loja_gateway: Arc<dyn LojaGateway + Sync + Send> = Arc::new(InMemoryLojaRepository::new());
         let user_group_validator = UserGroupValidator::new();
         let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> =
             Arc::new(user_group_validator);
@@ -157,6 +410,11 @@ mod tests {
         let rocket = rocket::build()
             .mount("/", routes())
             .manage(produto_gateway)
+            .manage(foto_storage)
+            .manage(categoria_gateway)
+            .manage(agenda_gateway)
+            .manage(FUSO_PADRAO)
+            .manage(loja_gateway)
             .manage(user_group_validator);
 
         let client = Client::tracked(rocket).expect("valid rocket instance");
@@ -167,6 +425,95 @@ mod tests {
         assert_eq!(response.status(), Status::Ok);
     }
 
+    #[test]
+    fn test_get_cardapio() {
+        let mut mock_produto_gateway = produto_gateway::MockProdutoGateway::new();
+        mock_produto_gateway
+            .expect_get_produtos_da_loja()
+            .times(1)
+            .returning(|_| Ok(vec![create_valid_produto()]));
+
+        let produto_gateway: Arc<dyn produto_gateway::ProdutoGateway + Sync + Send> = Arc::new(mock_produto_gateway);
+        let foto_storage: Arc<dyn FotoStorageAdapter + Sync + Send> = Arc::new(MockFotoStorageAdapter::new());
+        let categoria_gateway: Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send> = Arc::new(InMemoryCategoriaRepository::new());
+        let agenda_gateway: Arc<dyn agenda_gateway::AgendaGateway + Sync + Send> = Arc::new(InMemoryAgendaRepository::new());
+        let loja_gateway: Arc<dyn LojaGateway + Sync + Send> = Arc::new(InMemoryLojaRepository::new());
+
+        let rocket = rocket::build()
+            .mount("/", routes())
+            .manage(produto_gateway)
+            .manage(foto_storage)
+            .manage(categoria_gateway)
+            .manage(agenda_gateway)
+            .manage(FUSO_PADRAO)
+            .manage(loja_gateway);
+
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+        let response = client.get("/cardapio").dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = response.into_string().unwrap();
+        assert!(response.starts_with(r##"[{"categoria":{"id":1,"nome":"Lanche""##));
+        assert!(response.contains(r##""produtos":[{"id":1,"nome":"Nome""##));
+    }
+
+    #[test]
+    fn test_busca_produtos() {
+        let mut mock_produto_gateway = produto_gateway::MockProdutoGateway::new();
+        mock_produto_gateway
+            .expect_busca_produtos()
+            .times(1)
+            .withf(|termo, loja_id| termo == "hambúrguer" && *loja_id == 1)
+            .returning(|_, _| Ok(vec![create_valid_produto()]));
+
+        let produto_gateway: Arc<dyn produto_gateway::ProdutoGateway + Sync + Send> = Arc::new(mock_produto_gateway);
+        let foto_storage: Arc<dyn FotoStorageAdapter + Sync + Send> = Arc::new(MockFotoStorageAdapter::new());
+        let categoria_gateway: Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send> = Arc::new(InMemoryCategoriaRepository::new());
+        let agenda_gateway: Arc<dyn agenda_gateway::AgendaGateway + Sync + Send> = Arc::new(InMemoryAgendaRepository::new());
+        let loja_gateway: Arc<dyn LojaGateway + Sync + Send> = Arc::new(InMemoryLojaRepository::new());
+
+        let rocket = rocket::build()
+            .mount("/", routes())
+            .manage(produto_gateway)
+            .manage(foto_storage)
+            .manage(categoria_gateway)
+            .manage(agenda_gateway)
+            .manage(FUSO_PADRAO)
+            .manage(loja_gateway);
+
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+        let response = client.get("/busca?q=hamb%C3%BArguer").dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = response.into_string().unwrap();
+        assert!(response.contains(r##""nome":"Nome""##));
+    }
+
+    #[test]
+    fn test_busca_produtos_termo_vazio() {
+        let produto_gateway: Arc<dyn produto_gateway::ProdutoGateway + Sync + Send> = Arc::new(produto_gateway::MockProdutoGateway::new());
+        let foto_storage: Arc<dyn FotoStorageAdapter + Sync + Send> = Arc::new(MockFotoStorageAdapter::new());
+        let categoria_gateway: Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send> = Arc::new(InMemoryCategoriaRepository::new());
+        let agenda_gateway: Arc<dyn agenda_gateway::AgendaGateway + Sync + Send> = Arc::new(InMemoryAgendaRepository::new());
+        let loja_gateway: Arc<dyn LojaGateway + Sync + Send> = Arc::new(InMemoryLojaRepository::new());
+
+        let rocket = rocket::build()
+            .mount("/", routes())
+            .manage(produto_gateway)
+            .manage(foto_storage)
+            .manage(categoria_gateway)
+            .manage(agenda_gateway)
+            .manage(FUSO_PADRAO)
+            .manage(loja_gateway);
+
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+        let response = client.get("/busca?q=").dispatch();
+
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
     #[test]
     fn test_post_create_produto() {
         let mut mock_produto_gateway = produto_gateway::MockProdutoGateway::new();
@@ -175,7 +522,11 @@ mod tests {
             .times(1)
             .returning(|produto| Ok(produto));
 
-        let produto_gateway: Arc<Mutex<dyn produto_gateway::ProdutoGateway + Sync + Send>> = Arc::new(Mutex::new(mock_produto_gateway));
+        let produto_gateway: Arc<dyn produto_gateway::ProdutoGateway + Sync + Send> = Arc::new(mock_produto_gateway);
+        let foto_storage: Arc<dyn FotoStorageAdapter + Sync + Send> = Arc::new(MockFotoStorageAdapter::new());
+        let categoria_gateway: Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send> = Arc::new(InMemoryCategoriaRepository::new());
+        let agenda_gateway: Arc<dyn agenda_gateway::AgendaGateway + Sync + Send> = Arc::new(InMemoryAgendaRepository::new());
+        let loja_gateway: Arc<dyn LojaGateway + Sync + Send> = Arc::new(InMemoryLojaRepository::new());
         let user_group_validator = UserGroupValidator::new();
         let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> =
             Arc::new(user_group_validator);
@@ -183,6 +534,11 @@ mod tests {
         let rocket = rocket::build()
             .mount("/", routes())
             .manage(produto_gateway)
+            .manage(foto_storage)
+            .manage(categoria_gateway)
+            .manage(agenda_gateway)
+            .manage(FUSO_PADRAO)
+            .manage(loja_gateway)
             .manage(user_group_validator);
 
         let client = Client::tracked(rocket).expect("valid rocket instance");
@@ -193,7 +549,7 @@ mod tests {
                 "nome": "teste",
                 "foto": "foto.png",
                 "descricao": "Descrição",
-                "categoria": "Lanche",
+                "categoria_id": 1,
                 "preco": 9.50,
                 "ingredientes": [
                     "Pão",
@@ -205,7 +561,7 @@ mod tests {
 
         let response = response.into_string().unwrap();
         assert_eq!(response.contains(r##""id":0"##), true);
-        assert_eq!(response.contains(r##""categoria":"Lanche""##), true);
+        assert!(response.contains(r##""categoria_id":1"##));
         assert_eq!(response.contains(r##""ingredientes":["Pão","Hambúrguer"]"##), true);
     }
 
@@ -222,7 +578,11 @@ mod tests {
             .times(1)
             .returning(|produto| Ok(produto));
 
-        let produto_gateway: Arc<Mutex<dyn produto_gateway::ProdutoGateway + Sync + Send>> = Arc::new(Mutex::new(mock_produto_gateway));
+        let produto_gateway: Arc<dyn produto_gateway::ProdutoGateway + Sync + Send> = Arc::new(mock_produto_gateway);
+        let foto_storage: Arc<dyn FotoStorageAdapter + Sync + Send> = Arc::new(MockFotoStorageAdapter::new());
+        let categoria_gateway: Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send> = Arc::new(InMemoryCategoriaRepository::new());
+        let agenda_gateway: Arc<dyn agenda_gateway::AgendaGateway + Sync + Send> = Arc::new(InMemoryAgendaRepository::new());
+        let loja_gateway: Arc<dyn LojaGateway + Sync + Send> = Arc::new(InMemoryLojaRepository::new());
         let user_group_validator = UserGroupValidator::new();
         let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> =
             Arc::new(user_group_validator);
@@ -230,6 +590,11 @@ mod tests {
         let rocket = rocket::build()
             .mount("/", routes())
             .manage(produto_gateway)
+            .manage(foto_storage)
+            .manage(categoria_gateway)
+            .manage(agenda_gateway)
+            .manage(FUSO_PADRAO)
+            .manage(loja_gateway)
             .manage(user_group_validator);
 
         let client = Client::tracked(rocket).expect("valid rocket instance");
@@ -238,7 +603,7 @@ mod tests {
             .header(Header::new("UserGroup", "Kitchen"))
             .body(r##"{
                 "nome": "Novo Nome",
-                "categoria": "Sobremesa",
+                "categoria_id": 4,
                 "preco": 15.70
             }"##).dispatch();
 
@@ -246,10 +611,102 @@ mod tests {
 
         let response = response.into_string().unwrap();
         assert_eq!(response.contains(r##""nome":"Novo Nome""##), true);
-        assert_eq!(response.contains(r##""categoria":"Sobremesa""##), true);
+        assert!(response.contains(r##""categoria_id":4"##));
         assert_eq!(response.contains(r##""preco":15.7"##), true);
     }
 
+    fn create_multipart_body(content_type: &str, conteudo: &[u8]) -> Vec<u8> {
+        let mut body = Vec::new();
+        body.extend_from_slice(b"--BOUNDARY\r\n");
+        body.extend_from_slice(b"Content-Disposition: form-data; name=\"foto\"; filename=\"foto\"\r\n");
+        body.extend_from_slice(format!("Content-Type: {}\r\n\r\n", content_type).as_bytes());
+        body.extend_from_slice(conteudo);
+        body.extend_from_slice(b"\r\n--BOUNDARY--\r\n");
+        body
+    }
+
+    #[test]
+    fn test_upload_foto() {
+        let mut mock_produto_gateway = produto_gateway::MockProdutoGateway::new();
+        mock_produto_gateway
+            .expect_get_produto_by_id()
+            .times(1)
+            .returning(|_| Ok(create_valid_produto()));
+        mock_produto_gateway
+            .expect_update_produto()
+            .times(1)
+            .returning(Ok);
+
+        let mut mock_foto_storage = MockFotoStorageAdapter::new();
+        mock_foto_storage
+            .expect_salva_foto()
+            .times(1)
+            .returning(|nome_arquivo, _| Ok(format!("/fotos/{}", nome_arquivo)));
+        mock_foto_storage.expect_remove_foto().times(1).returning(|_| Ok(()));
+
+        let produto_gateway: Arc<dyn produto_gateway::ProdutoGateway + Sync + Send> = Arc::new(mock_produto_gateway);
+        let foto_storage: Arc<dyn FotoStorageAdapter + Sync + Send> = Arc::new(mock_foto_storage);
+        let categoria_gateway: Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send> = Arc::new(InMemoryCategoriaRepository::new());
+        let agenda_gateway: Arc<dyn agenda_gateway::AgendaGateway + Sync + Send> = Arc::new(InMemoryAgendaRepository::new());
+        let loja_gateway: Arc<dyn LojaGateway + Sync + Send> = Arc::new(InMemoryLojaRepository::new());
+        let user_group_validator = UserGroupValidator::new();
+        let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> =
+            Arc::new(user_group_validator);
+
+        let rocket = rocket::build()
+            .mount("/", routes())
+            .manage(produto_gateway)
+            .manage(foto_storage)
+            .manage(categoria_gateway)
+            .manage(agenda_gateway)
+            .manage(FUSO_PADRAO)
+            .manage(loja_gateway)
+            .manage(user_group_validator);
+
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+        let response = client.post("/1/foto")
+            .header(ContentType::new("multipart", "form-data").with_params(("boundary", "BOUNDARY")))
+            .header(Header::new("UserGroup", "Kitchen"))
+            .body(create_multipart_body("image/png", b"\x89PNG\r\n\x1a\n0000"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = response.into_string().unwrap();
+        assert!(response.contains(r##""foto":"/fotos/produto-1-"##));
+    }
+
+    #[test]
+    fn test_upload_foto_tipo_invalido() {
+        let produto_gateway: Arc<dyn produto_gateway::ProdutoGateway + Sync + Send> = Arc::new(produto_gateway::MockProdutoGateway::new());
+        let foto_storage: Arc<dyn FotoStorageAdapter + Sync + Send> = Arc::new(MockFotoStorageAdapter::new());
+        let categoria_gateway: Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send> = Arc::new(InMemoryCategoriaRepository::new());
+        let agenda_gateway: Arc<dyn agenda_gateway::AgendaGateway + Sync + Send> = Arc::new(InMemoryAgendaRepository::new());
+        let loja_gateway: Arc<dyn LojaGateway + Sync + Send> = Arc::new(InMemoryLojaRepository::new());
+        let user_group_validator = UserGroupValidator::new();
+        let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> =
+            Arc::new(user_group_validator);
+
+        let rocket = rocket::build()
+            .mount("/", routes())
+            .manage(produto_gateway)
+            .manage(foto_storage)
+            .manage(categoria_gateway)
+            .manage(agenda_gateway)
+            .manage(FUSO_PADRAO)
+            .manage(loja_gateway)
+            .manage(user_group_validator);
+
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+        let response = client.post("/1/foto")
+            .header(ContentType::new("multipart", "form-data").with_params(("boundary", "BOUNDARY")))
+            .header(Header::new("UserGroup", "Kitchen"))
+            .body(create_multipart_body("text/plain", b"texto"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
     #[test]
     fn test_delete_produto() {
         let mut mock_produto_gateway = produto_gateway::MockProdutoGateway::new();
@@ -258,7 +715,11 @@ mod tests {
             .times(1)
             .returning(|_| Ok(()));
 
-        let produto_gateway: Arc<Mutex<dyn produto_gateway::ProdutoGateway + Sync + Send>> = Arc::new(Mutex::new(mock_produto_gateway));
+        let produto_gateway: Arc<dyn produto_gateway::ProdutoGateway + Sync + Send> = Arc::new(mock_produto_gateway);
+        let foto_storage: Arc<dyn FotoStorageAdapter + Sync + Send> = Arc::new(MockFotoStorageAdapter::new());
+        let categoria_gateway: Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send> = Arc::new(InMemoryCategoriaRepository::new());
+        let agenda_gateway: Arc<dyn agenda_gateway::AgendaGateway + Sync + Send> = Arc::new(InMemoryAgendaRepository::new());
+        let loja_gateway: Arc<dyn LojaGateway + Sync + Send> = Arc::new(InMemoryLojaRepository::new());
         let user_group_validator = UserGroupValidator::new();
         let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> =
             Arc::new(user_group_validator);
@@ -266,6 +727,11 @@ mod tests {
         let rocket = rocket::build()
             .mount("/", routes())
             .manage(produto_gateway)
+            .manage(foto_storage)
+            .manage(categoria_gateway)
+            .manage(agenda_gateway)
+            .manage(FUSO_PADRAO)
+            .manage(loja_gateway)
             .manage(user_group_validator);
 
         let client = Client::tracked(rocket).expect("valid rocket instance");
@@ -276,6 +742,166 @@ mod tests {
         assert_eq!(response.status(), Status::Ok);
     }
 
+    #[test]
+    fn test_exporta_e_importa_catalogo() {
+        let produto_gateway: Arc<dyn produto_gateway::ProdutoGateway + Sync + Send> = Arc::new(InMemoryProdutoRepository::new());
+        let foto_storage: Arc<dyn FotoStorageAdapter + Sync + Send> = Arc::new(MockFotoStorageAdapter::new());
+        let categoria_gateway: Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send> = Arc::new(InMemoryCategoriaRepository::new());
+        let agenda_gateway: Arc<dyn agenda_gateway::AgendaGateway + Sync + Send> = Arc::new(InMemoryAgendaRepository::new());
+        let loja_gateway: Arc<dyn LojaGateway + Sync + Send> = Arc::new(InMemoryLojaRepository::new());
+        let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> =
+            Arc::new(UserGroupValidator::new());
+
+        let rocket = rocket::build()
+            .mount("/", routes())
+            .manage(produto_gateway)
+            .manage(foto_storage)
+            .manage(categoria_gateway)
+            .manage(agenda_gateway)
+            .manage(FUSO_PADRAO)
+            .manage(loja_gateway)
+            .manage(user_group_validator);
+
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+        let response = client.get("/catalogo?formato=csv")
+            .header(Header::new("UserGroup", "Admin"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.content_type(), Some(ContentType::CSV));
+
+        let catalogo = response.into_string().unwrap();
+        assert!(catalogo.starts_with("id,nome,foto,descricao,categoria_id,preco,ingredientes,loja_id\n0,Hamburguer,"));
+
+        let catalogo = format!("{}{}", catalogo, ",Milkshake,,Milkshake de morango,42,-12.0,Leite|Morango,\n");
+        let response = client.post("/catalogo?formato=csv&dry_run=true")
+            .header(Header::new("UserGroup", "Admin"))
+            .body(catalogo)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = response.into_string().unwrap();
+        assert!(response.contains(r##""aplicado":false"##));
+        assert!(response.contains(r##""atualizados":1"##));
+        assert!(response.contains(r##"{"linha":2,"campo":"categoria_id","erro":"Categoria não encontrada"}"##));
+
+        let response = client.post("/catalogo")
+            .header(Header::new("UserGroup", "Kitchen"))
+            .body("[]")
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+
+    #[test]
+    fn test_define_produto_loja() {
+        let produto_gateway: Arc<dyn produto_gateway::ProdutoGateway + Sync + Send> = Arc::new(InMemoryProdutoRepository::new());
+        let foto_storage: Arc<dyn FotoStorageAdapter + Sync + Send> = Arc::new(MockFotoStorageAdapter::new());
+        let categoria_gateway: Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send> = Arc::new(InMemoryCategoriaRepository::new());
+        let agenda_gateway: Arc<dyn agenda_gateway::AgendaGateway + Sync + Send> = Arc::new(InMemoryAgendaRepository::new());
+        let loja_gateway: Arc<dyn LojaGateway + Sync + Send> = Arc::new(InMemoryLojaRepository::new());
+        let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> =
+            Arc::new(UserGroupValidator::new());
+
+        let rocket = rocket::build()
+            .mount("/", routes())
+            .manage(produto_gateway)
+            .manage(foto_storage)
+            .manage(categoria_gateway)
+            .manage(agenda_gateway)
+            .manage(FUSO_PADRAO)
+            .manage(loja_gateway)
+            .manage(user_group_validator);
+
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+        let response = client.put("/0/loja")
+            .header(Header::new("UserGroup", "Admin"))
+            .header(Header::new("X-Loja-Id", "1"))
+            .header(ContentType::JSON)
+            .body(r##"{"preco": 3.5}"##)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = client.get("/0").dispatch();
+        assert!(response.into_string().unwrap().contains(r##""preco":3.5"##));
+
+        let response = client.put("/0/loja")
+            .header(Header::new("UserGroup", "Admin"))
+            .header(ContentType::JSON)
+            .body(r##"{"preco": null, "disponivel": false}"##)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = client.get("/0").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn test_define_traducao() {
+        let produto_gateway: Arc<dyn produto_gateway::ProdutoGateway + Sync + Send> = Arc::new(InMemoryProdutoRepository::new());
+        let foto_storage: Arc<dyn FotoStorageAdapter + Sync + Send> = Arc::new(MockFotoStorageAdapter::new());
+        let categoria_gateway: Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send> = Arc::new(InMemoryCategoriaRepository::new());
+        let agenda_gateway: Arc<dyn agenda_gateway::AgendaGateway + Sync + Send> = Arc::new(InMemoryAgendaRepository::new());
+        let loja_gateway: Arc<dyn LojaGateway + Sync + Send> = Arc::new(InMemoryLojaRepository::new());
+        let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> =
+            Arc::new(UserGroupValidator::new());
+
+        let rocket = rocket::build()
+            .mount("/", routes())
+            .register("/", catchers())
+            .manage(produto_gateway)
+            .manage(foto_storage)
+            .manage(categoria_gateway)
+            .manage(agenda_gateway)
+            .manage(FUSO_PADRAO)
+            .manage(loja_gateway)
+            .manage(user_group_validator);
+
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+        let response = client.put("/0/traducoes/en")
+            .header(Header::new("UserGroup", "Admin"))
+            .header(ContentType::JSON)
+            .body(r##"{"nome": "Burger"}"##)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = client.get("/0")
+            .header(Header::new("Accept-Language", "en-US,en;q=0.9"))
+            .dispatch();
+        let response = response.into_string().unwrap();
+        assert!(response.contains(r##""nome":"Burger""##));
+        assert!(response.contains(r##""descricao":"hamburguer com uma carne e salada""##));
+
+        let response = client.get("/0").dispatch();
+        assert!(response.into_string().unwrap().contains(r##""nome":"Hamburguer""##));
+
+        let response = client.put("/0/traducoes/fr")
+            .header(Header::new("UserGroup", "Admin"))
+            .header(ContentType::JSON)
+            .body(r##"{"nome": "Hamburger"}"##)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::BadRequest);
+
+        let response = client.get("/0/traducoes")
+            .header(Header::new("UserGroup", "Admin"))
+            .dispatch();
+        assert!(response.into_string().unwrap().contains(r##""idioma":"en""##));
+
+        let response = client.get("/9")
+            .header(Header::new("Accept-Language", "es"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::NotFound);
+        let response = response.into_string().unwrap();
+        assert!(response.contains(r##""title":"¡Producto no encontrado!""##));
+        assert!(response.contains(r##""code":"nao_encontrado""##));
+    }
+
     #[test]
     fn test_handle_not_found() {
         let mut mock_produto_gateway = produto_gateway::MockProdutoGateway::new();
@@ -284,12 +910,21 @@ mod tests {
             .times(1)
             .returning(|_| Err(DomainError::NotFound));
 
-        let produto_gateway: Arc<Mutex<dyn produto_gateway::ProdutoGateway + Sync + Send>> = Arc::new(Mutex::new(mock_produto_gateway));
+        let produto_gateway: Arc<dyn produto_gateway::ProdutoGateway + Sync + Send> = Arc::new(mock_produto_gateway);
+        let foto_storage: Arc<dyn FotoStorageAdapter + Sync + Send> = Arc::new(MockFotoStorageAdapter::new());
+        let categoria_gateway: Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send> = Arc::new(InMemoryCategoriaRepository::new());
+        let agenda_gateway: Arc<dyn agenda_gateway::AgendaGateway + Sync + Send> = Arc::new(InMemoryAgendaRepository::new());
+        let loja_gateway: Arc<dyn LojaGateway + Sync + Send> = Arc::new(InMemoryLojaRepository::new());
 
         let rocket = rocket::build()
             .mount("/", routes())
             .register("/", catchers())
-            .manage(produto_gateway);
+            .manage(produto_gateway)
+            .manage(foto_storage)
+            .manage(categoria_gateway)
+            .manage(agenda_gateway)
+            .manage(FUSO_PADRAO)
+            .manage(loja_gateway);
 
         let client = Client::tracked(rocket).expect("valid rocket instance");
         let response = client.get("/1").dispatch();
@@ -297,6 +932,7 @@ mod tests {
         assert_eq!(response.status(), Status::NotFound);
 
         let response = response.into_string().unwrap();
-        assert_eq!(r##"{"msg":"Produto não encontrado!","status":404}"##, response);
+        assert!(response.contains(r##""title":"Produto não encontrado!""##));
+        assert!(response.contains(r##""code":"nao_encontrado""##));
     }
 }
@@ -0,0 +1,189 @@
+use std::sync::Arc;
+
+use chrono_tz::Tz;
+
+use rocket::http::ContentType;
+use rocket::serde::json::Json;
+use rocket::State;
+use rocket_okapi::{openapi, openapi_get_routes};
+use schemars::JsonSchema;
+
+use crate::api::error_handling::Problema;
+use crate::api::route_guards::admin_route_guard::AdminGuard;
+use crate::api::route_guards::loja_route_guard::LojaGuard;
+use crate::controllers::relatorio_controller::RelatorioController;
+use crate::traits::{categoria_gateway, pedido_gateway};
+use crate::use_cases::desempenho_da_cozinha_use_case::DesempenhoDaCozinha;
+
+pub struct EstadoDosRelatorios {
+    pub pedido_repository: Arc<dyn pedido_gateway::PedidoGateway + Sync + Send>,
+    pub categoria_repository: Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send>,
+    pub fuso_da_loja: Tz,
+}
+
+impl EstadoDosRelatorios {
+    fn relatorio_controller(&self) -> RelatorioController {
+        RelatorioController::new(self.pedido_repository.clone(), self.categoria_repository.clone(), self.fuso_da_loja)
+    }
+}
+
+// Período comum a todos os relatórios. Datas no formato AAAA-MM-DD, no fuso informado
+// (padrão: o fuso da loja).
+#[derive(FromForm, JsonSchema)]
+pub struct Periodo {
+    inicio: Option<String>,
+    fim: Option<String>,
+    fuso: Option<String>,
+}
+
+fn content_type(formato: &Option<String>) -> ContentType {
+    match formato.as_deref().map(str::to_lowercase).as_deref() {
+        Some("csv") => ContentType::CSV,
+        _ => ContentType::JSON,
+    }
+}
+
+// Receita, pedidos, ticket médio e cancelamentos por dia, semana ou mês.
+#[openapi(tag = "Relatórios")]
+#[get("/vendas?<agrupamento>&<formato>&<periodo..>")]
+async fn get_vendas(
+    estado: &State<EstadoDosRelatorios>,
+    periodo: Periodo,
+    agrupamento: Option<String>,
+    formato: Option<String>,
+    loja: LojaGuard,
+    _usuario_admin: AdminGuard,
+) -> Result<(ContentType, String), Problema> {
+    let relatorio_controller = estado.relatorio_controller();
+    let content_type = content_type(&formato);
+    let relatorio = relatorio_controller
+        .vendas(loja.0, periodo.inicio, periodo.fim, periodo.fuso, agrupamento, formato)
+        .await?;
+    Ok((content_type, relatorio))
+}
+
+#[openapi(tag = "Relatórios")]
+#[get("/vendas/categorias?<formato>&<periodo..>")]
+async fn get_vendas_por_categoria(
+    estado: &State<EstadoDosRelatorios>,
+    periodo: Periodo,
+    formato: Option<String>,
+    loja: LojaGuard,
+    _usuario_admin: AdminGuard,
+) -> Result<(ContentType, String), Problema> {
+    let relatorio_controller = estado.relatorio_controller();
+    let content_type = content_type(&formato);
+    let relatorio = relatorio_controller
+        .vendas_por_categoria(loja.0, periodo.inicio, periodo.fim, periodo.fuso, formato)
+        .await?;
+    Ok((content_type, relatorio))
+}
+
+// Produtos dos mais vendidos para os menos; `limite` traz só os primeiros.
+#[openapi(tag = "Relatórios")]
+#[get("/vendas/produtos?<limite>&<formato>&<periodo..>")]
+async fn get_vendas_por_produto(
+    estado: &State<EstadoDosRelatorios>,
+    periodo: Periodo,
+    limite: Option<usize>,
+    formato: Option<String>,
+    loja: LojaGuard,
+    _usuario_admin: AdminGuard,
+) -> Result<(ContentType, String), Problema> {
+    let relatorio_controller = estado.relatorio_controller();
+    let content_type = content_type(&formato);
+    let relatorio = relatorio_controller
+        .vendas_por_produto(loja.0, periodo.inicio, periodo.fim, periodo.fuso, limite, formato)
+        .await?;
+    Ok((content_type, relatorio))
+}
+
+// Duração média e p90 de cada etapa da cozinha (Pago a EmPreparacao, EmPreparacao a Pronto e
+// Pronto a Finalizado), no total, por hora do dia e por produto.
+#[openapi(tag = "Relatórios")]
+#[get("/cozinha?<periodo..>")]
+async fn get_desempenho_da_cozinha(
+    estado: &State<EstadoDosRelatorios>,
+    periodo: Periodo,
+    loja: LojaGuard,
+    _usuario_admin: AdminGuard,
+) -> Result<Json<DesempenhoDaCozinha>, Problema> {
+    let relatorio_controller = estado.relatorio_controller();
+    let desempenho = relatorio_controller
+        .desempenho_da_cozinha(loja.0, periodo.inicio, periodo.fim, periodo.fuso)
+        .await?;
+    Ok(Json(desempenho))
+}
+
+pub fn routes() -> Vec<rocket::Route> {
+    openapi_get_routes![get_vendas, get_vendas_por_categoria, get_vendas_por_produto, get_desempenho_da_cozinha]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use rocket::{http::{Header, Status}, local::blocking::Client};
+    use crate::adapters::user_group_validator::UserGroupValidator;
+    use crate::entities::pedido::{Pedido, Status as StatusPedido};
+    use crate::gateways::{in_memory_categoria_gateway::InMemoryCategoriaRepository, in_memory_loja_gateway::InMemoryLojaRepository, in_memory_pedido_gateway::InMemoryPedidoRepository};
+    use crate::traits::{loja_gateway::LojaGateway, pedido_gateway::PedidoGateway, user_group_validator_adapter::UserGroupValidatorAdapter};
+    use crate::use_cases::relatorios_de_vendas_use_case::FUSO_PADRAO;
+
+    #[test]
+    fn test_relatorio_de_vendas() {
+        let pedido_gateway = InMemoryPedidoRepository::new();
+        let agora = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
+        let pedido = Pedido::new(2, None, None, None, None, None, StatusPedido::Finalizado, agora.clone(), agora);
+        rocket::tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(pedido_gateway.create_pedido(pedido))
+            .unwrap();
+
+        let pedido_gateway: Arc<dyn PedidoGateway + Sync + Send> = Arc::new(pedido_gateway);
+        let categoria_gateway: Arc<dyn categoria_gateway::CategoriaGateway + Sync + Send> = Arc::new(InMemoryCategoriaRepository::new());
+        let loja_gateway: Arc<dyn LojaGateway + Sync + Send> = Arc::new(InMemoryLojaRepository::new());
+        let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> = Arc::new(UserGroupValidator::new());
+
+        let rocket = rocket::build()
+            .mount("/", routes())
+            .manage(EstadoDosRelatorios {
+                pedido_repository: pedido_gateway,
+                categoria_repository: categoria_gateway,
+                fuso_da_loja: FUSO_PADRAO,
+            })
+            .manage(loja_gateway)
+            .manage(user_group_validator);
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+
+        let hoje = Utc::now().format("%Y-%m-%d").to_string();
+        let ontem = (Utc::now() - chrono::Duration::days(1)).format("%Y-%m-%d").to_string();
+        let amanha = (Utc::now() + chrono::Duration::days(1)).format("%Y-%m-%d").to_string();
+        let url = format!("/vendas?inicio={}&fim={}&fuso=UTC&formato=csv", ontem, amanha);
+
+        let response = client.get(url.clone()).header(Header::new("UserGroup", "Kitchen")).dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+
+        let response = client.get(url).header(Header::new("UserGroup", "Admin")).dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.content_type(), Some(ContentType::CSV));
+        let csv = response.into_string().unwrap();
+        let linhas: Vec<&str> = csv.lines().collect();
+        assert_eq!(linhas[0], "periodo,pedidos,receita,descontos,ticket_medio,cancelados,taxa_de_cancelamento");
+        assert_eq!(linhas.len(), 5);
+        assert!(linhas.contains(&format!("{},1,0.0,0.0,0.0,0,0.0", hoje).as_str()));
+        assert_eq!(linhas[4], "total,1,0.0,0.0,0.0,0,0.0");
+
+        let response = client
+            .get(format!("/vendas/produtos?inicio={}&fim={}&fuso=Marte/Olimpo", hoje, hoje))
+            .header(Header::new("UserGroup", "Admin"))
+            .dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+
+        let response = client
+            .get("/vendas/categorias?fim=2024-01-01")
+            .header(Header::new("UserGroup", "Admin"))
+            .dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+}
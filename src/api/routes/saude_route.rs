@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::State;
+use rocket_okapi::{openapi, openapi_get_routes};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::controllers::saude_controller::SaudeController;
+use crate::traits::dependencia_adapter::DependenciaAdapter;
+use crate::use_cases::verificacao_de_saude_use_case::Prontidao;
+
+// Dependências verificadas pelo /health/ready; vazia no ambiente de teste.
+pub type Dependencias = Vec<Arc<dyn DependenciaAdapter + Sync + Send>>;
+
+#[derive(Serialize, JsonSchema)]
+pub struct Vivacidade {
+    pub status: String,
+}
+
+#[openapi(tag = "Saúde")]
+#[get("/live")]
+async fn live() -> Json<Vivacidade> {
+    Json(Vivacidade {
+        status: "ok".to_string(),
+    })
+}
+
+#[openapi(tag = "Saúde")]
+#[get("/ready")]
+async fn ready(dependencias: &State<Dependencias>) -> (Status, Json<Prontidao>) {
+    let saude_controller = SaudeController::new(dependencias.inner().clone());
+    let prontidao = saude_controller.verifica_prontidao().await;
+    let status = match prontidao.pronta {
+        true => Status::Ok,
+        false => Status::ServiceUnavailable,
+    };
+    (status, Json(prontidao))
+}
+
+pub fn routes() -> Vec<rocket::Route> {
+    openapi_get_routes![live, ready]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::postgres_dependencia::PostgresDependencia;
+    use rocket::local::blocking::Client;
+
+    fn client(dependencias: Dependencias) -> Client {
+        let rocket = rocket::build()
+            .mount("/health", routes())
+            .manage(dependencias);
+        Client::tracked(rocket).expect("valid rocket instance")
+    }
+
+    #[test]
+    fn test_live() {
+        let client = client(vec![]);
+        let response = client.get("/health/live").dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().unwrap(), "{\"status\":\"ok\"}");
+    }
+
+    #[test]
+    fn test_ready() {
+        let client = client(vec![]);
+        let response = client.get("/health/ready").dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(
+            response.into_string().unwrap(),
+            "{\"pronta\":true,\"dependencias\":[]}"
+        );
+    }
+
+    #[test]
+    fn test_ready_com_dependencia_indisponivel() {
+        let client = client(vec![Arc::new(PostgresDependencia::indisponivel(
+            "connection refused".to_string(),
+        ))]);
+        let response = client.get("/health/ready").dispatch();
+
+        assert_eq!(response.status(), Status::ServiceUnavailable);
+        let corpo = response.into_string().unwrap();
+        assert!(corpo.contains("\"pronta\":false"));
+        assert!(corpo.contains("\"nome\":\"postgres\""));
+        assert!(corpo.contains("\"erro\":\"connection refused\""));
+    }
+}
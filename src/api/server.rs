@@ -1,27 +1,76 @@
+use rocket::data::{Limits, ToByteUnit};
+use rocket::fs::{FileServer, Options};
 use rocket::response::Redirect;
+use rocket::figment::Figment;
 use rocket::{Build, Rocket};
 use rocket_okapi::settings::UrlObject;
 use rocket_okapi::swagger_ui::*;
 use std::net::{IpAddr, Ipv4Addr};
 use std::sync::Arc;
-use tokio::sync::Mutex;
 
+use super::cpf_mascarado::MascaraDeCpfs;
 use super::error_handling::generic_catchers;
-use super::routes::{pedido_route, produto_route};
+use super::metricas::{self, MetricasFairing};
+use super::request_id::{rastreadas, RequestIdFairing};
+use super::routes::cliente_route::EstadoDaProtecaoDeDados;
+use super::routes::pedido_route::EstadoDosPedidos;
+use super::routes::relatorio_route::EstadoDosRelatorios;
+use super::routes::saude_route::{self, Dependencias};
+use super::routes::{agenda_route, categoria_route, cliente_route, loja_route, pedido_route, produto_route, relatorio_route};
 use crate::adapters::{
-    api_key_validator::ApiKeyValidator, user_group_validator::UserGroupValidator,
+    api_key_validator::ApiKeyValidator, jwt_token_validator::JwtTokenValidator,
+    local_foto_storage::LocalFotoStorage, postgres_dependencia::PostgresDependencia,
+    user_group_validator::UserGroupValidator,
 };
 use crate::api::config::Config;
-use crate::external::postgres;
+use crate::external::postgres::{self, cifragem_de_cpfs, migracoes};
+use crate::gateways::in_memory_agenda_gateway::InMemoryAgendaRepository;
+use crate::gateways::in_memory_auditoria_gateway::InMemoryAuditoriaRepository;
+use crate::gateways::in_memory_categoria_gateway::InMemoryCategoriaRepository;
+use crate::gateways::in_memory_cliente_gateway::InMemoryClienteRepository;
+use crate::gateways::in_memory_idempotencia_gateway::InMemoryIdempotenciaRepository;
+use crate::gateways::in_memory_loja_gateway::InMemoryLojaRepository;
 use crate::gateways::in_memory_pedido_gateway::InMemoryPedidoRepository;
+use crate::gateways::in_memory_pontos_gateway::InMemoryPontosRepository;
 use crate::gateways::in_memory_produto_gateway::InMemoryProdutoRepository;
 use crate::gateways::{
+    postgres_agenda_gateway::PostgresAgendaRepository,
+    postgres_auditoria_gateway::PostgresAuditoriaRepository,
+    postgres_categoria_gateway::PostgresCategoriaRepository,
+    postgres_cliente_gateway::PostgresClienteRepository,
+    postgres_idempotencia_gateway::PostgresIdempotenciaRepository,
+    postgres_loja_gateway::PostgresLojaRepository,
     postgres_pedido_gateway::PostgresPedidoGateway,
+    postgres_pontos_gateway::PostgresPontosRepository,
     postgres_produto_gateway::PostgresProdutoRepository,
 };
 use crate::traits::api_key_validator_adapter::ApiKeyValidatorAdapter;
+use crate::traits::foto_storage_adapter::FotoStorageAdapter;
+use crate::traits::token_validator_adapter::TokenValidatorAdapter;
 use crate::traits::user_group_validator_adapter::UserGroupValidatorAdapter;
-use crate::traits::{pedido_gateway::PedidoGateway, produto_gateway::ProdutoGateway};
+use crate::traits::{
+    agenda_gateway::AgendaGateway, auditoria_gateway::AuditoriaGateway,
+    categoria_gateway::CategoriaGateway, cliente_gateway::ClienteGateway,
+    idempotencia_gateway::IdempotenciaGateway,
+    loja_gateway::LojaGateway, pedido_gateway::PedidoGateway, pontos_gateway::PontosGateway,
+    produto_gateway::ProdutoGateway,
+};
+use crate::use_cases::gerenciamento_de_produtos_use_case::TAMANHO_MAXIMO_FOTO;
+use crate::use_cases::programa_de_fidelidade_use_case::ProgramaDeFidelidadeUseCase;
+
+// Gateways da API, em memória ou no Postgres, e as dependências checadas no /health/ready.
+type Gateways = (
+    Arc<dyn AgendaGateway + Sync + Send>,
+    Arc<dyn CategoriaGateway + Sync + Send>,
+    Arc<dyn ClienteGateway + Sync + Send>,
+    Arc<dyn LojaGateway + Sync + Send>,
+    Arc<dyn ProdutoGateway + Sync + Send>,
+    Arc<dyn PedidoGateway + Sync + Send>,
+    Arc<dyn IdempotenciaGateway + Sync + Send>,
+    Arc<dyn PontosGateway + Sync + Send>,
+    Arc<dyn AuditoriaGateway + Sync + Send>,
+    Dependencias,
+);
 
 #[get("/")]
 fn redirect_to_docs() -> Redirect {
@@ -32,50 +81,96 @@ pub async fn main() -> Rocket<Build> {
     let config = Config::build();
 
     if config.env == "test" {
-        println!("Running test environment");
+        tracing::info!("Usando ambiente de teste");
     }
 
-    let (produto_gateway, pedido_gateway): (
-        Arc<Mutex<dyn ProdutoGateway + Sync + Send>>,
-        Arc<Mutex<dyn PedidoGateway + Sync + Send>>,
-    ) = {
+    let (agenda_gateway, categoria_gateway, cliente_gateway, loja_gateway, produto_gateway, pedido_gateway, idempotencia_gateway, pontos_gateway, auditoria_gateway, dependencias): Gateways = {
         if config.env == "test" {
             (
-                Arc::new(Mutex::new(InMemoryProdutoRepository::new())),
-                Arc::new(Mutex::new(InMemoryPedidoRepository::new())),
+                Arc::new(InMemoryAgendaRepository::new()),
+                Arc::new(InMemoryCategoriaRepository::new()),
+                Arc::new(InMemoryClienteRepository::new()),
+                Arc::new(InMemoryLojaRepository::new()),
+                Arc::new(InMemoryProdutoRepository::new()),
+                Arc::new(InMemoryPedidoRepository::new()),
+                Arc::new(InMemoryIdempotenciaRepository::new(config.idempotencia_janela)),
+                Arc::new(InMemoryPontosRepository::new()),
+                Arc::new(InMemoryAuditoriaRepository::new()),
+                vec![],
             )
         } else {
-            print!("Connecting to database...");
-            print!("Database URL: {}", config.db_url);
+            tracing::info!("Conectando ao banco de dados");
             let postgres_connection_manager =
-                postgres::PgConnectionManager::new(config.db_url.clone())
-                    .await
-                    .unwrap();
+                match postgres::PgConnectionManager::new(
+                    config.db_url.clone(),
+                    config.db_pool_tamanho,
+                    config.db_tempo_limite,
+                )
+                .await
+                {
+                    Ok(postgres_connection_manager) => postgres_connection_manager,
+                    Err(erro) => {
+                        tracing::error!(erro = %erro, "Não foi possível conectar ao banco de dados");
+                        let dependencia = PostgresDependencia::indisponivel(erro.to_string());
+                        return servidor_indisponivel(vec![Arc::new(dependencia)]);
+                    }
+                };
 
-            let postgres_client = Arc::new(postgres_connection_manager.client);
+            let postgres_pool = postgres_connection_manager.pool;
+            if let Err(erro) = migracoes::migra(&postgres_pool).await {
+                tracing::error!(erro = ?erro, "Não foi possível migrar o banco de dados");
+                let dependencia = PostgresDependencia::indisponivel(format!("{:?}", erro));
+                return servidor_indisponivel(vec![Arc::new(dependencia)]);
+            }
+            let cifrador_de_cpf = config.cifrador_de_cpf();
+            if let Err(erro) = cifragem_de_cpfs::cifra_cpfs_em_claro(&postgres_pool, &cifrador_de_cpf).await {
+                tracing::error!(erro = ?erro, "Não foi possível cifrar os CPFs do banco de dados");
+                let dependencia = PostgresDependencia::indisponivel(format!("{:?}", erro));
+                return servidor_indisponivel(vec![Arc::new(dependencia)]);
+            }
+            let dependencias: Dependencias =
+                vec![Arc::new(PostgresDependencia::new(postgres_pool.clone()))];
 
-            let tables = postgres::get_tables();
+            let agenda_gateway = Arc::new(PostgresAgendaRepository::new(postgres_pool.clone()));
 
-            let produto_gateway = Arc::new(Mutex::new(
-                PostgresProdutoRepository::new(postgres_client.clone(), tables.clone()).await,
+            let categoria_gateway = Arc::new(PostgresCategoriaRepository::new(postgres_pool.clone()));
+
+            let cliente_gateway = Arc::new(PostgresClienteRepository::new(
+                postgres_pool.clone(),
+                cifrador_de_cpf.clone(),
             ));
 
-            let pedido_gateway = Arc::new(Mutex::new(
-                PostgresPedidoGateway::new(
-                    postgres_client.clone(),
-                    tables,
-                    produto_gateway.clone(),
-                )
-                .await,
+            let loja_gateway = Arc::new(PostgresLojaRepository::new(postgres_pool.clone()));
+
+            let produto_gateway = Arc::new(PostgresProdutoRepository::new(postgres_pool.clone()));
+
+            let pedido_gateway = Arc::new(PostgresPedidoGateway::new(
+                postgres_pool.clone(),
+                produto_gateway.clone(),
+                cifrador_de_cpf.clone(),
+            ));
+
+            let idempotencia_gateway = Arc::new(PostgresIdempotenciaRepository::new(
+                postgres_pool.clone(),
+                config.idempotencia_janela,
+                cifrador_de_cpf.clone(),
             ));
 
-            (produto_gateway, pedido_gateway)
+            let pontos_gateway = Arc::new(PostgresPontosRepository::new(postgres_pool.clone(), cifrador_de_cpf));
+
+            let auditoria_gateway = Arc::new(PostgresAuditoriaRepository::new(postgres_pool.clone()));
+
+            (agenda_gateway, categoria_gateway, cliente_gateway, loja_gateway, produto_gateway, pedido_gateway, idempotencia_gateway, pontos_gateway, auditoria_gateway, dependencias)
         }
     };
 
-    let server_config = rocket::Config::figment()
-        .merge(("address", IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0))))
-        .merge(("port", 3000));
+    let server_config = configuracao_do_servidor(3000)
+        .merge((
+            "limits",
+            Limits::default()
+                .limit("file", TAMANHO_MAXIMO_FOTO.bytes())
+                .limit("data-form", (TAMANHO_MAXIMO_FOTO + 64 * 1024).bytes()),
+        ));
 
     let api_key_validator = ApiKeyValidator::new(config.api_key.clone());
     let api_key_validator: Arc<dyn ApiKeyValidatorAdapter + Sync + Send> =
@@ -85,8 +180,16 @@ pub async fn main() -> Rocket<Build> {
     let user_group_validator: Arc<dyn UserGroupValidatorAdapter + Sync + Send> =
         Arc::new(user_group_validator);
 
+    let token_validator: Arc<dyn TokenValidatorAdapter + Sync + Send> =
+        Arc::new(JwtTokenValidator::new(config.jwt_secret.clone()));
+
+    let foto_storage: Arc<dyn FotoStorageAdapter + Sync + Send> = Arc::new(
+        LocalFotoStorage::new(config.fotos_dir.clone(), config.fotos_url.clone()),
+    );
+
     rocket::build()
         .mount("/", routes![redirect_to_docs])
+        .mount("/", metricas::routes())
         .register("/", generic_catchers())
         .mount(
             "/docs/",
@@ -94,21 +197,109 @@ pub async fn main() -> Rocket<Build> {
                 urls: vec![
                     UrlObject::new("Produtos", "/produtos/openapi.json"),
                     UrlObject::new("Pedidos", "/pedidos/openapi.json"),
+                    UrlObject::new("Categorias", "/categorias/openapi.json"),
+                    UrlObject::new("Agendas", "/agendas/openapi.json"),
+                    UrlObject::new("Lojas", "/lojas/openapi.json"),
+                    UrlObject::new("Clientes", "/clientes/openapi.json"),
+                    UrlObject::new("Relatórios", "/relatorios/openapi.json"),
+                    UrlObject::new("Saúde", "/health/openapi.json"),
                 ],
                 ..Default::default()
             }),
         )
-        .mount("/produtos", produto_route::routes())
-        .mount("/pedidos", pedido_route::routes())
+        .mount("/produtos", rastreadas(produto_route::routes()))
+        .mount("/pedidos", rastreadas(pedido_route::routes()))
+        .mount("/categorias", rastreadas(categoria_route::routes()))
+        .mount("/agendas", rastreadas(agenda_route::routes()))
+        .mount("/lojas", rastreadas(loja_route::routes()))
+        .mount("/clientes", rastreadas(cliente_route::routes()))
+        .mount("/relatorios", rastreadas(relatorio_route::routes()))
+        .mount("/health", saude_route::routes())
+        .mount("/fotos", FileServer::new(config.fotos_dir.clone(), Options::Missing))
         .register("/produtos", produto_route::catchers())
         .register("/pedidos", pedido_route::catchers())
+        .register("/categorias", categoria_route::catchers())
+        .register("/agendas", agenda_route::catchers())
+        .register("/lojas", loja_route::catchers())
+        .register("/clientes", cliente_route::catchers())
+        .manage(EstadoDosPedidos {
+            pedido_repository: pedido_gateway.clone(),
+            produto_repository: produto_gateway.clone(),
+            categoria_repository: categoria_gateway.clone(),
+            agenda_repository: agenda_gateway.clone(),
+            cliente_repository: cliente_gateway.clone(),
+            idempotencia_repository: idempotencia_gateway.clone(),
+            programa_de_fidelidade_use_case: Arc::new(ProgramaDeFidelidadeUseCase::new(
+                pontos_gateway.clone(),
+                pedido_gateway.clone(),
+                config.regras_de_fidelidade(),
+            )),
+            fuso: config.fuso_horario,
+        })
+        .manage(EstadoDosRelatorios {
+            pedido_repository: pedido_gateway.clone(),
+            categoria_repository: categoria_gateway.clone(),
+            fuso_da_loja: config.fuso_horario,
+        })
+        .manage(EstadoDaProtecaoDeDados {
+            cliente_repository: cliente_gateway.clone(),
+            pedido_repository: pedido_gateway.clone(),
+            pontos_repository: pontos_gateway.clone(),
+            auditoria_repository: auditoria_gateway,
+            pseudonimizador: config.pseudonimizador(),
+        })
+        .manage(agenda_gateway)
+        .manage(categoria_gateway)
+        .manage(cliente_gateway)
+        .manage(loja_gateway)
         .manage(produto_gateway)
         .manage(pedido_gateway)
+        .manage(idempotencia_gateway)
+        .manage(pontos_gateway)
+        .manage(config.regras_de_fidelidade())
+        .manage(config.fuso_horario)
         .manage(api_key_validator)
         .manage(user_group_validator)
+        .manage(token_validator)
+        .manage(foto_storage)
+        .manage(dependencias)
+        .attach(RequestIdFairing)
+        .attach(MascaraDeCpfs)
+        .attach(MetricasFairing)
         .configure(server_config)
 }
 
+fn configuracao_do_servidor(porta: u16) -> Figment {
+    rocket::Config::figment()
+        .merge(("address", IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0))))
+        .merge(("port", porta))
+}
+
+// Sem banco não há como atender a API: sobem só as sondas, reportando a falha no /health/ready.
+fn servidor_indisponivel(dependencias: Dependencias) -> Rocket<Build> {
+    rocket::build()
+        .mount("/health", saude_route::routes())
+        .manage(dependencias)
+        .configure(configuracao_do_servidor(3000))
+}
+
+// Servidor HTTP mínimo do listener: sondas de saúde e, com o banco conectado, o /metrics.
+pub fn servidor_do_listener(
+    pedido_gateway: Option<Arc<dyn PedidoGateway + Sync + Send>>,
+    dependencias: Dependencias,
+    porta: u16,
+) -> Rocket<Build> {
+    let servidor = rocket::build()
+        .mount("/health", saude_route::routes())
+        .manage(dependencias)
+        .configure(configuracao_do_servidor(porta));
+
+    match pedido_gateway {
+        Some(pedido_gateway) => servidor.mount("/", metricas::routes()).manage(pedido_gateway),
+        None => servidor,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,2 +1,7 @@
 pub mod assertion_concern;
-pub mod domain_error;
\ No newline at end of file
+pub mod cifrador_de_cpf;
+pub mod domain_error;
+pub mod metricas;
+pub mod paginacao;
+pub mod pseudonimo;
+pub mod text_search;
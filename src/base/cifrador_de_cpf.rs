@@ -0,0 +1,129 @@
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::cpf::Cpf;
+use crate::entities::documento_fiscal::DocumentoFiscal;
+
+const TAMANHO_NONCE: usize = 12;
+
+fn hmac(chave: &[u8], mensagem: &[u8]) -> Vec<u8> {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(chave).expect("HMAC aceita chave de qualquer tamanho");
+    mac.update(mensagem);
+    mac.finalize().into_bytes().to_vec()
+}
+
+// CPF guardado no banco: cifrado com AES-256-GCM (nonce aleatório, então o mesmo CPF nunca
+// dá o mesmo texto) e acompanhado de um HMAC-SHA256 para as buscas por igualdade. As duas
+// chaves derivam da chave da configuração. Só os gateways do Postgres usam.
+#[derive(Clone)]
+pub struct CifradorDeCpf {
+    cifra: Aes256Gcm,
+    chave_do_hash: Vec<u8>,
+}
+
+impl CifradorDeCpf {
+    pub fn new(chave: &str) -> Self {
+        let chave_da_cifra = hmac(chave.as_bytes(), b"cifra");
+        CifradorDeCpf {
+            cifra: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&chave_da_cifra)),
+            chave_do_hash: hmac(chave.as_bytes(), b"hash"),
+        }
+    }
+
+    // Nonce e texto cifrado, em base64.
+    fn cifra_texto(&self, texto: &str) -> String {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let mut cifrado = nonce.to_vec();
+        cifrado.extend(
+            self.cifra
+                .encrypt(&nonce, texto.as_bytes())
+                .expect("AES-GCM só falha com mensagens gigantes"),
+        );
+        STANDARD.encode(cifrado)
+    }
+
+    fn decifra_texto(&self, cifrado: &str) -> Result<String, DomainError> {
+        let invalido = || DomainError::Database("CPF cifrado inválido ou com outra chave".to_string());
+        let cifrado = STANDARD.decode(cifrado).map_err(|_| invalido())?;
+        if cifrado.len() <= TAMANHO_NONCE {
+            return Err(invalido());
+        }
+        let (nonce, texto) = cifrado.split_at(TAMANHO_NONCE);
+        let texto = self
+            .cifra
+            .decrypt(Nonce::from_slice(nonce), texto)
+            .map_err(|_| invalido())?;
+        String::from_utf8(texto).map_err(|_| invalido())
+    }
+
+    fn hash_texto(&self, texto: &str) -> String {
+        hmac(&self.chave_do_hash, texto.as_bytes())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    pub fn cifra(&self, cpf: &Cpf) -> String {
+        self.cifra_texto(&cpf.0)
+    }
+
+    pub fn decifra(&self, cifrado: &str) -> Result<Cpf, DomainError> {
+        Ok(Cpf(self.decifra_texto(cifrado)?))
+    }
+
+    pub fn hash(&self, cpf: &Cpf) -> String {
+        self.hash_texto(&cpf.0)
+    }
+
+    // O documento fiscal da nota também pode ser um CPF, então é guardado do mesmo jeito.
+    // O hash de um documento com CPF é igual ao hash do próprio CPF.
+    pub fn cifra_documento(&self, documento: &DocumentoFiscal) -> String {
+        self.cifra_texto(&documento.0)
+    }
+
+    pub fn decifra_documento(&self, cifrado: &str) -> Result<DocumentoFiscal, DomainError> {
+        Ok(DocumentoFiscal(self.decifra_texto(cifrado)?))
+    }
+
+    pub fn hash_documento(&self, documento: &DocumentoFiscal) -> String {
+        self.hash_texto(&documento.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cifra_e_decifra() {
+        let cifrador = CifradorDeCpf::new("chave");
+        let cpf = Cpf::new("123.456.789-09".to_string()).unwrap();
+
+        let cifrado = cifrador.cifra(&cpf);
+        assert!(!cifrado.contains("12345678909"));
+        assert_ne!(cifrado, cifrador.cifra(&cpf));
+        assert_eq!(cifrador.decifra(&cifrado).unwrap(), cpf);
+
+        assert!(CifradorDeCpf::new("outra").decifra(&cifrado).is_err());
+        assert!(cifrador.decifra("12345678909").is_err());
+    }
+
+    #[test]
+    fn test_hash_e_deterministico_e_depende_da_chave() {
+        let cifrador = CifradorDeCpf::new("chave");
+        let cpf = Cpf::new("123.456.789-09".to_string()).unwrap();
+        let outro_cpf = Cpf::new("097.855.456-60".to_string()).unwrap();
+
+        assert_eq!(cifrador.hash(&cpf), cifrador.hash(&cpf));
+        assert_eq!(cifrador.hash(&cpf).len(), 64);
+        assert_ne!(cifrador.hash(&cpf), cifrador.hash(&outro_cpf));
+        assert_ne!(cifrador.hash(&cpf), CifradorDeCpf::new("outra").hash(&cpf));
+
+        let documento = DocumentoFiscal::new(cpf.get_string()).unwrap();
+        assert_eq!(cifrador.hash_documento(&documento), cifrador.hash(&cpf));
+    }
+}
@@ -3,8 +3,47 @@ pub enum DomainError {
     AlreadyExists,
     Empty,
     Unauthorized,
+    // Autenticado, mas sem acesso ao recurso (ex.: pedidos de outro cliente).
+    Forbidden,
     NotFound,
     Invalid(String),
+    // Valor recusado num campo conhecido da entrada.
+    InvalidField { field: String, message: String },
     NonPositive,
     Database(String),
-}
\ No newline at end of file
+    // A entidade mudou desde a versão que o cliente leu (controle de concorrência otimista).
+    Conflict,
+}
+
+impl DomainError {
+    // Associa ao campo da entrada os erros de validação que não sabem de onde vieram.
+    pub fn no_campo(self, field: &str) -> DomainError {
+        let message = match self {
+            DomainError::Empty => "Campo obrigatório".to_string(),
+            DomainError::NonPositive => "Valor não pode ser negativo".to_string(),
+            DomainError::Invalid(message) => message,
+            erro => return erro,
+        };
+        DomainError::InvalidField {
+            field: field.to_string(),
+            message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_campo() {
+        let erro = DomainError::NonPositive.no_campo("preco");
+        assert!(matches!(
+            erro,
+            DomainError::InvalidField { ref field, .. } if field == "preco"
+        ));
+
+        let erro = DomainError::NotFound.no_campo("preco");
+        assert!(matches!(erro, DomainError::NotFound));
+    }
+}
@@ -0,0 +1,180 @@
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGaugeVec, Opts, Registry,
+    TextEncoder,
+};
+use std::future::Future;
+use std::sync::LazyLock;
+use std::time::Instant;
+
+// Métricas do processo no formato do Prometheus, compartilhadas pela API e pelo listener.
+pub struct Metricas {
+    registry: Registry,
+    requisicoes_http: IntCounterVec,
+    duracao_requisicoes_http: HistogramVec,
+    pedidos_criados: IntCounter,
+    transicoes_de_status: IntCounterVec,
+    pagamentos: IntCounterVec,
+    mensagens_da_fila: IntCounterVec,
+    duracao_consultas: HistogramVec,
+    pedidos_por_status: IntGaugeVec,
+}
+
+static METRICAS: LazyLock<Metricas> = LazyLock::new(Metricas::new);
+
+impl Metricas {
+    fn new() -> Self {
+        let registry = Registry::new();
+
+        let requisicoes_http = IntCounterVec::new(
+            Opts::new("http_requests_total", "Requisições HTTP atendidas"),
+            &["method", "route", "status"],
+        )
+        .unwrap();
+        let duracao_requisicoes_http = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "Duração das requisições HTTP em segundos",
+            ),
+            &["method", "route", "status"],
+        )
+        .unwrap();
+        let pedidos_criados =
+            IntCounter::new("pedidos_criados_total", "Pedidos criados").unwrap();
+        let transicoes_de_status = IntCounterVec::new(
+            Opts::new(
+                "pedido_transicoes_status_total",
+                "Mudanças de status de pedidos, pelo status de destino",
+            ),
+            &["para"],
+        )
+        .unwrap();
+        let pagamentos = IntCounterVec::new(
+            Opts::new("pagamentos_total", "Pagamentos recebidos, por resultado"),
+            &["resultado"],
+        )
+        .unwrap();
+        let mensagens_da_fila = IntCounterVec::new(
+            Opts::new("fila_mensagens_total", "Mensagens consumidas da fila, por resultado"),
+            &["resultado"],
+        )
+        .unwrap();
+        let duracao_consultas = HistogramVec::new(
+            HistogramOpts::new(
+                "db_query_duration_seconds",
+                "Duração das consultas ao banco de dados em segundos",
+            )
+            .buckets(vec![0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0]),
+            &["consulta"],
+        )
+        .unwrap();
+        let pedidos_por_status = IntGaugeVec::new(
+            Opts::new("pedidos_por_status", "Quantidade atual de pedidos em cada status"),
+            &["status"],
+        )
+        .unwrap();
+
+        registry.register(Box::new(requisicoes_http.clone())).unwrap();
+        registry.register(Box::new(duracao_requisicoes_http.clone())).unwrap();
+        registry.register(Box::new(pedidos_criados.clone())).unwrap();
+        registry.register(Box::new(transicoes_de_status.clone())).unwrap();
+        registry.register(Box::new(pagamentos.clone())).unwrap();
+        registry.register(Box::new(mensagens_da_fila.clone())).unwrap();
+        registry.register(Box::new(duracao_consultas.clone())).unwrap();
+        registry.register(Box::new(pedidos_por_status.clone())).unwrap();
+
+        Metricas {
+            registry,
+            requisicoes_http,
+            duracao_requisicoes_http,
+            pedidos_criados,
+            transicoes_de_status,
+            pagamentos,
+            mensagens_da_fila,
+            duracao_consultas,
+            pedidos_por_status,
+        }
+    }
+}
+
+pub fn registra_requisicao(method: &str, route: &str, status: u16, duracao_em_segundos: f64) {
+    let status = status.to_string();
+    let labels = [method, route, status.as_str()];
+    METRICAS.requisicoes_http.with_label_values(&labels).inc();
+    METRICAS
+        .duracao_requisicoes_http
+        .with_label_values(&labels)
+        .observe(duracao_em_segundos);
+}
+
+pub fn registra_pedido_criado() {
+    METRICAS.pedidos_criados.inc();
+}
+
+pub fn registra_transicao_de_status(para: &str) {
+    METRICAS.transicoes_de_status.with_label_values(&[para]).inc();
+}
+
+pub fn registra_pagamento(aprovado: bool) {
+    let resultado = if aprovado { "aprovado" } else { "recusado" };
+    METRICAS.pagamentos.with_label_values(&[resultado]).inc();
+}
+
+pub fn registra_mensagem_da_fila(processada: bool) {
+    let resultado = if processada { "processada" } else { "falha" };
+    METRICAS.mensagens_da_fila.with_label_values(&[resultado]).inc();
+}
+
+// Mede a duração de uma consulta ao banco; `consulta` identifica o SQL executado.
+pub async fn mede_consulta<F: Future>(consulta: &str, consulta_futura: F) -> F::Output {
+    let inicio = Instant::now();
+    let resultado = consulta_futura.await;
+    METRICAS
+        .duracao_consultas
+        .with_label_values(&[consulta])
+        .observe(inicio.elapsed().as_secs_f64());
+    resultado
+}
+
+// Substitui a contagem de pedidos por status, zerando os status que não aparecem mais.
+pub fn atualiza_pedidos_por_status(contagem: &[(String, usize)]) {
+    METRICAS.pedidos_por_status.reset();
+    for (status, quantidade) in contagem {
+        METRICAS
+            .pedidos_por_status
+            .with_label_values(&[status.as_str()])
+            .set(*quantidade as i64);
+    }
+}
+
+pub fn exporta() -> String {
+    let mut buffer = Vec::new();
+    TextEncoder::new()
+        .encode(&METRICAS.registry.gather(), &mut buffer)
+        .unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_exporta_metricas_registradas() {
+        registra_requisicao("GET", "/testes/<id>", 200, 0.01);
+        registra_pagamento(true);
+        registra_mensagem_da_fila(false);
+        let resultado = mede_consulta("query_de_teste", async { 42 }).await;
+        atualiza_pedidos_por_status(&[("Pago".to_string(), 3)]);
+
+        let texto = exporta();
+
+        assert_eq!(resultado, 42);
+        assert!(texto.contains(
+            "http_requests_total{method=\"GET\",route=\"/testes/<id>\",status=\"200\"}"
+        ));
+        assert!(texto.contains("pagamentos_total{resultado=\"aprovado\"}"));
+        assert!(texto.contains("fila_mensagens_total{resultado=\"falha\"}"));
+        assert!(texto.contains("db_query_duration_seconds_count{consulta=\"query_de_teste\"} 1"));
+        assert!(texto.contains("pedidos_por_status{status=\"Pago\"} 3"));
+    }
+}
@@ -0,0 +1,118 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::base::domain_error::DomainError;
+
+pub const TAMANHO_PADRAO: usize = 20;
+pub const TAMANHO_MAXIMO: usize = 100;
+
+// Página pedida numa listagem. A primeira página é a 1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Paginacao {
+    pagina: usize,
+    tamanho: usize,
+}
+
+impl Paginacao {
+    pub fn new(pagina: Option<usize>, tamanho: Option<usize>) -> Result<Self, DomainError> {
+        let pagina = pagina.unwrap_or(1);
+        let tamanho = tamanho.unwrap_or(TAMANHO_PADRAO);
+        if pagina == 0 {
+            return Err(DomainError::InvalidField {
+                field: "pagina".to_string(),
+                message: "A primeira página é a 1".to_string(),
+            });
+        }
+        if tamanho == 0 || tamanho > TAMANHO_MAXIMO {
+            return Err(DomainError::InvalidField {
+                field: "tamanho".to_string(),
+                message: format!("O tamanho da página vai de 1 a {}", TAMANHO_MAXIMO),
+            });
+        }
+        Ok(Paginacao { pagina, tamanho })
+    }
+
+    pub fn pagina(&self) -> usize {
+        self.pagina
+    }
+
+    pub fn tamanho(&self) -> usize {
+        self.tamanho
+    }
+
+    // Quantos itens vêm antes da página.
+    pub fn deslocamento(&self) -> usize {
+        (self.pagina - 1) * self.tamanho
+    }
+
+    // Recorta a página de uma lista já ordenada e completa.
+    pub fn aplica<T>(&self, itens: Vec<T>) -> Pagina<T> {
+        let total = itens.len();
+        let itens = itens
+            .into_iter()
+            .skip(self.deslocamento())
+            .take(self.tamanho)
+            .collect();
+        Pagina::new(itens, self, total)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct Pagina<T> {
+    itens: Vec<T>,
+    pagina: usize,
+    tamanho: usize,
+    total: usize,
+}
+
+impl<T> Pagina<T> {
+    pub fn new(itens: Vec<T>, paginacao: &Paginacao, total: usize) -> Self {
+        Pagina {
+            itens,
+            pagina: paginacao.pagina,
+            tamanho: paginacao.tamanho,
+            total,
+        }
+    }
+
+    pub fn itens(&self) -> &Vec<T> {
+        &self.itens
+    }
+
+    pub fn total(&self) -> usize {
+        self.total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paginacao_new() {
+        let paginacao = Paginacao::new(None, None).unwrap();
+        assert_eq!(paginacao.pagina(), 1);
+        assert_eq!(paginacao.tamanho(), TAMANHO_PADRAO);
+        assert_eq!(paginacao.deslocamento(), 0);
+
+        assert!(matches!(
+            Paginacao::new(Some(0), None),
+            Err(DomainError::InvalidField { ref field, .. }) if field == "pagina"
+        ));
+        assert!(matches!(
+            Paginacao::new(None, Some(TAMANHO_MAXIMO + 1)),
+            Err(DomainError::InvalidField { ref field, .. }) if field == "tamanho"
+        ));
+    }
+
+    #[test]
+    fn test_paginacao_aplica() {
+        let pagina = Paginacao::new(Some(2), Some(2)).unwrap().aplica(vec![1, 2, 3, 4, 5]);
+        assert_eq!(pagina.itens(), &vec![3, 4]);
+        assert_eq!(pagina.total(), 5);
+
+        let pagina = Paginacao::new(Some(4), Some(2)).unwrap().aplica(vec![1, 2, 3, 4, 5]);
+        assert!(pagina.itens().is_empty());
+        assert_eq!(pagina.total(), 5);
+    }
+}
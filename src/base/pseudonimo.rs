@@ -0,0 +1,54 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::entities::cpf::Cpf;
+
+const PREFIXO: &str = "anon_";
+
+// Gera o pseudônimo que substitui o CPF nos pedidos anonimizados: HMAC-SHA256 do CPF com
+// uma chave da configuração. O mesmo CPF sempre dá o mesmo pseudônimo, o que mantém
+// agregados por cliente, mas sem a chave não se volta ao CPF.
+#[derive(Clone)]
+pub struct Pseudonimizador {
+    chave: String,
+}
+
+impl Pseudonimizador {
+    pub fn new(chave: String) -> Self {
+        Pseudonimizador { chave }
+    }
+
+    pub fn pseudonimo(&self, cpf: &Cpf) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.chave.as_bytes())
+            .expect("HMAC aceita chave de qualquer tamanho");
+        mac.update(cpf.0.as_bytes());
+        let digest: String = mac
+            .finalize()
+            .into_bytes()
+            .iter()
+            .take(16)
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        format!("{}{}", PREFIXO, digest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pseudonimo_depende_do_cpf_e_da_chave() {
+        let cpf = Cpf::new("123.456.789-09".to_string()).unwrap();
+        let outro_cpf = Cpf::new("097.855.456-60".to_string()).unwrap();
+        let pseudonimizador = Pseudonimizador::new("chave".to_string());
+
+        let pseudonimo = pseudonimizador.pseudonimo(&cpf);
+        assert!(pseudonimo.starts_with("anon_"));
+        assert_eq!(pseudonimo.len(), 37);
+        assert!(!pseudonimo.contains("12345678909"));
+        assert_eq!(pseudonimo, pseudonimizador.pseudonimo(&cpf));
+        assert_ne!(pseudonimo, pseudonimizador.pseudonimo(&outro_cpf));
+        assert_ne!(pseudonimo, Pseudonimizador::new("outra".to_string()).pseudonimo(&cpf));
+    }
+}
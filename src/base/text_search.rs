@@ -0,0 +1,142 @@
+// Busca textual em memória, equivalente à busca full-text do Postgres
+// (config `portuguese` + `unaccent` + `pg_trgm`): ignora acentos e caixa
+// e tolera pequenos erros de digitação.
+
+pub const PESO_NOME: f64 = 1.0;
+pub const PESO_DESCRICAO: f64 = 0.4;
+pub const PESO_INGREDIENTES: f64 = 0.2;
+
+pub fn normalize(value: &str) -> String {
+    value
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ã' | 'ä' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'í' | 'ì' | 'î' | 'ï' => 'i',
+            'ó' | 'ò' | 'ô' | 'õ' | 'ö' => 'o',
+            'ú' | 'ù' | 'û' | 'ü' => 'u',
+            'ç' => 'c',
+            'ñ' => 'n',
+            _ => c,
+        })
+        .collect()
+}
+
+pub fn tokenize(value: &str) -> Vec<String> {
+    normalize(value)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(|token| token.to_string())
+        .collect()
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let custo = if ca == cb { 0 } else { 1 };
+            current[j + 1] = (previous[j] + custo)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+fn distancia_tolerada(termo: &str) -> usize {
+    match termo.chars().count() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+fn termo_corresponde(termo: &str, token: &str) -> bool {
+    if termo == token {
+        return true;
+    }
+    // Prefixo cobre plurais e flexões simples ("hamburgueres")
+    if termo.chars().count() >= 4 && (token.starts_with(termo) || termo.starts_with(token)) {
+        return token.chars().count() >= 4;
+    }
+    levenshtein(termo, token) <= distancia_tolerada(termo)
+}
+
+fn campo_corresponde(termo: &str, tokens: &[String]) -> bool {
+    tokens.iter().any(|token| termo_corresponde(termo, token))
+}
+
+/// Pontua um conjunto de campos ponderados contra a consulta. Retorna `None`
+/// quando algum termo da consulta não aparece em nenhum campo.
+pub fn score(query: &str, campos: &[(&str, f64)]) -> Option<f64> {
+    let termos = tokenize(query);
+    if termos.is_empty() {
+        return None;
+    }
+    let campos: Vec<(Vec<String>, f64)> = campos
+        .iter()
+        .map(|(valor, peso)| (tokenize(valor), *peso))
+        .collect();
+
+    let mut total = 0.0;
+    for termo in termos.iter() {
+        let melhor_peso = campos
+            .iter()
+            .filter(|(tokens, _)| campo_corresponde(termo, tokens))
+            .map(|(_, peso)| *peso)
+            .fold(None, |melhor: Option<f64>, peso| {
+                Some(melhor.map_or(peso, |m| m.max(peso)))
+            });
+        total += melhor_peso?;
+    }
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_remove_acentos_e_caixa() {
+        assert_eq!(normalize("Hambúrguer"), "hamburguer");
+        assert_eq!(normalize("Pão de Açúcar"), "pao de acucar");
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("Pão, carne e queijo!"),
+            vec!["pao", "carne", "e", "queijo"]
+        );
+    }
+
+    #[test]
+    fn test_score_sem_acento() {
+        let score = score("hamburguer", &[("Hambúrguer", PESO_NOME)]);
+        assert_eq!(score, Some(PESO_NOME));
+    }
+
+    #[test]
+    fn test_score_tolera_erro_de_digitacao() {
+        assert!(score("hamburger", &[("Hambúrguer", PESO_NOME)]).is_some());
+        assert!(score("quejo", &[("Queijo", PESO_NOME)]).is_some());
+    }
+
+    #[test]
+    fn test_score_pondera_campos() {
+        let no_nome = score("queijo", &[("Queijo quente", PESO_NOME), ("", PESO_DESCRICAO)]);
+        let na_descricao = score("queijo", &[("Misto", PESO_NOME), ("Com queijo", PESO_DESCRICAO)]);
+        assert!(no_nome.unwrap() > na_descricao.unwrap());
+    }
+
+    #[test]
+    fn test_score_sem_correspondencia() {
+        assert_eq!(score("sorvete", &[("Hambúrguer", PESO_NOME)]), None);
+        assert_eq!(score("", &[("Hambúrguer", PESO_NOME)]), None);
+    }
+}
@@ -1,17 +1,92 @@
 use tech_challenge::api;
+use tech_challenge::api::config::Config;
+use tech_challenge::external::postgres::{cifragem_de_cpfs, migracoes, PgConnectionManager};
+use tech_challenge::external::telemetria;
+
+const USO_MIGRATE: &str = "uso: api migrate [up | down [passos] | status]";
 
 #[rocket::main]
 async fn main() -> Result<(), rocket::Error> {
+  telemetria::inicializa();
+
+  let argumentos: Vec<String> = std::env::args().skip(1).collect();
+  if argumentos.first().map(String::as_str) == Some("migrate") {
+    std::process::exit(migrate(&argumentos[1..]).await);
+  }
+
   let server = api::server::main().await;
 
   match server.launch().await {
     Ok(_) => {
-      println!("Server started");
+      tracing::info!("Servidor encerrado");
       Ok(())
     },
     Err(e) => {
-      print!("Error starting server: {:?}", e);
+      tracing::error!(erro = ?e, "Erro ao iniciar o servidor");
       Err(e)
     }
   }
 }
+
+enum ComandoMigrate {
+  Up,
+  Down(usize),
+  Status,
+}
+
+fn comando_migrate(argumentos: &[String]) -> Option<ComandoMigrate> {
+  let argumentos: Vec<&str> = argumentos.iter().map(String::as_str).collect();
+  match argumentos.as_slice() {
+    [] | ["up"] => Some(ComandoMigrate::Up),
+    ["down"] => Some(ComandoMigrate::Down(1)),
+    ["down", passos] => passos.parse().ok().map(ComandoMigrate::Down),
+    ["status"] => Some(ComandoMigrate::Status),
+    _ => None,
+  }
+}
+
+// Subcomando `migrate`: aplica (up), desfaz (down) ou lista (status) as migrações embutidas.
+// Devolve o código de saída do processo.
+async fn migrate(argumentos: &[String]) -> i32 {
+  let Some(comando) = comando_migrate(argumentos) else {
+    eprintln!("{}", USO_MIGRATE);
+    return 2;
+  };
+
+  let config = Config::build();
+  let cifrador_de_cpf = config.cifrador_de_cpf();
+  let pool = match PgConnectionManager::new(config.db_url, config.db_pool_tamanho, config.db_tempo_limite).await {
+    Ok(postgres_connection_manager) => postgres_connection_manager.pool,
+    Err(e) => {
+      tracing::error!(erro = %e, "Não foi possível conectar ao banco de dados");
+      return 1;
+    }
+  };
+
+  let resultado = match comando {
+    ComandoMigrate::Up => match migracoes::migra(&pool).await {
+      Ok(versoes) => {
+        tracing::info!(?versoes, "Migrações aplicadas");
+        cifragem_de_cpfs::cifra_cpfs_em_claro(&pool, &cifrador_de_cpf).await.map(|_| ())
+      }
+      Err(e) => Err(e),
+    },
+    ComandoMigrate::Down(passos) => cifragem_de_cpfs::desfaz_migracoes(&pool, passos, &cifrador_de_cpf)
+      .await
+      .map(|versoes| tracing::info!(?versoes, "Migrações desfeitas")),
+    ComandoMigrate::Status => migracoes::situacao(&pool).await.map(|situacao| {
+      for migracao in situacao {
+        let estado = if migracao.aplicada { "aplicada" } else { "pendente" };
+        println!("{:04} {:<20} {}", migracao.versao, migracao.nome, estado);
+      }
+    }),
+  };
+
+  match resultado {
+    Ok(()) => 0,
+    Err(e) => {
+      tracing::error!(erro = ?e, "Erro ao executar as migrações");
+      1
+    }
+  }
+}
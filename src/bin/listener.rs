@@ -1,57 +1,109 @@
 use tech_challenge::api::config::Config;
+use tech_challenge::adapters::postgres_dependencia::PostgresDependencia;
+use tech_challenge::api::server::servidor_do_listener;
 use tech_challenge::rabbit::RabbitMQPagamentoUpdateSubscriber;
 use std::sync::Arc;
-use tokio::sync::Mutex;
 
-use tech_challenge::external::postgres;
+use tech_challenge::external::postgres::{self, cifragem_de_cpfs, migracoes};
+use tech_challenge::external::telemetria;
+use tech_challenge::gateways::in_memory_agenda_gateway::InMemoryAgendaRepository;
+use tech_challenge::gateways::in_memory_categoria_gateway::InMemoryCategoriaRepository;
 use tech_challenge::gateways::in_memory_pedido_gateway::InMemoryPedidoRepository;
+use tech_challenge::gateways::in_memory_pontos_gateway::InMemoryPontosRepository;
 use tech_challenge::gateways::in_memory_produto_gateway::InMemoryProdutoRepository;
 use tech_challenge::gateways::{
+    postgres_agenda_gateway::PostgresAgendaRepository,
+    postgres_categoria_gateway::PostgresCategoriaRepository,
     postgres_pedido_gateway::PostgresPedidoGateway,
+    postgres_pontos_gateway::PostgresPontosRepository,
     postgres_produto_gateway::PostgresProdutoRepository,
 };
 
-use tech_challenge::traits::{pedido_gateway::PedidoGateway, produto_gateway::ProdutoGateway};
+use tech_challenge::traits::{
+    agenda_gateway::AgendaGateway, categoria_gateway::CategoriaGateway,
+    dependencia_adapter::DependenciaAdapter, pedido_gateway::PedidoGateway,
+    pontos_gateway::PontosGateway, produto_gateway::ProdutoGateway,
+};
+
+// Gateways do listener, em memória ou no Postgres, e as dependências checadas no /health/ready.
+type Gateways = (
+    Arc<dyn AgendaGateway + Sync + Send>,
+    Arc<dyn CategoriaGateway + Sync + Send>,
+    Arc<dyn ProdutoGateway + Sync + Send>,
+    Arc<dyn PedidoGateway + Sync + Send>,
+    Arc<dyn PontosGateway + Sync + Send>,
+    Vec<Arc<dyn DependenciaAdapter + Sync + Send>>,
+);
+
+// Sem banco não há o que consumir: fica só respondendo que não está pronto.
+async fn responde_indisponivel(erro: String, porta: u16) {
+    let dependencia = PostgresDependencia::indisponivel(erro);
+    let servidor = servidor_do_listener(None, vec![Arc::new(dependencia)], porta);
+    if let Err(e) = servidor.launch().await {
+        tracing::error!(erro = ?e, "Erro ao iniciar o servidor HTTP");
+    }
+}
 
 #[tokio::main]
 async fn main() {
+    telemetria::inicializa();
     let config = Config::build();
 
-    let (produto_gateway, pedido_gateway): (
-        Arc<Mutex<dyn ProdutoGateway + Sync + Send>>,
-        Arc<Mutex<dyn PedidoGateway + Sync + Send>>,
-    ) = {
+    let (agenda_gateway, categoria_gateway, produto_gateway, pedido_gateway, pontos_gateway, mut dependencias): Gateways = {
         if config.env == "test" {
             (
-                Arc::new(Mutex::new(InMemoryProdutoRepository::new())),
-                Arc::new(Mutex::new(InMemoryPedidoRepository::new())),
+                Arc::new(InMemoryAgendaRepository::new()),
+                Arc::new(InMemoryCategoriaRepository::new()),
+                Arc::new(InMemoryProdutoRepository::new()),
+                Arc::new(InMemoryPedidoRepository::new()),
+                Arc::new(InMemoryPontosRepository::new()),
+                vec![],
             )
         } else {
-            print!("Connecting to database...");
-            print!("Database URL: {}", config.db_url);
+            tracing::info!("Conectando ao banco de dados");
             let postgres_connection_manager =
-                postgres::PgConnectionManager::new(config.db_url.clone())
-                    .await
-                    .unwrap();
+                match postgres::PgConnectionManager::new(
+                    config.db_url.clone(),
+                    config.db_pool_tamanho,
+                    config.db_tempo_limite,
+                )
+                .await
+                {
+                    Ok(postgres_connection_manager) => postgres_connection_manager,
+                    Err(e) => {
+                        tracing::error!(erro = %e, "Não foi possível conectar ao banco de dados");
+                        return responde_indisponivel(e.to_string(), config.metricas_porta).await;
+                    }
+                };
 
-            let postgres_client = Arc::new(postgres_connection_manager.client);
+            let postgres_pool = postgres_connection_manager.pool;
+            if let Err(e) = migracoes::migra(&postgres_pool).await {
+                tracing::error!(erro = ?e, "Não foi possível migrar o banco de dados");
+                return responde_indisponivel(format!("{:?}", e), config.metricas_porta).await;
+            }
+            let cifrador_de_cpf = config.cifrador_de_cpf();
+            if let Err(e) = cifragem_de_cpfs::cifra_cpfs_em_claro(&postgres_pool, &cifrador_de_cpf).await {
+                tracing::error!(erro = ?e, "Não foi possível cifrar os CPFs do banco de dados");
+                return responde_indisponivel(format!("{:?}", e), config.metricas_porta).await;
+            }
+            let dependencia: Arc<dyn DependenciaAdapter + Sync + Send> =
+                Arc::new(PostgresDependencia::new(postgres_pool.clone()));
 
-            let tables = postgres::get_tables();
+            let agenda_gateway = Arc::new(PostgresAgendaRepository::new(postgres_pool.clone()));
 
-            let produto_gateway = Arc::new(Mutex::new(
-                PostgresProdutoRepository::new(postgres_client.clone(), tables.clone()).await,
-            ));
+            let categoria_gateway = Arc::new(PostgresCategoriaRepository::new(postgres_pool.clone()));
 
-            let pedido_gateway = Arc::new(Mutex::new(
-                PostgresPedidoGateway::new(
-                    postgres_client.clone(),
-                    tables,
-                    produto_gateway.clone(),
-                )
-                .await,
+            let produto_gateway = Arc::new(PostgresProdutoRepository::new(postgres_pool.clone()));
+
+            let pedido_gateway = Arc::new(PostgresPedidoGateway::new(
+                postgres_pool.clone(),
+                produto_gateway.clone(),
+                cifrador_de_cpf.clone(),
             ));
 
-            (produto_gateway, pedido_gateway)
+            let pontos_gateway = Arc::new(PostgresPontosRepository::new(postgres_pool.clone(), cifrador_de_cpf));
+
+            (agenda_gateway, categoria_gateway, produto_gateway, pedido_gateway, pontos_gateway, vec![dependencia])
         }
     };
 
@@ -59,17 +111,29 @@ async fn main() {
         config.clone(),
         produto_gateway.clone(),
         pedido_gateway.clone(),
+        categoria_gateway.clone(),
+        agenda_gateway.clone(),
+        pontos_gateway.clone(),
     );
+    dependencias.push(pagamento_update_subscriber.dependencia());
+
+    let servidor =
+        servidor_do_listener(Some(pedido_gateway.clone()), dependencias, config.metricas_porta);
+    tokio::spawn(async move {
+        if let Err(e) = servidor.launch().await {
+            tracing::error!(erro = ?e, "Erro ao iniciar o servidor HTTP");
+        }
+    });
 
     match pagamento_update_subscriber
         .subscribe_pagamento_queue()
         .await
     {
         Ok(_) => {
-            println!("Conectado ao RabbitMQ");
+            tracing::info!("Conexão com o RabbitMQ encerrada");
         }
         Err(e) => {
-            println!("Erro ao conectar ao RabbitMQ: {:?}", e);
+            tracing::error!(erro = ?e, "Erro ao conectar ao RabbitMQ");
         }
     }
 }
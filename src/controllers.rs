@@ -1,2 +1,10 @@
 pub mod pedido_controller;
 pub mod produto_controller;
+pub mod categoria_controller;
+pub mod agenda_controller;
+pub mod loja_controller;
+pub mod cliente_controller;
+pub mod fidelidade_controller;
+pub mod saude_controller;
+pub mod protecao_de_dados_controller;
+pub mod relatorio_controller;
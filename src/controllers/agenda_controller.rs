@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::agenda::Agenda;
+use crate::traits::{agenda_gateway::AgendaGateway, categoria_gateway::CategoriaGateway, produto_gateway::ProdutoGateway};
+use crate::use_cases::gerenciamento_de_agendas_use_case::{AgendaUseCase, CreateAgendaInput};
+
+pub struct AgendaController {
+    agenda_use_case: AgendaUseCase,
+}
+
+impl AgendaController {
+    pub fn new(
+        agenda_repository: Arc<dyn AgendaGateway + Sync + Send>,
+        produto_repository: Arc<dyn ProdutoGateway + Sync + Send>,
+        categoria_repository: Arc<dyn CategoriaGateway + Sync + Send>,
+    ) -> AgendaController {
+        let agenda_use_case = AgendaUseCase::new(agenda_repository, produto_repository, categoria_repository);
+        AgendaController { agenda_use_case }
+    }
+
+    pub async fn get_agendas(
+        &self,
+    ) -> Result<Vec<Agenda>, DomainError> {
+        self.agenda_use_case.get_agendas().await
+    }
+
+    pub async fn create_agenda(
+        &self,
+        agenda_input: CreateAgendaInput,
+    ) -> Result<Agenda, DomainError> {
+        self.agenda_use_case.create_agenda(agenda_input).await
+    }
+
+    pub async fn delete_agenda(
+        &self,
+        id: usize,
+    ) -> Result<(), DomainError> {
+        self.agenda_use_case.delete_agenda(id).await
+    }
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::categoria::Categoria;
+use crate::traits::{categoria_gateway::CategoriaGateway, produto_gateway::ProdutoGateway};
+use crate::use_cases::gerenciamento_de_categorias_use_case::{CategoriaUseCase, CreateCategoriaInput, UpdateCategoriaInput};
+
+pub struct CategoriaController {
+    categoria_use_case: CategoriaUseCase,
+}
+
+impl CategoriaController {
+    pub fn new(
+        categoria_repository: Arc<dyn CategoriaGateway + Sync + Send>,
+        produto_repository: Arc<dyn ProdutoGateway + Sync + Send>,
+    ) -> CategoriaController {
+        let categoria_use_case = CategoriaUseCase::new(categoria_repository, produto_repository);
+        CategoriaController { categoria_use_case }
+    }
+
+    pub async fn get_categorias(
+        &self,
+    ) -> Result<Vec<Categoria>, DomainError> {
+        self.categoria_use_case.get_categorias().await
+    }
+
+    pub async fn get_categoria_by_id(
+        &self,
+        id: usize,
+    ) -> Result<Categoria, DomainError> {
+        self.categoria_use_case.get_categoria_by_id(id).await
+    }
+
+    pub async fn create_categoria(
+        &self,
+        categoria_input: CreateCategoriaInput,
+    ) -> Result<Categoria, DomainError> {
+        self.categoria_use_case.create_categoria(categoria_input).await
+    }
+
+    pub async fn update_categoria(
+        &self,
+        id: usize,
+        categoria_input: UpdateCategoriaInput,
+    ) -> Result<Categoria, DomainError> {
+        self.categoria_use_case.update_categoria(id, categoria_input).await
+    }
+
+    pub async fn delete_categoria(
+        &self,
+        id: usize,
+    ) -> Result<(), DomainError> {
+        self.categoria_use_case.delete_categoria(id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::{categoria_gateway::MockCategoriaGateway, produto_gateway::MockProdutoGateway};
+
+    #[tokio::test]
+    async fn test_get_categorias() {
+        let mut mock_categoria_gateway = MockCategoriaGateway::new();
+        mock_categoria_gateway.expect_get_categorias().returning(|| Ok(vec![]));
+
+        let categoria_controller = CategoriaController::new(
+            Arc::new(mock_categoria_gateway),
+            Arc::new(MockProdutoGateway::new()),
+        );
+
+        let result = categoria_controller.get_categorias().await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_get_categoria_by_id_not_found() {
+        let mut mock_categoria_gateway = MockCategoriaGateway::new();
+        mock_categoria_gateway.expect_get_categoria_by_id().returning(|_| Err(DomainError::NotFound));
+
+        let categoria_controller = CategoriaController::new(
+            Arc::new(mock_categoria_gateway),
+            Arc::new(MockProdutoGateway::new()),
+        );
+
+        let result = categoria_controller.get_categoria_by_id(9).await;
+        assert!(matches!(result, Err(DomainError::NotFound)));
+    }
+}
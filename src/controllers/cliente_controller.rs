@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use crate::base::domain_error::DomainError;
+use crate::base::paginacao::Pagina;
+use crate::entities::{cliente::Cliente, cpf::Cpf, pedido::Pedido};
+use crate::traits::{cliente_gateway::ClienteGateway, pedido_gateway::PedidoGateway};
+use crate::use_cases::gerenciamento_de_clientes_use_case::{ClienteUseCase, CreateClienteInput, UpdateClienteInput};
+
+pub struct ClienteController {
+    cliente_use_case: ClienteUseCase,
+}
+
+impl ClienteController {
+    pub fn new(
+        cliente_repository: Arc<dyn ClienteGateway + Sync + Send>,
+        pedido_repository: Arc<dyn PedidoGateway + Sync + Send>,
+    ) -> ClienteController {
+        let cliente_use_case = ClienteUseCase::new(cliente_repository, pedido_repository);
+        ClienteController { cliente_use_case }
+    }
+
+    pub async fn get_cliente_by_cpf(
+        &self,
+        cpf: String,
+    ) -> Result<Cliente, DomainError> {
+        self.cliente_use_case.get_cliente_by_cpf(cpf).await
+    }
+
+    pub async fn create_cliente(
+        &self,
+        cliente_input: CreateClienteInput,
+    ) -> Result<Cliente, DomainError> {
+        self.cliente_use_case.create_cliente(cliente_input).await
+    }
+
+    pub async fn update_cliente(
+        &self,
+        cpf: String,
+        cliente_input: UpdateClienteInput,
+    ) -> Result<Cliente, DomainError> {
+        self.cliente_use_case.update_cliente(cpf, cliente_input).await
+    }
+
+    pub async fn historico_de_pedidos(
+        &self,
+        cpf: String,
+        cliente_autenticado: &Cpf,
+        pagina: Option<usize>,
+        tamanho: Option<usize>,
+    ) -> Result<Pagina<Pedido>, DomainError> {
+        self.cliente_use_case
+            .historico_de_pedidos(cpf, cliente_autenticado, pagina, tamanho)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::{cliente_gateway::MockClienteGateway, pedido_gateway::MockPedidoGateway};
+
+    #[tokio::test]
+    async fn test_get_cliente_by_cpf_invalido() {
+        let mut mock_cliente_gateway = MockClienteGateway::new();
+        mock_cliente_gateway.expect_get_cliente_by_cpf().times(0);
+
+        let cliente_controller = ClienteController::new(
+            Arc::new(mock_cliente_gateway),
+            Arc::new(MockPedidoGateway::new()),
+        );
+
+        let result = cliente_controller.get_cliente_by_cpf("123".to_string()).await;
+        assert!(matches!(result, Err(DomainError::InvalidField { ref field, .. }) if field == "cpf"));
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use crate::base::domain_error::DomainError;
+use crate::base::paginacao::Pagina;
+use crate::entities::{
+    cpf::Cpf,
+    pedido::Pedido,
+    pontos::{MovimentoDePontos, RegrasDeFidelidade, SaldoDePontos},
+};
+use crate::traits::{pedido_gateway::PedidoGateway, pontos_gateway::PontosGateway};
+use crate::use_cases::programa_de_fidelidade_use_case::{ProgramaDeFidelidadeUseCase, ResgateInput};
+
+pub struct FidelidadeController {
+    programa_de_fidelidade_use_case: ProgramaDeFidelidadeUseCase,
+}
+
+impl FidelidadeController {
+    pub fn new(
+        pontos_repository: Arc<dyn PontosGateway + Sync + Send>,
+        pedido_repository: Arc<dyn PedidoGateway + Sync + Send>,
+        regras: RegrasDeFidelidade,
+    ) -> FidelidadeController {
+        let programa_de_fidelidade_use_case =
+            ProgramaDeFidelidadeUseCase::new(pontos_repository, pedido_repository, regras);
+        FidelidadeController {
+            programa_de_fidelidade_use_case,
+        }
+    }
+
+    pub async fn saldo(&self, cpf: String, cliente_autenticado: &Cpf) -> Result<SaldoDePontos, DomainError> {
+        self.programa_de_fidelidade_use_case
+            .saldo(cpf, cliente_autenticado)
+            .await
+    }
+
+    pub async fn extrato(
+        &self,
+        cpf: String,
+        cliente_autenticado: &Cpf,
+        pagina: Option<usize>,
+        tamanho: Option<usize>,
+    ) -> Result<Pagina<MovimentoDePontos>, DomainError> {
+        self.programa_de_fidelidade_use_case
+            .extrato(cpf, cliente_autenticado, pagina, tamanho)
+            .await
+    }
+
+    pub async fn resgata(
+        &self,
+        cpf: String,
+        cliente_autenticado: &Cpf,
+        resgate: ResgateInput,
+    ) -> Result<Pedido, DomainError> {
+        self.programa_de_fidelidade_use_case
+            .resgata(cpf, cliente_autenticado, resgate)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gateways::in_memory_pontos_gateway::InMemoryPontosRepository;
+    use crate::traits::pedido_gateway::MockPedidoGateway;
+
+    #[tokio::test]
+    async fn test_saldo_sem_movimentos() {
+        let controller = FidelidadeController::new(
+            Arc::new(InMemoryPontosRepository::new()),
+            Arc::new(MockPedidoGateway::new()),
+            RegrasDeFidelidade::new(1.0, 0.05, 365),
+        );
+        let cliente = Cpf::new("123.456.789-09".to_string()).unwrap();
+
+        let saldo = controller.saldo("12345678909".to_string(), &cliente).await.unwrap();
+        assert_eq!(saldo.saldo, 0);
+        assert!(saldo.lotes.is_empty());
+    }
+}
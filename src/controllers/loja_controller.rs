@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::loja::Loja;
+use crate::traits::loja_gateway::LojaGateway;
+use crate::use_cases::gerenciamento_de_lojas_use_case::{CreateLojaInput, LojaUseCase, UpdateLojaInput};
+
+pub struct LojaController {
+    loja_use_case: LojaUseCase,
+}
+
+impl LojaController {
+    pub fn new(loja_repository: Arc<dyn LojaGateway + Sync + Send>) -> LojaController {
+        let loja_use_case = LojaUseCase::new(loja_repository);
+        LojaController { loja_use_case }
+    }
+
+    pub async fn get_lojas(
+        &self,
+    ) -> Result<Vec<Loja>, DomainError> {
+        self.loja_use_case.get_lojas().await
+    }
+
+    pub async fn get_loja_by_id(
+        &self,
+        id: usize,
+    ) -> Result<Loja, DomainError> {
+        self.loja_use_case.get_loja_by_id(id).await
+    }
+
+    pub async fn create_loja(
+        &self,
+        loja_input: CreateLojaInput,
+    ) -> Result<Loja, DomainError> {
+        self.loja_use_case.create_loja(loja_input).await
+    }
+
+    pub async fn update_loja(
+        &self,
+        id: usize,
+        loja_input: UpdateLojaInput,
+    ) -> Result<Loja, DomainError> {
+        self.loja_use_case.update_loja(id, loja_input).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::loja_gateway::MockLojaGateway;
+
+    #[tokio::test]
+    async fn test_get_loja_by_id_not_found() {
+        let mut mock_loja_gateway = MockLojaGateway::new();
+        mock_loja_gateway.expect_get_loja_by_id().returning(|_| Err(DomainError::NotFound));
+
+        let loja_controller = LojaController::new(Arc::new(mock_loja_gateway));
+
+        let result = loja_controller.get_loja_by_id(9).await;
+        assert!(matches!(result, Err(DomainError::NotFound)));
+    }
+}
@@ -1,66 +1,129 @@
+use chrono::Utc;
+use chrono_tz::Tz;
 use std::sync::Arc;
 
-use tokio::sync::Mutex;
 
 use crate::base::domain_error::DomainError;
+use crate::entities::cpf::Cpf;
 use crate::entities::pedido::{self, Pedido};
-use crate::traits::{pedido_gateway::PedidoGateway, produto_gateway::ProdutoGateway};
+use crate::traits::{
+    agenda_gateway::AgendaGateway, categoria_gateway::CategoriaGateway,
+    cliente_gateway::ClienteGateway, idempotencia_gateway::IdempotenciaGateway,
+    pedido_gateway::PedidoGateway, produto_gateway::ProdutoGateway,
+};
 
 use crate::use_cases::{
+    desempenho_da_cozinha_use_case::{DesempenhoDaCozinhaUseCase, PrevisaoDePreparo},
     pedidos_e_pagamentos_use_case::CreatePedidoInput,
+    pedidos_e_pagamentos_use_case::PedidoRepetido,
     pedidos_e_pagamentos_use_case::PedidosEPagamentosUseCase,
     preparacao_e_entrega_use_case::PreparacaoeEntregaUseCase,
+    programa_de_fidelidade_use_case::ProgramaDeFidelidadeUseCase,
 };
 
 pub struct PedidoController {
+    pedido_repository: Arc<dyn PedidoGateway + Sync + Send>,
+    cliente_repository: Arc<dyn ClienteGateway + Sync + Send>,
+    idempotencia_repository: Arc<dyn IdempotenciaGateway + Sync + Send>,
+    programa_de_fidelidade_use_case: Arc<ProgramaDeFidelidadeUseCase>,
     pedidos_e_pagamentos_use_case: PedidosEPagamentosUseCase,
     preparacao_e_entrega_use_case: PreparacaoeEntregaUseCase,
 }
 
 impl PedidoController {
     pub fn new(
-        pedido_repository: Arc<Mutex<dyn PedidoGateway + Sync + Send>>,
-        produto_repository: Arc<Mutex<dyn ProdutoGateway + Sync + Send>>,
+        pedido_repository: Arc<dyn PedidoGateway + Sync + Send>,
+        produto_repository: Arc<dyn ProdutoGateway + Sync + Send>,
+        categoria_repository: Arc<dyn CategoriaGateway + Sync + Send>,
+        agenda_repository: Arc<dyn AgendaGateway + Sync + Send>,
+        cliente_repository: Arc<dyn ClienteGateway + Sync + Send>,
+        idempotencia_repository: Arc<dyn IdempotenciaGateway + Sync + Send>,
+        programa_de_fidelidade_use_case: Arc<ProgramaDeFidelidadeUseCase>,
     ) -> PedidoController {
         let pedidos_e_pagamentos_use_case = PedidosEPagamentosUseCase::new(
             pedido_repository.clone(),
             produto_repository,
+            categoria_repository,
+            agenda_repository,
         );
-        let preparacao_e_entrega_use_case = PreparacaoeEntregaUseCase::new(pedido_repository);
+        let preparacao_e_entrega_use_case = PreparacaoeEntregaUseCase::new(pedido_repository.clone());
 
         PedidoController {
+            pedido_repository,
+            cliente_repository,
+            idempotencia_repository,
+            programa_de_fidelidade_use_case,
             pedidos_e_pagamentos_use_case,
             preparacao_e_entrega_use_case,
         }
     }
 
-    pub async fn get_pedidos(&self) -> Result<Vec<Pedido>, DomainError> {
-        self.pedidos_e_pagamentos_use_case.lista_pedidos().await
+    pub async fn get_pedidos(&self, loja_id: usize) -> Result<Vec<Pedido>, DomainError> {
+        self.pedidos_e_pagamentos_use_case.lista_pedidos(loja_id).await
     }
 
-    pub async fn get_pedido_by_id(&self, id: usize) -> Result<Pedido, DomainError> {
+    pub async fn get_pedido_by_id(&self, id: usize, loja_id: usize) -> Result<Pedido, DomainError> {
         self.pedidos_e_pagamentos_use_case
-            .seleciona_pedido_por_id(id)
+            .seleciona_pedido_por_id(id, loja_id)
             .await
     }
 
     pub async fn novo_pedido(
         &self,
         pedido_input: CreatePedidoInput,
+        loja_id: usize,
+        fuso: Tz,
+    ) -> Result<Pedido, DomainError> {
+        self.pedidos_e_pagamentos_use_case
+            .novo_pedido(
+                pedido_input,
+                loja_id,
+                self.cliente_repository.clone(),
+                Utc::now().with_timezone(&fuso),
+            )
+            .await
+    }
+
+    pub async fn novo_pedido_idempotente(
+        &self,
+        pedido_input: CreatePedidoInput,
+        loja_id: usize,
+        chave: String,
+        fuso: Tz,
     ) -> Result<Pedido, DomainError> {
         self.pedidos_e_pagamentos_use_case
-            .novo_pedido(pedido_input)
+            .novo_pedido_idempotente(
+                pedido_input,
+                loja_id,
+                chave,
+                self.cliente_repository.clone(),
+                self.idempotencia_repository.clone(),
+                Utc::now().with_timezone(&fuso),
+            )
             .await
     }
 
-    pub async fn get_pedidos_novos(&self) -> Result<Vec<Pedido>, DomainError> {
-        self.preparacao_e_entrega_use_case.get_pedidos_novos().await
+    pub async fn repete_pedido(
+        &self,
+        pedido_id: usize,
+        cliente: &Cpf,
+        fuso: Tz,
+    ) -> Result<PedidoRepetido, DomainError> {
+        self.pedidos_e_pagamentos_use_case
+            .repete_pedido(pedido_id, cliente, Utc::now().with_timezone(&fuso))
+            .await
+    }
+
+    pub async fn get_pedidos_novos(&self, loja_id: usize) -> Result<Vec<Pedido>, DomainError> {
+        self.preparacao_e_entrega_use_case.get_pedidos_novos(loja_id).await
     }
 
     pub async fn atualiza_status_pedido(
         &self,
         id: usize,
         status: &str,
+        loja_id: usize,
+        versao: Option<usize>,
     ) -> Result<Pedido, DomainError> {
         let status = match status {
             "Cancelado" => pedido::Status::Cancelado,
@@ -70,36 +133,57 @@ impl PedidoController {
             "Pago" => pedido::Status::Pago,
             "Pendente" => pedido::Status::Pendente,
             "Pronto" => pedido::Status::Pronto,
-            _ => return Err(DomainError::Invalid("Status inválido".to_string())),
+            _ => {
+                return Err(DomainError::InvalidField {
+                    field: "status".to_string(),
+                    message: "Status inválido".to_string(),
+                })
+            }
         };
         self.preparacao_e_entrega_use_case
-            .atualiza_status(id, status)
+            .atualiza_status(id, status, loja_id, versao, &self.programa_de_fidelidade_use_case)
             .await
     }
 
+    pub async fn previsao_de_preparo(
+        &self,
+        id: usize,
+        loja_id: usize,
+        fuso: Tz,
+    ) -> Result<PrevisaoDePreparo, DomainError> {
+        DesempenhoDaCozinhaUseCase::new(self.pedido_repository.clone())
+            .previsao(id, loja_id, fuso, Utc::now())
+            .await
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::entities::cpf::Cpf;
     use crate::entities::ingredientes::Ingredientes;
     use crate::entities::pedido::Pedido;
-    use crate::entities::produto::{Categoria, Produto};
+    use crate::entities::pontos::RegrasDeFidelidade;
+    use crate::entities::produto::Produto;
+    use crate::gateways::in_memory_cliente_gateway::InMemoryClienteRepository;
+    use crate::gateways::in_memory_idempotencia_gateway::InMemoryIdempotenciaRepository;
+    use crate::gateways::in_memory_pontos_gateway::InMemoryPontosRepository;
     use crate::traits::pedido_gateway::MockPedidoGateway;
     use crate::traits::produto_gateway::MockProdutoGateway;
+    use crate::traits::categoria_gateway::MockCategoriaGateway;
+    use crate::traits::agenda_gateway::MockAgendaGateway;
     use crate::use_cases::pedidos_e_pagamentos_use_case::CreatePedidoInput;
+    use crate::use_cases::relatorios_de_vendas_use_case::FUSO_PADRAO;
     use mockall::predicate::*;
     use pedido::Status;
 
-    fn create_valid_produto(categoria: Categoria) -> Produto {
+    fn create_valid_produto(categoria_id: usize) -> Produto {
         let _now = "2021-08-01 00:00:00.000+0000".to_string();
         Produto::new(
             1,
             "Cheeseburger".to_string(),
             "cheeseburger.png".to_string(),
             "O clássico pão, carne e queijo!".to_string(),
-            categoria,
+            categoria_id,
             9.99,
             Ingredientes::new(vec![
                 "Pão".to_string(),
@@ -115,7 +199,7 @@ mod tests {
     fn create_valid_pedido() -> Pedido {
         let _now = "2021-08-01 00:00:00.000+0000".to_string();
         let cliente = Cpf::new("123.456.789-09".to_string()).unwrap();
-        let produto = create_valid_produto(Categoria::Lanche);
+        let produto = create_valid_produto(1);
         Pedido::new(
             1,
             Some(cliente),
@@ -129,9 +213,31 @@ mod tests {
         )
     }
 
+    fn build_controller(
+        pedido_gateway: MockPedidoGateway,
+        agenda_gateway: MockAgendaGateway,
+    ) -> PedidoController {
+        let pedido_gateway = Arc::new(pedido_gateway);
+        let programa_de_fidelidade = ProgramaDeFidelidadeUseCase::new(
+            Arc::new(InMemoryPontosRepository::new()),
+            pedido_gateway.clone(),
+            RegrasDeFidelidade::new(1.0, 0.05, 365),
+        );
+        PedidoController::new(
+            pedido_gateway,
+            Arc::new(MockProdutoGateway::new()),
+            Arc::new(MockCategoriaGateway::new()),
+            Arc::new(agenda_gateway),
+            Arc::new(InMemoryClienteRepository::new()),
+            Arc::new(InMemoryIdempotenciaRepository::new(std::time::Duration::from_secs(60))),
+            Arc::new(programa_de_fidelidade),
+        )
+    }
+
     fn create_valid_input() -> CreatePedidoInput {
         CreatePedidoInput {
             cliente_id: None,
+            documento_fiscal: None,
             lanche_id: None,
             acompanhamento_id: None,
             bebida_id: None,
@@ -144,14 +250,11 @@ mod tests {
         mock_pedido_gateway
             .expect_lista_pedidos()
             .times(1)
-            .returning(|| Ok(vec![]));
+            .returning(|_| Ok(vec![]));
 
-        let pedido_gateway = Arc::new(Mutex::new(mock_pedido_gateway));
-        let produto_gateway = Arc::new(Mutex::new(MockProdutoGateway::new()));
+        let controller = build_controller(mock_pedido_gateway, MockAgendaGateway::new());
 
-        let controller = PedidoController::new(pedido_gateway, produto_gateway);
-
-        let result = controller.get_pedidos().await;
+        let result = controller.get_pedidos(1).await;
         assert!(result.is_ok());
         assert_eq!(result.unwrap().len(), 0);
     }
@@ -163,17 +266,14 @@ mod tests {
 
         let mut mock_pedido_gateway = MockPedidoGateway::new();
         mock_pedido_gateway
-            .expect_get_pedido_by_id()
+            .expect_get_pedido_da_loja()
             .times(1)
-            .with(eq(1))
-            .returning(move |_| Ok(pedido_retornado.clone()));
-
-        let pedido_gateway = Arc::new(Mutex::new(mock_pedido_gateway));
-        let produto_gateway = Arc::new(Mutex::new(MockProdutoGateway::new()));
+            .with(eq(1), eq(1))
+            .returning(move |_, _| Ok(pedido_retornado.clone()));
 
-        let controller = PedidoController::new(pedido_gateway, produto_gateway);
+        let controller = build_controller(mock_pedido_gateway, MockAgendaGateway::new());
 
-        let result = controller.get_pedido_by_id(1).await;
+        let result = controller.get_pedido_by_id(1, 1).await;
         assert!(result.is_ok());
         assert_eq!(result.unwrap().id(), pedido_esperado.id());
     }
@@ -189,14 +289,16 @@ mod tests {
             .times(1)
             .returning(move |_| Ok(pedido_retornado.clone()));
 
-        let pedido_gateway = Arc::new(Mutex::new(mock_pedido_gateway));
-        let produto_gateway = Arc::new(Mutex::new(MockProdutoGateway::new()));
+        let mut mock_agenda_gateway = MockAgendaGateway::new();
+        mock_agenda_gateway.expect_get_agendas().returning(|| Ok(vec![]));
 
-        let controller = PedidoController::new(pedido_gateway, produto_gateway);
+        let controller = build_controller(mock_pedido_gateway, mock_agenda_gateway);
 
         let pedido_input = create_valid_input();
 
-        let result = controller.novo_pedido(pedido_input).await;
+        let result = controller
+            .novo_pedido(pedido_input, 1, FUSO_PADRAO)
+            .await;
 
         assert!(result.is_ok());
 
@@ -211,14 +313,11 @@ mod tests {
         mock_pedido_gateway
             .expect_get_pedidos_novos()
             .times(1)
-            .returning(move || Ok(vec![pedido_retornado.clone()]));
+            .returning(move |_| Ok(vec![pedido_retornado.clone()]));
 
-        let pedido_gateway = Arc::new(Mutex::new(mock_pedido_gateway));
-        let produto_gateway = Arc::new(Mutex::new(MockProdutoGateway::new()));
+        let controller = build_controller(mock_pedido_gateway, MockAgendaGateway::new());
 
-        let controller = PedidoController::new(pedido_gateway, produto_gateway);
-
-        let result = controller.get_pedidos_novos().await;
+        let result = controller.get_pedidos_novos(1).await;
         assert!(result.is_ok());
         assert_eq!(result.unwrap().len(), 1);
     }
@@ -229,18 +328,21 @@ mod tests {
         let pedido_esperado = create_valid_pedido();
 
         let mut mock_pedido_gateway = MockPedidoGateway::new();
+        mock_pedido_gateway
+            .expect_get_pedido_da_loja()
+            .times(1)
+            .returning(|_, _| Ok(create_valid_pedido()));
         mock_pedido_gateway
             .expect_atualiza_status()
             .times(1)
-            .with(eq(1), eq(Status::Finalizado))
-            .returning(move |_, _| Ok(pedido_retornado.clone()));
-
-        let pedido_gateway = Arc::new(Mutex::new(mock_pedido_gateway));
-        let produto_gateway = Arc::new(Mutex::new(MockProdutoGateway::new()));
+            .with(eq(1), eq(Status::Finalizado), eq(1))
+            .returning(move |_, _, _| Ok(pedido_retornado.clone()));
 
-        let controller = PedidoController::new(pedido_gateway, produto_gateway);
+        let controller = build_controller(mock_pedido_gateway, MockAgendaGateway::new());
 
-        let result = controller.atualiza_status_pedido(1, "Finalizado").await;
+        let result = controller
+            .atualiza_status_pedido(1, "Finalizado", 1, Some(1))
+            .await;
         assert!(result.is_ok());
         assert_eq!(result.unwrap().id(), pedido_esperado.id());
     }
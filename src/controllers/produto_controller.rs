@@ -1,32 +1,80 @@
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use std::sync::Arc;
-use tokio::sync::Mutex;
 
 use crate::base::domain_error::DomainError;
-use crate::entities::produto::Produto;
-use crate::traits::produto_gateway::ProdutoGateway;
-use crate::use_cases::gerenciamento_de_produtos_use_case::{CreateProdutoInput, ProdutoUseCase, UpdateProdutoInput};
+use crate::entities::{idioma::{Idioma, TraducaoProduto}, loja::ProdutoLoja, produto::Produto};
+use crate::traits::{agenda_gateway::AgendaGateway, categoria_gateway::CategoriaGateway, foto_storage_adapter::FotoStorageAdapter, produto_gateway::ProdutoGateway};
+use crate::use_cases::gerenciamento_de_produtos_use_case::{CreateProdutoInput, FotoInput, ProdutoLojaInput, ProdutoUseCase, SecaoCardapio, TraducaoInput, UpdateProdutoInput};
+use crate::use_cases::importacao_de_catalogo_use_case::{CatalogoUseCase, FormatoCatalogo, RelatorioImportacao};
 
 pub struct ProdutoController {
     produto_use_case: ProdutoUseCase,
+    catalogo_use_case: CatalogoUseCase,
 }
 
 impl ProdutoController {
-    pub fn new(produto_repository: Arc<Mutex<dyn ProdutoGateway + Sync + Send>>) -> ProdutoController {
-        let produto_use_case = ProdutoUseCase::new(produto_repository);
-        ProdutoController { produto_use_case }
+    pub fn new(
+        produto_repository: Arc<dyn ProdutoGateway + Sync + Send>,
+        categoria_repository: Arc<dyn CategoriaGateway + Sync + Send>,
+        agenda_repository: Arc<dyn AgendaGateway + Sync + Send>,
+    ) -> ProdutoController {
+        let catalogo_use_case = CatalogoUseCase::new(produto_repository.clone(), categoria_repository.clone());
+        let produto_use_case = ProdutoUseCase::new(produto_repository, categoria_repository, agenda_repository);
+        ProdutoController { produto_use_case, catalogo_use_case }
+    }
+
+    // As agendas valem no fuso da loja, qualquer que seja o deslocamento informado em `em`.
+    fn parse_momento(em: Option<String>, fuso: Tz) -> Result<DateTime<Tz>, DomainError> {
+        match em {
+            Some(em) => DateTime::parse_from_rfc3339(&em)
+                .map(|momento| momento.with_timezone(&fuso))
+                .map_err(|_| DomainError::InvalidField {
+                    field: "em".to_string(),
+                    message: format!("Data inválida: {}", em),
+                }),
+            None => Ok(Utc::now().with_timezone(&fuso)),
+        }
     }
 
     pub async fn get_produto(
         &self,
+        loja_id: usize,
+        idioma: Idioma,
+        em: Option<String>,
+        fuso: Tz,
     ) -> Result<Vec<Produto>, DomainError> {
-        self.produto_use_case.get_produtos().await
+        let momento = ProdutoController::parse_momento(em, fuso)?;
+        self.produto_use_case.get_produtos(loja_id, idioma, momento).await
     }
 
     pub async fn get_produto_by_id(
         &self,
         id: usize,
+        loja_id: usize,
+        idioma: Idioma,
     ) -> Result<Produto, DomainError> {
-        self.produto_use_case.get_produto_by_id(id).await
+        self.produto_use_case.get_produto_by_id(id, loja_id, idioma).await
+    }
+
+    pub async fn get_cardapio(
+        &self,
+        loja_id: usize,
+        idioma: Idioma,
+        em: Option<String>,
+        fuso: Tz,
+    ) -> Result<Vec<SecaoCardapio>, DomainError> {
+        let momento = ProdutoController::parse_momento(em, fuso)?;
+        self.produto_use_case.get_cardapio(loja_id, idioma, momento).await
+    }
+
+    pub async fn busca_produtos(
+        &self,
+        termo: String,
+        loja_id: usize,
+        idioma: Idioma,
+    ) -> Result<Vec<Produto>, DomainError> {
+        self.produto_use_case.busca_produtos(termo, loja_id, idioma).await
     }
 
     pub async fn create_produto(
@@ -40,8 +88,44 @@ impl ProdutoController {
         &self,
         id: usize,
         produto_input: UpdateProdutoInput,
+        versao: Option<usize>,
     ) -> Result<Produto, DomainError> {
-        self.produto_use_case.update_produto(id, produto_input).await
+        self.produto_use_case.update_produto(id, produto_input, versao).await
+    }
+
+    pub async fn atualiza_foto(
+        &self,
+        id: usize,
+        foto: FotoInput,
+        foto_storage: Arc<dyn FotoStorageAdapter + Sync + Send>,
+    ) -> Result<Produto, DomainError> {
+        self.produto_use_case.atualiza_foto(id, foto, foto_storage).await
+    }
+
+    pub async fn define_produto_loja(
+        &self,
+        loja_id: usize,
+        produto_id: usize,
+        ajuste: ProdutoLojaInput,
+    ) -> Result<ProdutoLoja, DomainError> {
+        self.produto_use_case.define_produto_loja(loja_id, produto_id, ajuste).await
+    }
+
+    pub async fn get_traducoes(
+        &self,
+        produto_id: usize,
+    ) -> Result<Vec<TraducaoProduto>, DomainError> {
+        self.produto_use_case.get_traducoes(produto_id).await
+    }
+
+    pub async fn define_traducao(
+        &self,
+        produto_id: usize,
+        idioma: String,
+        traducao: TraducaoInput,
+    ) -> Result<TraducaoProduto, DomainError> {
+        let idioma = idioma.parse::<Idioma>()?;
+        self.produto_use_case.define_traducao(produto_id, idioma, traducao).await
     }
 
     pub async fn delete_produto(
@@ -50,22 +134,64 @@ impl ProdutoController {
     ) -> Result<(), DomainError> {
         self.produto_use_case.delete_produto(id).await
     }
+
+    pub async fn exporta_catalogo(
+        &self,
+        formato: String,
+    ) -> Result<String, DomainError> {
+        let formato: FormatoCatalogo = formato.parse()?;
+        self.catalogo_use_case.exporta_catalogo(formato).await
+    }
+
+    pub async fn importa_catalogo(
+        &self,
+        conteudo: String,
+        formato: String,
+        dry_run: bool,
+    ) -> Result<RelatorioImportacao, DomainError> {
+        let formato: FormatoCatalogo = formato.parse()?;
+        self.catalogo_use_case.importa_catalogo(conteudo, formato, dry_run).await
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{entities::{ingredientes::Ingredientes, produto::Categoria}, traits::produto_gateway::MockProdutoGateway};
+    use crate::{entities::{categoria::{Categoria, DadosDaCategoria, ItemPedido}, ingredientes::Ingredientes}, traits::{agenda_gateway::MockAgendaGateway, categoria_gateway::MockCategoriaGateway, foto_storage_adapter::MockFotoStorageAdapter, produto_gateway::MockProdutoGateway}};
+    use crate::use_cases::relatorios_de_vendas_use_case::FUSO_PADRAO;
+
+    fn categoria_repository() -> Arc<MockCategoriaGateway> {
+        let mut mock_categoria_gateway = MockCategoriaGateway::new();
+        mock_categoria_gateway.expect_get_categoria_by_id().returning(|id| Ok(Categoria::new(
+            id,
+            DadosDaCategoria {
+                nome: "Lanche".to_string(),
+                icone: None,
+                ordem: 1,
+                ativa: true,
+                item_pedido: Some(ItemPedido::Lanche),
+            },
+            "2021-10-10".to_string(),
+            "2021-10-10".to_string(),
+        )));
+        Arc::new(mock_categoria_gateway)
+    }
+
+    fn agenda_repository() -> Arc<MockAgendaGateway> {
+        let mut mock_agenda_gateway = MockAgendaGateway::new();
+        mock_agenda_gateway.expect_get_agendas().returning(|| Ok(vec![]));
+        Arc::new(mock_agenda_gateway)
+    }
 
     #[tokio::test]
     async fn test_get_produto() {
         let mut mock_produto_gateway = MockProdutoGateway::new();
-        mock_produto_gateway.expect_get_produtos().returning(|| Ok(vec![]));
+        mock_produto_gateway.expect_get_produtos_da_loja().returning(|_| Ok(vec![]));
 
-        let produto_repository = Arc::new(Mutex::new(mock_produto_gateway));
-        let produto_controller = ProdutoController::new(produto_repository);
+        let produto_repository = Arc::new(mock_produto_gateway);
+        let produto_controller = ProdutoController::new(produto_repository, categoria_repository(), agenda_repository());
 
-        let result = produto_controller.get_produto().await;
+        let result = produto_controller.get_produto(1, Idioma::PtBr, None, FUSO_PADRAO).await;
         assert!(result.is_ok());
     }
 
@@ -77,7 +203,7 @@ mod tests {
             "Nome".to_string(),
             "Foto".to_string(),
             "Descricao".to_string(),
-            Categoria::Lanche,
+            1,
             1.0,
             Ingredientes::new(vec![
                 "Pão".to_string(),
@@ -87,11 +213,70 @@ mod tests {
             "2021-10-10".to_string(),
             "2021-10-10".to_string(),
         )));
+        mock_produto_gateway.expect_get_produtos_loja().returning(|_| Ok(vec![]));
+
+        let produto_repository = Arc::new(mock_produto_gateway);
+        let produto_controller = ProdutoController::new(produto_repository, categoria_repository(), agenda_repository());
+
+        let result = produto_controller.get_produto_by_id(1, 1, Idioma::PtBr).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_get_cardapio() {
+        let mut mock_produto_gateway = MockProdutoGateway::new();
+        mock_produto_gateway.expect_get_produtos_da_loja().returning(|_| Ok(vec![]));
+
+        let mut mock_categoria_gateway = MockCategoriaGateway::new();
+        mock_categoria_gateway.expect_get_categorias().returning(|| Ok(vec![]));
 
-        let produto_repository = Arc::new(Mutex::new(mock_produto_gateway));
-        let produto_controller = ProdutoController::new(produto_repository);
+        let produto_repository = Arc::new(mock_produto_gateway);
+        let produto_controller = ProdutoController::new(produto_repository, Arc::new(mock_categoria_gateway), agenda_repository());
 
-        let result = produto_controller.get_produto_by_id(1).await;
+        let result = produto_controller.get_cardapio(1, Idioma::PtBr, Some("2024-05-06T12:00:00-03:00".to_string()), FUSO_PADRAO).await;
+        assert!(result.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_produto_data_invalida() {
+        let produto_repository = Arc::new(MockProdutoGateway::new());
+        let produto_controller = ProdutoController::new(produto_repository, categoria_repository(), agenda_repository());
+
+        let result = produto_controller.get_produto(1, Idioma::PtBr, Some("amanhã".to_string()), FUSO_PADRAO).await;
+        assert!(matches!(result, Err(DomainError::InvalidField { ref field, .. }) if field == "em"));
+    }
+
+    #[tokio::test]
+    async fn test_define_traducao_idioma_invalido() {
+        let produto_repository = Arc::new(MockProdutoGateway::new());
+        let produto_controller = ProdutoController::new(produto_repository, categoria_repository(), agenda_repository());
+
+        let traducao = TraducaoInput {
+            nome: Some("Hamburger".to_string()),
+            descricao: None,
+        };
+        let result = produto_controller.define_traducao(1, "fr".to_string(), traducao).await;
+        assert!(matches!(result, Err(DomainError::InvalidField { ref field, .. }) if field == "idioma"));
+    }
+
+    #[tokio::test]
+    async fn test_exporta_catalogo_formato_invalido() {
+        let produto_repository = Arc::new(MockProdutoGateway::new());
+        let produto_controller = ProdutoController::new(produto_repository, categoria_repository(), agenda_repository());
+
+        let result = produto_controller.exporta_catalogo("xlsx".to_string()).await;
+        assert!(matches!(result, Err(DomainError::Invalid(_))));
+    }
+
+    #[tokio::test]
+    async fn test_busca_produtos() {
+        let mut mock_produto_gateway = MockProdutoGateway::new();
+        mock_produto_gateway.expect_busca_produtos().returning(|_, _| Ok(vec![]));
+
+        let produto_repository = Arc::new(mock_produto_gateway);
+        let produto_controller = ProdutoController::new(produto_repository, categoria_repository(), agenda_repository());
+
+        let result = produto_controller.busca_produtos("hamburguer".to_string(), 1, Idioma::PtBr).await;
         assert!(result.is_ok());
     }
 
@@ -103,7 +288,7 @@ mod tests {
             "Nome".to_string(),
             "Foto".to_string(),
             "Descricao".to_string(),
-            Categoria::Lanche,
+            1,
             1.0,
             Ingredientes::new(vec![
                 "Pão".to_string(),
@@ -114,20 +299,21 @@ mod tests {
             "2021-10-10".to_string(),
         )));
 
-        let produto_repository = Arc::new(Mutex::new(mock_produto_gateway));
-        let produto_controller = ProdutoController::new(produto_repository);
+        let produto_repository = Arc::new(mock_produto_gateway);
+        let produto_controller = ProdutoController::new(produto_repository, categoria_repository(), agenda_repository());
 
         let produto_input = CreateProdutoInput {
             nome: "Nome".to_string(),
             foto: "Foto".to_string(),
             descricao: "Descricao".to_string(),
-            categoria: Categoria::Lanche,
+            categoria_id: 1,
             preco: 1.0,
             ingredientes: Ingredientes::new(vec![
                 "Pão".to_string(),
                 "Hambúrguer".to_string(),
                 "Queijo".to_string(),
             ]).unwrap(),
+            loja_id: None,
         };
 
         let result = produto_controller.create_produto(produto_input).await;
@@ -142,7 +328,7 @@ mod tests {
             "Nome".to_string(),
             "Foto".to_string(),
             "Descricao".to_string(),
-            Categoria::Lanche,
+            1,
             1.0,
             Ingredientes::new(vec![
                 "Pão".to_string(),
@@ -154,33 +340,72 @@ mod tests {
         )));
         mock_produto_gateway.expect_update_produto().returning(|produto| Ok(produto));
 
-        let produto_repository = Arc::new(Mutex::new(mock_produto_gateway));
-        let produto_controller = ProdutoController::new(produto_repository);
+        let produto_repository = Arc::new(mock_produto_gateway);
+        let produto_controller = ProdutoController::new(produto_repository, categoria_repository(), agenda_repository());
 
         let produto_input = UpdateProdutoInput {
             nome: Some("Nome".to_string()),
             foto: Some("Foto".to_string()),
             descricao: Some("Descricao".to_string()),
-            categoria: Some(Categoria::Lanche),
+            categoria_id: Some(1),
             preco: Some(1.0),
             ingredientes: Some(Ingredientes::new(vec![
                 "Pão".to_string(),
                 "Hambúrguer".to_string(),
                 "Queijo".to_string(),
             ]).unwrap()),
+            loja_id: None,
         };
 
-        let result = produto_controller.update_produto(1, produto_input).await;
+        let result = produto_controller.update_produto(1, produto_input, None).await;
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_atualiza_foto() {
+        let mut mock_produto_gateway = MockProdutoGateway::new();
+        mock_produto_gateway.expect_get_produto_by_id().returning(|_| Ok(Produto::new(
+            1,
+            "Nome".to_string(),
+            "Foto".to_string(),
+            "Descricao".to_string(),
+            1,
+            1.0,
+            Ingredientes::new(vec![
+                "Pão".to_string(),
+                "Hambúrguer".to_string(),
+                "Queijo".to_string(),
+            ]).unwrap(),
+            "2021-10-10".to_string(),
+            "2021-10-10".to_string(),
+        )));
+        mock_produto_gateway.expect_update_produto().returning(Ok);
+
+        let mut mock_foto_storage = MockFotoStorageAdapter::new();
+        mock_foto_storage
+            .expect_salva_foto()
+            .returning(|nome_arquivo, _| Ok(format!("/fotos/{}", nome_arquivo)));
+        mock_foto_storage.expect_remove_foto().returning(|_| Ok(()));
+
+        let produto_repository = Arc::new(mock_produto_gateway);
+        let produto_controller = ProdutoController::new(produto_repository, categoria_repository(), agenda_repository());
+
+        let foto = FotoInput {
+            content_type: "image/jpeg".to_string(),
+            conteudo: vec![0xFF, 0xD8, 0xFF, 0xE0],
+        };
+
+        let result = produto_controller.atualiza_foto(1, foto, Arc::new(mock_foto_storage)).await;
+        assert!(result.unwrap().foto().ends_with(".jpg"));
+    }
+
     #[tokio::test]
     async fn test_delete_produto() {
         let mut mock_produto_gateway = MockProdutoGateway::new();
         mock_produto_gateway.expect_delete_produto().returning(|_| Ok(()));
 
-        let produto_repository = Arc::new(Mutex::new(mock_produto_gateway));
-        let produto_controller = ProdutoController::new(produto_repository);
+        let produto_repository = Arc::new(mock_produto_gateway);
+        let produto_controller = ProdutoController::new(produto_repository, categoria_repository(), agenda_repository());
 
         let result = produto_controller.delete_produto(1).await;
         assert!(result.is_ok());
@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use crate::base::domain_error::DomainError;
+use crate::base::pseudonimo::Pseudonimizador;
+use crate::traits::{
+    auditoria_gateway::AuditoriaGateway, cliente_gateway::ClienteGateway,
+    pedido_gateway::PedidoGateway, pontos_gateway::PontosGateway,
+};
+use crate::use_cases::protecao_de_dados_use_case::{Anonimizacao, DadosDoTitular, ProtecaoDeDadosUseCase};
+
+pub struct ProtecaoDeDadosController {
+    protecao_de_dados_use_case: ProtecaoDeDadosUseCase,
+}
+
+impl ProtecaoDeDadosController {
+    pub fn new(
+        cliente_repository: Arc<dyn ClienteGateway + Sync + Send>,
+        pedido_repository: Arc<dyn PedidoGateway + Sync + Send>,
+        pontos_repository: Arc<dyn PontosGateway + Sync + Send>,
+        auditoria_repository: Arc<dyn AuditoriaGateway + Sync + Send>,
+        pseudonimizador: Pseudonimizador,
+    ) -> ProtecaoDeDadosController {
+        let protecao_de_dados_use_case = ProtecaoDeDadosUseCase::new(
+            cliente_repository,
+            pedido_repository,
+            pontos_repository,
+            auditoria_repository,
+            pseudonimizador,
+        );
+        ProtecaoDeDadosController {
+            protecao_de_dados_use_case,
+        }
+    }
+
+    pub async fn exporta_dados(&self, cpf: String, request_id: String) -> Result<DadosDoTitular, DomainError> {
+        self.protecao_de_dados_use_case.exporta_dados(cpf, request_id).await
+    }
+
+    pub async fn anonimiza(&self, cpf: String, request_id: String) -> Result<Anonimizacao, DomainError> {
+        self.protecao_de_dados_use_case.anonimiza(cpf, request_id).await
+    }
+}
@@ -0,0 +1,149 @@
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::base::domain_error::DomainError;
+use crate::traits::{categoria_gateway::CategoriaGateway, pedido_gateway::PedidoGateway};
+use crate::use_cases::desempenho_da_cozinha_use_case::{DesempenhoDaCozinha, DesempenhoDaCozinhaUseCase};
+use crate::use_cases::relatorios_de_vendas_use_case::{
+    para_csv, Agrupamento, FormatoDeRelatorio, PeriodoDoRelatorio, RelatoriosDeVendasUseCase,
+};
+
+pub struct RelatorioController {
+    relatorios_de_vendas_use_case: RelatoriosDeVendasUseCase,
+    desempenho_da_cozinha_use_case: DesempenhoDaCozinhaUseCase,
+    fuso_da_loja: Tz,
+}
+
+fn parse_data(campo: &str, data: Option<String>) -> Result<NaiveDate, DomainError> {
+    let data = data.ok_or_else(|| DomainError::InvalidField {
+        field: campo.to_string(),
+        message: "Data obrigatória (AAAA-MM-DD)".to_string(),
+    })?;
+    NaiveDate::parse_from_str(&data, "%Y-%m-%d").map_err(|_| DomainError::InvalidField {
+        field: campo.to_string(),
+        message: format!("Data inválida (AAAA-MM-DD): {}", data),
+    })
+}
+
+fn parse_periodo(
+    inicio: Option<String>,
+    fim: Option<String>,
+    fuso: Option<String>,
+    fuso_da_loja: Tz,
+) -> Result<PeriodoDoRelatorio, DomainError> {
+    let fuso = match fuso {
+        Some(fuso) => Tz::from_str(&fuso).map_err(|_| DomainError::InvalidField {
+            field: "fuso".to_string(),
+            message: format!("Fuso horário inválido: {}", fuso),
+        })?,
+        None => fuso_da_loja,
+    };
+    PeriodoDoRelatorio::new(parse_data("inicio", inicio)?, parse_data("fim", fim)?, fuso)
+}
+
+fn parse_formato(formato: Option<String>) -> Result<FormatoDeRelatorio, DomainError> {
+    formato.map_or(Ok(FormatoDeRelatorio::Json), |formato| formato.parse())
+}
+
+fn para_json<T: serde::Serialize>(relatorio: &T) -> Result<String, DomainError> {
+    serde_json::to_string(relatorio).map_err(|erro| DomainError::Invalid(erro.to_string()))
+}
+
+impl RelatorioController {
+    pub fn new(
+        pedido_repository: Arc<dyn PedidoGateway + Sync + Send>,
+        categoria_repository: Arc<dyn CategoriaGateway + Sync + Send>,
+        fuso_da_loja: Tz,
+    ) -> RelatorioController {
+        let relatorios_de_vendas_use_case = RelatoriosDeVendasUseCase::new(pedido_repository.clone(), categoria_repository);
+        let desempenho_da_cozinha_use_case = DesempenhoDaCozinhaUseCase::new(pedido_repository);
+        RelatorioController {
+            relatorios_de_vendas_use_case,
+            desempenho_da_cozinha_use_case,
+            fuso_da_loja,
+        }
+    }
+
+    // No CSV, uma linha por período e a última com o total.
+    pub async fn vendas(
+        &self,
+        loja_id: usize,
+        inicio: Option<String>,
+        fim: Option<String>,
+        fuso: Option<String>,
+        agrupamento: Option<String>,
+        formato: Option<String>,
+    ) -> Result<String, DomainError> {
+        let periodo = parse_periodo(inicio, fim, fuso, self.fuso_da_loja)?;
+        let agrupamento = agrupamento.map_or(Ok(Agrupamento::Dia), |agrupamento| agrupamento.parse())?;
+        let formato = parse_formato(formato)?;
+        let vendas = self
+            .relatorios_de_vendas_use_case
+            .vendas_por_periodo(loja_id, periodo, agrupamento)
+            .await?;
+        match formato {
+            FormatoDeRelatorio::Json => para_json(&vendas),
+            FormatoDeRelatorio::Csv => {
+                let mut linhas = vendas.periodos;
+                linhas.push(vendas.total);
+                para_csv(&linhas)
+            }
+        }
+    }
+
+    pub async fn vendas_por_categoria(
+        &self,
+        loja_id: usize,
+        inicio: Option<String>,
+        fim: Option<String>,
+        fuso: Option<String>,
+        formato: Option<String>,
+    ) -> Result<String, DomainError> {
+        let periodo = parse_periodo(inicio, fim, fuso, self.fuso_da_loja)?;
+        let formato = parse_formato(formato)?;
+        let categorias = self
+            .relatorios_de_vendas_use_case
+            .vendas_por_categoria(loja_id, periodo)
+            .await?;
+        match formato {
+            FormatoDeRelatorio::Json => para_json(&categorias),
+            FormatoDeRelatorio::Csv => para_csv(&categorias),
+        }
+    }
+
+    pub async fn vendas_por_produto(
+        &self,
+        loja_id: usize,
+        inicio: Option<String>,
+        fim: Option<String>,
+        fuso: Option<String>,
+        limite: Option<usize>,
+        formato: Option<String>,
+    ) -> Result<String, DomainError> {
+        let periodo = parse_periodo(inicio, fim, fuso, self.fuso_da_loja)?;
+        let formato = parse_formato(formato)?;
+        let produtos = self
+            .relatorios_de_vendas_use_case
+            .vendas_por_produto(loja_id, periodo, limite)
+            .await?;
+        match formato {
+            FormatoDeRelatorio::Json => para_json(&produtos),
+            FormatoDeRelatorio::Csv => para_csv(&produtos),
+        }
+    }
+
+    pub async fn desempenho_da_cozinha(
+        &self,
+        loja_id: usize,
+        inicio: Option<String>,
+        fim: Option<String>,
+        fuso: Option<String>,
+    ) -> Result<DesempenhoDaCozinha, DomainError> {
+        let periodo = parse_periodo(inicio, fim, fuso, self.fuso_da_loja)?;
+        self.desempenho_da_cozinha_use_case
+            .desempenho(loja_id, periodo)
+            .await
+    }
+}
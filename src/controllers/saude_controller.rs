@@ -0,0 +1,21 @@
+use std::sync::Arc;
+
+use crate::traits::dependencia_adapter::DependenciaAdapter;
+use crate::use_cases::verificacao_de_saude_use_case::{Prontidao, VerificacaoDeSaudeUseCase};
+
+pub struct SaudeController {
+    verificacao_de_saude_use_case: VerificacaoDeSaudeUseCase,
+}
+
+impl SaudeController {
+    pub fn new(dependencias: Vec<Arc<dyn DependenciaAdapter + Sync + Send>>) -> SaudeController {
+        let verificacao_de_saude_use_case = VerificacaoDeSaudeUseCase::new(dependencias);
+        SaudeController {
+            verificacao_de_saude_use_case,
+        }
+    }
+
+    pub async fn verifica_prontidao(&self) -> Prontidao {
+        self.verificacao_de_saude_use_case.verifica_prontidao().await
+    }
+}
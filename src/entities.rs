@@ -1,4 +1,14 @@
 pub mod produto;
 pub mod pedido;
 pub mod cpf;
-pub mod ingredientes;
\ No newline at end of file
+pub mod ingredientes;
+pub mod categoria;
+pub mod agenda;
+pub mod loja;
+pub mod idioma;
+pub mod idempotencia;
+pub mod cliente;
+pub mod pontos;
+pub mod auditoria;
+pub mod documento_fiscal;
+pub mod transicao_de_status;
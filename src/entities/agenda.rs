@@ -0,0 +1,307 @@
+use chrono::{DateTime, Datelike, NaiveTime};
+use chrono_tz::Tz;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    base::{assertion_concern, domain_error::DomainError},
+    entities::produto::Produto,
+};
+
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema, PartialEq)]
+pub enum AlvoAgenda {
+    Produto,
+    Categoria,
+}
+
+// Campos da agenda definidos pelo administrador.
+#[derive(Clone, Debug)]
+pub struct DadosDaAgenda {
+    pub alvo: AlvoAgenda,
+    pub alvo_id: usize,
+    pub dias_semana: Vec<u32>,
+    pub hora_inicio: String,
+    pub hora_fim: String,
+    pub preco: Option<f64>,
+}
+
+// Sem preço a agenda é uma janela de disponibilidade; com preço é um preço programado.
+// Dias da semana vão de 0 (domingo) a 6 (sábado) e os horários usam o formato HH:MM,
+// no fuso horário da loja.
+// Quando hora_fim <= hora_inicio a janela atravessa a meia-noite (iguais = 24 horas).
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct Agenda {
+    id: usize,
+    alvo: AlvoAgenda,
+    alvo_id: usize,
+    dias_semana: Vec<u32>,
+    hora_inicio: String,
+    hora_fim: String,
+    preco: Option<f64>,
+    data_criacao: String,
+    data_atualizacao: String,
+}
+
+fn parse_hora(hora: &str) -> Result<NaiveTime, DomainError> {
+    NaiveTime::parse_from_str(hora, "%H:%M")
+        .map_err(|_| DomainError::Invalid(format!("Horário inválido: {}", hora)))
+}
+
+impl Agenda {
+    pub fn new(id: usize, dados: DadosDaAgenda, data_criacao: String, data_atualizacao: String) -> Self {
+        Agenda {
+            id,
+            alvo: dados.alvo,
+            alvo_id: dados.alvo_id,
+            dias_semana: dados.dias_semana,
+            hora_inicio: dados.hora_inicio,
+            hora_fim: dados.hora_fim,
+            preco: dados.preco,
+            data_criacao,
+            data_atualizacao,
+        }
+    }
+
+    pub fn validate_entity(&self) -> Result<(), DomainError> {
+        if self.dias_semana.is_empty() {
+            return Err(DomainError::Empty);
+        }
+        if self.dias_semana.iter().any(|dia| *dia > 6) {
+            return Err(DomainError::Invalid("Dia da semana inválido".to_string()));
+        }
+        parse_hora(&self.hora_inicio)?;
+        parse_hora(&self.hora_fim)?;
+        if let Some(preco) = self.preco {
+            assertion_concern::assert_argument_not_negative(preco)?;
+        }
+        assertion_concern::assert_argument_timestamp_format(self.data_criacao.clone())?;
+        assertion_concern::assert_argument_timestamp_format(self.data_atualizacao.clone())?;
+        Ok(())
+    }
+
+    pub fn vigente_em(&self, momento: &DateTime<Tz>) -> bool {
+        let (inicio, fim) = match (parse_hora(&self.hora_inicio), parse_hora(&self.hora_fim)) {
+            (Ok(inicio), Ok(fim)) => (inicio, fim),
+            _ => return false,
+        };
+        let hora = momento.time();
+        let dia = momento.weekday().num_days_from_sunday();
+        let dia_anterior = (dia + 6) % 7;
+
+        if inicio < fim {
+            self.dias_semana.contains(&dia) && hora >= inicio && hora < fim
+        } else {
+            (self.dias_semana.contains(&dia) && hora >= inicio)
+                || (self.dias_semana.contains(&dia_anterior) && hora < fim)
+        }
+    }
+
+    pub fn aplica_a(&self, produto: &Produto) -> bool {
+        match self.alvo {
+            AlvoAgenda::Produto => self.alvo_id == *produto.id(),
+            AlvoAgenda::Categoria => self.alvo_id == *produto.categoria_id(),
+        }
+    }
+
+    // Retorna o produto com o preço vigente no momento, ou None se estiver fora das
+    // janelas de disponibilidade do próprio produto ou da sua categoria. O preço programado
+    // do produto prevalece sobre o da categoria.
+    pub fn aplica(
+        produto: &Produto,
+        agendas: &[Agenda],
+        momento: &DateTime<Tz>,
+    ) -> Option<Produto> {
+        let agendas: Vec<&Agenda> = agendas.iter().filter(|agenda| agenda.aplica_a(produto)).collect();
+
+        for alvo in [AlvoAgenda::Produto, AlvoAgenda::Categoria] {
+            let mut janelas = agendas
+                .iter()
+                .filter(|agenda| agenda.alvo == alvo && agenda.preco.is_none())
+                .peekable();
+            if janelas.peek().is_some() && !janelas.any(|agenda| agenda.vigente_em(momento)) {
+                return None;
+            }
+        }
+
+        let mut produto = produto.clone();
+        let preco_programado = [AlvoAgenda::Produto, AlvoAgenda::Categoria]
+            .iter()
+            .find_map(|alvo| {
+                agendas
+                    .iter()
+                    .filter(|agenda| agenda.alvo == *alvo && agenda.vigente_em(momento))
+                    .filter_map(|agenda| agenda.preco)
+                    .reduce(f64::min)
+            });
+        if let Some(preco) = preco_programado {
+            produto.set_preco(preco).ok()?;
+        }
+        Some(produto)
+    }
+
+    // Getters
+    pub fn id(&self) -> &usize {
+        &self.id
+    }
+
+    pub fn alvo(&self) -> &AlvoAgenda {
+        &self.alvo
+    }
+
+    pub fn alvo_id(&self) -> &usize {
+        &self.alvo_id
+    }
+
+    pub fn dias_semana(&self) -> &Vec<u32> {
+        &self.dias_semana
+    }
+
+    pub fn hora_inicio(&self) -> &String {
+        &self.hora_inicio
+    }
+
+    pub fn hora_fim(&self) -> &String {
+        &self.hora_fim
+    }
+
+    pub fn preco(&self) -> Option<f64> {
+        self.preco
+    }
+
+    pub fn data_criacao(&self) -> &String {
+        &self.data_criacao
+    }
+
+    pub fn data_atualizacao(&self) -> &String {
+        &self.data_atualizacao
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::ingredientes::Ingredientes;
+
+    const AGORA: &str = "2021-08-01 00:00:00.000+0000";
+
+    fn create_produto() -> Produto {
+        Produto::new(
+            1,
+            "Café".to_string(),
+            "cafe.png".to_string(),
+            "Café coado".to_string(),
+            5,
+            6.0,
+            Ingredientes::new(vec!["Café".to_string()]).unwrap(),
+            AGORA.to_string(),
+            AGORA.to_string(),
+        )
+    }
+
+    fn create_agenda(alvo: AlvoAgenda, alvo_id: usize, inicio: &str, fim: &str, preco: Option<f64>) -> Agenda {
+        Agenda::new(
+            1,
+            DadosDaAgenda {
+                alvo,
+                alvo_id,
+                dias_semana: vec![1, 2, 3, 4, 5],
+                hora_inicio: inicio.to_string(),
+                hora_fim: fim.to_string(),
+                preco,
+            },
+            AGORA.to_string(),
+            AGORA.to_string(),
+        )
+    }
+
+    fn momento(valor: &str) -> DateTime<Tz> {
+        DateTime::parse_from_rfc3339(valor)
+            .unwrap()
+            .with_timezone(&chrono_tz::America::Sao_Paulo)
+    }
+
+    #[test]
+    fn test_agenda_validate_entity() {
+        assert!(create_agenda(AlvoAgenda::Categoria, 5, "06:00", "11:00", None)
+            .validate_entity()
+            .is_ok());
+        assert!(create_agenda(AlvoAgenda::Categoria, 5, "06:00", "11:00", Some(4.0))
+            .validate_entity()
+            .is_ok());
+        assert!(matches!(
+            create_agenda(AlvoAgenda::Produto, 1, "6h", "11:00", None).validate_entity(),
+            Err(DomainError::Invalid(_))
+        ));
+        assert!(matches!(
+            create_agenda(AlvoAgenda::Produto, 1, "06:00", "11:00", Some(-1.0)).validate_entity(),
+            Err(DomainError::NonPositive)
+        ));
+    }
+
+    #[test]
+    fn test_agenda_vigente_em() {
+        let agenda = create_agenda(AlvoAgenda::Categoria, 5, "06:00", "11:00", None);
+        // 2024-05-06 é uma segunda-feira
+        assert!(agenda.vigente_em(&momento("2024-05-06T10:59:00-03:00")));
+        assert!(!agenda.vigente_em(&momento("2024-05-06T11:00:00-03:00")));
+        assert!(!agenda.vigente_em(&momento("2024-05-05T10:00:00-03:00")));
+
+        let madrugada = create_agenda(AlvoAgenda::Categoria, 5, "22:00", "02:00", None);
+        assert!(madrugada.vigente_em(&momento("2024-05-10T23:30:00-03:00")));
+        assert!(madrugada.vigente_em(&momento("2024-05-11T01:30:00-03:00")));
+        assert!(!madrugada.vigente_em(&momento("2024-05-12T01:30:00-03:00")));
+    }
+
+    #[test]
+    fn test_agenda_aplica_disponibilidade() {
+        let agendas = vec![create_agenda(AlvoAgenda::Categoria, 5, "06:00", "11:00", None)];
+        let produto = create_produto();
+
+        assert!(Agenda::aplica(&produto, &agendas, &momento("2024-05-06T08:00:00-03:00")).is_some());
+        assert!(Agenda::aplica(&produto, &agendas, &momento("2024-05-06T12:00:00-03:00")).is_none());
+        assert!(Agenda::aplica(&produto, &[], &momento("2024-05-06T12:00:00-03:00")).is_some());
+    }
+
+    #[test]
+    fn test_agenda_aplica_preco_programado() {
+        let agendas = vec![
+            create_agenda(AlvoAgenda::Produto, 1, "17:00", "19:00", Some(4.5)),
+            create_agenda(AlvoAgenda::Produto, 1, "18:00", "19:00", Some(4.0)),
+            create_agenda(AlvoAgenda::Produto, 2, "00:00", "23:59", Some(1.0)),
+        ];
+        let produto = create_produto();
+
+        let happy_hour = Agenda::aplica(&produto, &agendas, &momento("2024-05-06T17:30:00-03:00")).unwrap();
+        assert_eq!(happy_hour.preco(), 4.5);
+
+        let happy_hour = Agenda::aplica(&produto, &agendas, &momento("2024-05-06T18:30:00-03:00")).unwrap();
+        assert_eq!(happy_hour.preco(), 4.0);
+
+        let normal = Agenda::aplica(&produto, &agendas, &momento("2024-05-06T20:00:00-03:00")).unwrap();
+        assert_eq!(normal.preco(), 6.0);
+    }
+
+    #[test]
+    fn test_agenda_aplica_preco_programado_da_categoria() {
+        let produto = create_produto();
+        let categoria = vec![create_agenda(AlvoAgenda::Categoria, 5, "17:00", "19:00", Some(5.0))];
+        let happy_hour = Agenda::aplica(&produto, &categoria, &momento("2024-05-06T18:00:00-03:00")).unwrap();
+        assert_eq!(happy_hour.preco(), 5.0);
+
+        let com_produto = vec![
+            create_agenda(AlvoAgenda::Categoria, 5, "17:00", "19:00", Some(3.0)),
+            create_agenda(AlvoAgenda::Produto, 1, "17:00", "19:00", Some(4.5)),
+        ];
+        let happy_hour = Agenda::aplica(&produto, &com_produto, &momento("2024-05-06T18:00:00-03:00")).unwrap();
+        assert_eq!(happy_hour.preco(), 4.5);
+    }
+
+    #[test]
+    fn test_agenda_vigente_em_usa_o_fuso_da_loja() {
+        let agenda = create_agenda(AlvoAgenda::Categoria, 5, "06:00", "11:00", None);
+        // 13:30 e 15:30 em UTC são 10:30 e 12:30 em São Paulo.
+        assert!(agenda.vigente_em(&momento("2024-05-06T13:30:00Z")));
+        assert!(!agenda.vigente_em(&momento("2024-05-06T15:30:00Z")));
+    }
+}
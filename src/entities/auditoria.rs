@@ -0,0 +1,59 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema, PartialEq)]
+pub enum AcaoDeAuditoria {
+    // Pedidos de acesso e de eliminação de dados do titular (LGPD).
+    ExportacaoDeDados,
+    AnonimizacaoDeDados,
+}
+
+// Registro de uma operação sobre os dados pessoais de um titular. O titular é guardado pelo
+// pseudônimo do CPF, para que o próprio registro não volte a expor quem foi anonimizado.
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct RegistroDeAuditoria {
+    id: usize,
+    acao: AcaoDeAuditoria,
+    titular: String,
+    request_id: String,
+    data_criacao: String,
+}
+
+impl RegistroDeAuditoria {
+    pub fn new(
+        id: usize,
+        acao: AcaoDeAuditoria,
+        titular: String,
+        request_id: String,
+        data_criacao: String,
+    ) -> Self {
+        RegistroDeAuditoria {
+            id,
+            acao,
+            titular,
+            request_id,
+            data_criacao,
+        }
+    }
+
+    // Getters
+    pub fn id(&self) -> &usize {
+        &self.id
+    }
+
+    pub fn acao(&self) -> &AcaoDeAuditoria {
+        &self.acao
+    }
+
+    pub fn titular(&self) -> &String {
+        &self.titular
+    }
+
+    pub fn request_id(&self) -> &String {
+        &self.request_id
+    }
+
+    pub fn data_criacao(&self) -> &String {
+        &self.data_criacao
+    }
+}
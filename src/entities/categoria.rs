@@ -0,0 +1,187 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::base::{assertion_concern, domain_error::DomainError};
+
+// Posição do pedido que os produtos da categoria podem ocupar.
+// Categorias sem item (ex.: Sobremesa) aparecem no cardápio mas não compõem pedidos.
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema, PartialEq)]
+pub enum ItemPedido {
+    Lanche,
+    Acompanhamento,
+    Bebida,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct Categoria {
+    id: usize,
+    nome: String,
+    icone: Option<String>,
+    ordem: usize,
+    ativa: bool,
+    item_pedido: Option<ItemPedido>,
+    data_criacao: String,
+    data_atualizacao: String,
+}
+
+// Campos da categoria definidos pelo administrador.
+#[derive(Clone, Debug)]
+pub struct DadosDaCategoria {
+    pub nome: String,
+    pub icone: Option<String>,
+    pub ordem: usize,
+    pub ativa: bool,
+    pub item_pedido: Option<ItemPedido>,
+}
+
+impl Categoria {
+    pub fn new(
+        id: usize,
+        dados: DadosDaCategoria,
+        data_criacao: String,
+        data_atualizacao: String,
+    ) -> Self {
+        Categoria {
+            id,
+            nome: dados.nome,
+            icone: dados.icone,
+            ordem: dados.ordem,
+            ativa: dados.ativa,
+            item_pedido: dados.item_pedido,
+            data_criacao,
+            data_atualizacao,
+        }
+    }
+
+    pub fn validate_entity(&self) -> Result<(), DomainError> {
+        assertion_concern::assert_argument_not_empty(self.nome.clone())?;
+        assertion_concern::assert_argument_timestamp_format(self.data_criacao.clone())?;
+        assertion_concern::assert_argument_timestamp_format(self.data_atualizacao.clone())?;
+        Ok(())
+    }
+
+    pub fn permite_item(&self, item: &ItemPedido) -> bool {
+        self.ativa && self.item_pedido.as_ref() == Some(item)
+    }
+
+    // Getters
+    pub fn id(&self) -> &usize {
+        &self.id
+    }
+
+    pub fn nome(&self) -> &String {
+        &self.nome
+    }
+
+    pub fn icone(&self) -> Option<&String> {
+        self.icone.as_ref()
+    }
+
+    pub fn ordem(&self) -> usize {
+        self.ordem
+    }
+
+    pub fn ativa(&self) -> bool {
+        self.ativa
+    }
+
+    pub fn item_pedido(&self) -> Option<&ItemPedido> {
+        self.item_pedido.as_ref()
+    }
+
+    pub fn data_criacao(&self) -> &String {
+        &self.data_criacao
+    }
+
+    pub fn data_atualizacao(&self) -> &String {
+        &self.data_atualizacao
+    }
+
+    // Setters
+    pub fn set_nome(&mut self, nome: String) -> Result<(), DomainError> {
+        assertion_concern::assert_argument_not_empty(nome.clone())?;
+        self.nome = nome;
+        Ok(())
+    }
+
+    pub fn set_icone(&mut self, icone: Option<String>) {
+        self.icone = icone;
+    }
+
+    pub fn set_ordem(&mut self, ordem: usize) {
+        self.ordem = ordem;
+    }
+
+    pub fn set_ativa(&mut self, ativa: bool) {
+        self.ativa = ativa;
+    }
+
+    pub fn set_item_pedido(&mut self, item_pedido: Option<ItemPedido>) {
+        self.item_pedido = item_pedido;
+    }
+
+    pub fn set_data_atualizacao(&mut self, data_atualizacao: String) -> Result<(), DomainError> {
+        assertion_concern::assert_argument_timestamp_format(data_atualizacao.clone())?;
+        self.data_atualizacao = data_atualizacao;
+        Ok(())
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_valid_categoria() -> Categoria {
+        let _now = "2021-08-01 00:00:00.000+0000".to_string();
+        Categoria::new(
+            1,
+            DadosDaCategoria {
+                nome: "Lanche".to_string(),
+                icone: Some("lanche.svg".to_string()),
+                ordem: 1,
+                ativa: true,
+                item_pedido: Some(ItemPedido::Lanche),
+            },
+            _now.clone(),
+            _now,
+        )
+    }
+
+    #[test]
+    fn test_categoria_creation_valid() {
+        let categoria = create_valid_categoria();
+        assert_eq!(categoria.id(), &1);
+        assert_eq!(categoria.nome(), "Lanche");
+        assert_eq!(categoria.icone(), Some(&"lanche.svg".to_string()));
+        assert_eq!(categoria.ordem(), 1);
+        assert!(categoria.ativa());
+        assert_eq!(categoria.item_pedido(), Some(&ItemPedido::Lanche));
+        assert!(categoria.validate_entity().is_ok());
+    }
+
+    #[test]
+    fn test_categoria_validate_entity_empty_nome() {
+        let mut categoria = create_valid_categoria();
+        let result = categoria.set_nome("".to_string());
+        assert!(
+            matches!(result, Err(DomainError::Empty)),
+            "Esperado Err(DomainError::Empty), obtido {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_categoria_permite_item() {
+        let mut categoria = create_valid_categoria();
+        assert!(categoria.permite_item(&ItemPedido::Lanche));
+        assert!(!categoria.permite_item(&ItemPedido::Bebida));
+
+        categoria.set_ativa(false);
+        assert!(!categoria.permite_item(&ItemPedido::Lanche));
+
+        categoria.set_ativa(true);
+        categoria.set_item_pedido(None);
+        assert!(!categoria.permite_item(&ItemPedido::Lanche));
+    }
+}
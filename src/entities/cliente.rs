@@ -0,0 +1,172 @@
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    base::{assertion_concern, domain_error::DomainError},
+    entities::cpf::Cpf,
+};
+
+// Cliente cadastrado, identificado pelo CPF. Pedidos sem CPF são do cliente anônimo e não
+// têm cadastro.
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct Cliente {
+    cpf: Cpf,
+    nome: String,
+    email: Option<String>,
+    // Consentimentos dados pelo cliente (LGPD); nenhum é presumido.
+    aceita_marketing: bool,
+    aceita_compartilhamento: bool,
+    data_criacao: String,
+    data_atualizacao: String,
+}
+
+impl Cliente {
+    pub fn new(
+        cpf: Cpf,
+        nome: String,
+        email: Option<String>,
+        aceita_marketing: bool,
+        aceita_compartilhamento: bool,
+        data_criacao: String,
+        data_atualizacao: String,
+    ) -> Self {
+        Cliente {
+            cpf,
+            nome,
+            email,
+            aceita_marketing,
+            aceita_compartilhamento,
+            data_criacao,
+            data_atualizacao,
+        }
+    }
+
+    pub fn validate_entity(&self) -> Result<(), DomainError> {
+        assertion_concern::assert_argument_not_empty(self.nome.clone())
+            .map_err(|erro| erro.no_campo("nome"))?;
+        if let Some(email) = &self.email {
+            Cliente::valida_email(email)?;
+        }
+        assertion_concern::assert_argument_timestamp_format(self.data_criacao.clone())?;
+        assertion_concern::assert_argument_timestamp_format(self.data_atualizacao.clone())?;
+        Ok(())
+    }
+
+    fn valida_email(email: &str) -> Result<(), DomainError> {
+        let regex_pattern = Regex::new(r"^[^@\s]+@[^@\s]+\.[^@\s]+$").unwrap();
+        if regex_pattern.is_match(email) {
+            Ok(())
+        } else {
+            Err(DomainError::InvalidField {
+                field: "email".to_string(),
+                message: "E-mail inválido".to_string(),
+            })
+        }
+    }
+
+    // Getters
+    pub fn cpf(&self) -> &Cpf {
+        &self.cpf
+    }
+
+    pub fn nome(&self) -> &String {
+        &self.nome
+    }
+
+    pub fn email(&self) -> Option<&String> {
+        self.email.as_ref()
+    }
+
+    pub fn aceita_marketing(&self) -> bool {
+        self.aceita_marketing
+    }
+
+    pub fn aceita_compartilhamento(&self) -> bool {
+        self.aceita_compartilhamento
+    }
+
+    pub fn data_criacao(&self) -> &String {
+        &self.data_criacao
+    }
+
+    pub fn data_atualizacao(&self) -> &String {
+        &self.data_atualizacao
+    }
+
+    // Setters
+    pub fn set_cpf(&mut self, cpf: Cpf) {
+        self.cpf = cpf;
+    }
+
+    pub fn set_nome(&mut self, nome: String) -> Result<(), DomainError> {
+        assertion_concern::assert_argument_not_empty(nome.clone())
+            .map_err(|erro| erro.no_campo("nome"))?;
+        self.nome = nome;
+        Ok(())
+    }
+
+    pub fn set_email(&mut self, email: Option<String>) -> Result<(), DomainError> {
+        if let Some(email) = &email {
+            Cliente::valida_email(email)?;
+        }
+        self.email = email;
+        Ok(())
+    }
+
+    pub fn set_aceita_marketing(&mut self, aceita_marketing: bool) {
+        self.aceita_marketing = aceita_marketing;
+    }
+
+    pub fn set_aceita_compartilhamento(&mut self, aceita_compartilhamento: bool) {
+        self.aceita_compartilhamento = aceita_compartilhamento;
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AGORA: &str = "2021-08-01 00:00:00.000+0000";
+
+    fn create_valid_cliente() -> Cliente {
+        Cliente::new(
+            Cpf::new("123.456.789-09".to_string()).unwrap(),
+            "Maria".to_string(),
+            Some("maria@email.com".to_string()),
+            false,
+            false,
+            AGORA.to_string(),
+            AGORA.to_string(),
+        )
+    }
+
+    #[test]
+    fn test_cliente_validate_entity() {
+        let cliente = create_valid_cliente();
+        assert!(cliente.validate_entity().is_ok());
+        assert_eq!(cliente.cpf().get_string(), "12345678909");
+
+        let mut cliente = create_valid_cliente();
+        cliente.nome = "".to_string();
+        assert!(matches!(
+            cliente.validate_entity(),
+            Err(DomainError::InvalidField { ref field, .. }) if field == "nome"
+        ));
+    }
+
+    #[test]
+    fn test_cliente_set_email() {
+        let mut cliente = create_valid_cliente();
+
+        assert!(matches!(
+            cliente.set_email(Some("maria.email.com".to_string())),
+            Err(DomainError::InvalidField { ref field, .. }) if field == "email"
+        ));
+        assert_eq!(cliente.email(), Some(&"maria@email.com".to_string()));
+
+        cliente.set_email(None).unwrap();
+        assert_eq!(cliente.email(), None);
+    }
+}
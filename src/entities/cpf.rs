@@ -4,6 +4,7 @@ use regex::Regex;
 
 use crate::base::domain_error::DomainError;
 
+// Serializado completo; quem mascara para cada perfil é a camada da API.
 #[derive(Clone, Deserialize, Debug, JsonSchema, Serialize)]
 pub struct Cpf(pub String);
 
@@ -12,20 +13,22 @@ impl Cpf {
         if codigo.is_empty() {
             return Err(DomainError::Empty);
         }
-        // Default admin user
-        if codigo == "000.000.000-00" {
-            return Ok(Cpf("00000000000".to_string()));
-        }
         let regex_pattern = Regex::new(r"^\d{3}\.\d{3}\.\d{3}-\d{2}$|^\d{11}$").unwrap();
         if regex_pattern.is_match(&codigo) {
             let codigo = Cpf::keep_only_numbers(codigo);
             if Cpf::validate(codigo.clone()) {
                 Ok(Cpf(codigo))
             } else {
-                Err(DomainError::Invalid("CPF".to_string()))
+                Err(DomainError::InvalidField {
+                field: "cpf".to_string(),
+                message: "CPF inválido".to_string(),
+            })
             }
         } else {
-            Err(DomainError::Invalid("CPF".to_string()))
+            Err(DomainError::InvalidField {
+                field: "cpf".to_string(),
+                message: "CPF inválido".to_string(),
+            })
         }
     }
 
@@ -38,6 +41,10 @@ impl Cpf {
 
     fn validate(codigo: String) -> bool {
         let cpf = codigo.chars().map(|d| d.to_digit(10).unwrap()).collect::<Vec<u32>>();
+        // Sequências como 000.000.000-00 passam nos dígitos verificadores, mas não são CPFs.
+        if cpf.iter().all(|digito| *digito == cpf[0]) {
+            return false;
+        }
         let dv1 = (0..9).map(|i| cpf[i] * (10 - i as u32)).sum::<u32>() % 11;
         let dv1 = if dv1 < 2 { 0 } else { 11 - dv1 };
         let dv2 = (0..10).map(|i| cpf[i] * (11 - i as u32)).sum::<u32>() % 11;
@@ -48,6 +55,15 @@ impl Cpf {
     pub fn get_string(&self) -> String {
         self.0.clone()
     }
+
+    // ***.456.789-**: só os dígitos do meio. O que não tem os 11 dígitos de um CPF volta como veio.
+    pub fn mascarado(&self) -> String {
+        let digitos: String = self.0.chars().filter(char::is_ascii_digit).collect();
+        if digitos.len() != 11 {
+            return self.0.clone();
+        }
+        format!("***.{}.{}-**", &digitos[3..6], &digitos[6..9])
+    }
 }
 
 impl PartialEq for Cpf {
@@ -61,13 +77,12 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_cpf_admin() {
+    fn test_cpf_digitos_repetidos() {
         let cpf = Cpf::new("000.000.000-00".to_string());
-        assert!(cpf.is_ok());
+        assert!(matches!(cpf, Err(DomainError::InvalidField { ref field, .. }) if field == "cpf"));
 
-        let cpf = cpf.unwrap();
-
-        assert_eq!(cpf.get_string(), "00000000000");
+        let cpf = Cpf::new("11111111111".to_string());
+        assert!(cpf.is_err());
     }
     #[test]
     fn test_cpf_valid() {
@@ -84,8 +99,18 @@ mod tests {
         assert!(cpf.is_err());
     }
     #[test]
+    fn test_cpf_mascarado() {
+        let cpf = Cpf::new("123.456.789-09".to_string()).unwrap();
+        assert_eq!(cpf.mascarado(), "***.456.789-**");
+        assert_eq!(serde_json::to_string(&cpf).unwrap(), r#""12345678909""#);
+
+        assert_eq!(Cpf("123.456.789-09".to_string()).mascarado(), "***.456.789-**");
+        assert_eq!(Cpf("anon_12".to_string()).mascarado(), "anon_12");
+        assert_eq!(Cpf("ção".to_string()).mascarado(), "ção");
+    }
+    #[test]
     fn test_cpf_invalid_value() {
         let cpf = Cpf::new("wrong".to_string());
-        assert!(cpf.is_err());
+        assert!(matches!(cpf, Err(DomainError::InvalidField { ref field, .. }) if field == "cpf"));
     }
 }
@@ -0,0 +1,161 @@
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::base::domain_error::DomainError;
+use crate::entities::cpf::Cpf;
+
+#[derive(Clone, Serialize, Debug, JsonSchema, PartialEq)]
+pub enum TipoDeDocumento {
+    Cpf,
+    Cnpj,
+}
+
+// Documento em nome de quem sai a nota fiscal: CPF, de pessoa física, ou CNPJ, de empresa.
+// Guardado sem pontuação. O CNPJ pode ser alfanumérico: doze caracteres entre 0-9 e A-Z
+// seguidos de dois dígitos verificadores.
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema, PartialEq)]
+pub struct DocumentoFiscal(pub String);
+
+fn invalido() -> DomainError {
+    DomainError::InvalidField {
+        field: "documento_fiscal".to_string(),
+        message: "CPF ou CNPJ inválido".to_string(),
+    }
+}
+
+// Dígito verificador do CNPJ: cada caractere vale o código ASCII menos 48 (os dígitos
+// continuam valendo 0-9 e as letras passam a valer 17-42), com pesos de 2 a 9 da direita
+// para a esquerda.
+fn digito_verificador_do_cnpj(caracteres: &[u8]) -> u32 {
+    let soma: u32 = caracteres
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(posicao, caractere)| (*caractere as u32 - 48) * (posicao as u32 % 8 + 2))
+        .sum();
+    let resto = soma % 11;
+    if resto < 2 { 0 } else { 11 - resto }
+}
+
+impl DocumentoFiscal {
+    pub fn new(codigo: String) -> Result<Self, DomainError> {
+        if codigo.is_empty() {
+            return Err(DomainError::Empty);
+        }
+        let regex_pattern = Regex::new(
+            r"^\d{3}\.\d{3}\.\d{3}-\d{2}$|^\d{11}$|^[0-9A-Za-z]{2}\.[0-9A-Za-z]{3}\.[0-9A-Za-z]{3}/[0-9A-Za-z]{4}-\d{2}$|^[0-9A-Za-z]{12}\d{2}$",
+        )
+        .unwrap();
+        if !regex_pattern.is_match(&codigo) {
+            return Err(invalido());
+        }
+        let codigo: String = codigo
+            .chars()
+            .filter(|caractere| caractere.is_ascii_alphanumeric())
+            .map(|caractere| caractere.to_ascii_uppercase())
+            .collect();
+        if codigo.len() == 11 {
+            let cpf = Cpf::new(codigo).map_err(|_| invalido())?;
+            return Ok(DocumentoFiscal(cpf.get_string()));
+        }
+        if DocumentoFiscal::valida_cnpj(&codigo) {
+            Ok(DocumentoFiscal(codigo))
+        } else {
+            Err(invalido())
+        }
+    }
+
+    fn valida_cnpj(codigo: &str) -> bool {
+        let cnpj = codigo.as_bytes();
+        // Como no CPF, sequências repetidas passam nos dígitos verificadores.
+        if cnpj.iter().all(|caractere| *caractere == cnpj[0]) {
+            return false;
+        }
+        let dv1 = digito_verificador_do_cnpj(&cnpj[..12]);
+        let dv2 = digito_verificador_do_cnpj(&[&cnpj[..12], &[b'0' + dv1 as u8]].concat());
+        (cnpj[12] - b'0') as u32 == dv1 && (cnpj[13] - b'0') as u32 == dv2
+    }
+
+    pub fn tipo(&self) -> TipoDeDocumento {
+        if self.0.len() == 11 {
+            TipoDeDocumento::Cpf
+        } else {
+            TipoDeDocumento::Cnpj
+        }
+    }
+
+    pub fn cpf(&self) -> Option<Cpf> {
+        match self.tipo() {
+            TipoDeDocumento::Cpf => Some(Cpf(self.0.clone())),
+            TipoDeDocumento::Cnpj => None,
+        }
+    }
+
+    pub fn get_string(&self) -> String {
+        self.0.clone()
+    }
+
+    // Com a pontuação de costume: 123.456.789-09 ou 12.ABC.345/01DE-35.
+    pub fn formatado(&self) -> String {
+        let codigo = &self.0;
+        match self.tipo() {
+            TipoDeDocumento::Cpf => format!("{}.{}.{}-{}", &codigo[..3], &codigo[3..6], &codigo[6..9], &codigo[9..]),
+            TipoDeDocumento::Cnpj if codigo.len() == 14 => format!(
+                "{}.{}.{}/{}-{}",
+                &codigo[..2],
+                &codigo[2..5],
+                &codigo[5..8],
+                &codigo[8..12],
+                &codigo[12..]
+            ),
+            TipoDeDocumento::Cnpj => codigo.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_documento_cpf() {
+        let documento = DocumentoFiscal::new("123.456.789-09".to_string()).unwrap();
+        assert_eq!(documento.tipo(), TipoDeDocumento::Cpf);
+        assert_eq!(documento.get_string(), "12345678909");
+        assert_eq!(documento.formatado(), "123.456.789-09");
+        assert_eq!(serde_json::to_string(&documento).unwrap(), r#""12345678909""#);
+
+        let documento = DocumentoFiscal::new("123.456.789-00".to_string());
+        assert!(matches!(documento, Err(DomainError::InvalidField { ref field, .. }) if field == "documento_fiscal"));
+    }
+
+    #[test]
+    fn test_documento_cnpj_numerico() {
+        let documento = DocumentoFiscal::new("11.222.333/0001-81".to_string()).unwrap();
+        assert_eq!(documento.tipo(), TipoDeDocumento::Cnpj);
+        assert_eq!(documento.get_string(), "11222333000181");
+        assert_eq!(documento.formatado(), "11.222.333/0001-81");
+        assert_eq!(serde_json::to_string(&documento).unwrap(), r#""11222333000181""#);
+
+        assert!(DocumentoFiscal::new("11222333000182".to_string()).is_err());
+        assert!(DocumentoFiscal::new("00000000000000".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_documento_cnpj_alfanumerico() {
+        let documento = DocumentoFiscal::new("12.ABC.345/01DE-35".to_string()).unwrap();
+        assert_eq!(documento.tipo(), TipoDeDocumento::Cnpj);
+        assert_eq!(documento.get_string(), "12ABC34501DE35");
+        assert_eq!(documento.formatado(), "12.ABC.345/01DE-35");
+        assert_eq!(
+            DocumentoFiscal::new("12abc34501de35".to_string()).unwrap(),
+            documento
+        );
+
+        assert!(DocumentoFiscal::new("12ABC34501DE36".to_string()).is_err());
+        // Os dígitos verificadores são sempre numéricos.
+        assert!(DocumentoFiscal::new("12ABC34501DEA5".to_string()).is_err());
+        assert!(DocumentoFiscal::new("1234".to_string()).is_err());
+    }
+}
@@ -0,0 +1,37 @@
+use crate::entities::pedido::Pedido;
+
+// Uso de uma chave Idempotency-Key. A impressão digital identifica o corpo da requisição
+// original e o pedido é a resposta devolvida às repetições; sem pedido, a requisição
+// original ainda está em andamento.
+#[derive(Clone, Debug)]
+pub struct RegistroDeIdempotencia {
+    chave: String,
+    impressao_digital: String,
+    pedido: Option<Pedido>,
+}
+
+impl RegistroDeIdempotencia {
+    pub fn new(chave: String, impressao_digital: String, pedido: Option<Pedido>) -> Self {
+        RegistroDeIdempotencia {
+            chave,
+            impressao_digital,
+            pedido,
+        }
+    }
+
+    pub fn chave(&self) -> &String {
+        &self.chave
+    }
+
+    pub fn impressao_digital(&self) -> &String {
+        &self.impressao_digital
+    }
+
+    pub fn pedido(&self) -> Option<&Pedido> {
+        self.pedido.as_ref()
+    }
+
+    pub fn set_pedido(&mut self, pedido: Pedido) {
+        self.pedido = Some(pedido);
+    }
+}
@@ -0,0 +1,218 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::{
+    base::{assertion_concern, domain_error::DomainError},
+    entities::produto::Produto,
+};
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub enum Idioma {
+    #[default]
+    #[serde(rename = "pt-BR")]
+    PtBr,
+    #[serde(rename = "en")]
+    En,
+    #[serde(rename = "es")]
+    Es,
+}
+
+impl Idioma {
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Idioma::PtBr => "pt-BR",
+            Idioma::En => "en",
+            Idioma::Es => "es",
+        }
+    }
+
+    // Escolhe o idioma de maior peso do header Accept-Language que a API atende; sem nenhum, pt-BR.
+    pub fn from_accept_language(header: &str) -> Idioma {
+        let mut preferencias: Vec<(f32, Idioma)> = header
+            .split(',')
+            .filter_map(|faixa| {
+                let mut partes = faixa.split(';');
+                let tag = partes.next()?.trim();
+                let peso = partes
+                    .filter_map(|parametro| parametro.trim().strip_prefix("q="))
+                    .find_map(|peso| peso.trim().parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                let idioma = match tag {
+                    "*" => Idioma::default(),
+                    _ => tag.parse::<Idioma>().ok()?,
+                };
+                (peso > 0.0).then_some((peso, idioma))
+            })
+            .collect();
+        preferencias.sort_by(|a, b| b.0.total_cmp(&a.0));
+        preferencias
+            .first()
+            .map(|(_, idioma)| *idioma)
+            .unwrap_or_default()
+    }
+}
+
+impl FromStr for Idioma {
+    type Err = DomainError;
+
+    // Aceita a tag completa ou só o idioma principal (en-US vira en).
+    fn from_str(tag: &str) -> Result<Self, Self::Err> {
+        let principal = tag.split(['-', '_']).next().unwrap_or_default();
+        match principal.to_lowercase().as_str() {
+            "pt" => Ok(Idioma::PtBr),
+            "en" => Ok(Idioma::En),
+            "es" => Ok(Idioma::Es),
+            _ => Err(DomainError::InvalidField {
+                field: "idioma".to_string(),
+                message: format!("Idioma não suportado: {}", tag),
+            }),
+        }
+    }
+}
+
+// Tradução do nome e/ou descrição de um produto; campos ausentes ficam no original em pt-BR.
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct TraducaoProduto {
+    produto_id: usize,
+    idioma: Idioma,
+    nome: Option<String>,
+    descricao: Option<String>,
+}
+
+impl TraducaoProduto {
+    pub fn new(
+        produto_id: usize,
+        idioma: Idioma,
+        nome: Option<String>,
+        descricao: Option<String>,
+    ) -> Self {
+        TraducaoProduto {
+            produto_id,
+            idioma,
+            nome,
+            descricao,
+        }
+    }
+
+    pub fn validate_entity(&self) -> Result<(), DomainError> {
+        if self.idioma == Idioma::PtBr {
+            return Err(DomainError::Invalid("pt-BR é o idioma original do produto".to_string()));
+        }
+        if let Some(nome) = &self.nome {
+            assertion_concern::assert_argument_not_empty(nome.clone())?;
+        }
+        if let Some(descricao) = &self.descricao {
+            assertion_concern::assert_argument_not_empty(descricao.clone())?;
+        }
+        Ok(())
+    }
+
+    // Retorna o produto no idioma pedido, mantendo o texto original onde não há tradução.
+    pub fn aplica(
+        produto: &Produto,
+        idioma: Idioma,
+        traducoes: &[TraducaoProduto],
+    ) -> Result<Produto, DomainError> {
+        let mut produto = produto.clone();
+        let traducao = traducoes
+            .iter()
+            .find(|traducao| traducao.idioma == idioma && traducao.produto_id == *produto.id());
+        if let Some(traducao) = traducao {
+            if let Some(nome) = &traducao.nome {
+                produto.set_nome(nome.clone())?;
+            }
+            if let Some(descricao) = &traducao.descricao {
+                produto.set_descricao(descricao.clone())?;
+            }
+        }
+        Ok(produto)
+    }
+
+    // Getters
+    pub fn produto_id(&self) -> &usize {
+        &self.produto_id
+    }
+
+    pub fn idioma(&self) -> Idioma {
+        self.idioma
+    }
+
+    pub fn nome(&self) -> Option<&String> {
+        self.nome.as_ref()
+    }
+
+    pub fn descricao(&self) -> Option<&String> {
+        self.descricao.as_ref()
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::ingredientes::Ingredientes;
+
+    const AGORA: &str = "2021-08-01 00:00:00.000+0000";
+
+    fn create_produto() -> Produto {
+        Produto::new(
+            1,
+            "Suco de laranja".to_string(),
+            "suco.png".to_string(),
+            "Suco natural".to_string(),
+            2,
+            8.0,
+            Ingredientes::new(vec!["Laranja".to_string()]).unwrap(),
+            AGORA.to_string(),
+            AGORA.to_string(),
+        )
+    }
+
+    #[test]
+    fn test_idioma_from_str() {
+        assert_eq!("pt-BR".parse::<Idioma>().unwrap(), Idioma::PtBr);
+        assert_eq!("en-US".parse::<Idioma>().unwrap(), Idioma::En);
+        assert_eq!("ES".parse::<Idioma>().unwrap(), Idioma::Es);
+        assert!(matches!("fr".parse::<Idioma>(), Err(DomainError::InvalidField { .. })));
+    }
+
+    #[test]
+    fn test_idioma_from_accept_language() {
+        assert_eq!(Idioma::from_accept_language("en-US,en;q=0.9"), Idioma::En);
+        assert_eq!(Idioma::from_accept_language("fr-FR, es;q=0.5, en;q=0.8"), Idioma::En);
+        assert_eq!(Idioma::from_accept_language("fr-FR, de"), Idioma::PtBr);
+        assert_eq!(Idioma::from_accept_language("en;q=0, es"), Idioma::Es);
+        assert_eq!(Idioma::from_accept_language(""), Idioma::PtBr);
+    }
+
+    #[test]
+    fn test_traducao_validate_entity() {
+        let traducao = TraducaoProduto::new(1, Idioma::En, Some("Orange juice".to_string()), None);
+        assert!(traducao.validate_entity().is_ok());
+
+        let traducao = TraducaoProduto::new(1, Idioma::PtBr, Some("Suco".to_string()), None);
+        assert!(matches!(traducao.validate_entity(), Err(DomainError::Invalid(_))));
+
+        let traducao = TraducaoProduto::new(1, Idioma::Es, None, Some("".to_string()));
+        assert!(matches!(traducao.validate_entity(), Err(DomainError::Empty)));
+    }
+
+    #[test]
+    fn test_traducao_aplica() {
+        let traducoes = vec![
+            TraducaoProduto::new(1, Idioma::En, Some("Orange juice".to_string()), None),
+            TraducaoProduto::new(1, Idioma::Es, Some("Zumo de naranja".to_string()), Some("Zumo natural".to_string())),
+        ];
+
+        let produto = TraducaoProduto::aplica(&create_produto(), Idioma::En, &traducoes).unwrap();
+        assert_eq!(produto.nome(), "Orange juice");
+        assert_eq!(produto.descricao(), "Suco natural");
+
+        let produto = TraducaoProduto::aplica(&create_produto(), Idioma::Es, &traducoes).unwrap();
+        assert_eq!(produto.descricao(), "Zumo natural");
+
+        let produto = TraducaoProduto::aplica(&create_produto(), Idioma::PtBr, &traducoes).unwrap();
+        assert_eq!(produto.nome(), "Suco de laranja");
+    }
+}
@@ -0,0 +1,202 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    base::{assertion_concern, domain_error::DomainError},
+    entities::produto::Produto,
+};
+
+// Loja usada quando a requisição não informa nenhuma (instalações com uma única loja).
+pub const LOJA_PADRAO: usize = 1;
+
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct Loja {
+    id: usize,
+    nome: String,
+    ativa: bool,
+    data_criacao: String,
+    data_atualizacao: String,
+}
+
+impl Loja {
+    pub fn new(
+        id: usize,
+        nome: String,
+        ativa: bool,
+        data_criacao: String,
+        data_atualizacao: String,
+    ) -> Self {
+        Loja {
+            id,
+            nome,
+            ativa,
+            data_criacao,
+            data_atualizacao,
+        }
+    }
+
+    pub fn validate_entity(&self) -> Result<(), DomainError> {
+        assertion_concern::assert_argument_not_empty(self.nome.clone())?;
+        assertion_concern::assert_argument_timestamp_format(self.data_criacao.clone())?;
+        assertion_concern::assert_argument_timestamp_format(self.data_atualizacao.clone())?;
+        Ok(())
+    }
+
+    // Getters
+    pub fn id(&self) -> &usize {
+        &self.id
+    }
+
+    pub fn nome(&self) -> &String {
+        &self.nome
+    }
+
+    pub fn ativa(&self) -> bool {
+        self.ativa
+    }
+
+    pub fn data_criacao(&self) -> &String {
+        &self.data_criacao
+    }
+
+    pub fn data_atualizacao(&self) -> &String {
+        &self.data_atualizacao
+    }
+
+    // Setters
+    pub fn set_nome(&mut self, nome: String) -> Result<(), DomainError> {
+        assertion_concern::assert_argument_not_empty(nome.clone())?;
+        self.nome = nome;
+        Ok(())
+    }
+
+    pub fn set_ativa(&mut self, ativa: bool) {
+        self.ativa = ativa;
+    }
+}
+
+// Ajuste do catálogo de uma loja: sobrescreve o preço e/ou retira o produto do cardápio.
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct ProdutoLoja {
+    loja_id: usize,
+    produto_id: usize,
+    preco: Option<f64>,
+    disponivel: bool,
+}
+
+impl ProdutoLoja {
+    pub fn new(loja_id: usize, produto_id: usize, preco: Option<f64>, disponivel: bool) -> Self {
+        ProdutoLoja {
+            loja_id,
+            produto_id,
+            preco,
+            disponivel,
+        }
+    }
+
+    pub fn validate_entity(&self) -> Result<(), DomainError> {
+        if let Some(preco) = self.preco {
+            assertion_concern::assert_argument_not_negative(preco)?;
+        }
+        Ok(())
+    }
+
+    // Retorna o produto como é vendido na loja, ou None se a loja não o vende.
+    pub fn aplica(produto: &Produto, loja_id: usize, ajustes: &[ProdutoLoja]) -> Option<Produto> {
+        if !produto.visivel_na_loja(loja_id) {
+            return None;
+        }
+        let mut produto = produto.clone();
+        let ajuste = ajustes
+            .iter()
+            .find(|ajuste| ajuste.loja_id == loja_id && ajuste.produto_id == *produto.id());
+        if let Some(ajuste) = ajuste {
+            if !ajuste.disponivel {
+                return None;
+            }
+            if let Some(preco) = ajuste.preco {
+                produto.set_preco(preco).ok()?;
+            }
+        }
+        Some(produto)
+    }
+
+    // Getters
+    pub fn loja_id(&self) -> &usize {
+        &self.loja_id
+    }
+
+    pub fn produto_id(&self) -> &usize {
+        &self.produto_id
+    }
+
+    pub fn preco(&self) -> Option<f64> {
+        self.preco
+    }
+
+    pub fn disponivel(&self) -> bool {
+        self.disponivel
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::ingredientes::Ingredientes;
+
+    const AGORA: &str = "2021-08-01 00:00:00.000+0000";
+
+    fn create_produto(loja_id: Option<usize>) -> Produto {
+        let mut produto = Produto::new(
+            1,
+            "Café".to_string(),
+            "cafe.png".to_string(),
+            "Café coado".to_string(),
+            2,
+            6.0,
+            Ingredientes::new(vec!["Café".to_string()]).unwrap(),
+            AGORA.to_string(),
+            AGORA.to_string(),
+        );
+        produto.set_loja_id(loja_id);
+        produto
+    }
+
+    #[test]
+    fn test_loja_validate_entity() {
+        let loja = Loja::new(1, "Centro".to_string(), true, AGORA.to_string(), AGORA.to_string());
+        assert!(loja.validate_entity().is_ok());
+
+        let loja = Loja::new(1, "".to_string(), true, AGORA.to_string(), AGORA.to_string());
+        assert!(matches!(loja.validate_entity(), Err(DomainError::Empty)));
+    }
+
+    #[test]
+    fn test_produto_loja_validate_entity() {
+        assert!(ProdutoLoja::new(1, 1, Some(5.0), true).validate_entity().is_ok());
+        assert!(matches!(
+            ProdutoLoja::new(1, 1, Some(-5.0), true).validate_entity(),
+            Err(DomainError::NonPositive)
+        ));
+    }
+
+    #[test]
+    fn test_produto_loja_aplica() {
+        let ajustes = vec![
+            ProdutoLoja::new(1, 1, Some(7.5), true),
+            ProdutoLoja::new(2, 1, None, false),
+        ];
+
+        let produto = ProdutoLoja::aplica(&create_produto(None), 1, &ajustes).unwrap();
+        assert_eq!(produto.preco(), 7.5);
+
+        assert!(ProdutoLoja::aplica(&create_produto(None), 2, &ajustes).is_none());
+
+        let produto = ProdutoLoja::aplica(&create_produto(None), 3, &ajustes).unwrap();
+        assert_eq!(produto.preco(), 6.0);
+
+        assert!(ProdutoLoja::aplica(&create_produto(Some(3)), 1, &ajustes).is_none());
+        assert!(ProdutoLoja::aplica(&create_produto(Some(3)), 3, &ajustes).is_some());
+    }
+}
@@ -3,13 +3,13 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     base::{assertion_concern, domain_error::DomainError},
-    entities::{cpf::Cpf, produto::Produto},
+    entities::{cpf::Cpf, documento_fiscal::DocumentoFiscal, loja::LOJA_PADRAO, produto::Produto},
 };
 
 // Considerar Ordem de Status
 // Pendente => Pago => EmPreparacao => Pronto => Finalizado => (Cancelado)
 // Cancelado em qualquer ponto
-#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema, PartialEq)]
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema, PartialEq, Eq, Hash)]
 pub enum Status {
     EmPreparacao,
     Pago,
@@ -24,15 +24,34 @@ pub enum Status {
 pub struct Pedido {
     id: usize,
     cliente: Option<Cpf>,
+    // CPF ou CNPJ da nota fiscal; não precisa ser o do cliente do programa de fidelidade.
+    #[serde(default)]
+    documento_fiscal: Option<DocumentoFiscal>,
     lanche: Option<Produto>,
     acompanhamento: Option<Produto>,
     bebida: Option<Produto>,
     pagamento: Option<String>,
     status: Status,
+    // Desconto do resgate de pontos de fidelidade, abatido do valor total.
+    #[serde(default)]
+    desconto: f64,
+    #[serde(default = "loja_padrao")]
+    loja_id: usize,
+    // Incrementada a cada atualização; serve de ETag para o controle de concorrência.
+    #[serde(default = "versao_inicial")]
+    versao: usize,
     data_criacao: String,
     data_atualizacao: String,
 }
 
+fn loja_padrao() -> usize {
+    LOJA_PADRAO
+}
+
+fn versao_inicial() -> usize {
+    1
+}
+
 impl Pedido {
     pub fn new(
         id: usize,
@@ -48,11 +67,15 @@ impl Pedido {
         Pedido {
             id,
             cliente,
+            documento_fiscal: None,
             lanche,
             acompanhamento,
             bebida,
             pagamento,
             status,
+            desconto: 0.0,
+            loja_id: LOJA_PADRAO,
+            versao: versao_inicial(),
             data_criacao,
             data_atualizacao,
         }
@@ -80,6 +103,10 @@ impl Pedido {
         self.cliente.as_ref()
     }
 
+    pub fn documento_fiscal(&self) -> Option<&DocumentoFiscal> {
+        self.documento_fiscal.as_ref()
+    }
+
     pub fn lanche(&self) -> Option<&Produto> {
         self.lanche.as_ref()
     }
@@ -100,6 +127,18 @@ impl Pedido {
         &self.status
     }
 
+    pub fn desconto(&self) -> f64 {
+        self.desconto
+    }
+
+    pub fn loja_id(&self) -> &usize {
+        &self.loja_id
+    }
+
+    pub fn versao(&self) -> usize {
+        self.versao
+    }
+
     pub fn data_criacao(&self) -> &String {
         &self.data_criacao
     }
@@ -108,7 +147,8 @@ impl Pedido {
         &self.data_atualizacao
     }
 
-    pub fn valor_total(&self) -> f64 {
+    // Soma dos itens, antes do desconto.
+    pub fn subtotal(&self) -> f64 {
         let valor_lanche = match self.lanche() {
             Some(produto) => produto.preco(),
             None => 0.0,
@@ -127,7 +167,19 @@ impl Pedido {
         valor_lanche + valor_bebida + valor_acompanhamento
     }
 
+    pub fn valor_total(&self) -> f64 {
+        (self.subtotal() - self.desconto).max(0.0)
+    }
+
     // Setters
+    pub fn set_cliente(&mut self, cliente: Option<Cpf>) {
+        self.cliente = cliente;
+    }
+
+    pub fn set_documento_fiscal(&mut self, documento_fiscal: Option<DocumentoFiscal>) {
+        self.documento_fiscal = documento_fiscal;
+    }
+
     pub fn set_pagamento(&mut self, pagamento: String) {
         self.pagamento = Some(pagamento);
     }
@@ -136,6 +188,18 @@ impl Pedido {
         self.status = status;
     }
 
+    pub fn set_desconto(&mut self, desconto: f64) {
+        self.desconto = desconto;
+    }
+
+    pub fn set_loja_id(&mut self, loja_id: usize) {
+        self.loja_id = loja_id;
+    }
+
+    pub fn set_versao(&mut self, versao: usize) {
+        self.versao = versao;
+    }
+
     pub fn set_data_atualizacao(&mut self, data_atualizacao: String) -> Result<(), DomainError> {
         assertion_concern::assert_argument_timestamp_format(data_atualizacao.clone())?;
         self.data_atualizacao = data_atualizacao;
@@ -148,17 +212,16 @@ impl Pedido {
 mod tests {
     use super::*;
     use crate::entities::ingredientes::Ingredientes;
-    use crate::entities::produto::Categoria;
     use crate::entities::produto::Produto;
 
-    fn create_valid_produto(categoria: Categoria) -> Produto {
+    fn create_valid_produto(categoria_id: usize) -> Produto {
         let _now = "2021-08-01 00:00:00.000+0000".to_string();
         Produto::new(
             1,
             "Cheeseburger".to_string(),
             "cheeseburger.png".to_string(),
             "O clássico pão, carne e queijo!".to_string(),
-            categoria,
+            categoria_id,
             9.99,
             Ingredientes::new(vec![
                 "Pão".to_string(),
@@ -174,7 +237,7 @@ mod tests {
     fn create_valid_pedido() -> Pedido {
         let _now = "2021-08-01 00:00:00.000+0000".to_string();
         let cliente = Cpf::new("123.456.789-09".to_string()).unwrap();
-        let produto = create_valid_produto(Categoria::Lanche);
+        let produto = create_valid_produto(1);
         Pedido::new(
             1,
             Some(cliente),
@@ -292,9 +355,9 @@ mod tests {
     fn test_soma_valor_total_pedido() {
         let _now = "2021-08-01 00:00:00.000+0000".to_string();
         let cliente = Cpf::new("123.456.789-09".to_string()).unwrap();
-        let lanche = create_valid_produto(Categoria::Lanche);
-        let acompanhamento = create_valid_produto(Categoria::Acompanhamento);
-        let bebida = create_valid_produto(Categoria::Bebida);
+        let lanche = create_valid_produto(1);
+        let acompanhamento = create_valid_produto(3);
+        let bebida = create_valid_produto(2);
         let pedido = Pedido::new(
             1,
             Some(cliente),
@@ -313,7 +376,7 @@ mod tests {
     fn test_soma_valor_total_pedido_apenas_lanche() {
         let _now = "2021-08-01 00:00:00.000+0000".to_string();
         let cliente = Cpf::new("123.456.789-09".to_string()).unwrap();
-        let lanche = create_valid_produto(Categoria::Lanche);
+        let lanche = create_valid_produto(1);
         let pedido = Pedido::new(
             1,
             Some(cliente),
@@ -328,6 +391,28 @@ mod tests {
         assert_eq!(pedido.valor_total(), 9.99);
     }
 
+    #[test]
+    fn test_valor_total_com_desconto() {
+        let _now = "2021-08-01 00:00:00.000+0000".to_string();
+        let mut pedido = Pedido::new(
+            1,
+            None,
+            Some(create_valid_produto(1)),
+            None,
+            None,
+            None,
+            Status::Pendente,
+            _now.clone(),
+            _now,
+        );
+        pedido.set_desconto(2.0);
+        assert_eq!(pedido.subtotal(), 9.99);
+        assert_eq!(pedido.valor_total(), 7.99);
+
+        pedido.set_desconto(20.0);
+        assert_eq!(pedido.valor_total(), 0.0);
+    }
+
     #[test]
     fn test_pedido_set_pagamento() {
         let mut pedido = create_valid_pedido();
@@ -0,0 +1,304 @@
+use chrono::{DateTime, Duration, FixedOffset};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{cpf::Cpf, pedido::Pedido};
+
+const FORMATO_DATA: &str = "%Y-%m-%d %H:%M:%S%.3f%z";
+
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema, PartialEq)]
+pub enum TipoDeMovimento {
+    // Pontos ganhos com um pedido finalizado.
+    Acumulo,
+    // Pontos trocados por desconto num pedido.
+    Resgate,
+    // Desfazem o acúmulo e o resgate de um pedido cancelado.
+    EstornoDeAcumulo,
+    EstornoDeResgate,
+}
+
+// Lançamento no extrato de pontos do cliente. Créditos são positivos e têm validade;
+// débitos são negativos. Cada pedido tem no máximo um lançamento de cada tipo.
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct MovimentoDePontos {
+    id: usize,
+    cliente: Cpf,
+    pedido_id: usize,
+    tipo: TipoDeMovimento,
+    pontos: i64,
+    validade: Option<String>,
+    data_criacao: String,
+}
+
+impl MovimentoDePontos {
+    pub fn new(
+        id: usize,
+        cliente: Cpf,
+        pedido_id: usize,
+        tipo: TipoDeMovimento,
+        pontos: i64,
+        validade: Option<String>,
+        data_criacao: String,
+    ) -> Self {
+        MovimentoDePontos {
+            id,
+            cliente,
+            pedido_id,
+            tipo,
+            pontos,
+            validade,
+            data_criacao,
+        }
+    }
+
+    // Getters
+    pub fn id(&self) -> &usize {
+        &self.id
+    }
+
+    pub fn cliente(&self) -> &Cpf {
+        &self.cliente
+    }
+
+    pub fn pedido_id(&self) -> &usize {
+        &self.pedido_id
+    }
+
+    pub fn tipo(&self) -> &TipoDeMovimento {
+        &self.tipo
+    }
+
+    pub fn pontos(&self) -> i64 {
+        self.pontos
+    }
+
+    pub fn validade(&self) -> Option<&String> {
+        self.validade.as_ref()
+    }
+
+    pub fn data_criacao(&self) -> &String {
+        &self.data_criacao
+    }
+
+    // Setters
+    pub fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    pub fn set_cliente(&mut self, cliente: Cpf) {
+        self.cliente = cliente;
+    }
+}
+
+#[derive(Clone, Serialize, Debug, JsonSchema, PartialEq)]
+pub struct LoteDePontos {
+    pub pontos: i64,
+    pub validade: String,
+}
+
+// Saldo do cliente, separado em lotes pela data de vencimento.
+#[derive(Clone, Serialize, Debug, JsonSchema)]
+pub struct SaldoDePontos {
+    pub saldo: i64,
+    pub lotes: Vec<LoteDePontos>,
+}
+
+struct Lote {
+    pedido_id: usize,
+    pontos: i64,
+    validade: DateTime<FixedOffset>,
+    validade_original: String,
+}
+
+fn instante(data: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_str(data, FORMATO_DATA).ok()
+}
+
+impl SaldoDePontos {
+    // Refaz o extrato em ordem cronológica: os débitos consomem primeiro os pontos que
+    // vencem antes, exceto o estorno de um acúmulo, que retira os pontos do próprio pedido.
+    // Pontos vencidos sem uso saem do saldo, e o saldo nunca fica negativo.
+    pub fn calcula(movimentos: &[MovimentoDePontos], agora: &DateTime<FixedOffset>) -> Self {
+        let mut movimentos: Vec<(DateTime<FixedOffset>, &MovimentoDePontos)> = movimentos
+            .iter()
+            .filter_map(|movimento| Some((instante(movimento.data_criacao())?, movimento)))
+            .collect();
+        movimentos.sort_by(|(a, movimento_a), (b, movimento_b)| {
+            a.cmp(b).then_with(|| movimento_a.id().cmp(movimento_b.id()))
+        });
+
+        let mut lotes: Vec<Lote> = Vec::new();
+        for (momento, movimento) in movimentos {
+            lotes.retain(|lote| lote.validade > momento);
+            if movimento.pontos() > 0 {
+                let Some(validade_original) = movimento.validade() else {
+                    continue;
+                };
+                let Some(validade) = instante(validade_original) else {
+                    continue;
+                };
+                let posicao = lotes.partition_point(|lote| lote.validade <= validade);
+                lotes.insert(
+                    posicao,
+                    Lote {
+                        pedido_id: *movimento.pedido_id(),
+                        pontos: movimento.pontos(),
+                        validade,
+                        validade_original: validade_original.clone(),
+                    },
+                );
+                continue;
+            }
+
+            let mut debito = -movimento.pontos();
+            if *movimento.tipo() == TipoDeMovimento::EstornoDeAcumulo {
+                if let Some(lote) = lotes
+                    .iter_mut()
+                    .find(|lote| lote.pedido_id == *movimento.pedido_id())
+                {
+                    let consumido = debito.min(lote.pontos);
+                    lote.pontos -= consumido;
+                    debito -= consumido;
+                }
+            }
+            for lote in lotes.iter_mut() {
+                let consumido = debito.min(lote.pontos);
+                lote.pontos -= consumido;
+                debito -= consumido;
+            }
+            lotes.retain(|lote| lote.pontos > 0);
+        }
+
+        let lotes: Vec<LoteDePontos> = lotes
+            .into_iter()
+            .filter(|lote| lote.validade > *agora && lote.pontos > 0)
+            .map(|lote| LoteDePontos {
+                pontos: lote.pontos,
+                validade: lote.validade_original,
+            })
+            .collect();
+        SaldoDePontos {
+            saldo: lotes.iter().map(|lote| lote.pontos).sum(),
+            lotes,
+        }
+    }
+}
+
+// Regras do programa de fidelidade, vindas da configuração.
+#[derive(Clone, Debug)]
+pub struct RegrasDeFidelidade {
+    // Pontos ganhos por real do valor total do pedido finalizado.
+    pontos_por_real: f64,
+    // Desconto, em reais, de cada ponto resgatado.
+    valor_do_ponto: f64,
+    validade_em_dias: i64,
+}
+
+impl RegrasDeFidelidade {
+    pub fn new(pontos_por_real: f64, valor_do_ponto: f64, validade_em_dias: i64) -> Self {
+        RegrasDeFidelidade {
+            pontos_por_real,
+            valor_do_ponto,
+            validade_em_dias,
+        }
+    }
+
+    // Frações de ponto são descartadas.
+    pub fn pontos_do_pedido(&self, pedido: &Pedido) -> i64 {
+        (pedido.valor_total() * self.pontos_por_real).floor() as i64
+    }
+
+    // Desconto em reais, arredondado para centavos.
+    pub fn desconto(&self, pontos: i64) -> f64 {
+        (pontos as f64 * self.valor_do_ponto * 100.0).round() / 100.0
+    }
+
+    pub fn validade(&self, a_partir_de: &DateTime<FixedOffset>) -> String {
+        (*a_partir_de + Duration::days(self.validade_em_dias))
+            .format(FORMATO_DATA)
+            .to_string()
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(dia: u32) -> String {
+        format!("2024-01-{:02} 12:00:00.000+0000", dia)
+    }
+
+    fn movimento(pedido_id: usize, tipo: TipoDeMovimento, pontos: i64, dia: u32, validade: Option<u32>) -> MovimentoDePontos {
+        MovimentoDePontos::new(
+            pedido_id,
+            Cpf::new("123.456.789-09".to_string()).unwrap(),
+            pedido_id,
+            tipo,
+            pontos,
+            validade.map(data),
+            data(dia),
+        )
+    }
+
+    #[test]
+    fn test_saldo_consome_primeiro_os_pontos_que_vencem_antes() {
+        let movimentos = vec![
+            movimento(1, TipoDeMovimento::Acumulo, 10, 1, Some(20)),
+            movimento(2, TipoDeMovimento::Acumulo, 30, 2, Some(25)),
+            movimento(3, TipoDeMovimento::Resgate, -15, 3, None),
+        ];
+
+        let saldo = SaldoDePontos::calcula(&movimentos, &instante(&data(4)).unwrap());
+        assert_eq!(saldo.saldo, 25);
+        assert_eq!(saldo.lotes, vec![LoteDePontos { pontos: 25, validade: data(25) }]);
+
+        // Depois do dia 25 não sobra nada.
+        let saldo = SaldoDePontos::calcula(&movimentos, &instante(&data(26)).unwrap());
+        assert_eq!(saldo.saldo, 0);
+    }
+
+    #[test]
+    fn test_saldo_descarta_pontos_vencidos() {
+        let movimentos = vec![
+            movimento(1, TipoDeMovimento::Acumulo, 10, 1, Some(5)),
+            movimento(2, TipoDeMovimento::Acumulo, 30, 2, Some(25)),
+            // Os 10 pontos do pedido 1 venceram antes do resgate.
+            movimento(3, TipoDeMovimento::Resgate, -20, 6, None),
+        ];
+
+        let saldo = SaldoDePontos::calcula(&movimentos, &instante(&data(7)).unwrap());
+        assert_eq!(saldo.saldo, 10);
+    }
+
+    #[test]
+    fn test_estorno_de_acumulo_retira_os_pontos_do_proprio_pedido() {
+        let movimentos = vec![
+            movimento(1, TipoDeMovimento::Acumulo, 10, 1, Some(20)),
+            movimento(2, TipoDeMovimento::Acumulo, 30, 2, Some(25)),
+            movimento(2, TipoDeMovimento::EstornoDeAcumulo, -30, 3, None),
+        ];
+
+        let saldo = SaldoDePontos::calcula(&movimentos, &instante(&data(4)).unwrap());
+        assert_eq!(saldo.lotes, vec![LoteDePontos { pontos: 10, validade: data(20) }]);
+
+        // Pontos já usados não deixam o saldo negativo.
+        let movimentos = vec![
+            movimento(1, TipoDeMovimento::Acumulo, 10, 1, Some(20)),
+            movimento(2, TipoDeMovimento::Resgate, -8, 2, None),
+            movimento(1, TipoDeMovimento::EstornoDeAcumulo, -10, 3, None),
+        ];
+        let saldo = SaldoDePontos::calcula(&movimentos, &instante(&data(4)).unwrap());
+        assert_eq!(saldo.saldo, 0);
+    }
+
+    #[test]
+    fn test_regras_de_fidelidade() {
+        let regras = RegrasDeFidelidade::new(1.0, 0.05, 30);
+        assert_eq!(regras.desconto(150), 7.5);
+        assert_eq!(
+            regras.validade(&instante(&data(1)).unwrap()),
+            "2024-01-31 12:00:00.000+0000"
+        );
+    }
+}
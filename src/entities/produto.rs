@@ -9,34 +9,35 @@ use crate::{
     entities::ingredientes::Ingredientes,
 };
 
-#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema, PartialEq)]
-pub enum Categoria {
-    Lanche,
-    Bebida,
-    Acompanhamento,
-    Sobremesa,
-}
-
 #[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
 pub struct Produto {
     id: usize,
     nome: String,
     foto: String,
     descricao: String,
-    categoria: Categoria,
+    categoria_id: usize,
     preco: f64,
     ingredientes: Ingredientes,
+    #[serde(default)]
+    loja_id: Option<usize>,
+    // Incrementada a cada atualização; serve de ETag para o controle de concorrência.
+    #[serde(default = "versao_inicial")]
+    versao: usize,
     data_criacao: String,
     data_atualizacao: String,
 }
 
+fn versao_inicial() -> usize {
+    1
+}
+
 impl Produto {
     pub fn new(
         id: usize,
         nome: String,
         foto: String,
         descricao: String,
-        categoria: Categoria,
+        categoria_id: usize,
         preco: f64,
         ingredientes: Ingredientes,
         data_criacao: String,
@@ -47,9 +48,11 @@ impl Produto {
             nome,
             foto,
             descricao,
-            categoria,
+            categoria_id,
             preco,
             ingredientes,
+            loja_id: None,
+            versao: versao_inicial(),
             data_criacao,
             data_atualizacao,
         }
@@ -81,8 +84,8 @@ impl Produto {
         &self.descricao
     }
 
-    pub fn categoria(&self) -> &Categoria {
-        &self.categoria
+    pub fn categoria_id(&self) -> &usize {
+        &self.categoria_id
     }
 
     pub fn preco(&self) -> f64 {
@@ -93,6 +96,19 @@ impl Produto {
         &self.ingredientes
     }
 
+    // Produtos sem loja fazem parte do catálogo de todas as lojas.
+    pub fn loja_id(&self) -> Option<&usize> {
+        self.loja_id.as_ref()
+    }
+
+    pub fn visivel_na_loja(&self, loja_id: usize) -> bool {
+        self.loja_id.is_none_or(|produto_loja_id| produto_loja_id == loja_id)
+    }
+
+    pub fn versao(&self) -> usize {
+        self.versao
+    }
+
     pub fn data_criacao(&self) -> &String {
         &self.data_criacao
     }
@@ -118,8 +134,8 @@ impl Produto {
         Ok(())
     }
 
-    pub fn set_categoria(&mut self, categoria: Categoria) {
-        self.categoria = categoria;
+    pub fn set_categoria_id(&mut self, categoria_id: usize) {
+        self.categoria_id = categoria_id;
     }
 
     pub fn set_preco(&mut self, preco: f64) -> Result<(), DomainError> {
@@ -132,6 +148,14 @@ impl Produto {
         self.ingredientes = ingredientes;
     }
 
+    pub fn set_loja_id(&mut self, loja_id: Option<usize>) {
+        self.loja_id = loja_id;
+    }
+
+    pub fn set_versao(&mut self, versao: usize) {
+        self.versao = versao;
+    }
+
     pub fn set_data_atualizacao(&mut self, data_atualizacao: String) -> Result<(), DomainError> {
         assertion_concern::assert_argument_timestamp_format(data_atualizacao.clone())?;
         self.data_atualizacao = data_atualizacao;
@@ -151,7 +175,7 @@ mod tests {
             "Cheeseburger".to_string(),
             "cheeseburger.png".to_string(),
             "O clássico pão, carne e queijo!".to_string(),
-            Categoria::Lanche,
+            1,
             9.99,
             Ingredientes::new(vec![
                 "Pão".to_string(),
@@ -171,7 +195,7 @@ mod tests {
         assert_eq!(produto.nome(), "Cheeseburger");
         assert_eq!(produto.foto(), "cheeseburger.png");
         assert_eq!(produto.descricao(), "O clássico pão, carne e queijo!");
-        assert_eq!(produto.categoria(), &Categoria::Lanche);
+        assert_eq!(produto.categoria_id(), &1);
         assert_eq!(produto.preco(), 9.99);
         assert_eq!(
             produto.ingredientes().to_vec_string(),
@@ -195,7 +219,7 @@ mod tests {
             "".to_string(),
             "cheeseburger.png".to_string(),
             "O clássico pão, carne e queijo!".to_string(),
-            Categoria::Lanche,
+            1,
             9.99,
             Ingredientes::new(vec![
                 "Pão".to_string(),
@@ -222,7 +246,7 @@ mod tests {
             "Cheeseburger".to_string(),
             "cheeseburger.png".to_string(),
             "O clássico pão, carne e queijo!".to_string(),
-            Categoria::Lanche,
+            1,
             -10.0,
             Ingredientes::new(vec![
                 "Pão".to_string(),
@@ -260,8 +284,8 @@ mod tests {
     #[test]
     fn test_produto_set_categoria_valid() {
         let mut produto = create_valid_produto();
-        produto.set_categoria(Categoria::Bebida);
-        assert_eq!(produto.categoria(), &Categoria::Bebida);
+        produto.set_categoria_id(2);
+        assert_eq!(produto.categoria_id(), &2);
     }
 
     #[test]
@@ -0,0 +1,36 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::entities::pedido::Status;
+
+// Momento em que um pedido passou a ter um status. É o que permite medir quanto tempo a
+// cozinha leva em cada etapa.
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema, PartialEq)]
+pub struct TransicaoDeStatus {
+    pedido_id: usize,
+    status: Status,
+    data_criacao: String,
+}
+
+impl TransicaoDeStatus {
+    pub fn new(pedido_id: usize, status: Status, data_criacao: String) -> Self {
+        TransicaoDeStatus {
+            pedido_id,
+            status,
+            data_criacao,
+        }
+    }
+
+    // Getters
+    pub fn pedido_id(&self) -> &usize {
+        &self.pedido_id
+    }
+
+    pub fn status(&self) -> &Status {
+        &self.status
+    }
+
+    pub fn data_criacao(&self) -> &String {
+        &self.data_criacao
+    }
+}
@@ -1 +1,2 @@
-pub mod postgres;
\ No newline at end of file
+pub mod postgres;
+pub mod telemetria;
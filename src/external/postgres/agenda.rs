@@ -0,0 +1,35 @@
+use chrono::{DateTime, Utc};
+use postgres_from_row::FromRow;
+
+use crate::entities::agenda::{Agenda, AlvoAgenda, DadosDaAgenda};
+
+impl FromRow for Agenda {
+    fn from_row(row: &tokio_postgres::Row) -> Self {
+        Self::try_from_row(row).unwrap()
+    }
+
+    fn try_from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        let id: i32 = row.try_get("id")?;
+        let alvo: String = row.try_get("alvo")?;
+        let alvo_id: i32 = row.try_get("alvo_id")?;
+        let dias_semana: Vec<i32> = row.try_get("dias_semana")?;
+        let data_criacao: std::time::SystemTime = row.try_get("data_criacao")?;
+        let data_criacao: DateTime<Utc> = data_criacao.into();
+        let data_atualizacao: std::time::SystemTime = row.try_get("data_atualizacao")?;
+        let data_atualizacao: DateTime<Utc> = data_atualizacao.into();
+
+        Ok(Agenda::new(
+            id as usize,
+            DadosDaAgenda {
+                alvo: alvo.parse::<AlvoAgenda>().unwrap_or(AlvoAgenda::Produto),
+                alvo_id: alvo_id as usize,
+                dias_semana: dias_semana.into_iter().map(|dia| dia as u32).collect(),
+                hora_inicio: row.try_get("hora_inicio")?,
+                hora_fim: row.try_get("hora_fim")?,
+                preco: row.try_get("preco")?,
+            },
+            data_criacao.format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
+            data_atualizacao.format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
+        ))
+    }
+}
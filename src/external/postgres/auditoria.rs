@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use postgres_from_row::FromRow;
+
+use crate::entities::auditoria::{AcaoDeAuditoria, RegistroDeAuditoria};
+
+impl FromRow for RegistroDeAuditoria {
+    fn from_row(row: &tokio_postgres::Row) -> Self {
+        Self::try_from_row(row).unwrap()
+    }
+
+    fn try_from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        let id: i32 = row.try_get("id")?;
+        let data_criacao: std::time::SystemTime = row.try_get("data_criacao")?;
+        let data_criacao: DateTime<Utc> = data_criacao.into();
+
+        Ok(RegistroDeAuditoria::new(
+            id as usize,
+            row.try_get::<_, &str>("acao")?
+                .parse::<AcaoDeAuditoria>()
+                .unwrap(),
+            row.try_get("titular")?,
+            row.try_get("request_id")?,
+            data_criacao.format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
+        ))
+    }
+}
@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use postgres_from_row::FromRow;
+
+use crate::entities::categoria::{Categoria, DadosDaCategoria, ItemPedido};
+
+impl FromRow for Categoria {
+    fn from_row(row: &tokio_postgres::Row) -> Self {
+        Self::try_from_row(row).unwrap()
+    }
+
+    fn try_from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        let id: i32 = row.try_get("id")?;
+        let ordem: i32 = row.try_get("ordem")?;
+        let item_pedido: Option<String> = row.try_get("item_pedido")?;
+        let data_criacao: std::time::SystemTime = row.try_get("data_criacao")?;
+        let data_criacao: DateTime<Utc> = data_criacao.into();
+        let data_atualizacao: std::time::SystemTime = row.try_get("data_atualizacao")?;
+        let data_atualizacao: DateTime<Utc> = data_atualizacao.into();
+
+        Ok(Categoria::new(
+            id as usize,
+            DadosDaCategoria {
+                nome: row.try_get("nome")?,
+                icone: row.try_get("icone")?,
+                ordem: ordem as usize,
+                ativa: row.try_get("ativa")?,
+                item_pedido: item_pedido.and_then(|item| item.parse::<ItemPedido>().ok()),
+            },
+            data_criacao.format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
+            data_atualizacao.format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
+        ))
+    }
+}
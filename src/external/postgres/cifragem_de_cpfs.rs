@@ -0,0 +1,79 @@
+use deadpool_postgres::Pool;
+
+use crate::base::cifrador_de_cpf::CifradorDeCpf;
+use crate::base::domain_error::DomainError;
+use crate::entities::cpf::Cpf;
+use crate::external::postgres::migracoes;
+
+// Migração que criou as colunas de hash (0014_cpf_cifrado.sql).
+const VERSAO_DA_CIFRAGEM: i64 = 14;
+
+// Tabela, coluna do CPF e coluna do hash, na ordem das referências: o extrato de pontos
+// aponta para o hash do cliente.
+const COLUNAS_DE_CPF: &[(&str, &str, &str)] = &[
+    ("cliente", "cpf", "cpf_hash"),
+    ("pedido", "cliente", "cliente_hash"),
+    ("movimento_de_pontos", "cliente", "cliente_hash"),
+];
+
+// Cifra os CPFs gravados em claro (as linhas ainda sem hash), tudo numa transação. Pode
+// rodar a cada subida: sem CPFs em claro, não faz nada. Devolve quantas linhas cifrou.
+pub async fn cifra_cpfs_em_claro(pool: &Pool, cifrador: &CifradorDeCpf) -> Result<usize, DomainError> {
+    let mut conexao = pool.get().await?;
+    let transacao = conexao.transaction().await?;
+    let mut cifrados = 0;
+    for (tabela, coluna, hash) in COLUNAS_DE_CPF {
+        let em_claro = format!("SELECT DISTINCT {coluna} FROM {tabela} WHERE {coluna} IS NOT NULL AND {hash} IS NULL");
+        let cifra = format!("UPDATE {tabela} SET {coluna} = $2, {hash} = $3 WHERE {coluna} = $1 AND {hash} IS NULL");
+        for row in transacao.query(&em_claro, &[]).await? {
+            let cpf = Cpf(row.get(0));
+            cifrados += transacao
+                .execute(&cifra, &[&cpf.0, &cifrador.cifra(&cpf), &cifrador.hash(&cpf)])
+                .await?;
+        }
+    }
+    transacao.commit().await?;
+    if cifrados > 0 {
+        tracing::info!(linhas = cifrados, "CPFs em claro cifrados");
+    }
+    Ok(cifrados as usize)
+}
+
+// O inverso: volta os CPFs para o texto em claro.
+async fn decifra_cpfs(pool: &Pool, cifrador: &CifradorDeCpf) -> Result<usize, DomainError> {
+    let mut conexao = pool.get().await?;
+    let transacao = conexao.transaction().await?;
+    let mut decifrados = 0;
+    for (tabela, coluna, hash) in COLUNAS_DE_CPF.iter().rev() {
+        let cifrados = format!("SELECT {coluna} FROM {tabela} WHERE {hash} IS NOT NULL");
+        let decifra = format!("UPDATE {tabela} SET {coluna} = $2, {hash} = NULL WHERE {coluna} = $1");
+        for row in transacao.query(&cifrados, &[]).await? {
+            let cifrado: String = row.get(0);
+            let cpf = cifrador.decifra(&cifrado)?;
+            decifrados += transacao.execute(&decifra, &[&cifrado, &cpf.0]).await?;
+        }
+    }
+    transacao.commit().await?;
+    tracing::info!(linhas = decifrados, "CPFs decifrados");
+    Ok(decifrados as usize)
+}
+
+// Desfaz as últimas `passos` migrações. Os CPFs só voltam ao texto em claro enquanto as
+// colunas de hash existem: se a migração que as criou estiver entre as desfeitas, decifra antes.
+pub async fn desfaz_migracoes(
+    pool: &Pool,
+    passos: usize,
+    cifrador: &CifradorDeCpf,
+) -> Result<Vec<i64>, DomainError> {
+    let mut aplicadas: Vec<i64> = migracoes::situacao(pool)
+        .await?
+        .into_iter()
+        .filter(|migracao| migracao.aplicada)
+        .map(|migracao| migracao.versao)
+        .collect();
+    aplicadas.sort_unstable_by(|a, b| b.cmp(a));
+    if aplicadas.iter().take(passos).any(|versao| *versao == VERSAO_DA_CIFRAGEM) {
+        decifra_cpfs(pool, cifrador).await?;
+    }
+    migracoes::desfaz(pool, passos).await
+}
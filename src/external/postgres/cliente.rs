@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use postgres_from_row::FromRow;
+
+use crate::entities::{cliente::Cliente, cpf::Cpf};
+
+impl FromRow for Cliente {
+    fn from_row(row: &tokio_postgres::Row) -> Self {
+        Self::try_from_row(row).unwrap()
+    }
+
+    fn try_from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        let data_criacao: std::time::SystemTime = row.try_get("data_criacao")?;
+        let data_criacao: DateTime<Utc> = data_criacao.into();
+        let data_atualizacao: std::time::SystemTime = row.try_get("data_atualizacao")?;
+        let data_atualizacao: DateTime<Utc> = data_atualizacao.into();
+
+        Ok(Cliente::new(
+            Cpf(row.try_get("cpf")?),
+            row.try_get("nome")?,
+            row.try_get("email")?,
+            row.try_get("aceita_marketing")?,
+            row.try_get("aceita_compartilhamento")?,
+            data_criacao.format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
+            data_atualizacao.format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
+        ))
+    }
+}
@@ -0,0 +1,20 @@
+use postgres_from_row::FromRow;
+use tokio_postgres::types::Json;
+
+use crate::entities::{idempotencia::RegistroDeIdempotencia, pedido::Pedido};
+
+impl FromRow for RegistroDeIdempotencia {
+    fn from_row(row: &tokio_postgres::Row) -> Self {
+        Self::try_from_row(row).unwrap()
+    }
+
+    fn try_from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        let pedido: Option<Json<Pedido>> = row.try_get("pedido")?;
+
+        Ok(RegistroDeIdempotencia::new(
+            row.try_get("chave")?,
+            row.try_get("impressao_digital")?,
+            pedido.map(|pedido| pedido.0),
+        ))
+    }
+}
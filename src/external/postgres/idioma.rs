@@ -0,0 +1,21 @@
+use postgres_from_row::FromRow;
+
+use crate::entities::idioma::{Idioma, TraducaoProduto};
+
+impl FromRow for TraducaoProduto {
+    fn from_row(row: &tokio_postgres::Row) -> Self {
+        Self::try_from_row(row).unwrap()
+    }
+
+    fn try_from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        let produto_id: i32 = row.try_get("produto_id")?;
+        let idioma: String = row.try_get("idioma")?;
+
+        Ok(TraducaoProduto::new(
+            produto_id as usize,
+            idioma.parse::<Idioma>().unwrap_or_default(),
+            row.try_get("nome")?,
+            row.try_get("descricao")?,
+        ))
+    }
+}
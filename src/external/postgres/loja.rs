@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+use postgres_from_row::FromRow;
+
+use crate::entities::loja::{Loja, ProdutoLoja};
+
+impl FromRow for Loja {
+    fn from_row(row: &tokio_postgres::Row) -> Self {
+        Self::try_from_row(row).unwrap()
+    }
+
+    fn try_from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        let id: i32 = row.try_get("id")?;
+        let data_criacao: std::time::SystemTime = row.try_get("data_criacao")?;
+        let data_criacao: DateTime<Utc> = data_criacao.into();
+        let data_atualizacao: std::time::SystemTime = row.try_get("data_atualizacao")?;
+        let data_atualizacao: DateTime<Utc> = data_atualizacao.into();
+
+        Ok(Loja::new(
+            id as usize,
+            row.try_get("nome")?,
+            row.try_get("ativa")?,
+            data_criacao.format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
+            data_atualizacao.format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
+        ))
+    }
+}
+
+impl FromRow for ProdutoLoja {
+    fn from_row(row: &tokio_postgres::Row) -> Self {
+        Self::try_from_row(row).unwrap()
+    }
+
+    fn try_from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        let loja_id: i32 = row.try_get("loja_id")?;
+        let produto_id: i32 = row.try_get("produto_id")?;
+
+        Ok(ProdutoLoja::new(
+            loja_id as usize,
+            produto_id as usize,
+            row.try_get("preco")?,
+            row.try_get("disponivel")?,
+        ))
+    }
+}
@@ -0,0 +1,380 @@
+use deadpool_postgres::Pool;
+use sha2::{Digest, Sha256};
+use tokio_postgres::Client;
+
+use crate::base::domain_error::DomainError;
+
+// Chave do pg_advisory_lock: só uma instância (api ou listener) migra o banco por vez.
+const TRAVA_DE_MIGRACAO: i64 = 0x7465_6368;
+
+// Bancos criados pelos scripts SQL aplicados à mão (Makefile/docker-entrypoint) só
+// receberam 0001 e 0002 e não têm o histórico.
+const VERSAO_LEGADA: i64 = 2;
+
+const CREATE_HISTORICO: &str = "CREATE TABLE IF NOT EXISTS migracoes (versao BIGINT PRIMARY KEY, nome TEXT NOT NULL, checksum TEXT NOT NULL, aplicada_em TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP)";
+const QUERY_HISTORICO_EXISTE: &str = "SELECT to_regclass('public.migracoes') IS NOT NULL";
+const QUERY_SCHEMA_LEGADO: &str = "SELECT to_regclass('public.pedido') IS NOT NULL";
+const QUERY_HISTORICO: &str = "SELECT versao, nome, checksum FROM migracoes ORDER BY versao";
+const INSERT_HISTORICO: &str = "INSERT INTO migracoes (versao, nome, checksum) VALUES ($1, $2, $3)";
+const DELETE_HISTORICO: &str = "DELETE FROM migracoes WHERE versao = $1";
+const LOCK: &str = "SELECT pg_advisory_lock($1)";
+const UNLOCK: &str = "SELECT pg_advisory_unlock($1)";
+
+pub struct Migracao {
+    pub versao: i64,
+    pub nome: &'static str,
+    up: &'static str,
+    down: &'static str,
+}
+
+impl Migracao {
+    const fn new(versao: i64, nome: &'static str, up: &'static str, down: &'static str) -> Self {
+        Migracao {
+            versao,
+            nome,
+            up,
+            down,
+        }
+    }
+
+    pub fn checksum(&self) -> String {
+        Sha256::digest(self.up.as_bytes())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+}
+
+pub const MIGRACOES: &[Migracao] = &[
+    Migracao::new(
+        1,
+        "create_table",
+        include_str!("../../../migrations/0001_create_table.sql"),
+        include_str!("../../../migrations/0001_create_table.down.sql"),
+    ),
+    Migracao::new(
+        2,
+        "insert_basic",
+        include_str!("../../../migrations/0002_insert_basic.sql"),
+        include_str!("../../../migrations/0002_insert_basic.down.sql"),
+    ),
+    Migracao::new(
+        3,
+        "busca_produtos",
+        include_str!("../../../migrations/0003_busca_produtos.sql"),
+        include_str!("../../../migrations/0003_busca_produtos.down.sql"),
+    ),
+    Migracao::new(
+        4,
+        "categorias",
+        include_str!("../../../migrations/0004_categorias.sql"),
+        include_str!("../../../migrations/0004_categorias.down.sql"),
+    ),
+    Migracao::new(
+        5,
+        "agendas",
+        include_str!("../../../migrations/0005_agendas.sql"),
+        include_str!("../../../migrations/0005_agendas.down.sql"),
+    ),
+    Migracao::new(
+        6,
+        "lojas",
+        include_str!("../../../migrations/0006_lojas.sql"),
+        include_str!("../../../migrations/0006_lojas.down.sql"),
+    ),
+    Migracao::new(
+        7,
+        "traducoes",
+        include_str!("../../../migrations/0007_traducoes.sql"),
+        include_str!("../../../migrations/0007_traducoes.down.sql"),
+    ),
+    Migracao::new(
+        8,
+        "versionamento",
+        include_str!("../../../migrations/0008_versionamento.sql"),
+        include_str!("../../../migrations/0008_versionamento.down.sql"),
+    ),
+    Migracao::new(
+        9,
+        "idempotencia",
+        include_str!("../../../migrations/0009_idempotencia.sql"),
+        include_str!("../../../migrations/0009_idempotencia.down.sql"),
+    ),
+    Migracao::new(
+        10,
+        "clientes",
+        include_str!("../../../migrations/0010_clientes.sql"),
+        include_str!("../../../migrations/0010_clientes.down.sql"),
+    ),
+    Migracao::new(
+        11,
+        "pedidos_por_cliente",
+        include_str!("../../../migrations/0011_pedidos_por_cliente.sql"),
+        include_str!("../../../migrations/0011_pedidos_por_cliente.down.sql"),
+    ),
+    Migracao::new(
+        12,
+        "fidelidade",
+        include_str!("../../../migrations/0012_fidelidade.sql"),
+        include_str!("../../../migrations/0012_fidelidade.down.sql"),
+    ),
+    Migracao::new(
+        13,
+        "lgpd",
+        include_str!("../../../migrations/0013_lgpd.sql"),
+        include_str!("../../../migrations/0013_lgpd.down.sql"),
+    ),
+    Migracao::new(
+        14,
+        "cpf_cifrado",
+        include_str!("../../../migrations/0014_cpf_cifrado.sql"),
+        include_str!("../../../migrations/0014_cpf_cifrado.down.sql"),
+    ),
+    Migracao::new(
+        15,
+        "documento_fiscal",
+        include_str!("../../../migrations/0015_documento_fiscal.sql"),
+        include_str!("../../../migrations/0015_documento_fiscal.down.sql"),
+    ),
+    Migracao::new(
+        16,
+        "relatorios_de_vendas",
+        include_str!("../../../migrations/0016_relatorios_de_vendas.sql"),
+        include_str!("../../../migrations/0016_relatorios_de_vendas.down.sql"),
+    ),
+    Migracao::new(
+        17,
+        "transicoes_de_status",
+        include_str!("../../../migrations/0017_transicoes_de_status.sql"),
+        include_str!("../../../migrations/0017_transicoes_de_status.down.sql"),
+    ),
+];
+
+// Linha da tabela migracoes.
+pub struct MigracaoAplicada {
+    pub versao: i64,
+    pub nome: String,
+    pub checksum: String,
+}
+
+pub struct SituacaoDaMigracao {
+    pub versao: i64,
+    pub nome: &'static str,
+    pub aplicada: bool,
+}
+
+// Confere o histórico com as migrações embutidas e devolve as que faltam aplicar, em ordem.
+// Uma migração aplicada que mudou de conteúdo, ou que esta versão da aplicação não conhece,
+// interrompe tudo: o schema do banco não é mais o que o código espera.
+fn pendentes<'a>(
+    migracoes: &'a [Migracao],
+    aplicadas: &[MigracaoAplicada],
+) -> Result<Vec<&'a Migracao>, DomainError> {
+    for aplicada in aplicadas {
+        match migracoes.iter().find(|migracao| migracao.versao == aplicada.versao) {
+            Some(migracao) if migracao.checksum() == aplicada.checksum => {}
+            Some(migracao) => {
+                return Err(DomainError::Database(format!(
+                    "Migração {} ({}) foi alterada depois de aplicada",
+                    migracao.versao, migracao.nome
+                )))
+            }
+            None => {
+                return Err(DomainError::Database(format!(
+                    "Migração {} ({}) aplicada no banco não existe nesta versão da aplicação",
+                    aplicada.versao, aplicada.nome
+                )))
+            }
+        }
+    }
+    Ok(migracoes
+        .iter()
+        .filter(|migracao| !aplicadas.iter().any(|aplicada| aplicada.versao == migracao.versao))
+        .collect())
+}
+
+async fn historico(client: &Client) -> Result<Vec<MigracaoAplicada>, DomainError> {
+    let existe: bool = client.query_one(QUERY_HISTORICO_EXISTE, &[]).await?.get(0);
+    if !existe {
+        return Ok(vec![]);
+    }
+    Ok(client
+        .query(QUERY_HISTORICO, &[])
+        .await?
+        .iter()
+        .map(|row| MigracaoAplicada {
+            versao: row.get(0),
+            nome: row.get(1),
+            checksum: row.get(2),
+        })
+        .collect())
+}
+
+async fn cria_historico(client: &mut Client) -> Result<(), DomainError> {
+    let existe: bool = client.query_one(QUERY_HISTORICO_EXISTE, &[]).await?.get(0);
+    if existe {
+        return Ok(());
+    }
+    let legado: bool = client.query_one(QUERY_SCHEMA_LEGADO, &[]).await?.get(0);
+
+    let transacao = client.transaction().await?;
+    transacao.batch_execute(CREATE_HISTORICO).await?;
+    if legado {
+        tracing::warn!(versao = VERSAO_LEGADA, "Banco sem histórico de migrações, registrando o schema existente");
+        for migracao in MIGRACOES.iter().filter(|migracao| migracao.versao <= VERSAO_LEGADA) {
+            transacao
+                .execute(INSERT_HISTORICO, &[&migracao.versao, &migracao.nome, &migracao.checksum()])
+                .await?;
+        }
+    }
+    transacao.commit().await?;
+    Ok(())
+}
+
+async fn aplica_pendentes(client: &mut Client) -> Result<Vec<i64>, DomainError> {
+    cria_historico(client).await?;
+    let aplicadas = historico(client).await?;
+
+    let mut versoes = Vec::new();
+    for migracao in pendentes(MIGRACOES, &aplicadas)? {
+        let transacao = client.transaction().await?;
+        transacao.batch_execute(migracao.up).await.map_err(|erro| {
+            DomainError::Database(format!("Migração {} ({}): {}", migracao.versao, migracao.nome, erro))
+        })?;
+        transacao
+            .execute(INSERT_HISTORICO, &[&migracao.versao, &migracao.nome, &migracao.checksum()])
+            .await?;
+        transacao.commit().await?;
+        tracing::info!(versao = migracao.versao, nome = migracao.nome, "Migração aplicada");
+        versoes.push(migracao.versao);
+    }
+    Ok(versoes)
+}
+
+async fn desfaz_ultimas(client: &mut Client, passos: usize) -> Result<Vec<i64>, DomainError> {
+    let aplicadas = historico(client).await?;
+    pendentes(MIGRACOES, &aplicadas)?;
+
+    let mut versoes = Vec::new();
+    for aplicada in aplicadas.iter().rev().take(passos) {
+        let migracao = MIGRACOES
+            .iter()
+            .find(|migracao| migracao.versao == aplicada.versao)
+            .ok_or(DomainError::NotFound)?;
+        let transacao = client.transaction().await?;
+        transacao.batch_execute(migracao.down).await.map_err(|erro| {
+            DomainError::Database(format!("Migração {} ({}): {}", migracao.versao, migracao.nome, erro))
+        })?;
+        transacao.execute(DELETE_HISTORICO, &[&migracao.versao]).await?;
+        transacao.commit().await?;
+        tracing::info!(versao = migracao.versao, nome = migracao.nome, "Migração desfeita");
+        versoes.push(migracao.versao);
+    }
+    Ok(versoes)
+}
+
+// Aplica as migrações pendentes. Devolve as versões aplicadas.
+pub async fn migra(pool: &Pool) -> Result<Vec<i64>, DomainError> {
+    let mut conexao = pool.get().await?;
+    conexao.execute(LOCK, &[&TRAVA_DE_MIGRACAO]).await?;
+    let resultado = aplica_pendentes(&mut conexao).await;
+    conexao.execute(UNLOCK, &[&TRAVA_DE_MIGRACAO]).await?;
+    resultado
+}
+
+// Desfaz as últimas `passos` migrações aplicadas, da mais nova para a mais antiga.
+pub async fn desfaz(pool: &Pool, passos: usize) -> Result<Vec<i64>, DomainError> {
+    let mut conexao = pool.get().await?;
+    conexao.execute(LOCK, &[&TRAVA_DE_MIGRACAO]).await?;
+    let resultado = desfaz_ultimas(&mut conexao, passos).await;
+    conexao.execute(UNLOCK, &[&TRAVA_DE_MIGRACAO]).await?;
+    resultado
+}
+
+pub async fn situacao(pool: &Pool) -> Result<Vec<SituacaoDaMigracao>, DomainError> {
+    let conexao = pool.get().await?;
+    let aplicadas = historico(&conexao).await?;
+    pendentes(MIGRACOES, &aplicadas)?;
+    Ok(MIGRACOES
+        .iter()
+        .map(|migracao| SituacaoDaMigracao {
+            versao: migracao.versao,
+            nome: migracao.nome,
+            aplicada: aplicadas.iter().any(|aplicada| aplicada.versao == migracao.versao),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::external::postgres::BancoDeTeste;
+
+    fn aplicada(migracao: &Migracao) -> MigracaoAplicada {
+        MigracaoAplicada {
+            versao: migracao.versao,
+            nome: migracao.nome.to_string(),
+            checksum: migracao.checksum(),
+        }
+    }
+
+    #[test]
+    fn test_migracoes_em_ordem_e_com_down() {
+        for (indice, migracao) in MIGRACOES.iter().enumerate() {
+            assert_eq!(migracao.versao, indice as i64 + 1);
+            assert!(!migracao.up.trim().is_empty());
+            assert!(!migracao.down.trim().is_empty());
+        }
+        assert!(MIGRACOES.len() as i64 >= VERSAO_LEGADA);
+    }
+
+    #[test]
+    fn test_pendentes() {
+        let aplicadas: Vec<MigracaoAplicada> = MIGRACOES[..3].iter().map(aplicada).collect();
+
+        let pendentes = pendentes(MIGRACOES, &aplicadas).unwrap();
+
+        assert_eq!(pendentes.len(), MIGRACOES.len() - 3);
+        assert_eq!(pendentes[0].versao, 4);
+    }
+
+    #[test]
+    fn test_pendentes_recusa_migracao_alterada() {
+        let mut aplicadas: Vec<MigracaoAplicada> = MIGRACOES[..2].iter().map(aplicada).collect();
+        aplicadas[1].checksum = "outro".to_string();
+
+        let erro = pendentes(MIGRACOES, &aplicadas).err().unwrap();
+
+        assert!(matches!(erro, DomainError::Database(mensagem) if mensagem.contains("Migração 2")));
+    }
+
+    #[test]
+    fn test_pendentes_recusa_migracao_desconhecida() {
+        let aplicadas = vec![MigracaoAplicada {
+            versao: 999,
+            nome: "futura".to_string(),
+            checksum: "".to_string(),
+        }];
+
+        assert!(pendentes(MIGRACOES, &aplicadas).is_err());
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_migra_banco_legado() {
+        let legado = BancoDeTeste::cria("migracoes_legado", 1).await;
+        {
+            // O que o deploy antigo deixava no banco.
+            let conexao = legado.pool.get().await.unwrap();
+            conexao.batch_execute(MIGRACOES[0].up).await.unwrap();
+            conexao.batch_execute(MIGRACOES[1].up).await.unwrap();
+        }
+
+        let aplicadas = migra(&legado.pool).await.unwrap();
+        let situacao = situacao(&legado.pool).await.unwrap();
+        legado.apaga().await;
+
+        assert_eq!(aplicadas, (3..=MIGRACOES.len() as i64).collect::<Vec<i64>>());
+        assert!(situacao.iter().all(|migracao| migracao.aplicada));
+    }
+}
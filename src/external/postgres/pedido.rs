@@ -2,125 +2,68 @@ use chrono::{DateTime, Utc};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use postgres_from_row::FromRow;
-use std::collections::HashMap;
 
 use crate::entities::pedido::Status;
 
-use super::table::{ColumnDefault, ColumnNullable, ColumnTypes};
-
-pub fn get_pedido_table_columns() -> HashMap<String, (ColumnTypes, ColumnNullable, ColumnDefault)>
-{
-    let mut columns = HashMap::new();
-    columns.insert(
-        "id".to_string(),
-        (
-            ColumnTypes::Index,
-            ColumnNullable(false),
-            ColumnDefault(None),
-        ),
-    );
-    columns.insert(
-        "cliente".to_string(),
-        (
-            ColumnTypes::Char(11),
-            ColumnNullable(true),
-            ColumnDefault(None),
-        ),
-    );
-    columns.insert(
-        "lanche_id".to_string(),
-        (
-            ColumnTypes::Integer,
-            ColumnNullable(true),
-            ColumnDefault(None),
-        ),
-    );
-    columns.insert(
-        "acompanhamento_id".to_string(),
-        (
-            ColumnTypes::Integer,
-            ColumnNullable(true),
-            ColumnDefault(None),
-        ),
-    );
-    columns.insert(
-        "bebida_id".to_string(),
-        (
-            ColumnTypes::Integer,
-            ColumnNullable(true),
-            ColumnDefault(None),
-        ),
-    );
-    columns.insert(
-        "pagamento".to_string(),
-        (
-            ColumnTypes::Text,
-            ColumnNullable(true),
-            ColumnDefault(None),
-        ),
-    );
-    columns.insert(
-        "status".to_string(),
-        (
-            ColumnTypes::Text,
-            ColumnNullable(false),
-            ColumnDefault(None),
-        ),
-    );
-
-    columns.insert(
-        "data_criacao".to_string(),
-        (
-            ColumnTypes::Timestamp,
-            ColumnNullable(true),
-            ColumnDefault(Some("CURRENT_TIMESTAMP".to_string())),
-        ),
-    );
-    columns.insert(
-        "data_atualizacao".to_string(),
-        (
-            ColumnTypes::Timestamp,
-            ColumnNullable(true),
-            ColumnDefault(Some("CURRENT_TIMESTAMP".to_string())),
-        ),
-    );
-
-    columns
-}
-
 #[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
 pub struct ProxyPedido {
     id: usize,
     cliente: Option<String>,
+    documento_fiscal: Option<String>,
     lanche_id: Option<usize>,
     acompanhamento_id: Option<usize>,
     bebida_id: Option<usize>,
-    pagamento: String,
+    lanche_preco: Option<f64>,
+    acompanhamento_preco: Option<f64>,
+    bebida_preco: Option<f64>,
+    desconto: f64,
+    pagamento: Option<String>,
     status: Status,
+    loja_id: usize,
+    versao: usize,
     data_criacao: String,
     data_atualizacao: String,
 }
 
+// Colunas do pedido gravado, fora o id e as datas.
+#[derive(Clone, Debug)]
+pub struct DadosDoPedido {
+    pub cliente: Option<String>,
+    pub lanche_id: Option<usize>,
+    pub acompanhamento_id: Option<usize>,
+    pub bebida_id: Option<usize>,
+    pub lanche_preco: Option<f64>,
+    pub acompanhamento_preco: Option<f64>,
+    pub bebida_preco: Option<f64>,
+    pub desconto: f64,
+    pub pagamento: Option<String>,
+    pub status: Status,
+    pub loja_id: usize,
+    pub versao: usize,
+}
+
 impl ProxyPedido {
     pub fn new(
         id: usize,
-        cliente: Option<String>,
-        lanche_id: Option<usize>,
-        acompanhamento_id: Option<usize>,
-        bebida_id: Option<usize>,
-        pagamento: String,
-        status: Status,
+        dados: DadosDoPedido,
         data_criacao: String,
         data_atualizacao: String,
     ) -> Self {
         ProxyPedido {
             id,
-            cliente,
-            lanche_id,
-            acompanhamento_id,
-            bebida_id,
-            pagamento,
-            status,
+            cliente: dados.cliente,
+            documento_fiscal: None,
+            lanche_id: dados.lanche_id,
+            acompanhamento_id: dados.acompanhamento_id,
+            bebida_id: dados.bebida_id,
+            lanche_preco: dados.lanche_preco,
+            acompanhamento_preco: dados.acompanhamento_preco,
+            bebida_preco: dados.bebida_preco,
+            desconto: dados.desconto,
+            pagamento: dados.pagamento,
+            status: dados.status,
+            loja_id: dados.loja_id,
+            versao: dados.versao,
             data_criacao,
             data_atualizacao,
         }
@@ -134,6 +77,18 @@ impl ProxyPedido {
         self.cliente.as_ref()
     }
 
+    pub fn set_cliente(&mut self, cliente: Option<String>) {
+        self.cliente = cliente;
+    }
+
+    pub fn documento_fiscal(&self) -> Option<&String> {
+        self.documento_fiscal.as_ref()
+    }
+
+    pub fn set_documento_fiscal(&mut self, documento_fiscal: Option<String>) {
+        self.documento_fiscal = documento_fiscal;
+    }
+
     pub fn lanche_id(&self) -> Option<&usize> {
         self.lanche_id.as_ref()
     }
@@ -146,14 +101,38 @@ impl ProxyPedido {
         self.bebida_id.as_ref()
     }
 
-    pub fn pagamento(&self) -> &String {
-        &self.pagamento
+    pub fn lanche_preco(&self) -> Option<f64> {
+        self.lanche_preco
+    }
+
+    pub fn acompanhamento_preco(&self) -> Option<f64> {
+        self.acompanhamento_preco
+    }
+
+    pub fn bebida_preco(&self) -> Option<f64> {
+        self.bebida_preco
+    }
+
+    pub fn desconto(&self) -> f64 {
+        self.desconto
+    }
+
+    pub fn pagamento(&self) -> Option<&String> {
+        self.pagamento.as_ref()
     }
 
     pub fn status(&self) -> &Status {
         &self.status
     }
 
+    pub fn loja_id(&self) -> &usize {
+        &self.loja_id
+    }
+
+    pub fn versao(&self) -> usize {
+        self.versao
+    }
+
     pub fn data_criacao(&self) -> &String {
         &self.data_criacao
     }
@@ -170,22 +149,38 @@ impl FromRow for ProxyPedido {
         let lanche_id: Option<i32> = row.get("lanche_id");
         let acompanhamento_id: Option<i32> = row.get("acompanhamento_id");
         let bebida_id: Option<i32> = row.get("bebida_id");
+        let lanche_preco: Option<f64> = row.get("lanche_preco");
+        let acompanhamento_preco: Option<f64> = row.get("acompanhamento_preco");
+        let bebida_preco: Option<f64> = row.get("bebida_preco");
+        let loja_id: i32 = row.get("loja_id");
+        let versao: i32 = row.get("versao");
         let data_criacao: std::time::SystemTime = row.get("data_criacao");
         let data_criacao: DateTime<Utc> = data_criacao.into();
         let data_atualizacao: std::time::SystemTime = row.get("data_atualizacao");
         let data_atualizacao: DateTime<Utc> = data_atualizacao.into();
 
-    ProxyPedido::new(
-            id as usize,
+        let dados = DadosDoPedido {
             cliente,
-            lanche_id.map(|id| id as usize),
-            acompanhamento_id.map(|id| id as usize),
-            bebida_id.map(|id| id as usize),
-            row.get("pagamento"),
-            row.get::<_, &str>("status").parse::<Status>().unwrap(),
+            lanche_id: lanche_id.map(|id| id as usize),
+            acompanhamento_id: acompanhamento_id.map(|id| id as usize),
+            bebida_id: bebida_id.map(|id| id as usize),
+            lanche_preco,
+            acompanhamento_preco,
+            bebida_preco,
+            desconto: row.get("desconto"),
+            pagamento: row.get("pagamento"),
+            status: row.get::<_, &str>("status").parse::<Status>().unwrap(),
+            loja_id: loja_id as usize,
+            versao: versao as usize,
+        };
+        let mut proxy = ProxyPedido::new(
+            id as usize,
+            dados,
             data_criacao.format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
             data_atualizacao.format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
-        )
+        );
+        proxy.set_documento_fiscal(row.get("documento_fiscal"));
+        proxy
     }
 
     fn try_from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
@@ -194,22 +189,38 @@ impl FromRow for ProxyPedido {
         let lanche_id: Option<i32> = row.try_get("lanche_id")?;
         let acompanhamento_id: Option<i32> = row.try_get("acompanhamento_id")?;
         let bebida_id: Option<i32> = row.try_get("bebida_id")?;
+        let lanche_preco: Option<f64> = row.try_get("lanche_preco")?;
+        let acompanhamento_preco: Option<f64> = row.try_get("acompanhamento_preco")?;
+        let bebida_preco: Option<f64> = row.try_get("bebida_preco")?;
+        let loja_id: i32 = row.try_get("loja_id")?;
+        let versao: i32 = row.try_get("versao")?;
 
         let data_criacao: std::time::SystemTime = row.try_get("data_criacao")?;
         let data_criacao: DateTime<Utc> = data_criacao.into();
         let data_atualizacao: std::time::SystemTime = row.try_get("data_atualizacao")?;
         let data_atualizacao: DateTime<Utc> = data_atualizacao.into();
 
-        Ok(ProxyPedido::new(
-            id as usize,
+        let dados = DadosDoPedido {
             cliente,
-            lanche_id.map(|id| id as usize),
-            acompanhamento_id.map(|id| id as usize),
-            bebida_id.map(|id| id as usize),
-            row.try_get("pagamento")?,
-            row.try_get::<_, &str>("status")?.parse::<Status>().unwrap(),
+            lanche_id: lanche_id.map(|id| id as usize),
+            acompanhamento_id: acompanhamento_id.map(|id| id as usize),
+            bebida_id: bebida_id.map(|id| id as usize),
+            lanche_preco,
+            acompanhamento_preco,
+            bebida_preco,
+            desconto: row.try_get("desconto")?,
+            pagamento: row.try_get("pagamento")?,
+            status: row.try_get::<_, &str>("status")?.parse::<Status>().unwrap(),
+            loja_id: loja_id as usize,
+            versao: versao as usize,
+        };
+        let mut proxy = ProxyPedido::new(
+            id as usize,
+            dados,
             data_criacao.format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
             data_atualizacao.format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
-        ))
+        );
+        proxy.set_documento_fiscal(row.try_get("documento_fiscal")?);
+        Ok(proxy)
     }
 }
@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use postgres_from_row::FromRow;
+
+use crate::entities::{
+    cpf::Cpf,
+    pontos::{MovimentoDePontos, TipoDeMovimento},
+};
+
+impl FromRow for MovimentoDePontos {
+    fn from_row(row: &tokio_postgres::Row) -> Self {
+        Self::try_from_row(row).unwrap()
+    }
+
+    fn try_from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        let id: i32 = row.try_get("id")?;
+        let pedido_id: i32 = row.try_get("pedido_id")?;
+        let validade: Option<std::time::SystemTime> = row.try_get("validade")?;
+        let validade: Option<DateTime<Utc>> = validade.map(|validade| validade.into());
+        let data_criacao: std::time::SystemTime = row.try_get("data_criacao")?;
+        let data_criacao: DateTime<Utc> = data_criacao.into();
+
+        Ok(MovimentoDePontos::new(
+            id as usize,
+            Cpf(row.try_get("cliente")?),
+            pedido_id as usize,
+            row.try_get::<_, &str>("tipo")?
+                .parse::<TipoDeMovimento>()
+                .unwrap(),
+            row.try_get("pontos")?,
+            validade.map(|validade| validade.format("%Y-%m-%d %H:%M:%S%.3f%z").to_string()),
+            data_criacao.format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
+        ))
+    }
+}
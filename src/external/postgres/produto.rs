@@ -1,96 +1,16 @@
 use chrono::{DateTime, Utc};
 use postgres_from_row::FromRow;
-use std::collections::HashMap;
 
-use crate::entities::produto::Categoria;
 use crate::entities::produto::Produto;
 use crate::entities::ingredientes::Ingredientes;
 
-use super::table::{ColumnDefault, ColumnNullable, ColumnTypes};
-
-pub fn get_produto_table_columns() -> HashMap<String, (ColumnTypes, ColumnNullable, ColumnDefault)>
-{
-    let mut columns = HashMap::new();
-    columns.insert(
-        "id".to_string(),
-        (
-            ColumnTypes::Index,
-            ColumnNullable(false),
-            ColumnDefault(None),
-        ),
-    );
-    columns.insert(
-        "nome".to_string(),
-        (
-            ColumnTypes::Text,
-            ColumnNullable(false),
-            ColumnDefault(None),
-        ),
-    );
-    columns.insert(
-        "foto".to_string(),
-        (
-            ColumnTypes::Text,
-            ColumnNullable(false),
-            ColumnDefault(None),
-        ),
-    );
-    columns.insert(
-        "descricao".to_string(),
-        (
-            ColumnTypes::Text,
-            ColumnNullable(false),
-            ColumnDefault(None),
-        ),
-    );
-    columns.insert(
-        "categoria".to_string(),
-        (
-            ColumnTypes::Text,
-            ColumnNullable(false),
-            ColumnDefault(None),
-        ),
-    );
-    columns.insert(
-        "preco".to_string(),
-        (
-            ColumnTypes::Float,
-            ColumnNullable(false),
-            ColumnDefault(None),
-        ),
-    );
-    columns.insert(
-        "ingredientes".to_string(),
-        (
-            ColumnTypes::Text,
-            ColumnNullable(false),
-            ColumnDefault(None),
-        ),
-    );
-    columns.insert(
-        "data_criacao".to_string(),
-        (
-            ColumnTypes::Timestamp,
-            ColumnNullable(true),
-            ColumnDefault(Some("CURRENT_TIMESTAMP".to_string())),
-        ),
-    );
-    columns.insert(
-        "data_atualizacao".to_string(),
-        (
-            ColumnTypes::Timestamp,
-            ColumnNullable(true),
-            ColumnDefault(Some("CURRENT_TIMESTAMP".to_string())),
-        ),
-    );
-
-    columns
-}
-
 impl FromRow for Produto {
     fn from_row(row: &tokio_postgres::Row) -> Self {
         let id: i32 = row.get("id");
+        let categoria_id: i32 = row.get("categoria_id");
         let preco: f64 = row.get("preco");
+        let loja_id: Option<i32> = row.get("loja_id");
+        let versao: i32 = row.get("versao");
 
         let lista_ingredientes: Vec<String> = row.get("ingredientes");
         let ingredientes = match Ingredientes::new(lista_ingredientes) {
@@ -102,24 +22,30 @@ impl FromRow for Produto {
         let data_atualizacao: std::time::SystemTime = row.get("data_atualizacao");
         let data_atualizacao: DateTime<Utc> = data_atualizacao.into();
 
-        Produto::new(
+        let mut produto = Produto::new(
             id as usize,
             row.get("nome"),
             row.get("foto"),
             row.get("descricao"),
-            row.get::<_, &str>("categoria").parse::<Categoria>().unwrap(),
+            categoria_id as usize,
             preco,
             ingredientes,
             data_criacao.format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
             data_atualizacao
                 .format("%Y-%m-%d %H:%M:%S%.3f%z")
                 .to_string(),
-        )
+        );
+        produto.set_loja_id(loja_id.map(|loja_id| loja_id as usize));
+        produto.set_versao(versao as usize);
+        produto
     }
 
     fn try_from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
         let id: i32 = row.try_get("id")?;
+        let categoria_id: i32 = row.try_get("categoria_id")?;
         let preco: f64 = row.try_get("preco")?;
+        let loja_id: Option<i32> = row.try_get("loja_id")?;
+        let versao: i32 = row.try_get("versao")?;
 
         let lista_ingredientes_json: tokio_postgres::types::Json<Vec<String>> =
             row.get("ingredientes");
@@ -129,16 +55,19 @@ impl FromRow for Produto {
             Err(e) => panic!("Failed to create Ingredientes: {:?}", e),
         };
 
-        Ok(Produto::new(
+        let mut produto = Produto::new(
             id as usize,
             row.try_get("nome")?,
             row.try_get("foto")?,
             row.try_get("descricao")?,
-            (row.try_get::<_, &str>("categoria")?).parse::<Categoria>().unwrap(),
+            categoria_id as usize,
             preco,
             ingredientes,
             row.get("data_criacao"),
             row.get("data_atualizacao"),
-        ))
+        );
+        produto.set_loja_id(loja_id.map(|loja_id| loja_id as usize));
+        produto.set_versao(versao as usize);
+        Ok(produto)
     }
 }
@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use postgres_from_row::FromRow;
+
+use crate::entities::{pedido::Status, transicao_de_status::TransicaoDeStatus};
+
+impl FromRow for TransicaoDeStatus {
+    fn from_row(row: &tokio_postgres::Row) -> Self {
+        Self::try_from_row(row).unwrap()
+    }
+
+    fn try_from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        let pedido_id: i32 = row.try_get("pedido_id")?;
+        let data_criacao: std::time::SystemTime = row.try_get("data_criacao")?;
+        let data_criacao: DateTime<Utc> = data_criacao.into();
+
+        Ok(TransicaoDeStatus::new(
+            pedido_id as usize,
+            row.try_get::<_, &str>("status")?
+                .parse::<Status>()
+                .unwrap_or(Status::Invalido),
+            data_criacao.format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
+        ))
+    }
+}
@@ -0,0 +1,14 @@
+use tracing_subscriber::{fmt, EnvFilter};
+
+// Logs estruturados em JSON; o nível vem de RUST_LOG (padrão: info).
+// Cada evento carrega os campos dos spans ativos, como request_id e pedido_id.
+pub fn inicializa() {
+    let filtro = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+
+    let _ = fmt()
+        .json()
+        .with_env_filter(filtro)
+        .with_current_span(true)
+        .with_span_list(true)
+        .try_init();
+}
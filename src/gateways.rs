@@ -2,3 +2,17 @@ pub mod postgres_pedido_gateway;
 pub mod in_memory_pedido_gateway;
 pub mod postgres_produto_gateway;
 pub mod in_memory_produto_gateway;
+pub mod in_memory_categoria_gateway;
+pub mod postgres_categoria_gateway;
+pub mod in_memory_agenda_gateway;
+pub mod postgres_agenda_gateway;
+pub mod in_memory_loja_gateway;
+pub mod postgres_loja_gateway;
+pub mod in_memory_idempotencia_gateway;
+pub mod postgres_idempotencia_gateway;
+pub mod in_memory_cliente_gateway;
+pub mod postgres_cliente_gateway;
+pub mod in_memory_pontos_gateway;
+pub mod postgres_pontos_gateway;
+pub mod in_memory_auditoria_gateway;
+pub mod postgres_auditoria_gateway;
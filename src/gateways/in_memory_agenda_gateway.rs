@@ -0,0 +1,114 @@
+use chrono::Utc;
+use std::sync::RwLock;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::agenda::{Agenda, DadosDaAgenda};
+use crate::traits::agenda_gateway::AgendaGateway;
+
+pub struct InMemoryAgendaRepository {
+    _agendas: RwLock<Vec<Agenda>>,
+}
+
+impl InMemoryAgendaRepository {
+    pub fn new() -> Self {
+        InMemoryAgendaRepository {
+            _agendas: RwLock::new(vec![]),
+        }
+    }
+}
+
+impl Default for InMemoryAgendaRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl AgendaGateway for InMemoryAgendaRepository {
+    async fn get_agendas(&self) -> Result<Vec<Agenda>, DomainError> {
+        Ok(self._agendas.read().unwrap().clone())
+    }
+
+    async fn get_agenda_by_id(&self, id: usize) -> Result<Agenda, DomainError> {
+        self._agendas
+            .read()
+            .unwrap()
+            .iter()
+            .find(|agenda| *agenda.id() == id)
+            .cloned()
+            .ok_or(DomainError::NotFound)
+    }
+
+    async fn create_agenda(&self, agenda: Agenda) -> Result<Agenda, DomainError> {
+        let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
+        let mut agendas = self._agendas.write().unwrap();
+        let id = agendas.iter().map(|a| *a.id()).max().unwrap_or(0) + 1;
+
+        let nova_agenda = Agenda::new(
+            id,
+            DadosDaAgenda {
+                alvo: agenda.alvo().clone(),
+                alvo_id: *agenda.alvo_id(),
+                dias_semana: agenda.dias_semana().clone(),
+                hora_inicio: agenda.hora_inicio().clone(),
+                hora_fim: agenda.hora_fim().clone(),
+                preco: agenda.preco(),
+            },
+            _now.clone(),
+            _now,
+        );
+        agendas.push(nova_agenda.clone());
+        Ok(nova_agenda)
+    }
+
+    async fn delete_agenda(&self, id: usize) -> Result<(), DomainError> {
+        let mut agendas = self._agendas.write().unwrap();
+        let tamanho = agendas.len();
+        agendas.retain(|agenda| *agenda.id() != id);
+        match agendas.len() == tamanho {
+            true => Err(DomainError::NotFound),
+            false => Ok(()),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::agenda::AlvoAgenda;
+
+    #[tokio::test]
+    async fn test_adds_and_deletes() {
+        let agenda_repository = InMemoryAgendaRepository::new();
+        let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
+
+        let agenda = agenda_repository
+            .create_agenda(Agenda::new(
+                0,
+                DadosDaAgenda {
+                    alvo: AlvoAgenda::Categoria,
+                    alvo_id: 2,
+                    dias_semana: vec![0, 6],
+                    hora_inicio: "17:00".to_string(),
+                    hora_fim: "19:00".to_string(),
+                    preco: None,
+                },
+                _now.clone(),
+                _now,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(agenda.id(), &1);
+        assert_eq!(agenda_repository.get_agendas().await.unwrap().len(), 1);
+
+        agenda_repository.delete_agenda(1).await.unwrap();
+
+        assert!(matches!(
+            agenda_repository.get_agenda_by_id(1).await,
+            Err(DomainError::NotFound)
+        ));
+        assert!(agenda_repository.delete_agenda(1).await.is_err());
+    }
+}
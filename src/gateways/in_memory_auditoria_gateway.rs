@@ -0,0 +1,44 @@
+use chrono::Utc;
+use std::sync::RwLock;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::auditoria::RegistroDeAuditoria;
+use crate::traits::auditoria_gateway::AuditoriaGateway;
+
+pub struct InMemoryAuditoriaRepository {
+    _registros: RwLock<Vec<RegistroDeAuditoria>>,
+}
+
+impl InMemoryAuditoriaRepository {
+    pub fn new() -> Self {
+        tracing::info!("Usando repositório em memória");
+
+        InMemoryAuditoriaRepository {
+            _registros: RwLock::new(vec![]),
+        }
+    }
+}
+
+impl Default for InMemoryAuditoriaRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl AuditoriaGateway for InMemoryAuditoriaRepository {
+    async fn registra(&self, registro: RegistroDeAuditoria) -> Result<RegistroDeAuditoria, DomainError> {
+        let mut registros = self._registros.write().unwrap();
+        let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
+        let novo_registro = RegistroDeAuditoria::new(
+            registros.len() + 1,
+            registro.acao().clone(),
+            registro.titular().clone(),
+            registro.request_id().clone(),
+            _now,
+        );
+        registros.push(novo_registro.clone());
+        Ok(novo_registro)
+    }
+}
+
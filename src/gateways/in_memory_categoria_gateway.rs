@@ -0,0 +1,204 @@
+use chrono::Utc;
+use std::sync::RwLock;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::categoria::{Categoria, DadosDaCategoria, ItemPedido};
+use crate::traits::categoria_gateway::CategoriaGateway;
+
+pub struct InMemoryCategoriaRepository {
+    _categorias: RwLock<Vec<Categoria>>,
+}
+
+impl InMemoryCategoriaRepository {
+    pub fn new() -> Self {
+        let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
+
+        let categorias = vec![
+            ("Lanche", 1, Some(ItemPedido::Lanche)),
+            ("Bebida", 2, Some(ItemPedido::Bebida)),
+            ("Acompanhamento", 3, Some(ItemPedido::Acompanhamento)),
+            ("Sobremesa", 4, None),
+        ]
+        .into_iter()
+        .map(|(nome, id, item_pedido)| {
+            Categoria::new(
+                id,
+                DadosDaCategoria {
+                    nome: nome.to_string(),
+                    icone: None,
+                    ordem: id,
+                    ativa: true,
+                    item_pedido,
+                },
+                _now.clone(),
+                _now.clone(),
+            )
+        })
+        .collect();
+
+        tracing::info!("Usando repositório em memória");
+
+        InMemoryCategoriaRepository {
+            _categorias: RwLock::new(categorias),
+        }
+    }
+}
+
+impl Default for InMemoryCategoriaRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl CategoriaGateway for InMemoryCategoriaRepository {
+    async fn get_categorias(&self) -> Result<Vec<Categoria>, DomainError> {
+        let mut categorias = self._categorias.read().unwrap().clone();
+        categorias.sort_by_key(|categoria| categoria.ordem());
+        Ok(categorias)
+    }
+
+    async fn get_categoria_by_id(&self, id: usize) -> Result<Categoria, DomainError> {
+        let categorias = self._categorias.read().unwrap();
+        categorias
+            .iter()
+            .find(|categoria| *categoria.id() == id)
+            .cloned()
+            .ok_or(DomainError::NotFound)
+    }
+
+    async fn create_categoria(&self, categoria: Categoria) -> Result<Categoria, DomainError> {
+        let mut categorias = self._categorias.write().unwrap();
+        if categorias.iter().any(|c| c.nome() == categoria.nome()) {
+            return Err(DomainError::AlreadyExists);
+        }
+        let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
+        let id = categorias.iter().map(|c| *c.id()).max().unwrap_or(0) + 1;
+
+        let nova_categoria = Categoria::new(
+            id,
+            DadosDaCategoria {
+                nome: categoria.nome().to_string(),
+                icone: categoria.icone().cloned(),
+                ordem: categoria.ordem(),
+                ativa: categoria.ativa(),
+                item_pedido: categoria.item_pedido().cloned(),
+            },
+            _now.clone(),
+            _now,
+        );
+        categorias.push(nova_categoria.clone());
+        Ok(nova_categoria)
+    }
+
+    async fn update_categoria(&self, categoria: Categoria) -> Result<Categoria, DomainError> {
+        let mut categorias = self._categorias.write().unwrap();
+        if categorias
+            .iter()
+            .any(|c| c.nome() == categoria.nome() && c.id() != categoria.id())
+        {
+            return Err(DomainError::AlreadyExists);
+        }
+        for current in categorias.iter_mut() {
+            if current.id() == categoria.id() {
+                *current = categoria.clone();
+                return Ok(categoria);
+            }
+        }
+        Err(DomainError::NotFound)
+    }
+
+    async fn delete_categoria(&self, id: usize) -> Result<(), DomainError> {
+        let mut categorias = self._categorias.write().unwrap();
+        let tamanho = categorias.len();
+        categorias.retain(|categoria| *categoria.id() != id);
+        match categorias.len() == tamanho {
+            true => Err(DomainError::NotFound),
+            false => Ok(()),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_categoria(nome: &str, ordem: usize) -> Categoria {
+        let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
+        Categoria::new(
+            0,
+            DadosDaCategoria {
+                nome: nome.to_string(),
+                icone: None,
+                ordem,
+                ativa: true,
+                item_pedido: None,
+            },
+            _now.clone(),
+            _now,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_initiates_categorias() {
+        let categoria_repository = InMemoryCategoriaRepository::new();
+
+        let categorias = categoria_repository.get_categorias().await.unwrap();
+
+        assert_eq!(categorias.len(), 4);
+        assert_eq!(categorias[0].nome(), "Lanche");
+
+        let categoria = categoria_repository.get_categoria_by_id(4).await.unwrap();
+
+        assert_eq!(categoria.nome(), "Sobremesa");
+        assert_eq!(categoria.item_pedido(), None);
+    }
+
+    #[tokio::test]
+    async fn test_adds_and_orders() {
+        let categoria_repository = InMemoryCategoriaRepository::new();
+
+        let categoria = categoria_repository
+            .create_categoria(create_categoria("Café da manhã", 0))
+            .await
+            .unwrap();
+
+        assert_eq!(categoria.id(), &5);
+
+        let categorias = categoria_repository.get_categorias().await.unwrap();
+
+        assert_eq!(categorias.len(), 5);
+        assert_eq!(categorias[0].nome(), "Café da manhã");
+    }
+
+    #[tokio::test]
+    async fn test_rejects_duplicated_nome() {
+        let categoria_repository = InMemoryCategoriaRepository::new();
+
+        let result = categoria_repository
+            .create_categoria(create_categoria("Bebida", 9))
+            .await;
+
+        assert!(matches!(result, Err(DomainError::AlreadyExists)));
+    }
+
+    #[tokio::test]
+    async fn test_updates_and_deletes() {
+        let categoria_repository = InMemoryCategoriaRepository::new();
+
+        let mut categoria = categoria_repository.get_categoria_by_id(2).await.unwrap();
+        categoria.set_ativa(false);
+
+        let categoria = categoria_repository.update_categoria(categoria).await.unwrap();
+
+        assert!(!categoria.ativa());
+
+        categoria_repository.delete_categoria(2).await.unwrap();
+
+        let result = categoria_repository.get_categoria_by_id(2).await;
+
+        assert!(matches!(result, Err(DomainError::NotFound)));
+        assert!(categoria_repository.delete_categoria(2).await.is_err());
+    }
+}
@@ -0,0 +1,109 @@
+use chrono::Utc;
+use std::sync::RwLock;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::{cliente::Cliente, cpf::Cpf};
+use crate::traits::cliente_gateway::ClienteGateway;
+
+pub struct InMemoryClienteRepository {
+    _clientes: RwLock<Vec<Cliente>>,
+}
+
+impl InMemoryClienteRepository {
+    pub fn new() -> Self {
+        tracing::info!("Usando repositório em memória");
+
+        InMemoryClienteRepository {
+            _clientes: RwLock::new(vec![]),
+        }
+    }
+}
+
+impl Default for InMemoryClienteRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ClienteGateway for InMemoryClienteRepository {
+    async fn get_cliente_by_cpf(&self, cpf: &Cpf) -> Result<Cliente, DomainError> {
+        self._clientes
+            .read()
+            .unwrap()
+            .iter()
+            .find(|cliente| cliente.cpf() == cpf)
+            .cloned()
+            .ok_or(DomainError::NotFound)
+    }
+
+    async fn create_cliente(&self, cliente: Cliente) -> Result<Cliente, DomainError> {
+        let mut clientes = self._clientes.write().unwrap();
+        if clientes.iter().any(|atual| atual.cpf() == cliente.cpf()) {
+            return Err(DomainError::AlreadyExists);
+        }
+
+        let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
+        let novo_cliente = Cliente::new(
+            cliente.cpf().clone(),
+            cliente.nome().clone(),
+            cliente.email().cloned(),
+            cliente.aceita_marketing(),
+            cliente.aceita_compartilhamento(),
+            _now.clone(),
+            _now,
+        );
+        clientes.push(novo_cliente.clone());
+        Ok(novo_cliente)
+    }
+
+    async fn update_cliente(&self, cliente: Cliente) -> Result<Cliente, DomainError> {
+        for current in self._clientes.write().unwrap().iter_mut() {
+            if current.cpf() == cliente.cpf() {
+                *current = cliente.clone();
+                return Ok(cliente);
+            }
+        }
+        Err(DomainError::NotFound)
+    }
+
+    async fn delete_cliente(&self, cpf: &Cpf) -> Result<(), DomainError> {
+        let mut clientes = self._clientes.write().unwrap();
+        let quantidade = clientes.len();
+        clientes.retain(|cliente| cliente.cpf() != cpf);
+        if clientes.len() == quantidade {
+            return Err(DomainError::NotFound);
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_create_and_get_cliente() {
+        let cliente_repository = InMemoryClienteRepository::new();
+        let cpf = Cpf::new("123.456.789-09".to_string()).unwrap();
+
+        assert!(matches!(
+            cliente_repository.get_cliente_by_cpf(&cpf).await,
+            Err(DomainError::NotFound)
+        ));
+
+        let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
+        let cliente = Cliente::new(cpf.clone(), "Maria".to_string(), None, true, false, _now.clone(), _now);
+        cliente_repository.create_cliente(cliente.clone()).await.unwrap();
+
+        let cadastrado = cliente_repository.get_cliente_by_cpf(&cpf).await.unwrap();
+        assert_eq!(cadastrado.nome(), "Maria");
+        assert!(cadastrado.aceita_marketing());
+
+        assert!(matches!(
+            cliente_repository.create_cliente(cliente).await,
+            Err(DomainError::AlreadyExists)
+        ));
+    }
+}
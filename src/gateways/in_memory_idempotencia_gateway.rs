@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use crate::base::domain_error::DomainError;
+use crate::entities::{idempotencia::RegistroDeIdempotencia, pedido::Pedido};
+use crate::traits::idempotencia_gateway::IdempotenciaGateway;
+
+pub struct InMemoryIdempotenciaRepository {
+    janela: Duration,
+    _registros: RwLock<HashMap<String, (RegistroDeIdempotencia, Instant)>>,
+}
+
+impl InMemoryIdempotenciaRepository {
+    pub fn new(janela: Duration) -> Self {
+        tracing::info!("Usando repositório em memória");
+
+        InMemoryIdempotenciaRepository {
+            janela,
+            _registros: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl IdempotenciaGateway for InMemoryIdempotenciaRepository {
+    async fn reserva(&self, chave: String, impressao_digital: String) -> Result<Option<RegistroDeIdempotencia>, DomainError> {
+        let mut registros = self._registros.write().unwrap();
+        registros.retain(|_, (_, criado_em)| criado_em.elapsed() < self.janela);
+        match registros.get(&chave) {
+            Some((registro, _)) => Ok(Some(registro.clone())),
+            None => {
+                let registro = RegistroDeIdempotencia::new(chave.clone(), impressao_digital, None);
+                registros.insert(chave, (registro, Instant::now()));
+                Ok(None)
+            }
+        }
+    }
+
+    async fn conclui(&self, chave: String, pedido: Pedido) -> Result<(), DomainError> {
+        match self._registros.write().unwrap().get_mut(&chave) {
+            Some((registro, _)) => {
+                registro.set_pedido(pedido);
+                Ok(())
+            }
+            None => Err(DomainError::NotFound),
+        }
+    }
+
+    async fn libera(&self, chave: String) -> Result<(), DomainError> {
+        let mut registros = self._registros.write().unwrap();
+        if registros.get(&chave).is_some_and(|(registro, _)| registro.pedido().is_none()) {
+            registros.remove(&chave);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::pedido::Status;
+
+    fn pedido() -> Pedido {
+        Pedido::new(
+            7,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Status::Pendente,
+            "2024-01-17".to_string(),
+            "2024-01-17".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_reserva_e_conclui() {
+        let repository = InMemoryIdempotenciaRepository::new(Duration::from_secs(60));
+
+        let reserva = repository.reserva("chave".to_string(), "abc".to_string()).await.unwrap();
+        assert!(reserva.is_none());
+
+        let em_andamento = repository.reserva("chave".to_string(), "abc".to_string()).await.unwrap().unwrap();
+        assert!(em_andamento.pedido().is_none());
+
+        repository.conclui("chave".to_string(), pedido()).await.unwrap();
+        let concluido = repository.reserva("chave".to_string(), "abc".to_string()).await.unwrap().unwrap();
+        assert_eq!(concluido.impressao_digital(), "abc");
+        assert_eq!(*concluido.pedido().unwrap().id(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_libera() {
+        let repository = InMemoryIdempotenciaRepository::new(Duration::from_secs(60));
+
+        repository.reserva("chave".to_string(), "abc".to_string()).await.unwrap();
+        repository.libera("chave".to_string()).await.unwrap();
+
+        let reserva = repository.reserva("chave".to_string(), "abc".to_string()).await.unwrap();
+        assert!(reserva.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_chave_expirada_pode_ser_reutilizada() {
+        let repository = InMemoryIdempotenciaRepository::new(Duration::ZERO);
+
+        repository.reserva("chave".to_string(), "abc".to_string()).await.unwrap();
+        repository.conclui("chave".to_string(), pedido()).await.unwrap();
+
+        let reserva = repository.reserva("chave".to_string(), "outra".to_string()).await.unwrap();
+        assert!(reserva.is_none());
+    }
+}
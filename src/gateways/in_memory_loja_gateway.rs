@@ -0,0 +1,102 @@
+use chrono::Utc;
+use std::sync::RwLock;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::loja::{Loja, LOJA_PADRAO};
+use crate::traits::loja_gateway::LojaGateway;
+
+pub struct InMemoryLojaRepository {
+    _lojas: RwLock<Vec<Loja>>,
+}
+
+impl InMemoryLojaRepository {
+    pub fn new() -> Self {
+        let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
+
+        let loja_principal = Loja::new(
+            LOJA_PADRAO,
+            "Loja Principal".to_string(),
+            true,
+            _now.clone(),
+            _now,
+        );
+
+        tracing::info!("Usando repositório em memória");
+
+        InMemoryLojaRepository {
+            _lojas: RwLock::new(vec![loja_principal]),
+        }
+    }
+}
+
+impl Default for InMemoryLojaRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl LojaGateway for InMemoryLojaRepository {
+    async fn get_lojas(&self) -> Result<Vec<Loja>, DomainError> {
+        Ok(self._lojas.read().unwrap().clone())
+    }
+
+    async fn get_loja_by_id(&self, id: usize) -> Result<Loja, DomainError> {
+        self._lojas
+            .read()
+            .unwrap()
+            .iter()
+            .find(|loja| *loja.id() == id)
+            .cloned()
+            .ok_or(DomainError::NotFound)
+    }
+
+    async fn create_loja(&self, loja: Loja) -> Result<Loja, DomainError> {
+        let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
+        let mut lojas = self._lojas.write().unwrap();
+        let id = lojas.iter().map(|l| *l.id()).max().unwrap_or(0) + 1;
+
+        let nova_loja = Loja::new(id, loja.nome().to_string(), loja.ativa(), _now.clone(), _now);
+        lojas.push(nova_loja.clone());
+        Ok(nova_loja)
+    }
+
+    async fn update_loja(&self, loja: Loja) -> Result<Loja, DomainError> {
+        for current in self._lojas.write().unwrap().iter_mut() {
+            if current.id() == loja.id() {
+                *current = loja.clone();
+                return Ok(loja);
+            }
+        }
+        Err(DomainError::NotFound)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_initiates_loja_principal_and_adds() {
+        let loja_repository = InMemoryLojaRepository::new();
+
+        let loja = loja_repository.get_loja_by_id(LOJA_PADRAO).await.unwrap();
+
+        assert_eq!(loja.nome(), "Loja Principal");
+        assert!(loja.ativa());
+
+        let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
+        let loja = loja_repository
+            .create_loja(Loja::new(0, "Shopping".to_string(), true, _now.clone(), _now))
+            .await
+            .unwrap();
+
+        assert_eq!(loja.id(), &2);
+        assert_eq!(loja_repository.get_lojas().await.unwrap().len(), 2);
+        assert!(matches!(
+            loja_repository.get_loja_by_id(3).await,
+            Err(DomainError::NotFound)
+        ));
+    }
+}
@@ -1,18 +1,23 @@
-use chrono::Utc;
-use tokio::time::{sleep, Duration};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::RwLock;
 
 use crate::base::domain_error::DomainError;
+use crate::base::paginacao::{Pagina, Paginacao};
+use crate::entities::cpf::Cpf;
 use crate::entities::pedido::{Pedido, Status};
-use crate::entities::produto::{Categoria, Produto};
+use crate::entities::produto::Produto;
+use crate::entities::transicao_de_status::TransicaoDeStatus;
 
-use crate::entities::cpf::Cpf;
 use crate::entities::ingredientes::Ingredientes;
 
 use crate::traits::pedido_gateway::PedidoGateway;
 
-#[derive(Clone)]
 pub struct InMemoryPedidoRepository {
-    _pedidos: Vec<Pedido>,
+    _pedidos: RwLock<Vec<Pedido>>,
+    _transicoes: RwLock<Vec<TransicaoDeStatus>>,
+    // Pseudônimo do titular por pedido anonimizado, como a coluna cliente_pseudonimo.
+    _pseudonimos: RwLock<HashMap<usize, String>>,
 }
 
 impl InMemoryPedidoRepository {
@@ -24,7 +29,7 @@ impl InMemoryPedidoRepository {
             "Cheeseburger".to_string(),
             "cheeseburger.png".to_string(),
             "O clássico pão, carne e queijo!".to_string(),
-            Categoria::Lanche,
+            1,
             9.99,
             Ingredientes::new(vec![
                 "Pão".to_string(),
@@ -38,7 +43,7 @@ impl InMemoryPedidoRepository {
 
         let pedido = Pedido::new(
             1,
-            Some(Cpf::new("000.000.000-00".to_string()).unwrap()),
+            None,
             Some(lanche),
             None,
             None,
@@ -49,47 +54,71 @@ impl InMemoryPedidoRepository {
         );
 
 
-        println!("Usando repositório em memória!");
+        tracing::info!("Usando repositório em memória");
 
-        InMemoryPedidoRepository {
-            _pedidos: vec![pedido],
-        }
+        let repositorio = InMemoryPedidoRepository {
+            _pedidos: RwLock::new(vec![]),
+            _transicoes: RwLock::new(vec![]),
+            _pseudonimos: RwLock::new(HashMap::new()),
+        };
+        repositorio.registra_transicao(&pedido);
+        repositorio._pedidos.write().unwrap().push(pedido);
+        repositorio
+    }
+
+    fn registra_transicao(&self, pedido: &Pedido) {
+        self._transicoes.write().unwrap().push(TransicaoDeStatus::new(
+            *pedido.id(),
+            pedido.status().clone(),
+            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
+        ));
     }
 }
 
 #[async_trait]
 impl PedidoGateway for InMemoryPedidoRepository {
-    async fn lista_pedidos(&mut self) -> Result<Vec<Pedido>, DomainError> {
-        Ok(self._pedidos.clone())
+    async fn lista_pedidos(&self, loja_id: usize) -> Result<Vec<Pedido>, DomainError> {
+        Ok(self
+            ._pedidos
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|pedido| *pedido.loja_id() == loja_id)
+            .cloned()
+            .collect())
     }
 
-    async fn get_pedidos_novos(&self) -> Result<Vec<Pedido>, DomainError> {
+    async fn get_pedidos_novos(&self, loja_id: usize) -> Result<Vec<Pedido>, DomainError> {
         let mut pedidos: Vec<Pedido> = Vec::new();
-        for pedido in &self._pedidos {
-            if *pedido.status() == Status::Pendente {
+        for pedido in self._pedidos.read().unwrap().iter() {
+            if *pedido.status() == Status::Pendente && *pedido.loja_id() == loja_id {
                 pedidos.push(pedido.clone());
             }
         }
-        sleep(Duration::from_secs(1)).await;
         Ok(pedidos)
     }
 
-    async fn atualiza_status(&mut self, id: usize, status: Status) -> Result<Pedido, DomainError> {
-        let pedidos = &mut self._pedidos;
+    async fn atualiza_status(&self, id: usize, status: Status, versao: usize) -> Result<Pedido, DomainError> {
+        let mut pedidos = self._pedidos.write().unwrap();
         if status == Status::Invalido {
             return Err::<Pedido, _>(DomainError::Invalid("status".to_string()));
         }
         for pedido in pedidos.iter_mut() {
             if *pedido.id() == id {
+                if pedido.versao() != versao {
+                    return Err(DomainError::Conflict);
+                }
                 pedido.set_status(status.clone());
+                pedido.set_versao(versao + 1);
+                self.registra_transicao(pedido);
                 return Ok(pedido.clone());
             }
         }
         Err(DomainError::NotFound)
     }
 
-    async fn atualiza_pagamento_status(&mut self, id: usize, pagamento_id: String, status: Status) -> Result<Pedido, DomainError> {
-        let pedidos = &mut self._pedidos;
+    async fn atualiza_pagamento_status(&self, id: usize, pagamento_id: String, status: Status) -> Result<Pedido, DomainError> {
+        let mut pedidos = self._pedidos.write().unwrap();
         if status == Status::Invalido {
             return Err::<Pedido, _>(DomainError::Invalid("status".to_string()));
         }
@@ -97,20 +126,65 @@ impl PedidoGateway for InMemoryPedidoRepository {
             if *pedido.id() == id {
                 pedido.set_status(status.clone());
                 pedido.set_pagamento(pagamento_id.clone());
+                pedido.set_versao(pedido.versao() + 1);
+                self.registra_transicao(pedido);
                 return Ok(pedido.clone());
             }
         }
         Err(DomainError::NotFound)
     }
 
-    async fn create_pedido(&mut self, pedido: Pedido) -> Result<Pedido, DomainError> {
-        let pedidos = &mut self._pedidos;
+    async fn aplica_desconto(&self, id: usize, desconto: f64, versao: usize) -> Result<Pedido, DomainError> {
+        let mut pedidos = self._pedidos.write().unwrap();
+        for pedido in pedidos.iter_mut() {
+            if *pedido.id() == id {
+                if pedido.versao() != versao {
+                    return Err(DomainError::Conflict);
+                }
+                pedido.set_desconto(desconto);
+                pedido.set_versao(versao + 1);
+                return Ok(pedido.clone());
+            }
+        }
+        Err(DomainError::NotFound)
+    }
+
+    async fn anonimiza_pedidos_do_cliente(
+        &self,
+        cpf: &Cpf,
+        pseudonimo: &str,
+    ) -> Result<usize, DomainError> {
+        let mut anonimizados = 0;
+        let mut pseudonimos = self._pseudonimos.write().unwrap();
+        for pedido in self._pedidos.write().unwrap().iter_mut() {
+            let do_cliente = pedido.cliente() == Some(cpf);
+            let na_nota = pedido
+                .documento_fiscal()
+                .is_some_and(|documento| documento.cpf().as_ref() == Some(cpf));
+            if do_cliente {
+                pedido.set_cliente(None);
+                pseudonimos.insert(*pedido.id(), pseudonimo.to_string());
+            }
+            if na_nota {
+                pedido.set_documento_fiscal(None);
+            }
+            if do_cliente || na_nota {
+                pedido.set_versao(pedido.versao() + 1);
+                anonimizados += 1;
+            }
+        }
+        Ok(anonimizados)
+    }
+
+    async fn create_pedido(&self, pedido: Pedido) -> Result<Pedido, DomainError> {
+        let mut pedidos = self._pedidos.write().unwrap();
+        self.registra_transicao(&pedido);
         pedidos.push(pedido.clone());
         Ok(pedido)
     }
 
     async fn get_pedido_by_id(&self, pedido_id: usize) -> Result<Pedido, DomainError> {
-        let pedidos = &self._pedidos;
+        let pedidos = self._pedidos.read().unwrap();
         for pedido in pedidos.iter() {
             if *pedido.id() == pedido_id {
                 return Ok(pedido.clone());
@@ -118,6 +192,124 @@ impl PedidoGateway for InMemoryPedidoRepository {
         }
         Err(DomainError::NotFound)
     }
+
+    async fn get_pedido_da_loja(&self, pedido_id: usize, loja_id: usize) -> Result<Pedido, DomainError> {
+        let pedidos = self._pedidos.read().unwrap();
+        pedidos
+            .iter()
+            .find(|pedido| *pedido.id() == pedido_id && *pedido.loja_id() == loja_id)
+            .cloned()
+            .ok_or(DomainError::NotFound)
+    }
+
+    async fn lista_pedidos_do_cliente(
+        &self,
+        cpf: &Cpf,
+        paginacao: Paginacao,
+    ) -> Result<Pagina<Pedido>, DomainError> {
+        let mut pedidos: Vec<Pedido> = self
+            ._pedidos
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|pedido| pedido.cliente() == Some(cpf))
+            .cloned()
+            .collect();
+        pedidos.sort_by(|a, b| {
+            b.data_criacao()
+                .cmp(a.data_criacao())
+                .then_with(|| b.id().cmp(a.id()))
+        });
+        Ok(paginacao.aplica(pedidos))
+    }
+
+    async fn pedidos_do_titular(&self, cpf: &Cpf) -> Result<Vec<Pedido>, DomainError> {
+        let mut pedidos: Vec<Pedido> = self
+            ._pedidos
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|pedido| {
+                pedido.cliente() == Some(cpf)
+                    || pedido
+                        .documento_fiscal()
+                        .is_some_and(|documento| documento.cpf().as_ref() == Some(cpf))
+            })
+            .cloned()
+            .collect();
+        pedidos.sort_by(|a, b| {
+            b.data_criacao()
+                .cmp(a.data_criacao())
+                .then_with(|| b.id().cmp(a.id()))
+        });
+        Ok(pedidos)
+    }
+
+    async fn conta_pedidos_por_status(&self) -> Result<Vec<(Status, usize)>, DomainError> {
+        let mut contagem: Vec<(Status, usize)> = Vec::new();
+        for pedido in self._pedidos.read().unwrap().iter() {
+            match contagem.iter_mut().find(|(status, _)| status == pedido.status()) {
+                Some((_, quantidade)) => *quantidade += 1,
+                None => contagem.push((pedido.status().clone(), 1)),
+            }
+        }
+        Ok(contagem)
+    }
+
+    async fn pedidos_do_periodo(
+        &self,
+        loja_id: usize,
+        inicio: DateTime<Utc>,
+        fim: DateTime<Utc>,
+    ) -> Result<Vec<Pedido>, DomainError> {
+        let mut pedidos: Vec<(DateTime<Utc>, Pedido)> = self
+            ._pedidos
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|pedido| *pedido.loja_id() == loja_id)
+            .filter_map(|pedido| {
+                let criacao = DateTime::parse_from_str(pedido.data_criacao(), "%Y-%m-%d %H:%M:%S%.3f%z").ok()?;
+                Some((criacao.with_timezone(&Utc), pedido.clone()))
+            })
+            .filter(|(criacao, _)| *criacao >= inicio && *criacao < fim)
+            .collect();
+        pedidos.sort_by(|(a, pedido_a), (b, pedido_b)| a.cmp(b).then(pedido_a.id().cmp(pedido_b.id())));
+        Ok(pedidos.into_iter().map(|(_, pedido)| pedido).collect())
+    }
+
+    async fn transicoes_do_pedido(&self, pedido_id: usize) -> Result<Vec<TransicaoDeStatus>, DomainError> {
+        Ok(self
+            ._transicoes
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|transicao| *transicao.pedido_id() == pedido_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn transicoes_do_periodo(
+        &self,
+        loja_id: usize,
+        inicio: DateTime<Utc>,
+        fim: DateTime<Utc>,
+    ) -> Result<Vec<TransicaoDeStatus>, DomainError> {
+        let pedidos: Vec<usize> = self
+            .pedidos_do_periodo(loja_id, inicio, fim)
+            .await?
+            .iter()
+            .map(|pedido| *pedido.id())
+            .collect();
+        Ok(self
+            ._transicoes
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|transicao| pedidos.contains(transicao.pedido_id()))
+            .cloned()
+            .collect())
+    }
 }
 
 unsafe impl Sync for InMemoryPedidoRepository {}
@@ -126,12 +318,13 @@ unsafe impl Send for InMemoryPedidoRepository {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::entities::documento_fiscal::DocumentoFiscal;
 
     #[tokio::test]
     async fn test_initiates_pedidos() {
-        let mut pedido_repository = InMemoryPedidoRepository::new();
+        let pedido_repository = InMemoryPedidoRepository::new();
 
-        let pedidos = pedido_repository.lista_pedidos().await.unwrap();
+        let pedidos = pedido_repository.lista_pedidos(1).await.unwrap();
 
         assert_eq!(pedidos.len(), 1);
 
@@ -140,16 +333,59 @@ mod tests {
         assert_eq!(pedido.id(), &1);
     }
 
+    #[tokio::test]
+    async fn test_conta_pedidos_por_status() {
+        let pedido_repository = InMemoryPedidoRepository::new();
+        pedido_repository
+            .create_pedido(Pedido::new(
+                2,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Status::Pendente,
+                "2024-01-17".to_string(),
+                "2024-01-17".to_string(),
+            ))
+            .await
+            .unwrap();
+        pedido_repository.atualiza_status(1, Status::Pago, 1).await.unwrap();
+
+        let contagem = pedido_repository.conta_pedidos_por_status().await.unwrap();
+
+        assert_eq!(contagem, vec![(Status::Pago, 1), (Status::Pendente, 1)]);
+    }
+
+    #[tokio::test]
+    async fn test_registra_transicoes_de_status() {
+        let pedido_repository = InMemoryPedidoRepository::new();
+        pedido_repository.atualiza_status(1, Status::Pago, 1).await.unwrap();
+        pedido_repository.atualiza_status(1, Status::EmPreparacao, 2).await.unwrap();
+        // Versão desatualizada: nada muda nem é registrado.
+        assert!(pedido_repository.atualiza_status(1, Status::Pronto, 2).await.is_err());
+
+        let status: Vec<Status> = pedido_repository
+            .transicoes_do_pedido(1)
+            .await
+            .unwrap()
+            .iter()
+            .map(|transicao| transicao.status().clone())
+            .collect();
+        assert_eq!(status, vec![Status::Pendente, Status::Pago, Status::EmPreparacao]);
+        assert!(pedido_repository.transicoes_do_pedido(2).await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_adds_and_retrieves() {
-        let mut pedido_repository = InMemoryPedidoRepository::new();
+        let pedido_repository = InMemoryPedidoRepository::new();
 
         let lanche = Produto::new(
             2,
             "Cheeseburger".to_string(),
             "cheeseburger.png".to_string(),
             "O clássico pão, carne e queijo!".to_string(),
-            Categoria::Lanche,
+            1,
             9.99,
             Ingredientes::new(vec![
                 "Pão".to_string(),
@@ -163,7 +399,7 @@ mod tests {
 
         let pedido = Pedido::new(
             2,
-            Some(Cpf::new("000.000.000-00".to_string()).unwrap()),
+            Some(Cpf::new("123.456.789-09".to_string()).unwrap()),
             Some(lanche),
             None,
             None,
@@ -175,7 +411,7 @@ mod tests {
 
         pedido_repository.create_pedido(pedido.clone()).await.unwrap();
 
-        let pedidos = pedido_repository.lista_pedidos().await.unwrap();
+        let pedidos = pedido_repository.lista_pedidos(1).await.unwrap();
 
         assert_eq!(pedidos.len(), 2);
 
@@ -186,14 +422,14 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_pedidos_novos() {
-        let mut pedido_repository = InMemoryPedidoRepository::new();
+        let pedido_repository = InMemoryPedidoRepository::new();
 
         let lanche = Produto::new(
             2,
             "Cheeseburger".to_string(),
             "cheeseburger.png".to_string(),
             "O clássico pão, carne e queijo!".to_string(),
-            Categoria::Lanche,
+            1,
             9.99,
             Ingredientes::new(vec![
                 "Pão".to_string(),
@@ -207,7 +443,7 @@ mod tests {
 
         let pedido = Pedido::new(
             2,
-            Some(Cpf::new("000.000.000-00".to_string()).unwrap()),
+            Some(Cpf::new("123.456.789-09".to_string()).unwrap()),
             Some(lanche),
             None,
             None,
@@ -219,27 +455,44 @@ mod tests {
 
         pedido_repository.create_pedido(pedido.clone()).await.unwrap();
 
-        let pedidos_novos = pedido_repository.get_pedidos_novos().await.unwrap();
+        let pedidos_novos = pedido_repository.get_pedidos_novos(1).await.unwrap();
 
         assert_eq!(pedidos_novos.len(), 1);
+
+        let pedidos_novos = pedido_repository.get_pedidos_novos(2).await.unwrap();
+
+        assert!(pedidos_novos.is_empty());
     }
 
     #[tokio::test]
     async fn test_get_atualiza_status() {
-        let mut pedido_repository = InMemoryPedidoRepository::new();
+        let pedido_repository = InMemoryPedidoRepository::new();
 
         let pedido = pedido_repository.get_pedido_by_id(1).await.unwrap();
 
         assert_eq!(pedido.status(), &Status::Pendente);
 
-        let pedido = pedido_repository.atualiza_status(1, Status::EmPreparacao).await.unwrap();
+        let pedido = pedido_repository.atualiza_status(1, Status::EmPreparacao, 1).await.unwrap();
 
         assert_eq!(pedido.status(), &Status::EmPreparacao);
+        assert_eq!(pedido.versao(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_atualiza_status_com_versao_desatualizada() {
+        let pedido_repository = InMemoryPedidoRepository::new();
+
+        pedido_repository.atualiza_status(1, Status::EmPreparacao, 1).await.unwrap();
+        let result = pedido_repository.atualiza_status(1, Status::Cancelado, 1).await;
+
+        assert!(matches!(result, Err(DomainError::Conflict)));
+        let pedido = pedido_repository.get_pedido_by_id(1).await.unwrap();
+        assert_eq!(pedido.status(), &Status::EmPreparacao);
     }
 
     #[tokio::test]
     async fn test_get_atualiza_pagamento_status() {
-        let mut pedido_repository = InMemoryPedidoRepository::new();
+        let pedido_repository = InMemoryPedidoRepository::new();
 
         let pedido = pedido_repository.get_pedido_by_id(1).await.unwrap();
 
@@ -250,4 +503,98 @@ mod tests {
         assert_eq!(pedido.status(), &Status::Pago);
         assert_eq!(pedido.pagamento(), Some(&"pedido_id".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_lista_pedidos_do_cliente() {
+        let pedido_repository = InMemoryPedidoRepository::new();
+        let cpf = Cpf::new("123.456.789-09".to_string()).unwrap();
+
+        for (id, data) in [(2, "2024-01-17"), (3, "2024-01-19"), (4, "2024-01-18")] {
+            let pedido = Pedido::new(
+                id,
+                Some(cpf.clone()),
+                None,
+                None,
+                None,
+                None,
+                Status::Finalizado,
+                data.to_string(),
+                data.to_string(),
+            );
+            pedido_repository.create_pedido(pedido).await.unwrap();
+        }
+
+        let pagina = pedido_repository
+            .lista_pedidos_do_cliente(&cpf, Paginacao::new(Some(1), Some(2)).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(pagina.total(), 3);
+        let ids: Vec<usize> = pagina.itens().iter().map(|pedido| *pedido.id()).collect();
+        assert_eq!(ids, vec![3, 4]);
+
+        let outro_cpf = Cpf::new("097.855.456-60".to_string()).unwrap();
+        let pagina = pedido_repository
+            .lista_pedidos_do_cliente(&outro_cpf, Paginacao::new(None, None).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(pagina.total(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_anonimiza_pedidos_e_notas_do_cliente() {
+        let pedido_repository = InMemoryPedidoRepository::new();
+        let cpf = Cpf::new("123.456.789-09".to_string()).unwrap();
+        let cnpj = DocumentoFiscal::new("11.222.333/0001-81".to_string()).unwrap();
+
+        for (id, cliente, documento_fiscal) in [
+            (2, Some(cpf.clone()), Some(cnpj.clone())),
+            (3, None, DocumentoFiscal::new(cpf.get_string()).ok()),
+            (4, None, Some(cnpj.clone())),
+        ] {
+            let mut pedido = Pedido::new(
+                id,
+                cliente,
+                None,
+                None,
+                None,
+                None,
+                Status::Finalizado,
+                "2024-01-17".to_string(),
+                "2024-01-17".to_string(),
+            );
+            pedido.set_documento_fiscal(documento_fiscal);
+            pedido_repository.create_pedido(pedido).await.unwrap();
+        }
+
+        let ids: Vec<usize> = pedido_repository
+            .pedidos_do_titular(&cpf)
+            .await
+            .unwrap()
+            .iter()
+            .map(|pedido| *pedido.id())
+            .collect();
+        assert_eq!(ids, vec![3, 2]);
+
+        let anonimizados = pedido_repository
+            .anonimiza_pedidos_do_cliente(&cpf, "anon_1")
+            .await
+            .unwrap();
+        assert_eq!(anonimizados, 2);
+
+        let pedido = pedido_repository.get_pedido_by_id(2).await.unwrap();
+        assert!(pedido.cliente().is_none());
+        // O CNPJ da empresa não é dado do titular.
+        assert_eq!(pedido.documento_fiscal(), Some(&cnpj));
+        let pedido = pedido_repository.get_pedido_by_id(3).await.unwrap();
+        assert!(pedido.documento_fiscal().is_none());
+        let pedido = pedido_repository.get_pedido_by_id(4).await.unwrap();
+        assert_eq!(pedido.versao(), 1);
+
+        // Só o pedido que era do cliente guarda o pseudônimo; a nota não identifica um cliente.
+        let pseudonimos = pedido_repository._pseudonimos.read().unwrap();
+        assert_eq!(pseudonimos.get(&2).map(String::as_str), Some("anon_1"));
+        assert_eq!(pseudonimos.len(), 1);
+        assert!(pedido_repository.pedidos_do_titular(&cpf).await.unwrap().is_empty());
+    }
 }
@@ -0,0 +1,166 @@
+use chrono::Utc;
+use std::sync::RwLock;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::{
+    cpf::Cpf,
+    pontos::{MovimentoDePontos, SaldoDePontos},
+};
+use crate::traits::pontos_gateway::PontosGateway;
+
+pub struct InMemoryPontosRepository {
+    _movimentos: RwLock<Vec<MovimentoDePontos>>,
+}
+
+impl InMemoryPontosRepository {
+    pub fn new() -> Self {
+        tracing::info!("Usando repositório em memória");
+
+        InMemoryPontosRepository {
+            _movimentos: RwLock::new(vec![]),
+        }
+    }
+}
+
+fn insere(
+    movimentos: &mut Vec<MovimentoDePontos>,
+    movimento: MovimentoDePontos,
+) -> Result<MovimentoDePontos, DomainError> {
+    if movimentos.iter().any(|atual| {
+        atual.pedido_id() == movimento.pedido_id() && atual.tipo() == movimento.tipo()
+    }) {
+        return Err(DomainError::AlreadyExists);
+    }
+
+    let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
+    let novo_movimento = MovimentoDePontos::new(
+        movimentos.len() + 1,
+        movimento.cliente().clone(),
+        *movimento.pedido_id(),
+        movimento.tipo().clone(),
+        movimento.pontos(),
+        movimento.validade().cloned(),
+        _now,
+    );
+    movimentos.push(novo_movimento.clone());
+    Ok(novo_movimento)
+}
+
+impl Default for InMemoryPontosRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl PontosGateway for InMemoryPontosRepository {
+    async fn registra_movimento(
+        &self,
+        movimento: MovimentoDePontos,
+    ) -> Result<MovimentoDePontos, DomainError> {
+        insere(&mut self._movimentos.write().unwrap(), movimento)
+    }
+
+    async fn movimentos_do_cliente(&self, cpf: &Cpf) -> Result<Vec<MovimentoDePontos>, DomainError> {
+        Ok(self
+            ._movimentos
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|movimento| movimento.cliente() == cpf)
+            .cloned()
+            .collect())
+    }
+
+    async fn movimentos_do_pedido(&self, pedido_id: usize) -> Result<Vec<MovimentoDePontos>, DomainError> {
+        Ok(self
+            ._movimentos
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|movimento| *movimento.pedido_id() == pedido_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn registra_resgate(
+        &self,
+        resgate: MovimentoDePontos,
+    ) -> Result<Option<MovimentoDePontos>, DomainError> {
+        let mut movimentos = self._movimentos.write().unwrap();
+        let do_cliente: Vec<MovimentoDePontos> = movimentos
+            .iter()
+            .filter(|movimento| movimento.cliente() == resgate.cliente())
+            .cloned()
+            .collect();
+        if SaldoDePontos::calcula(&do_cliente, &Utc::now().fixed_offset()).saldo < -resgate.pontos() {
+            return Ok(None);
+        }
+        insere(&mut movimentos, resgate).map(Some)
+    }
+
+    async fn remove_movimentos_do_cliente(&self, cpf: &Cpf) -> Result<usize, DomainError> {
+        let mut movimentos = self._movimentos.write().unwrap();
+        let quantidade = movimentos.len();
+        movimentos.retain(|movimento| movimento.cliente() != cpf);
+        Ok(quantidade - movimentos.len())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::pontos::TipoDeMovimento;
+
+    #[tokio::test]
+    async fn test_registra_um_movimento_de_cada_tipo_por_pedido() {
+        let pontos_repository = InMemoryPontosRepository::new();
+        let cpf = Cpf::new("123.456.789-09".to_string()).unwrap();
+        let acumulo = MovimentoDePontos::new(
+            0,
+            cpf.clone(),
+            1,
+            TipoDeMovimento::Acumulo,
+            10,
+            Some("2099-01-01 00:00:00.000+0000".to_string()),
+            String::new(),
+        );
+
+        let registrado = pontos_repository.registra_movimento(acumulo.clone()).await.unwrap();
+        assert_eq!(registrado.id(), &1);
+        assert!(matches!(
+            pontos_repository.registra_movimento(acumulo).await,
+            Err(DomainError::AlreadyExists)
+        ));
+
+        assert_eq!(pontos_repository.movimentos_do_cliente(&cpf).await.unwrap().len(), 1);
+        assert!(pontos_repository.movimentos_do_pedido(2).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_registra_resgate_ate_o_saldo() {
+        let pontos_repository = InMemoryPontosRepository::new();
+        let cpf = Cpf::new("123.456.789-09".to_string()).unwrap();
+        let resgate = |pedido_id: usize, pontos: i64| {
+            MovimentoDePontos::new(0, cpf.clone(), pedido_id, TipoDeMovimento::Resgate, -pontos, None, String::new())
+        };
+        pontos_repository
+            .registra_movimento(MovimentoDePontos::new(
+                0,
+                cpf.clone(),
+                1,
+                TipoDeMovimento::Acumulo,
+                10,
+                Some("2099-01-01 00:00:00.000+0000".to_string()),
+                String::new(),
+            ))
+            .await
+            .unwrap();
+
+        assert!(pontos_repository.registra_resgate(resgate(2, 11)).await.unwrap().is_none());
+        assert!(pontos_repository.registra_resgate(resgate(2, 6)).await.unwrap().is_some());
+        assert!(pontos_repository.registra_resgate(resgate(3, 6)).await.unwrap().is_none());
+        assert!(pontos_repository.registra_resgate(resgate(3, 4)).await.unwrap().is_some());
+    }
+}
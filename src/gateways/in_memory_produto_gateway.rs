@@ -1,17 +1,19 @@
 use crate::{
-    entities::produto::Produto,
+    entities::{idioma::{Idioma, TraducaoProduto}, loja::ProdutoLoja, produto::Produto},
     traits::produto_gateway::ProdutoGateway,
-    base::domain_error::DomainError,
+    base::{domain_error::DomainError, text_search},
 };
 
 use chrono::Utc;
-use crate::entities::produto::Categoria;
 use crate::entities::ingredientes::Ingredientes;
 
+use std::sync::RwLock;
 use tokio::time::{sleep, Duration};
 
 pub struct InMemoryProdutoRepository {
-    _produto: Vec<Produto>,
+    _produto: RwLock<Vec<Produto>>,
+    _produtos_loja: RwLock<Vec<ProdutoLoja>>,
+    _traducoes: RwLock<Vec<TraducaoProduto>>,
 }
 
 impl InMemoryProdutoRepository {
@@ -19,7 +21,7 @@ impl InMemoryProdutoRepository {
         let _id = 0;
         let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
 
-        let categoria = Categoria::Lanche;
+        let categoria_id = 1;
 
         let ingredientes = Ingredientes::new(vec![
             String::from("Carne"),
@@ -32,32 +34,64 @@ impl InMemoryProdutoRepository {
             "Hamburguer".to_string(),
             "hamburguer.png".to_string(),
             "hamburguer com uma carne e salada".to_string(),
-            categoria,
+            categoria_id,
             15.99,
             ingredientes,
             _now.clone(),
             _now,
         );
 
-        println!("Usando repositório em memória!");
+        tracing::info!("Usando repositório em memória");
 
         InMemoryProdutoRepository {
-            _produto: vec![produto],
+            _produto: RwLock::new(vec![produto]),
+            _produtos_loja: RwLock::new(vec![]),
+            _traducoes: RwLock::new(vec![]),
         }
     }
 }
 
+// Mesmo cálculo de id do create_produto, reaproveitado pela importação.
+fn novo_produto(produtos: &[Produto], produto: Produto) -> Produto {
+    let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
+
+    let mut actual_produto = Produto::new(
+        produtos.len(),
+        produto.nome().to_string(),
+        produto.foto().to_string(),
+        produto.descricao().to_string(),
+        produto.categoria_id().to_owned(),
+        produto.preco().to_owned(),
+        produto.ingredientes().to_owned(),
+        _now.clone(),
+        _now,
+    );
+    actual_produto.set_loja_id(produto.loja_id().copied());
+    actual_produto
+}
+
 #[async_trait]
 impl ProdutoGateway for InMemoryProdutoRepository {
     async fn get_produtos(&self) -> Result<Vec<Produto>, DomainError> {
-        let produtos = self._produto.clone();
+        let produtos = self._produto.read().unwrap().clone();
         sleep(Duration::from_secs(1)).await;
         Ok(produtos)
     }
 
+    async fn get_produtos_da_loja(&self, loja_id: usize) -> Result<Vec<Produto>, DomainError> {
+        let produtos_loja = self._produtos_loja.read().unwrap();
+        Ok(self
+            ._produto
+            .read()
+            .unwrap()
+            .iter()
+            .filter_map(|produto| ProdutoLoja::aplica(produto, loja_id, &produtos_loja))
+            .collect())
+    }
+
     async fn get_produto_by_id(&self, id: usize) -> Result<Produto, DomainError> {
         sleep(Duration::from_secs(1)).await;
-        for produto in &self._produto {
+        for produto in self._produto.read().unwrap().iter() {
             if produto.id().to_owned() == id {
                 return Ok(produto.clone());
             }
@@ -65,51 +99,144 @@ impl ProdutoGateway for InMemoryProdutoRepository {
         Err(DomainError::NotFound)
     }
 
-    async fn create_produto(&mut self, produto: Produto) -> Result<Produto, DomainError> {
-        let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
-
-        let actual_produto = Produto::new(
-            self._produto.len(),
-            produto.nome().to_string(),
-            produto.foto().to_string(),
-            produto.descricao().to_string(),
-            produto.categoria().to_owned(),
-            produto.preco().to_owned(),
-            produto.ingredientes().to_owned(),
-            _now.clone(),
-            _now,
-        );
-
-        let mut produto_list = self._produto.clone();
-        produto_list.push(actual_produto.clone());
+    async fn get_produtos_by_ids(&self, ids: Vec<usize>) -> Result<Vec<Produto>, DomainError> {
+        sleep(Duration::from_secs(1)).await;
+        Ok(self
+            ._produto
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|produto| ids.contains(produto.id()))
+            .cloned()
+            .collect())
+    }
 
-        self._produto = produto_list;
+    async fn busca_produtos(&self, termo: String, loja_id: usize) -> Result<Vec<Produto>, DomainError> {
+        let mut resultados: Vec<(f64, Produto)> = Vec::new();
+        for produto in self.get_produtos_da_loja(loja_id).await? {
+            let ingredientes = produto.ingredientes().to_vec_string().join(" ");
+            let score = text_search::score(
+                &termo,
+                &[
+                    (produto.nome(), text_search::PESO_NOME),
+                    (produto.descricao(), text_search::PESO_DESCRICAO),
+                    (&ingredientes, text_search::PESO_INGREDIENTES),
+                ],
+            );
+            if let Some(score) = score {
+                resultados.push((score, produto));
+            }
+        }
+        resultados.sort_by(|a, b| b.0.total_cmp(&a.0));
+        Ok(resultados.into_iter().map(|(_, produto)| produto).collect())
+    }
 
+    async fn create_produto(&self, produto: Produto) -> Result<Produto, DomainError> {
+        let mut produtos = self._produto.write().unwrap();
+        let actual_produto = novo_produto(&produtos, produto);
+        produtos.push(actual_produto.clone());
         Ok(actual_produto)
     }
 
-    async fn update_produto(&mut self, new_produto_data: Produto) -> Result<Produto, DomainError> {
+    async fn update_produto(&self, new_produto_data: Produto) -> Result<Produto, DomainError> {
         sleep(Duration::from_secs(1)).await;
-        let mut produto_list = self._produto.clone();
-        for produto in &mut produto_list.iter_mut() {
-        if produto.id() == new_produto_data.id() {
-            self.delete_produto(produto.id().to_owned()).await.unwrap();
-            self.create_produto(new_produto_data.clone()).await.unwrap();
-            return Ok(new_produto_data.clone());
-        }
+        let mut produtos = self._produto.write().unwrap();
+        match produtos.iter_mut().find(|produto| produto.id() == new_produto_data.id()) {
+            Some(atual) if atual.versao() != new_produto_data.versao() => Err(DomainError::Conflict),
+            Some(atual) => {
+                let mut actual_produto = new_produto_data;
+                actual_produto.set_versao(atual.versao() + 1);
+                *atual = actual_produto.clone();
+                Ok(actual_produto)
+            }
+            None => Err(DomainError::NotFound),
         }
-        Err(DomainError::NotFound)
     }
 
-    async fn delete_produto(&mut self, id: usize) -> Result<(), DomainError> {
-        let produto_list = &mut self._produto;
-        for (index, produto) in produto_list.iter_mut().enumerate() {
-            if produto.id().to_owned() == id {
-                produto_list.remove(index);
-                return Ok(());
+    async fn delete_produto(&self, id: usize) -> Result<(), DomainError> {
+        let mut produtos = self._produto.write().unwrap();
+        match produtos.iter().position(|produto| *produto.id() == id) {
+            Some(index) => {
+                produtos.remove(index);
+                Ok(())
             }
+            None => Err(DomainError::NotFound),
         }
-        Err(DomainError::NotFound)
+    }
+
+    async fn get_produtos_loja(&self, loja_id: usize) -> Result<Vec<ProdutoLoja>, DomainError> {
+        Ok(self
+            ._produtos_loja
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|produto_loja| *produto_loja.loja_id() == loja_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn salva_produto_loja(&self, produto_loja: ProdutoLoja) -> Result<ProdutoLoja, DomainError> {
+        let mut produtos_loja = self._produtos_loja.write().unwrap();
+        produtos_loja.retain(|atual| {
+            atual.loja_id() != produto_loja.loja_id() || atual.produto_id() != produto_loja.produto_id()
+        });
+        produtos_loja.push(produto_loja.clone());
+        Ok(produto_loja)
+    }
+
+    async fn get_traducoes(&self, idioma: Idioma) -> Result<Vec<TraducaoProduto>, DomainError> {
+        Ok(self
+            ._traducoes
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|traducao| traducao.idioma() == idioma)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_traducoes_do_produto(&self, produto_id: usize) -> Result<Vec<TraducaoProduto>, DomainError> {
+        Ok(self
+            ._traducoes
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|traducao| *traducao.produto_id() == produto_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn salva_traducao(&self, traducao: TraducaoProduto) -> Result<TraducaoProduto, DomainError> {
+        let mut traducoes = self._traducoes.write().unwrap();
+        traducoes.retain(|atual| {
+            atual.produto_id() != traducao.produto_id() || atual.idioma() != traducao.idioma()
+        });
+        traducoes.push(traducao.clone());
+        Ok(traducao)
+    }
+
+    async fn importa_produtos(&self, novos: Vec<Produto>, atualizados: Vec<Produto>) -> Result<Vec<Produto>, DomainError> {
+        let mut produtos = self._produto.write().unwrap();
+        // Grava numa cópia e só a publica no fim: qualquer erro desfaz a importação inteira.
+        let mut importacao = produtos.clone();
+        let mut importados = Vec::new();
+        for produto in novos {
+            let actual_produto = novo_produto(&importacao, produto);
+            importacao.push(actual_produto.clone());
+            importados.push(actual_produto);
+        }
+        for produto in atualizados {
+            let atual = importacao
+                .iter_mut()
+                .find(|atual| atual.id() == produto.id())
+                .ok_or(DomainError::NotFound)?;
+            let mut produto = produto;
+            produto.set_versao(atual.versao() + 1);
+            *atual = produto.clone();
+            importados.push(produto);
+        }
+        *produtos = importacao;
+        Ok(importados)
     }
 }
 
@@ -133,9 +260,9 @@ mod tests {
 
     #[tokio::test]
     async fn test_adds_and_retrieves() {
-        let mut produto_repository = InMemoryProdutoRepository::new();
+        let produto_repository = InMemoryProdutoRepository::new();
 
-        let categoria = Categoria::Lanche;
+        let categoria_id = 1;
 
         let ingredientes = Ingredientes::new(vec![
             String::from("Carne"),
@@ -148,7 +275,7 @@ mod tests {
             "Hamburguer".to_string(),
             "hamburguer.png".to_string(),
             "hamburguer com uma carne e salada".to_string(),
-            categoria,
+            categoria_id,
             15.99,
             ingredientes,
             Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
@@ -168,28 +295,179 @@ mod tests {
         assert_eq!(produto.id(), &1);
     }
 
+    #[tokio::test]
+    async fn test_get_produtos_by_ids() {
+        let produto_repository = InMemoryProdutoRepository::new();
+
+        let mut outro = produto_repository.get_produto_by_id(0).await.unwrap();
+        outro.set_nome("X-Salada".to_string()).unwrap();
+        let outro = produto_repository.create_produto(outro).await.unwrap();
+
+        let produtos = produto_repository
+            .get_produtos_by_ids(vec![*outro.id(), 42])
+            .await
+            .unwrap();
+        assert_eq!(produtos.len(), 1);
+        assert_eq!(produtos[0].nome(), "X-Salada");
+    }
+
     #[tokio::test]
     async fn test_update() {
-        let mut produto_repository = InMemoryProdutoRepository::new();
+        let produto_repository = InMemoryProdutoRepository::new();
 
         let mut produto = produto_repository.get_produto_by_id(0).await.unwrap();
 
-        produto.set_categoria(Categoria::Bebida);
+        produto.set_categoria_id(2);
 
         let produto = produto_repository.update_produto(produto).await.unwrap();
 
-        assert_eq!(produto.categoria(), &Categoria::Bebida);
+        assert_eq!(produto.categoria_id(), &2);
+
+        let produto = produto_repository.get_produto_by_id(0).await.unwrap();
+
+        assert_eq!(produto.categoria_id(), &2);
+        assert_eq!(produto.versao(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_update_com_versao_desatualizada() {
+        let produto_repository = InMemoryProdutoRepository::new();
+
+        let lido_pelo_primeiro = produto_repository.get_produto_by_id(0).await.unwrap();
+        let mut lido_pelo_segundo = lido_pelo_primeiro.clone();
+
+        produto_repository.update_produto(lido_pelo_primeiro).await.unwrap();
+        lido_pelo_segundo.set_categoria_id(2);
+        let result = produto_repository.update_produto(lido_pelo_segundo).await;
 
+        assert!(matches!(result, Err(DomainError::Conflict)));
         let produto = produto_repository.get_produto_by_id(0).await.unwrap();
+        assert_eq!(produto.categoria_id(), &1);
+    }
+
+    #[tokio::test]
+    async fn test_importa_produtos_desfaz_em_caso_de_erro() {
+        let produto_repository = InMemoryProdutoRepository::new();
+
+        let novo = produto_repository.get_produto_by_id(0).await.unwrap();
+        let inexistente = Produto::new(
+            42,
+            "Milkshake".to_string(),
+            "milkshake.png".to_string(),
+            "Milkshake de morango".to_string(),
+            2,
+            12.0,
+            Ingredientes::new(vec![String::from("Morango")]).unwrap(),
+            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
+            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
+        );
+
+        let result = produto_repository.importa_produtos(vec![novo.clone()], vec![inexistente]).await;
+        assert!(matches!(result, Err(DomainError::NotFound)));
+        assert_eq!(produto_repository.get_produtos().await.unwrap().len(), 1);
+
+        let mut atualizado = novo.clone();
+        atualizado.set_preco(20.0).unwrap();
+        let importados = produto_repository.importa_produtos(vec![novo], vec![atualizado]).await.unwrap();
+        assert_eq!(importados.len(), 2);
+
+        let produtos = produto_repository.get_produtos().await.unwrap();
+        assert_eq!(produtos.len(), 2);
+        assert_eq!(produtos[0].preco(), 20.0);
+    }
+
+    #[tokio::test]
+    async fn test_get_produtos_da_loja() {
+        let produto_repository = InMemoryProdutoRepository::new();
+
+        let mut exclusivo = produto_repository.get_produto_by_id(0).await.unwrap();
+        exclusivo.set_loja_id(Some(2));
+        produto_repository.create_produto(exclusivo).await.unwrap();
+        produto_repository
+            .salva_produto_loja(ProdutoLoja::new(2, 0, Some(12.0), true))
+            .await
+            .unwrap();
+        produto_repository
+            .salva_produto_loja(ProdutoLoja::new(3, 0, None, false))
+            .await
+            .unwrap();
+
+        let produtos = produto_repository.get_produtos_da_loja(1).await.unwrap();
+        assert_eq!(produtos.len(), 1);
+        assert_eq!(produtos[0].preco(), 15.99);
+
+        let produtos = produto_repository.get_produtos_da_loja(2).await.unwrap();
+        assert_eq!(produtos.len(), 2);
+        assert_eq!(produtos[0].preco(), 12.0);
+
+        assert!(produto_repository.get_produtos_da_loja(3).await.unwrap().is_empty());
+        assert_eq!(produto_repository.get_produtos_loja(2).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_salva_traducao() {
+        let produto_repository = InMemoryProdutoRepository::new();
 
-        assert_eq!(produto.categoria(), &Categoria::Bebida);
+        produto_repository
+            .salva_traducao(TraducaoProduto::new(0, Idioma::En, Some("Burger".to_string()), None))
+            .await
+            .unwrap();
+        produto_repository
+            .salva_traducao(TraducaoProduto::new(0, Idioma::En, Some("Hamburger".to_string()), None))
+            .await
+            .unwrap();
+
+        let traducoes = produto_repository.get_traducoes(Idioma::En).await.unwrap();
+        assert_eq!(traducoes.len(), 1);
+        assert_eq!(traducoes[0].nome().unwrap(), "Hamburger");
+        assert!(produto_repository.get_traducoes(Idioma::Es).await.unwrap().is_empty());
+        assert_eq!(produto_repository.get_traducoes_do_produto(0).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_busca_produtos() {
+        let produto_repository = InMemoryProdutoRepository::new();
+
+        let ingredientes = Ingredientes::new(vec![
+            String::from("Pão"),
+            String::from("Hambúrguer"),
+            String::from("Queijo"),
+        ]).unwrap();
+
+        let produto = Produto::new(
+            1,
+            "Cheeseburger".to_string(),
+            "cheeseburger.png".to_string(),
+            "O clássico pão, carne e queijo!".to_string(),
+            1,
+            9.99,
+            ingredientes,
+            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
+            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
+        );
+
+        produto_repository.create_produto(produto).await.unwrap();
+
+        let produtos = produto_repository.busca_produtos("HAMBÚRGUER".to_string(), 1).await.unwrap();
+
+        assert_eq!(produtos.len(), 2);
+        assert_eq!(produtos[0].nome(), "Hamburguer");
+
+        let produtos = produto_repository.busca_produtos("quejo".to_string(), 1).await.unwrap();
+
+        assert_eq!(produtos.len(), 1);
+        assert_eq!(produtos[0].nome(), "Cheeseburger");
+
+        let produtos = produto_repository.busca_produtos("sorvete".to_string(), 1).await.unwrap();
+
+        assert!(produtos.is_empty());
     }
 
     #[tokio::test]
     async fn test_deletes() {
-        let mut produto_repository = InMemoryProdutoRepository::new();
+        let produto_repository = InMemoryProdutoRepository::new();
 
-        let categoria = Categoria::Lanche;
+        let categoria_id = 1;
 
         let ingredientes = Ingredientes::new(vec![
             String::from("Carne"),
@@ -202,7 +480,7 @@ mod tests {
             "Hamburguer".to_string(),
             "hamburguer.png".to_string(),
             "hamburguer com uma carne e salada".to_string(),
-            categoria,
+            categoria_id,
             15.99,
             ingredientes,
             Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string(),
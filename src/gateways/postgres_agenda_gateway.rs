@@ -0,0 +1,88 @@
+use postgres_from_row::FromRow;
+use deadpool_postgres::Pool;
+
+use crate::{
+    base::domain_error::DomainError, entities::agenda::Agenda,
+    traits::agenda_gateway::AgendaGateway,
+};
+
+use crate::base::metricas;
+
+pub struct PostgresAgendaRepository {
+    pool: Pool,
+}
+
+const CREATE_AGENDA: &str = "INSERT INTO agenda (alvo, alvo_id, dias_semana, hora_inicio, hora_fim, preco, data_criacao, data_atualizacao) VALUES ($1, $2, $3, $4, $5, $6, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP) RETURNING id, alvo, alvo_id, dias_semana, hora_inicio, hora_fim, preco, data_criacao, data_atualizacao";
+const QUERY_AGENDA_BY_ID: &str = "SELECT id, alvo, alvo_id, dias_semana, hora_inicio, hora_fim, preco, data_criacao, data_atualizacao FROM agenda WHERE id = $1";
+const QUERY_AGENDAS: &str = "SELECT id, alvo, alvo_id, dias_semana, hora_inicio, hora_fim, preco, data_criacao, data_atualizacao FROM agenda ORDER BY id";
+const DELETE_AGENDA: &str = "DELETE FROM agenda WHERE id = $1 RETURNING id";
+
+impl PostgresAgendaRepository {
+    pub fn new(pool: Pool) -> Self {
+        PostgresAgendaRepository { pool }
+    }
+}
+
+#[async_trait]
+impl AgendaGateway for PostgresAgendaRepository {
+    async fn get_agendas(&self) -> Result<Vec<Agenda>, DomainError> {
+        let client = self.pool.get().await?;
+        let agendas = metricas::mede_consulta(
+            "query_agendas",
+            client.query(QUERY_AGENDAS, &[]),
+        )
+        .await?;
+        Ok(agendas.iter().map(Agenda::from_row).collect())
+    }
+
+    async fn get_agenda_by_id(&self, id: usize) -> Result<Agenda, DomainError> {
+        let client = self.pool.get().await?;
+        let id = id as i32;
+        let agenda = metricas::mede_consulta(
+            "query_agenda_by_id",
+            client.query_opt(QUERY_AGENDA_BY_ID, &[&id]),
+        )
+        .await?;
+        match agenda {
+            Some(agenda) => Ok(Agenda::from_row(&agenda)),
+            None => Err(DomainError::NotFound),
+        }
+    }
+
+    async fn create_agenda(&self, agenda: Agenda) -> Result<Agenda, DomainError> {
+        let client = self.pool.get().await?;
+        let alvo = agenda.alvo().to_string();
+        let alvo_id = *agenda.alvo_id() as i32;
+        let dias_semana: Vec<i32> = agenda.dias_semana().iter().map(|dia| *dia as i32).collect();
+        let nova_agenda = metricas::mede_consulta(
+            "create_agenda",
+            client.query_one(
+                CREATE_AGENDA,
+                &[
+                    &alvo,
+                    &alvo_id,
+                    &dias_semana,
+                    agenda.hora_inicio(),
+                    agenda.hora_fim(),
+                    &agenda.preco(),
+                ],
+            ),
+        )
+        .await?;
+        Ok(Agenda::from_row(&nova_agenda))
+    }
+
+    async fn delete_agenda(&self, id: usize) -> Result<(), DomainError> {
+        let client = self.pool.get().await?;
+        let id = id as i32;
+        let deleted_agenda = metricas::mede_consulta(
+            "delete_agenda",
+            client.query_opt(DELETE_AGENDA, &[&id]),
+        )
+        .await?;
+        match deleted_agenda {
+            Some(_) => Ok(()),
+            None => Err(DomainError::NotFound),
+        }
+    }
+}
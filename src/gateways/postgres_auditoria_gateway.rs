@@ -0,0 +1,42 @@
+use postgres_from_row::FromRow;
+use deadpool_postgres::Pool;
+
+use crate::{
+    base::domain_error::DomainError,
+    entities::auditoria::RegistroDeAuditoria,
+    traits::auditoria_gateway::AuditoriaGateway,
+};
+
+use crate::base::metricas;
+
+pub struct PostgresAuditoriaRepository {
+    pool: Pool,
+}
+
+const CREATE_REGISTRO: &str = "INSERT INTO auditoria (acao, titular, request_id, data_criacao) VALUES ($1, $2, $3, CURRENT_TIMESTAMP) RETURNING id, acao, titular, request_id, data_criacao";
+
+impl PostgresAuditoriaRepository {
+    pub fn new(pool: Pool) -> Self {
+        PostgresAuditoriaRepository { pool }
+    }
+}
+
+#[async_trait]
+impl AuditoriaGateway for PostgresAuditoriaRepository {
+    async fn registra(&self, registro: RegistroDeAuditoria) -> Result<RegistroDeAuditoria, DomainError> {
+        let client = self.pool.get().await?;
+        let novo_registro = metricas::mede_consulta(
+            "create_registro_de_auditoria",
+            client.query_one(
+                CREATE_REGISTRO,
+                &[
+                    &registro.acao().to_string(),
+                    registro.titular(),
+                    registro.request_id(),
+                ],
+            ),
+        )
+        .await?;
+        Ok(RegistroDeAuditoria::from_row(&novo_registro))
+    }
+}
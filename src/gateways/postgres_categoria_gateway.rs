@@ -0,0 +1,130 @@
+use postgres_from_row::FromRow;
+use deadpool_postgres::Pool;
+
+use crate::{
+    base::domain_error::DomainError, entities::categoria::Categoria,
+    traits::categoria_gateway::CategoriaGateway,
+};
+
+use crate::base::metricas;
+
+pub struct PostgresCategoriaRepository {
+    pool: Pool,
+}
+
+const CREATE_CATEGORIA: &str = "INSERT INTO categoria (nome, icone, ordem, ativa, item_pedido, data_criacao, data_atualizacao) VALUES ($1, $2, $3, $4, $5, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP) RETURNING id, nome, icone, ordem, ativa, item_pedido, data_criacao, data_atualizacao";
+const QUERY_CATEGORIA_BY_ID: &str = "SELECT id, nome, icone, ordem, ativa, item_pedido, data_criacao, data_atualizacao FROM categoria WHERE id = $1";
+const QUERY_CATEGORIA_BY_NOME: &str = "SELECT id FROM categoria WHERE lower(nome) = lower($1) AND id <> $2";
+const QUERY_CATEGORIAS: &str = "SELECT id, nome, icone, ordem, ativa, item_pedido, data_criacao, data_atualizacao FROM categoria ORDER BY ordem, nome";
+const UPDATE_CATEGORIA: &str = "UPDATE categoria SET nome = $1, icone = $2, ordem = $3, ativa = $4, item_pedido = $5, data_atualizacao = CURRENT_TIMESTAMP WHERE id = $6 RETURNING id, nome, icone, ordem, ativa, item_pedido, data_criacao, data_atualizacao";
+const DELETE_CATEGORIA: &str = "DELETE FROM categoria WHERE id = $1 RETURNING id";
+
+impl PostgresCategoriaRepository {
+    pub fn new(pool: Pool) -> Self {
+        PostgresCategoriaRepository { pool }
+    }
+
+
+    async fn check_nome_disponivel(&self, nome: &str, id: i32) -> Result<(), DomainError> {
+        let client = self.pool.get().await?;
+        let existente = metricas::mede_consulta(
+            "query_categoria_by_nome",
+            client.query_opt(QUERY_CATEGORIA_BY_NOME, &[&nome, &id]),
+        )
+        .await?;
+        match existente {
+            Some(_) => Err(DomainError::AlreadyExists),
+            None => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl CategoriaGateway for PostgresCategoriaRepository {
+    async fn get_categorias(&self) -> Result<Vec<Categoria>, DomainError> {
+        let client = self.pool.get().await?;
+        let categorias = metricas::mede_consulta(
+            "query_categorias",
+            client.query(QUERY_CATEGORIAS, &[]),
+        )
+        .await?;
+        Ok(categorias.iter().map(Categoria::from_row).collect())
+    }
+
+    async fn get_categoria_by_id(&self, id: usize) -> Result<Categoria, DomainError> {
+        let client = self.pool.get().await?;
+        let id = id as i32;
+        let categoria = metricas::mede_consulta(
+            "query_categoria_by_id",
+            client.query_opt(QUERY_CATEGORIA_BY_ID, &[&id]),
+        )
+        .await?;
+        match categoria {
+            Some(categoria) => Ok(Categoria::from_row(&categoria)),
+            None => Err(DomainError::NotFound),
+        }
+    }
+
+    async fn create_categoria(&self, categoria: Categoria) -> Result<Categoria, DomainError> {
+        let client = self.pool.get().await?;
+        self.check_nome_disponivel(categoria.nome(), 0).await?;
+        let ordem = categoria.ordem() as i32;
+        let item_pedido = categoria.item_pedido().map(|item| item.to_string());
+        let nova_categoria = metricas::mede_consulta(
+            "create_categoria",
+            client.query_one(
+                CREATE_CATEGORIA,
+                &[
+                    &categoria.nome(),
+                    &categoria.icone(),
+                    &ordem,
+                    &categoria.ativa(),
+                    &item_pedido,
+                ],
+            ),
+        )
+        .await?;
+        Ok(Categoria::from_row(&nova_categoria))
+    }
+
+    async fn update_categoria(&self, categoria: Categoria) -> Result<Categoria, DomainError> {
+        let client = self.pool.get().await?;
+        let id = *categoria.id() as i32;
+        self.check_nome_disponivel(categoria.nome(), id).await?;
+        let ordem = categoria.ordem() as i32;
+        let item_pedido = categoria.item_pedido().map(|item| item.to_string());
+        let categoria_atualizada = metricas::mede_consulta(
+            "update_categoria",
+            client.query_opt(
+                UPDATE_CATEGORIA,
+                &[
+                    &categoria.nome(),
+                    &categoria.icone(),
+                    &ordem,
+                    &categoria.ativa(),
+                    &item_pedido,
+                    &id,
+                ],
+            ),
+        )
+        .await?;
+        match categoria_atualizada {
+            Some(categoria) => Ok(Categoria::from_row(&categoria)),
+            None => Err(DomainError::NotFound),
+        }
+    }
+
+    async fn delete_categoria(&self, id: usize) -> Result<(), DomainError> {
+        let client = self.pool.get().await?;
+        let id = id as i32;
+        let deleted_categoria = metricas::mede_consulta(
+            "delete_categoria",
+            client.query_opt(DELETE_CATEGORIA, &[&id]),
+        )
+        .await?;
+        match deleted_categoria {
+            Some(_) => Ok(()),
+            None => Err(DomainError::NotFound),
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use postgres_from_row::FromRow;
+use deadpool_postgres::Pool;
+use tokio_postgres::Row;
+
+use crate::{
+    base::{cifrador_de_cpf::CifradorDeCpf, domain_error::DomainError},
+    entities::{cliente::Cliente, cpf::Cpf},
+    traits::cliente_gateway::ClienteGateway,
+};
+
+use crate::base::metricas;
+
+pub struct PostgresClienteRepository {
+    pool: Pool,
+    cifrador: CifradorDeCpf,
+}
+
+const CREATE_CLIENTE: &str = "INSERT INTO cliente (cpf, cpf_hash, nome, email, aceita_marketing, aceita_compartilhamento, data_criacao, data_atualizacao) VALUES ($1, $2, $3, $4, $5, $6, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP) ON CONFLICT (cpf_hash) DO NOTHING RETURNING cpf, nome, email, aceita_marketing, aceita_compartilhamento, data_criacao, data_atualizacao";
+const QUERY_CLIENTE_BY_CPF: &str = "SELECT cpf, nome, email, aceita_marketing, aceita_compartilhamento, data_criacao, data_atualizacao FROM cliente WHERE cpf_hash = $1";
+const UPDATE_CLIENTE: &str = "UPDATE cliente SET nome = $2, email = $3, aceita_marketing = $4, aceita_compartilhamento = $5, data_atualizacao = CURRENT_TIMESTAMP WHERE cpf_hash = $1 RETURNING cpf, nome, email, aceita_marketing, aceita_compartilhamento, data_criacao, data_atualizacao";
+const DELETE_CLIENTE: &str = "DELETE FROM cliente WHERE cpf_hash = $1";
+
+impl PostgresClienteRepository {
+    pub fn new(pool: Pool, cifrador: CifradorDeCpf) -> Self {
+        PostgresClienteRepository { pool, cifrador }
+    }
+
+    // O CPF vem cifrado do banco.
+    fn cliente_from_row(&self, row: &Row) -> Result<Cliente, DomainError> {
+        let mut cliente = Cliente::from_row(row);
+        cliente.set_cpf(self.cifrador.decifra(&cliente.cpf().0)?);
+        Ok(cliente)
+    }
+}
+
+#[async_trait]
+impl ClienteGateway for PostgresClienteRepository {
+    async fn get_cliente_by_cpf(&self, cpf: &Cpf) -> Result<Cliente, DomainError> {
+        let client = self.pool.get().await?;
+        let cliente = metricas::mede_consulta(
+            "query_cliente_by_cpf",
+            client.query_opt(QUERY_CLIENTE_BY_CPF, &[&self.cifrador.hash(cpf)]),
+        )
+        .await?;
+        match cliente {
+            Some(cliente) => self.cliente_from_row(&cliente),
+            None => Err(DomainError::NotFound),
+        }
+    }
+
+    async fn create_cliente(&self, cliente: Cliente) -> Result<Cliente, DomainError> {
+        let client = self.pool.get().await?;
+        let novo_cliente = metricas::mede_consulta(
+            "create_cliente",
+            client.query_opt(
+                CREATE_CLIENTE,
+                &[
+                    &self.cifrador.cifra(cliente.cpf()),
+                    &self.cifrador.hash(cliente.cpf()),
+                    &cliente.nome(),
+                    &cliente.email(),
+                    &cliente.aceita_marketing(),
+                    &cliente.aceita_compartilhamento(),
+                ],
+            ),
+        )
+        .await?;
+        match novo_cliente {
+            Some(cliente) => self.cliente_from_row(&cliente),
+            None => Err(DomainError::AlreadyExists),
+        }
+    }
+
+    async fn update_cliente(&self, cliente: Cliente) -> Result<Cliente, DomainError> {
+        let client = self.pool.get().await?;
+        let cliente_atualizado = metricas::mede_consulta(
+            "update_cliente",
+            client.query_opt(
+                UPDATE_CLIENTE,
+                &[
+                    &self.cifrador.hash(cliente.cpf()),
+                    &cliente.nome(),
+                    &cliente.email(),
+                    &cliente.aceita_marketing(),
+                    &cliente.aceita_compartilhamento(),
+                ],
+            ),
+        )
+        .await?;
+        match cliente_atualizado {
+            Some(cliente) => self.cliente_from_row(&cliente),
+            None => Err(DomainError::NotFound),
+        }
+    }
+
+    async fn delete_cliente(&self, cpf: &Cpf) -> Result<(), DomainError> {
+        let client = self.pool.get().await?;
+        let removidos = metricas::mede_consulta(
+            "delete_cliente",
+            client.execute(DELETE_CLIENTE, &[&self.cifrador.hash(cpf)]),
+        )
+        .await?;
+        match removidos {
+            0 => Err(DomainError::NotFound),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use deadpool_postgres::Pool;
+use postgres_from_row::FromRow;
+use std::time::Duration;
+use tokio_postgres::types::Json;
+
+use crate::base::cifrador_de_cpf::CifradorDeCpf;
+use crate::base::domain_error::DomainError;
+use crate::base::metricas;
+use crate::entities::{cpf::Cpf, documento_fiscal::DocumentoFiscal};
+use crate::entities::{idempotencia::RegistroDeIdempotencia, pedido::Pedido};
+use crate::traits::idempotencia_gateway::IdempotenciaGateway;
+
+const DELETE_EXPIRADAS: &str = "DELETE FROM idempotencia WHERE criada_em < CURRENT_TIMESTAMP - make_interval(secs => $1)";
+const RESERVA_CHAVE: &str = "INSERT INTO idempotencia (chave, impressao_digital, criada_em) VALUES ($1, $2, CURRENT_TIMESTAMP) ON CONFLICT (chave) DO NOTHING RETURNING chave";
+const QUERY_REGISTRO: &str = "SELECT chave, impressao_digital, pedido FROM idempotencia WHERE chave = $1";
+const CONCLUI_REGISTRO: &str = "UPDATE idempotencia SET pedido = $2 WHERE chave = $1";
+const LIBERA_CHAVE: &str = "DELETE FROM idempotencia WHERE chave = $1 AND pedido IS NULL";
+
+pub struct PostgresIdempotenciaRepository {
+    pool: Pool,
+    janela: Duration,
+    cifrador: CifradorDeCpf,
+}
+
+impl PostgresIdempotenciaRepository {
+    pub fn new(pool: Pool, janela: Duration, cifrador: CifradorDeCpf) -> Self {
+        PostgresIdempotenciaRepository { pool, janela, cifrador }
+    }
+
+    // O pedido guardado como resposta tem os CPFs cifrados, como na tabela de pedidos.
+    fn cifra(&self, mut pedido: Pedido) -> Pedido {
+        let cliente = pedido.cliente().map(|cpf| Cpf(self.cifrador.cifra(cpf)));
+        let documento_fiscal = pedido
+            .documento_fiscal()
+            .map(|documento| DocumentoFiscal(self.cifrador.cifra_documento(documento)));
+        pedido.set_cliente(cliente);
+        pedido.set_documento_fiscal(documento_fiscal);
+        pedido
+    }
+
+    fn decifra(&self, mut pedido: Pedido) -> Result<Pedido, DomainError> {
+        let cliente = pedido.cliente().map(|cpf| self.cifrador.decifra(&cpf.0)).transpose()?;
+        let documento_fiscal = pedido
+            .documento_fiscal()
+            .map(|documento| self.cifrador.decifra_documento(&documento.0))
+            .transpose()?;
+        pedido.set_cliente(cliente);
+        pedido.set_documento_fiscal(documento_fiscal);
+        Ok(pedido)
+    }
+}
+
+#[async_trait]
+impl IdempotenciaGateway for PostgresIdempotenciaRepository {
+    async fn reserva(&self, chave: String, impressao_digital: String) -> Result<Option<RegistroDeIdempotencia>, DomainError> {
+        let client = self.pool.get().await?;
+        // Chaves vencidas podem ser reutilizadas: apagá-las antes libera o INSERT.
+        let janela = self.janela.as_secs_f64();
+        metricas::mede_consulta(
+            "delete_idempotencia_expiradas",
+            client.execute(DELETE_EXPIRADAS, &[&janela]),
+        )
+        .await?;
+
+        let reservada = metricas::mede_consulta(
+            "reserva_idempotencia",
+            client.query_opt(RESERVA_CHAVE, &[&chave, &impressao_digital]),
+        )
+        .await?;
+        if reservada.is_some() {
+            return Ok(None);
+        }
+
+        let registro = metricas::mede_consulta(
+            "query_idempotencia",
+            client.query_opt(QUERY_REGISTRO, &[&chave]),
+        )
+        .await?;
+        // A reserva concorrente falhou e foi liberada entre o INSERT e o SELECT.
+        let mut registro = registro
+            .map(|registro| RegistroDeIdempotencia::from_row(&registro))
+            .ok_or(DomainError::Conflict)?;
+        if let Some(pedido) = registro.pedido().cloned() {
+            registro.set_pedido(self.decifra(pedido)?);
+        }
+        Ok(Some(registro))
+    }
+
+    async fn conclui(&self, chave: String, pedido: Pedido) -> Result<(), DomainError> {
+        let client = self.pool.get().await?;
+        let atualizados = metricas::mede_consulta(
+            "conclui_idempotencia",
+            client.execute(CONCLUI_REGISTRO, &[&chave, &Json(&self.cifra(pedido))]),
+        )
+        .await?;
+        match atualizados {
+            0 => Err(DomainError::NotFound),
+            _ => Ok(()),
+        }
+    }
+
+    async fn libera(&self, chave: String) -> Result<(), DomainError> {
+        let client = self.pool.get().await?;
+        metricas::mede_consulta(
+            "libera_idempotencia",
+            client.execute(LIBERA_CHAVE, &[&chave]),
+        )
+        .await?;
+        Ok(())
+    }
+}
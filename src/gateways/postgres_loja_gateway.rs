@@ -0,0 +1,74 @@
+use postgres_from_row::FromRow;
+use deadpool_postgres::Pool;
+
+use crate::{
+    base::domain_error::DomainError, entities::loja::Loja, traits::loja_gateway::LojaGateway,
+};
+
+use crate::base::metricas;
+
+pub struct PostgresLojaRepository {
+    pool: Pool,
+}
+
+const CREATE_LOJA: &str = "INSERT INTO loja (nome, ativa, data_criacao, data_atualizacao) VALUES ($1, $2, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP) RETURNING id, nome, ativa, data_criacao, data_atualizacao";
+const QUERY_LOJA_BY_ID: &str = "SELECT id, nome, ativa, data_criacao, data_atualizacao FROM loja WHERE id = $1";
+const QUERY_LOJAS: &str = "SELECT id, nome, ativa, data_criacao, data_atualizacao FROM loja ORDER BY id";
+const UPDATE_LOJA: &str = "UPDATE loja SET nome = $1, ativa = $2, data_atualizacao = CURRENT_TIMESTAMP WHERE id = $3 RETURNING id, nome, ativa, data_criacao, data_atualizacao";
+
+impl PostgresLojaRepository {
+    pub fn new(pool: Pool) -> Self {
+        PostgresLojaRepository { pool }
+    }
+}
+
+#[async_trait]
+impl LojaGateway for PostgresLojaRepository {
+    async fn get_lojas(&self) -> Result<Vec<Loja>, DomainError> {
+        let client = self.pool.get().await?;
+        let lojas = metricas::mede_consulta(
+            "query_lojas",
+            client.query(QUERY_LOJAS, &[]),
+        )
+        .await?;
+        Ok(lojas.iter().map(Loja::from_row).collect())
+    }
+
+    async fn get_loja_by_id(&self, id: usize) -> Result<Loja, DomainError> {
+        let client = self.pool.get().await?;
+        let id = id as i32;
+        let loja = metricas::mede_consulta(
+            "query_loja_by_id",
+            client.query_opt(QUERY_LOJA_BY_ID, &[&id]),
+        )
+        .await?;
+        match loja {
+            Some(loja) => Ok(Loja::from_row(&loja)),
+            None => Err(DomainError::NotFound),
+        }
+    }
+
+    async fn create_loja(&self, loja: Loja) -> Result<Loja, DomainError> {
+        let client = self.pool.get().await?;
+        let nova_loja = metricas::mede_consulta(
+            "create_loja",
+            client.query_one(CREATE_LOJA, &[&loja.nome(), &loja.ativa()]),
+        )
+        .await?;
+        Ok(Loja::from_row(&nova_loja))
+    }
+
+    async fn update_loja(&self, loja: Loja) -> Result<Loja, DomainError> {
+        let client = self.pool.get().await?;
+        let id = *loja.id() as i32;
+        let loja_atualizada = metricas::mede_consulta(
+            "update_loja",
+            client.query_opt(UPDATE_LOJA, &[&loja.nome(), &loja.ativa(), &id]),
+        )
+        .await?;
+        match loja_atualizada {
+            Some(loja) => Ok(Loja::from_row(&loja)),
+            None => Err(DomainError::NotFound),
+        }
+    }
+}
@@ -1,26 +1,48 @@
 use bytes::BytesMut;
+use chrono::{DateTime, Utc};
 use postgres_from_row::FromRow;
+use std::collections::HashMap;
 use std::error::Error;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::time::SystemTime;
 use tokio_postgres::types::{FromSql, ToSql, Type};
-use tokio_postgres::Client;
+use tokio_postgres::Row;
+use deadpool_postgres::Pool;
 
+use crate::base::cifrador_de_cpf::CifradorDeCpf;
 use crate::base::domain_error::DomainError;
+use crate::base::paginacao::{Pagina, Paginacao};
 use crate::entities::cpf::Cpf;
+use crate::entities::documento_fiscal::DocumentoFiscal;
 use crate::entities::pedido::{Pedido, Status};
+use crate::entities::produto::Produto;
+use crate::entities::transicao_de_status::TransicaoDeStatus;
 use crate::traits::pedido_gateway::PedidoGateway;
+use std::str::FromStr;
 use crate::traits::produto_gateway::ProdutoGateway;
 
 use crate::external::postgres::pedido::ProxyPedido;
-use crate::external::postgres::table::Table;
+use crate::base::metricas;
 
-const CREATE_PEDIDO: &str = "INSERT INTO pedido (cliente_id, lanche_id, acompanhamento_id, bebida_id, pagamento, status, data_criacao, data_atualizacao) VALUES ($1, $2, $3, $4, $5, $6, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP) RETURNING id, cliente_id, lanche_id, acompanhamento_id, bebida_id, pagamento, CAST(status AS VARCHAR), data_criacao, data_atualizacao";
-const QUERY_PEDIDOS: &str = "SELECT id, cliente, lanche_id, acompanhamento_id, bebida_id, pagamento, CAST(status AS VARCHAR), data_criacao, data_atualizacao FROM pedido where  status  <> 'Finalizado' order by array_position(array['Pronto','EmPreparacao', 'Recebido'], CAST(status AS VARCHAR)), data_criacao asc";
-const QUERY_PEDIDO_BY_ID: &str = "SELECT id, cliente, lanche_id, acompanhamento_id, bebida_id, pagamento, CAST(status AS VARCHAR), data_criacao, data_atualizacao FROM pedido WHERE id = $1";
-const QUERY_PEDIDOS_NOVOS: &str = "SELECT id, cliente, lanche_id, acompanhamento_id, bebida_id, pagamento, CAST(status AS VARCHAR), data_criacao, data_atualizacao FROM pedido WHERE status IN ('Pendente', 'EmPreparacao')";
-const SET_PEDIDO_STATUS: &str = "UPDATE pedido SET status = $2, data_atualizacao = CURRENT_TIMESTAMP WHERE id = $1 RETURNING id, cliente, lanche_id, acompanhamento_id, bebida_id, pagamento, CAST(status AS VARCHAR), data_criacao, data_atualizacao";
-const SET_PEDIDO_PAGAMENTO_STATUS: &str = "UPDATE pedido SET pagamento = $2, status = $3, data_atualizacao = CURRENT_TIMESTAMP WHERE id = $1 RETURNING id, cliente, lanche_id, acompanhamento_id, bebida_id, pagamento, CAST(status AS VARCHAR), data_criacao, data_atualizacao";
+// A criação e as mudanças de status do pedido gravam também a transição, na mesma instrução.
+const CREATE_PEDIDO: &str = "WITH novo AS (INSERT INTO pedido (cliente, cliente_hash, documento_fiscal, documento_fiscal_hash, lanche_id, acompanhamento_id, bebida_id, lanche_preco, acompanhamento_preco, bebida_preco, pagamento, status, loja_id, data_criacao, data_atualizacao) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP) RETURNING id, cliente, documento_fiscal, lanche_id, acompanhamento_id, bebida_id, lanche_preco, acompanhamento_preco, bebida_preco, desconto, pagamento, CAST(status AS VARCHAR), loja_id, versao, data_criacao, data_atualizacao), transicao AS (INSERT INTO transicao_de_status (pedido_id, status, data_criacao) SELECT id, status, data_atualizacao FROM novo) SELECT * FROM novo";
+const QUERY_PEDIDOS: &str = "SELECT id, cliente, documento_fiscal, lanche_id, acompanhamento_id, bebida_id, lanche_preco, acompanhamento_preco, bebida_preco, desconto, pagamento, CAST(status AS VARCHAR), loja_id, versao, data_criacao, data_atualizacao FROM pedido where  status  <> 'Finalizado' AND loja_id = $1 order by array_position(array['Pronto','EmPreparacao', 'Recebido'], CAST(status AS VARCHAR)), data_criacao asc";
+const QUERY_PEDIDO_BY_ID: &str = "SELECT id, cliente, documento_fiscal, lanche_id, acompanhamento_id, bebida_id, lanche_preco, acompanhamento_preco, bebida_preco, desconto, pagamento, CAST(status AS VARCHAR), loja_id, versao, data_criacao, data_atualizacao FROM pedido WHERE id = $1";
+const QUERY_PEDIDO_DA_LOJA: &str = "SELECT id, cliente, documento_fiscal, lanche_id, acompanhamento_id, bebida_id, lanche_preco, acompanhamento_preco, bebida_preco, desconto, pagamento, CAST(status AS VARCHAR), loja_id, versao, data_criacao, data_atualizacao FROM pedido WHERE id = $1 AND loja_id = $2";
+const QUERY_PEDIDOS_NOVOS: &str = "SELECT id, cliente, documento_fiscal, lanche_id, acompanhamento_id, bebida_id, lanche_preco, acompanhamento_preco, bebida_preco, desconto, pagamento, CAST(status AS VARCHAR), loja_id, versao, data_criacao, data_atualizacao FROM pedido WHERE status IN ('Pendente', 'EmPreparacao') AND loja_id = $1";
+const SET_PEDIDO_STATUS: &str = "WITH atualizado AS (UPDATE pedido SET status = $2, versao = versao + 1, data_atualizacao = CURRENT_TIMESTAMP WHERE id = $1 AND versao = $3 RETURNING id, cliente, documento_fiscal, lanche_id, acompanhamento_id, bebida_id, lanche_preco, acompanhamento_preco, bebida_preco, desconto, pagamento, CAST(status AS VARCHAR), loja_id, versao, data_criacao, data_atualizacao), transicao AS (INSERT INTO transicao_de_status (pedido_id, status, data_criacao) SELECT id, status, data_atualizacao FROM atualizado) SELECT * FROM atualizado";
+const QUERY_PEDIDOS_DO_PERIODO: &str = "SELECT id, cliente, documento_fiscal, lanche_id, acompanhamento_id, bebida_id, lanche_preco, acompanhamento_preco, bebida_preco, desconto, pagamento, CAST(status AS VARCHAR), loja_id, versao, data_criacao, data_atualizacao FROM pedido WHERE loja_id = $1 AND data_criacao >= $2 AND data_criacao < $3 ORDER BY data_criacao, id";
+const QUERY_TRANSICOES_DO_PEDIDO: &str = "SELECT pedido_id, status, data_criacao FROM transicao_de_status WHERE pedido_id = $1 ORDER BY data_criacao, id";
+const QUERY_TRANSICOES_DO_PERIODO: &str = "SELECT transicao_de_status.pedido_id, transicao_de_status.status, transicao_de_status.data_criacao FROM transicao_de_status JOIN pedido ON pedido.id = transicao_de_status.pedido_id WHERE pedido.loja_id = $1 AND pedido.data_criacao >= $2 AND pedido.data_criacao < $3 ORDER BY transicao_de_status.data_criacao, transicao_de_status.id";
+const COUNT_PEDIDOS_POR_STATUS: &str = "SELECT CAST(status AS VARCHAR), COUNT(*) FROM pedido GROUP BY status";
+const PEDIDO_EXISTS: &str = "SELECT EXISTS (SELECT 1 FROM pedido WHERE id = $1)";
+const QUERY_PEDIDOS_DO_CLIENTE: &str = "SELECT id, cliente, documento_fiscal, lanche_id, acompanhamento_id, bebida_id, lanche_preco, acompanhamento_preco, bebida_preco, desconto, pagamento, CAST(status AS VARCHAR), loja_id, versao, data_criacao, data_atualizacao FROM pedido WHERE cliente_hash = $1 ORDER BY data_criacao DESC, id DESC LIMIT $2 OFFSET $3";
+const COUNT_PEDIDOS_DO_CLIENTE: &str = "SELECT COUNT(*) FROM pedido WHERE cliente_hash = $1";
+const QUERY_PEDIDOS_DO_TITULAR: &str = "SELECT id, cliente, documento_fiscal, lanche_id, acompanhamento_id, bebida_id, lanche_preco, acompanhamento_preco, bebida_preco, desconto, pagamento, CAST(status AS VARCHAR), loja_id, versao, data_criacao, data_atualizacao FROM pedido WHERE cliente_hash = $1 OR documento_fiscal_hash = $1 ORDER BY data_criacao DESC, id DESC";
+// Tira o CPF do titular tanto dos pedidos dele quanto das notas fiscais em que ele aparece.
+const ANONIMIZA_PEDIDOS_DO_CLIENTE: &str = "UPDATE pedido SET cliente = CASE WHEN cliente_hash = $1 THEN NULL ELSE cliente END, cliente_pseudonimo = CASE WHEN cliente_hash = $1 THEN $2 ELSE cliente_pseudonimo END, cliente_hash = CASE WHEN cliente_hash = $1 THEN NULL ELSE cliente_hash END, documento_fiscal = CASE WHEN documento_fiscal_hash = $1 THEN NULL ELSE documento_fiscal END, documento_fiscal_hash = CASE WHEN documento_fiscal_hash = $1 THEN NULL ELSE documento_fiscal_hash END, versao = versao + 1, data_atualizacao = CURRENT_TIMESTAMP WHERE cliente_hash = $1 OR documento_fiscal_hash = $1";
+const SET_PEDIDO_DESCONTO: &str = "UPDATE pedido SET desconto = $2, versao = versao + 1, data_atualizacao = CURRENT_TIMESTAMP WHERE id = $1 AND versao = $3 RETURNING id, cliente, documento_fiscal, lanche_id, acompanhamento_id, bebida_id, lanche_preco, acompanhamento_preco, bebida_preco, desconto, pagamento, CAST(status AS VARCHAR), loja_id, versao, data_criacao, data_atualizacao";
+const SET_PEDIDO_PAGAMENTO_STATUS: &str = "WITH atualizado AS (UPDATE pedido SET pagamento = $2, status = $3, versao = versao + 1, data_atualizacao = CURRENT_TIMESTAMP WHERE id = $1 RETURNING id, cliente, documento_fiscal, lanche_id, acompanhamento_id, bebida_id, lanche_preco, acompanhamento_preco, bebida_preco, desconto, pagamento, CAST(status AS VARCHAR), loja_id, versao, data_criacao, data_atualizacao), transicao AS (INSERT INTO transicao_de_status (pedido_id, status, data_criacao) SELECT id, status, data_atualizacao FROM atualizado) SELECT * FROM atualizado";
 
 impl<'a> FromSql<'a> for Status {
     fn from_sql(
@@ -77,169 +99,276 @@ impl ToSql for Status {
     }
 }
 
+// O preço gravado no pedido prevalece sobre o preço atual do produto (ex.: preço programado).
+fn com_preco_do_pedido(produto: Option<Produto>, preco: Option<f64>) -> Option<Produto> {
+    match (produto, preco) {
+        (Some(mut produto), Some(preco)) => {
+            produto.set_preco(preco).ok()?;
+            Some(produto)
+        }
+        (produto, _) => produto,
+    }
+}
+
 pub struct PostgresPedidoGateway {
-    client: Arc<Client>,
-    tables: Vec<Table>,
-    produto_repository: Arc<Mutex<dyn ProdutoGateway + Send + Sync>>,
+    pool: Pool,
+    produto_repository: Arc<dyn ProdutoGateway + Send + Sync>,
+    cifrador: CifradorDeCpf,
 }
 
 impl PostgresPedidoGateway {
-    pub async fn new(
-        client: Arc<Client>,
-        tables: Vec<Table>,
-        produto_repository: Arc<Mutex<dyn ProdutoGateway + Send + Sync>>,
+    pub fn new(
+        pool: Pool,
+        produto_repository: Arc<dyn ProdutoGateway + Send + Sync>,
+        cifrador: CifradorDeCpf,
     ) -> Self {
-        let repo = PostgresPedidoGateway {
-            client,
-            tables,
+        PostgresPedidoGateway {
+            pool,
             produto_repository,
-        };
-        repo.check_for_tables().await;
-        repo
+            cifrador,
+        }
     }
 
-    async fn check_for_tables(&self) {
-        for table in self.tables.iter() {
-            let query = table.get_create_if_not_exists_query();
-            self.client.execute(query.as_str(), &[]).await.unwrap();
+    // Busca os produtos com outra conexão do pool: quem chama devolve a sua antes, senão cada
+    // requisição segura duas conexões e, com o pool cheio, todas esperam umas pelas outras.
+    async fn pedidos_from_rows(&self, rows: &[Row]) -> Result<Vec<Pedido>, DomainError> {
+        let mut proxies: Vec<ProxyPedido> = rows.iter().map(ProxyPedido::from_row).collect();
+        // O CPF é gravado cifrado; daqui para dentro da aplicação ele circula em claro.
+        for proxy in proxies.iter_mut() {
+            if let Some(cliente) = proxy.cliente() {
+                let cpf = self.cifrador.decifra(cliente)?;
+                proxy.set_cliente(Some(cpf.get_string()));
+            }
+            if let Some(documento_fiscal) = proxy.documento_fiscal() {
+                let documento_fiscal = self.cifrador.decifra_documento(documento_fiscal)?;
+                proxy.set_documento_fiscal(Some(documento_fiscal.get_string()));
+            }
         }
+        carrega_pedidos(proxies, self.produto_repository.as_ref()).await
     }
 
-    async fn pedido_from_proxy(&self, pedido_row: &tokio_postgres::Row) -> Pedido {
-        let _pedido: ProxyPedido = ProxyPedido::from_row(&pedido_row);
-
-        let cliente = match _pedido.cliente() {
-            Some(_cliente) => {
-                match Cpf::new(_cliente.to_owned()) {
-                    Ok(cpf) => Some(cpf),
-                    Err(_) => None,
-                }
-            }
-            None => None,
-        };
+    async fn pedido_from_row(&self, row: &Row) -> Result<Pedido, DomainError> {
+        let mut pedidos = self.pedidos_from_rows(std::slice::from_ref(row)).await?;
+        Ok(pedidos.remove(0))
+    }
+}
 
-        let lanche = if let Some(lanche_id) = _pedido.lanche_id() {
-            let produto_repo = self.produto_repository.lock().await;
-            produto_repo.get_produto_by_id(*lanche_id).await.ok()
-        } else {
-            None
-        };
+// Monta os pedidos buscando de uma vez todos os produtos referenciados por eles, em vez de
+// uma consulta por item de cada pedido.
+pub async fn carrega_pedidos(
+    proxies: Vec<ProxyPedido>,
+    produto_repository: &(dyn ProdutoGateway + Send + Sync),
+) -> Result<Vec<Pedido>, DomainError> {
+    let mut ids: Vec<usize> = proxies
+        .iter()
+        .flat_map(|proxy| [proxy.lanche_id(), proxy.acompanhamento_id(), proxy.bebida_id()])
+        .flatten()
+        .copied()
+        .collect();
+    ids.sort_unstable();
+    ids.dedup();
 
-        let bebida = if let Some(bebida_id) = _pedido.bebida_id() {
-            let produto_repo = self.produto_repository.lock().await;
-            produto_repo.get_produto_by_id(*bebida_id).await.ok()
-        } else {
-            None
-        };
+    let produtos: HashMap<usize, Produto> = if ids.is_empty() {
+        HashMap::new()
+    } else {
+        produto_repository
+            .get_produtos_by_ids(ids)
+            .await?
+            .into_iter()
+            .map(|produto| (*produto.id(), produto))
+            .collect()
+    };
 
-        let acompanhamento = if let Some(acompanhamento_id) = _pedido.acompanhamento_id() {
-            let produto_repo = self.produto_repository.lock().await;
-            produto_repo
-                .get_produto_by_id(*acompanhamento_id)
-                .await
-                .ok()
-        } else {
-            None
-        };
+    Ok(proxies
+        .iter()
+        .map(|proxy| monta_pedido(proxy, &produtos))
+        .collect())
+}
 
-        Pedido::new(
-            *_pedido.id(),
-            cliente,
-            lanche,
-            acompanhamento,
-            bebida,
-            Some(_pedido.pagamento().clone()),
-            _pedido.status().clone(),
-            _pedido.data_criacao().clone(),
-            _pedido.data_atualizacao().clone(),
-        )
-    }
+fn monta_pedido(proxy: &ProxyPedido, produtos: &HashMap<usize, Produto>) -> Pedido {
+    let cliente = proxy
+        .cliente()
+        .and_then(|cliente| Cpf::new(cliente.to_owned()).ok());
+    let produto = |id: Option<&usize>, preco: Option<f64>| {
+        com_preco_do_pedido(id.and_then(|id| produtos.get(id)).cloned(), preco)
+    };
 
+    let mut pedido = Pedido::new(
+        *proxy.id(),
+        cliente,
+        produto(proxy.lanche_id(), proxy.lanche_preco()),
+        produto(proxy.acompanhamento_id(), proxy.acompanhamento_preco()),
+        produto(proxy.bebida_id(), proxy.bebida_preco()),
+        proxy.pagamento().cloned(),
+        proxy.status().clone(),
+        proxy.data_criacao().clone(),
+        proxy.data_atualizacao().clone(),
+    );
+    pedido.set_documento_fiscal(proxy.documento_fiscal().cloned().map(DocumentoFiscal));
+    pedido.set_desconto(proxy.desconto());
+    pedido.set_loja_id(*proxy.loja_id());
+    pedido.set_versao(proxy.versao());
+    pedido
 }
 
 #[async_trait]
 impl PedidoGateway for PostgresPedidoGateway {
-    async fn lista_pedidos(&mut self) -> Result<Vec<Pedido>, DomainError> {
-        let pedidos = self.client.query(QUERY_PEDIDOS, &[]).await.unwrap();
-        let mut pedidos_vec = Vec::new();
-        for pedido in pedidos {
-            let _pedido = self.pedido_from_proxy(&pedido).await;
-            pedidos_vec.push(_pedido.clone());
-        }
-        Ok(pedidos_vec)
+    async fn lista_pedidos(&self, loja_id: usize) -> Result<Vec<Pedido>, DomainError> {
+        let client = self.pool.get().await?;
+        let loja_id = loja_id as i32;
+        let pedidos = metricas::mede_consulta(
+            "query_pedidos",
+            client.query(QUERY_PEDIDOS, &[&loja_id]),
+        )
+        .await?;
+        drop(client);
+        self.pedidos_from_rows(&pedidos).await
     }
 
-    async fn get_pedidos_novos(&self) -> Result<Vec<Pedido>, DomainError> {
-        let pedidos_rows = self.client.query(QUERY_PEDIDOS_NOVOS, &[]).await.unwrap();
-        let mut pedidos_vec = Vec::new();
+    async fn get_pedidos_novos(&self, loja_id: usize) -> Result<Vec<Pedido>, DomainError> {
+        let client = self.pool.get().await?;
+        let loja_id = loja_id as i32;
+        let pedidos_rows = metricas::mede_consulta(
+            "query_pedidos_novos",
+            client.query(QUERY_PEDIDOS_NOVOS, &[&loja_id]),
+        )
+        .await?;
+        drop(client);
+        self.pedidos_from_rows(&pedidos_rows).await
+    }
 
-        for pedido_row in pedidos_rows {
-            let pedido = self.pedido_from_proxy(&pedido_row).await;
-            pedidos_vec.push(pedido);
-        }
+    #[tracing::instrument(skip(self))]
+    async fn atualiza_status(&self, id: usize, status: Status, versao: usize) -> Result<Pedido, DomainError> {
+        let client = self.pool.get().await?;
+        let _id = id as i32;
+        let versao = versao as i32;
+        let updated_pedido = metricas::mede_consulta(
+            "set_pedido_status",
+            client.query(SET_PEDIDO_STATUS, &[&_id, &status, &versao]),
+        )
+        .await?;
 
-        Ok(pedidos_vec)
+        match updated_pedido.first() {
+            Some(pedido) => {
+                drop(client);
+                self.pedido_from_row(pedido).await
+            }
+            // Nenhuma linha atualizada: o pedido não existe ou outra requisição já o alterou.
+            None => {
+                let existe: bool = client.query_one(PEDIDO_EXISTS, &[&_id]).await?.get(0);
+                Err(if existe { DomainError::Conflict } else { DomainError::NotFound })
+            }
+        }
     }
 
-    async fn atualiza_status(&mut self, id: usize, status: Status) -> Result<Pedido, DomainError> {
+    #[tracing::instrument(skip(self))]
+    async fn atualiza_pagamento_status(&self, id: usize, pagamento_id: String, status: Status) -> Result<Pedido, DomainError> {
+        let client = self.pool.get().await?;
         let _id = id as i32;
-        let updated_pedido = self
-            .client
-            .query(SET_PEDIDO_STATUS, &[&_id, &status])
-            .await
-            .unwrap();
+        let updated_pedido = metricas::mede_consulta(
+            "set_pedido_pagamento_status",
+            client.query(SET_PEDIDO_PAGAMENTO_STATUS, &[&_id, &pagamento_id, &status]),
+        )
+        .await?;
+        drop(client);
 
         let updated_pedido = updated_pedido.get(0);
         match updated_pedido {
-            Some(pedido) => Ok(self.pedido_from_proxy(&pedido).await),
+            Some(pedido) => self.pedido_from_row(pedido).await,
             None => Err(DomainError::NotFound),
         }
     }
 
-    async fn atualiza_pagamento_status(&mut self, id: usize, pagamento_id: String, status: Status) -> Result<Pedido, DomainError> {
+    #[tracing::instrument(skip(self))]
+    async fn aplica_desconto(&self, id: usize, desconto: f64, versao: usize) -> Result<Pedido, DomainError> {
+        let client = self.pool.get().await?;
         let _id = id as i32;
-        let updated_pedido = self
-            .client
-            .query(SET_PEDIDO_PAGAMENTO_STATUS, &[&_id, &pagamento_id, &status])
-            .await?;
+        let versao = versao as i32;
+        let updated_pedido = metricas::mede_consulta(
+            "set_pedido_desconto",
+            client.query(SET_PEDIDO_DESCONTO, &[&_id, &desconto, &versao]),
+        )
+        .await?;
 
-        let updated_pedido = updated_pedido.get(0);
-        match updated_pedido {
-            Some(pedido) => Ok(self.pedido_from_proxy(&pedido).await),
-            None => Err(DomainError::NotFound),
+        match updated_pedido.first() {
+            Some(pedido) => {
+                drop(client);
+                self.pedido_from_row(pedido).await
+            }
+            None => {
+                let existe: bool = client.query_one(PEDIDO_EXISTS, &[&_id]).await?.get(0);
+                Err(if existe { DomainError::Conflict } else { DomainError::NotFound })
+            }
         }
     }
 
-    async fn create_pedido(&mut self, pedido: Pedido) -> Result<Pedido, DomainError> {
-        let cliente_id = match pedido.cliente() {
-            Some(cliente) => cliente.get_string(),
-            None => "".to_string(),
-        };
+    #[tracing::instrument(skip_all)]
+    async fn anonimiza_pedidos_do_cliente(
+        &self,
+        cpf: &Cpf,
+        pseudonimo: &str,
+    ) -> Result<usize, DomainError> {
+        let client = self.pool.get().await?;
+        let anonimizados = metricas::mede_consulta(
+            "anonimiza_pedidos_do_cliente",
+            client.execute(ANONIMIZA_PEDIDOS_DO_CLIENTE, &[&self.cifrador.hash(cpf), &pseudonimo]),
+        )
+        .await?;
+        Ok(anonimizados as usize)
+    }
+
+    #[tracing::instrument(skip_all, fields(loja_id = *pedido.loja_id()))]
+    async fn create_pedido(&self, pedido: Pedido) -> Result<Pedido, DomainError> {
+        let client = self.pool.get().await?;
+        // Pedido do cliente anônimo é gravado sem cliente.
+        let cliente_id = pedido.cliente().map(|cliente| self.cifrador.cifra(cliente));
+        let cliente_hash = pedido.cliente().map(|cliente| self.cifrador.hash(cliente));
+        let documento_fiscal = pedido
+            .documento_fiscal()
+            .map(|documento| self.cifrador.cifra_documento(documento));
+        let documento_fiscal_hash = pedido
+            .documento_fiscal()
+            .map(|documento| self.cifrador.hash_documento(documento));
         let lanche_id = pedido.lanche().map(|lanche| *lanche.id() as i32);
         let acompanhamento_id = pedido
             .acompanhamento()
             .map(|acompanhamento| *acompanhamento.id() as i32);
         let bebida_id = pedido.bebida().map(|bebida| *bebida.id() as i32);
+        let lanche_preco = pedido.lanche().map(|lanche| lanche.preco());
+        let acompanhamento_preco = pedido
+            .acompanhamento()
+            .map(|acompanhamento| acompanhamento.preco());
+        let bebida_preco = pedido.bebida().map(|bebida| bebida.preco());
 
         let status = pedido.status();
-        let new_pedido_row = self
-            .client
-            .query_one(
+        let loja_id = *pedido.loja_id() as i32;
+        let new_pedido_row = metricas::mede_consulta(
+            "create_pedido",
+            client.query_one(
                 CREATE_PEDIDO,
                 &[
                     &cliente_id,
+                    &cliente_hash,
+                    &documento_fiscal,
+                    &documento_fiscal_hash,
                     &lanche_id,
                     &acompanhamento_id,
                     &bebida_id,
+                    &lanche_preco,
+                    &acompanhamento_preco,
+                    &bebida_preco,
                     &pedido.pagamento(),
                     &status,
+                    &loja_id,
                 ],
-            )
-            .await;
+            ),
+        )
+        .await;
+        drop(client);
         match new_pedido_row {
             Ok(row) => {
-                let new_pedido = self.pedido_from_proxy(&row).await;
-                println!("Novo pedido cadastrado: {:?}", new_pedido);
+                let new_pedido = self.pedido_from_row(&row).await?;
+                tracing::info!(pedido_id = *new_pedido.id(), "Novo pedido cadastrado");
                 Ok(new_pedido)
             }
             Err(_) => Err(DomainError::Invalid("Pedido".to_string())),
@@ -247,19 +376,276 @@ impl PedidoGateway for PostgresPedidoGateway {
     }
 
     async fn get_pedido_by_id(&self, pedido_id: usize) -> Result<Pedido, DomainError> {
+        let client = self.pool.get().await?;
         let pedido_id = pedido_id as i32;
-        let pedido_row_result = self
-            .client
-            .query_opt(QUERY_PEDIDO_BY_ID, &[&pedido_id])
-            .await;
+        let pedido_row_result = metricas::mede_consulta(
+            "query_pedido_by_id",
+            client.query_opt(QUERY_PEDIDO_BY_ID, &[&pedido_id]),
+        )
+        .await;
+        drop(client);
 
         match pedido_row_result {
-            Ok(Some(row)) => {
-                let pedido = self.pedido_from_proxy(&row).await;
-                Ok(pedido)
-            }
+            Ok(Some(row)) => self.pedido_from_row(&row).await,
             Ok(None) => Err(DomainError::NotFound),
             Err(_) => Err(DomainError::Invalid("Pedido".to_string())),
         }
     }
+
+    async fn get_pedido_da_loja(&self, pedido_id: usize, loja_id: usize) -> Result<Pedido, DomainError> {
+        let client = self.pool.get().await?;
+        let pedido_id = pedido_id as i32;
+        let loja_id = loja_id as i32;
+        let pedido_row_result = metricas::mede_consulta(
+            "query_pedido_da_loja",
+            client.query_opt(QUERY_PEDIDO_DA_LOJA, &[&pedido_id, &loja_id]),
+        )
+        .await;
+        drop(client);
+
+        match pedido_row_result {
+            Ok(Some(row)) => self.pedido_from_row(&row).await,
+            Ok(None) => Err(DomainError::NotFound),
+            Err(_) => Err(DomainError::Invalid("Pedido".to_string())),
+        }
+    }
+
+    async fn lista_pedidos_do_cliente(
+        &self,
+        cpf: &Cpf,
+        paginacao: Paginacao,
+    ) -> Result<Pagina<Pedido>, DomainError> {
+        let client = self.pool.get().await?;
+        let cpf = self.cifrador.hash(cpf);
+        let total: i64 = metricas::mede_consulta(
+            "count_pedidos_do_cliente",
+            client.query_one(COUNT_PEDIDOS_DO_CLIENTE, &[&cpf]),
+        )
+        .await?
+        .get(0);
+        let limite = paginacao.tamanho() as i64;
+        let deslocamento = paginacao.deslocamento() as i64;
+        let pedidos_rows = metricas::mede_consulta(
+            "query_pedidos_do_cliente",
+            client.query(QUERY_PEDIDOS_DO_CLIENTE, &[&cpf, &limite, &deslocamento]),
+        )
+        .await?;
+        drop(client);
+        let pedidos = self.pedidos_from_rows(&pedidos_rows).await?;
+        Ok(Pagina::new(pedidos, &paginacao, total as usize))
+    }
+
+    async fn pedidos_do_titular(&self, cpf: &Cpf) -> Result<Vec<Pedido>, DomainError> {
+        let client = self.pool.get().await?;
+        // O hash de um documento fiscal com CPF é igual ao hash do CPF.
+        let pedidos_rows = metricas::mede_consulta(
+            "query_pedidos_do_titular",
+            client.query(QUERY_PEDIDOS_DO_TITULAR, &[&self.cifrador.hash(cpf)]),
+        )
+        .await?;
+        drop(client);
+        self.pedidos_from_rows(&pedidos_rows).await
+    }
+
+    async fn conta_pedidos_por_status(&self) -> Result<Vec<(Status, usize)>, DomainError> {
+        let client = self.pool.get().await?;
+        let rows = metricas::mede_consulta(
+            "count_pedidos_por_status",
+            client.query(COUNT_PEDIDOS_POR_STATUS, &[]),
+        )
+        .await?;
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let status: String = row.get(0);
+                let quantidade: i64 = row.get(1);
+                (
+                    Status::from_str(&status).unwrap_or(Status::Invalido),
+                    quantidade as usize,
+                )
+            })
+            .collect())
+    }
+
+    async fn pedidos_do_periodo(
+        &self,
+        loja_id: usize,
+        inicio: DateTime<Utc>,
+        fim: DateTime<Utc>,
+    ) -> Result<Vec<Pedido>, DomainError> {
+        let client = self.pool.get().await?;
+        let loja_id = loja_id as i32;
+        let inicio = SystemTime::from(inicio);
+        let fim = SystemTime::from(fim);
+        let rows = metricas::mede_consulta(
+            "query_pedidos_do_periodo",
+            client.query(QUERY_PEDIDOS_DO_PERIODO, &[&loja_id, &inicio, &fim]),
+        )
+        .await?;
+        drop(client);
+        self.pedidos_from_rows(&rows).await
+    }
+
+    async fn transicoes_do_pedido(&self, pedido_id: usize) -> Result<Vec<TransicaoDeStatus>, DomainError> {
+        let client = self.pool.get().await?;
+        let pedido_id = pedido_id as i32;
+        let rows = metricas::mede_consulta(
+            "query_transicoes_do_pedido",
+            client.query(QUERY_TRANSICOES_DO_PEDIDO, &[&pedido_id]),
+        )
+        .await?;
+        Ok(rows.iter().map(TransicaoDeStatus::from_row).collect())
+    }
+
+    async fn transicoes_do_periodo(
+        &self,
+        loja_id: usize,
+        inicio: DateTime<Utc>,
+        fim: DateTime<Utc>,
+    ) -> Result<Vec<TransicaoDeStatus>, DomainError> {
+        let client = self.pool.get().await?;
+        let loja_id = loja_id as i32;
+        let inicio = SystemTime::from(inicio);
+        let fim = SystemTime::from(fim);
+        let rows = metricas::mede_consulta(
+            "query_transicoes_do_periodo",
+            client.query(QUERY_TRANSICOES_DO_PERIODO, &[&loja_id, &inicio, &fim]),
+        )
+        .await?;
+        Ok(rows.iter().map(TransicaoDeStatus::from_row).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::ingredientes::Ingredientes;
+    use crate::external::postgres::pedido::DadosDoPedido;
+    use crate::external::postgres::BancoDeTeste;
+    use crate::gateways::postgres_produto_gateway::PostgresProdutoRepository;
+    use crate::traits::produto_gateway::MockProdutoGateway;
+
+    fn produto(id: usize, preco: f64) -> Produto {
+        Produto::new(
+            id,
+            format!("Produto {}", id),
+            "produto.png".to_string(),
+            "Descrição".to_string(),
+            1,
+            preco,
+            Ingredientes::new(vec!["Pão".to_string()]).unwrap(),
+            "2024-01-17".to_string(),
+            "2024-01-17".to_string(),
+        )
+    }
+
+    fn proxy(id: usize, lanche_id: Option<usize>, bebida_id: Option<usize>) -> ProxyPedido {
+        let dados = DadosDoPedido {
+            cliente: None,
+            lanche_id,
+            acompanhamento_id: None,
+            bebida_id,
+            lanche_preco: Some(12.5),
+            acompanhamento_preco: None,
+            bebida_preco: None,
+            desconto: 0.0,
+            pagamento: None,
+            status: Status::Pendente,
+            loja_id: 1,
+            versao: 1,
+        };
+        ProxyPedido::new(
+            id,
+            dados,
+            "2024-01-17".to_string(),
+            "2024-01-17".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_carrega_pedidos_busca_produtos_numa_unica_consulta() {
+        let mut mock = MockProdutoGateway::new();
+        mock.expect_get_produto_by_id().never();
+        mock.expect_get_produtos_by_ids()
+            .times(1)
+            .withf(|ids| ids == &vec![1, 2])
+            .returning(|ids| Ok(ids.into_iter().map(|id| produto(id, 9.99)).collect()));
+
+        let proxies = (0..50).map(|id| proxy(id, Some(1), Some(2))).collect();
+        let pedidos = carrega_pedidos(proxies, &mock).await.unwrap();
+
+        assert_eq!(pedidos.len(), 50);
+        assert_eq!(*pedidos[49].id(), 49);
+        assert_eq!(pedidos[0].lanche().unwrap().preco(), 12.5);
+        assert_eq!(pedidos[0].bebida().unwrap().preco(), 9.99);
+        assert!(pedidos[0].acompanhamento().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_carrega_pedidos_sem_produtos_nao_consulta() {
+        let mut mock = MockProdutoGateway::new();
+        mock.expect_get_produtos_by_ids().never();
+
+        let pedidos = carrega_pedidos(vec![proxy(1, None, None)], &mock).await.unwrap();
+
+        assert_eq!(pedidos.len(), 1);
+        assert!(pedidos[0].lanche().is_none());
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_exporta_e_anonimiza_pedidos_e_notas_do_titular() {
+        let banco = BancoDeTeste::migrado("pedidos_do_titular", 2).await;
+        let pedido_repository = PostgresPedidoGateway::new(
+            banco.pool.clone(),
+            Arc::new(PostgresProdutoRepository::new(banco.pool.clone())),
+            CifradorDeCpf::new("chave"),
+        );
+        let cpf = Cpf::new("123.456.789-09".to_string()).unwrap();
+        let cnpj = DocumentoFiscal::new("11.222.333/0001-81".to_string()).unwrap();
+
+        let mut ids = Vec::new();
+        for (cliente, documento_fiscal) in [
+            (Some(cpf.clone()), Some(cnpj.clone())),
+            (None, DocumentoFiscal::new(cpf.get_string()).ok()),
+            (None, Some(cnpj.clone())),
+        ] {
+            let mut pedido = Pedido::new(
+                0,
+                cliente,
+                None,
+                None,
+                None,
+                None,
+                Status::Pendente,
+                String::new(),
+                String::new(),
+            );
+            pedido.set_documento_fiscal(documento_fiscal);
+            ids.push(*pedido_repository.create_pedido(pedido).await.unwrap().id());
+        }
+
+        let do_titular = pedido_repository.pedidos_do_titular(&cpf).await.unwrap();
+        let anonimizados = pedido_repository.anonimiza_pedidos_do_cliente(&cpf, "anon_1").await.unwrap();
+        let depois = pedido_repository.pedidos_do_titular(&cpf).await.unwrap();
+        let pseudonimo: Option<String> = banco
+            .pool
+            .get()
+            .await
+            .unwrap()
+            .query_one("SELECT cliente_pseudonimo FROM pedido WHERE id = $1", &[&(ids[0] as i32)])
+            .await
+            .unwrap()
+            .get(0);
+        let nota_da_empresa = pedido_repository.get_pedido_by_id(ids[2]).await.unwrap();
+        banco.apaga().await;
+
+        let mut exportados: Vec<usize> = do_titular.iter().map(|pedido| *pedido.id()).collect();
+        exportados.sort();
+        assert_eq!(exportados, vec![ids[0], ids[1]]);
+        assert_eq!(anonimizados, 2);
+        assert!(depois.is_empty());
+        assert_eq!(pseudonimo.as_deref(), Some("anon_1"));
+        assert_eq!(nota_da_empresa.documento_fiscal(), Some(&cnpj));
+    }
 }
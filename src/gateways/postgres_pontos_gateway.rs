@@ -0,0 +1,226 @@
+use chrono::Utc;
+use postgres_from_row::FromRow;
+use deadpool_postgres::Pool;
+use tokio_postgres::Row;
+
+use crate::{
+    base::{cifrador_de_cpf::CifradorDeCpf, domain_error::DomainError},
+    entities::{
+        cpf::Cpf,
+        pontos::{MovimentoDePontos, SaldoDePontos},
+    },
+    traits::pontos_gateway::PontosGateway,
+};
+
+use crate::base::metricas;
+
+pub struct PostgresPontosRepository {
+    pool: Pool,
+    cifrador: CifradorDeCpf,
+}
+
+const CREATE_MOVIMENTO: &str = "INSERT INTO movimento_de_pontos (cliente, cliente_hash, pedido_id, tipo, pontos, validade, data_criacao) VALUES ($1, $2, $3, $4, $5, $6::TEXT::TIMESTAMPTZ, CURRENT_TIMESTAMP) ON CONFLICT (pedido_id, tipo) DO NOTHING RETURNING id, cliente, pedido_id, tipo, pontos, validade, data_criacao";
+const QUERY_MOVIMENTOS_DO_CLIENTE: &str = "SELECT id, cliente, pedido_id, tipo, pontos, validade, data_criacao FROM movimento_de_pontos WHERE cliente_hash = $1 ORDER BY data_criacao, id";
+const QUERY_MOVIMENTOS_DO_PEDIDO: &str = "SELECT id, cliente, pedido_id, tipo, pontos, validade, data_criacao FROM movimento_de_pontos WHERE pedido_id = $1 ORDER BY data_criacao, id";
+const DELETE_MOVIMENTOS_DO_CLIENTE: &str = "DELETE FROM movimento_de_pontos WHERE cliente_hash = $1";
+// Trava os resgates do cliente até o fim da transação.
+const LOCK_RESGATES_DO_CLIENTE: &str = "SELECT pg_advisory_xact_lock(hashtext($1))";
+
+impl PostgresPontosRepository {
+    pub fn new(pool: Pool, cifrador: CifradorDeCpf) -> Self {
+        PostgresPontosRepository { pool, cifrador }
+    }
+
+    // O CPF vem cifrado do banco.
+    fn movimento_from_row(&self, row: &Row) -> Result<MovimentoDePontos, DomainError> {
+        let mut movimento = MovimentoDePontos::from_row(row);
+        movimento.set_cliente(self.cifrador.decifra(&movimento.cliente().0)?);
+        Ok(movimento)
+    }
+}
+
+#[async_trait]
+impl PontosGateway for PostgresPontosRepository {
+    #[tracing::instrument(skip_all, fields(pedido_id = *movimento.pedido_id(), tipo = %movimento.tipo()))]
+    async fn registra_movimento(
+        &self,
+        movimento: MovimentoDePontos,
+    ) -> Result<MovimentoDePontos, DomainError> {
+        let client = self.pool.get().await?;
+        let pedido_id = *movimento.pedido_id() as i32;
+        let novo_movimento = metricas::mede_consulta(
+            "create_movimento_de_pontos",
+            client.query_opt(
+                CREATE_MOVIMENTO,
+                &[
+                    &self.cifrador.cifra(movimento.cliente()),
+                    &self.cifrador.hash(movimento.cliente()),
+                    &pedido_id,
+                    &movimento.tipo().to_string(),
+                    &movimento.pontos(),
+                    &movimento.validade(),
+                ],
+            ),
+        )
+        .await?;
+        match novo_movimento {
+            Some(movimento) => self.movimento_from_row(&movimento),
+            None => Err(DomainError::AlreadyExists),
+        }
+    }
+
+    async fn movimentos_do_cliente(&self, cpf: &Cpf) -> Result<Vec<MovimentoDePontos>, DomainError> {
+        let client = self.pool.get().await?;
+        let movimentos = metricas::mede_consulta(
+            "query_movimentos_do_cliente",
+            client.query(QUERY_MOVIMENTOS_DO_CLIENTE, &[&self.cifrador.hash(cpf)]),
+        )
+        .await?;
+        movimentos
+            .iter()
+            .map(|movimento| self.movimento_from_row(movimento))
+            .collect()
+    }
+
+    async fn movimentos_do_pedido(&self, pedido_id: usize) -> Result<Vec<MovimentoDePontos>, DomainError> {
+        let client = self.pool.get().await?;
+        let pedido_id = pedido_id as i32;
+        let movimentos = metricas::mede_consulta(
+            "query_movimentos_do_pedido",
+            client.query(QUERY_MOVIMENTOS_DO_PEDIDO, &[&pedido_id]),
+        )
+        .await?;
+        movimentos
+            .iter()
+            .map(|movimento| self.movimento_from_row(movimento))
+            .collect()
+    }
+
+    #[tracing::instrument(skip_all, fields(pedido_id = *resgate.pedido_id()))]
+    async fn registra_resgate(
+        &self,
+        resgate: MovimentoDePontos,
+    ) -> Result<Option<MovimentoDePontos>, DomainError> {
+        let mut client = self.pool.get().await?;
+        let cliente_hash = self.cifrador.hash(resgate.cliente());
+        let pedido_id = *resgate.pedido_id() as i32;
+        // Sem saldo ou com erro, a transação é desfeita ao ser descartada.
+        let transacao = client.transaction().await?;
+        transacao.execute(LOCK_RESGATES_DO_CLIENTE, &[&cliente_hash]).await?;
+        let movimentos = metricas::mede_consulta(
+            "query_movimentos_do_cliente",
+            transacao.query(QUERY_MOVIMENTOS_DO_CLIENTE, &[&cliente_hash]),
+        )
+        .await?
+        .iter()
+        .map(|movimento| self.movimento_from_row(movimento))
+        .collect::<Result<Vec<MovimentoDePontos>, DomainError>>()?;
+        if SaldoDePontos::calcula(&movimentos, &Utc::now().fixed_offset()).saldo < -resgate.pontos() {
+            return Ok(None);
+        }
+
+        let novo_movimento = metricas::mede_consulta(
+            "create_movimento_de_pontos",
+            transacao.query_opt(
+                CREATE_MOVIMENTO,
+                &[
+                    &self.cifrador.cifra(resgate.cliente()),
+                    &cliente_hash,
+                    &pedido_id,
+                    &resgate.tipo().to_string(),
+                    &resgate.pontos(),
+                    &resgate.validade(),
+                ],
+            ),
+        )
+        .await?;
+        transacao.commit().await?;
+        match novo_movimento {
+            Some(movimento) => self.movimento_from_row(&movimento).map(Some),
+            None => Err(DomainError::AlreadyExists),
+        }
+    }
+
+    async fn remove_movimentos_do_cliente(&self, cpf: &Cpf) -> Result<usize, DomainError> {
+        let client = self.pool.get().await?;
+        let removidos = metricas::mede_consulta(
+            "delete_movimentos_do_cliente",
+            client.execute(DELETE_MOVIMENTOS_DO_CLIENTE, &[&self.cifrador.hash(cpf)]),
+        )
+        .await?;
+        Ok(removidos as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{cliente::Cliente, pontos::TipoDeMovimento};
+    use crate::external::postgres::BancoDeTeste;
+    use crate::gateways::postgres_cliente_gateway::PostgresClienteRepository;
+    use crate::traits::cliente_gateway::ClienteGateway;
+    use std::sync::Arc;
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[ignore]
+    async fn test_resgates_simultaneos_nao_gastam_os_mesmos_pontos() {
+        let banco = BancoDeTeste::migrado("resgates_simultaneos", 10).await;
+        let cifrador = CifradorDeCpf::new("chave");
+        let cpf = Cpf::new("123.456.789-09".to_string()).unwrap();
+        PostgresClienteRepository::new(banco.pool.clone(), cifrador.clone())
+            .create_cliente(Cliente::new(cpf.clone(), "Maria".to_string(), None, false, false, String::new(), String::new()))
+            .await
+            .unwrap();
+        let pedidos: Vec<i32> = banco
+            .pool
+            .get()
+            .await
+            .unwrap()
+            .query("INSERT INTO pedido (status) SELECT 'Pendente'::status_pedido_enum FROM generate_series(1, 11) RETURNING id", &[])
+            .await
+            .unwrap()
+            .iter()
+            .map(|row| row.get(0))
+            .collect();
+        let pontos_repository = Arc::new(PostgresPontosRepository::new(banco.pool.clone(), cifrador));
+        pontos_repository
+            .registra_movimento(MovimentoDePontos::new(
+                0,
+                cpf.clone(),
+                pedidos[0] as usize,
+                TipoDeMovimento::Acumulo,
+                10,
+                Some("2099-01-01 00:00:00.000+0000".to_string()),
+                String::new(),
+            ))
+            .await
+            .unwrap();
+
+        // 10 pontos de saldo e 10 resgates de 10 pontos ao mesmo tempo: só um passa.
+        let resgates: Vec<_> = pedidos[1..]
+            .iter()
+            .map(|pedido_id| {
+                let pontos_repository = pontos_repository.clone();
+                let resgate = MovimentoDePontos::new(
+                    0,
+                    cpf.clone(),
+                    *pedido_id as usize,
+                    TipoDeMovimento::Resgate,
+                    -10,
+                    None,
+                    String::new(),
+                );
+                tokio::spawn(async move { pontos_repository.registra_resgate(resgate).await })
+            })
+            .collect();
+        let mut registrados = 0;
+        for resgate in resgates {
+            if resgate.await.unwrap().unwrap().is_some() {
+                registrados += 1;
+            }
+        }
+        banco.apaga().await;
+
+        assert_eq!(registrados, 1);
+    }
+}
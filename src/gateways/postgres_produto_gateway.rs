@@ -1,97 +1,104 @@
-use bytes::BytesMut;
 use postgres_from_row::FromRow;
-use std::error::Error;
-use std::sync::Arc;
-use tokio_postgres::types::{FromSql, ToSql, Type};
-use tokio_postgres::Client;
+use deadpool_postgres::{Pool, Transaction};
 
 use crate::{
-    base::domain_error::DomainError, entities::produto::Categoria, entities::produto::Produto,
+    base::domain_error::DomainError,
+    entities::{idioma::{Idioma, TraducaoProduto}, loja::ProdutoLoja, produto::Produto},
     traits::produto_gateway::ProdutoGateway,
 };
 
-use crate::external::postgres::table::Table;
+use crate::base::metricas;
 
 pub struct PostgresProdutoRepository {
-    client: Arc<Client>,
-    tables: Vec<Table>,
+    pool: Pool,
 }
 
-const CREATE_PRODUCT: &str = "INSERT INTO produto (nome, foto, descricao, categoria, preco, ingredientes, data_criacao, data_atualizacao) VALUES ($1, $2, $3, $4, $5, $6, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP) RETURNING id, nome, foto, descricao, CAST(categoria AS VARCHAR) AS categoria, preco, ingredientes, data_criacao, data_atualizacao";
-const QUERY_PRODUCT_BY_ID: &str = "SELECT id, nome, foto, descricao, CAST(categoria AS VARCHAR) AS categoria, preco, ingredientes, data_criacao, data_atualizacao FROM produto WHERE id = $1";
-const QUERY_PRODUCTS: &str = "SELECT id, nome, foto, descricao, CAST(categoria AS VARCHAR) AS categoria, preco, ingredientes, data_criacao, data_atualizacao FROM produto";
-const UPDATE_PRODUCT: &str = "UPDATE produto SET nome = $1, foto = $2, descricao = $3, categoria = $4, preco = $5, ingredientes = $6, data_atualizacao = CURRENT_TIMESTAMP WHERE id = $7 RETURNING id, nome, foto, descricao, CAST(categoria AS VARCHAR) AS categoria, preco, ingredientes, data_criacao, data_atualizacao";
-const DELETE_PRODUCT: &str = "DELETE FROM produto WHERE id = $1 RETURNING id, nome, foto, descricao, CAST(categoria AS VARCHAR) AS categoria, preco, ingredientes, data_criacao, data_atualizacao";
-
-impl<'a> FromSql<'a> for Categoria {
-    fn from_sql(
-        _ty: &tokio_postgres::types::Type,
-        raw: &'a [u8],
-    ) -> Result<Self, Box<dyn Error + Sync + Send>> {
-        let value = std::str::from_utf8(raw)?;
-
-        match value {
-            "Lanche" => Ok(Categoria::Lanche),
-            "Bebida" => Ok(Categoria::Bebida),
-            "Acompanhamento" => Ok(Categoria::Acompanhamento),
-            "Sobremesa" => Ok(Categoria::Sobremesa),
-            _ => Err("Invalid categoria value".into()),
-        }
-    }
-    fn accepts(_ty: &tokio_postgres::types::Type) -> bool {
-        true
-    }
-}
-
-impl ToSql for Categoria {
-    fn to_sql(
-        &self,
-        _ty: &Type,
-        out: &mut BytesMut,
-    ) -> Result<tokio_postgres::types::IsNull, Box<dyn std::error::Error + 'static + Send + Sync>>
-    {
-        match self {
-            Categoria::Lanche => out.extend_from_slice(b"Lanche"),
-            Categoria::Bebida => out.extend_from_slice(b"Bebida"),
-            Categoria::Acompanhamento => out.extend_from_slice(b"Acompanhamento"),
-            Categoria::Sobremesa => out.extend_from_slice(b"Sobremesa"),
-        }
-        Ok(tokio_postgres::types::IsNull::No)
-    }
-
-    fn accepts(_ty: &Type) -> bool {
-        true
-    }
-
-    fn to_sql_checked(
-        &self,
-        ty: &Type,
-        out: &mut BytesMut,
-    ) -> Result<tokio_postgres::types::IsNull, Box<dyn std::error::Error + 'static + Send + Sync>>
-    {
-        self.to_sql(ty, out)
-    }
-}
+const CREATE_PRODUCT: &str = "INSERT INTO produto (nome, foto, descricao, categoria_id, preco, ingredientes, loja_id, data_criacao, data_atualizacao) VALUES ($1, $2, $3, $4, $5, $6, $7, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP) RETURNING id, nome, foto, descricao, categoria_id, preco, ingredientes, loja_id, versao, data_criacao, data_atualizacao";
+const QUERY_PRODUCT_BY_ID: &str = "SELECT id, nome, foto, descricao, categoria_id, preco, ingredientes, loja_id, versao, data_criacao, data_atualizacao FROM produto WHERE id = $1";
+const QUERY_PRODUCTS_BY_IDS: &str = "SELECT id, nome, foto, descricao, categoria_id, preco, ingredientes, loja_id, versao, data_criacao, data_atualizacao FROM produto WHERE id = ANY($1)";
+const QUERY_PRODUCTS: &str = "SELECT id, nome, foto, descricao, categoria_id, preco, ingredientes, loja_id, versao, data_criacao, data_atualizacao FROM produto";
+const SEARCH_PRODUCTS: &str = "SELECT id, nome, foto, descricao, categoria_id, preco, ingredientes, loja_id, versao, data_criacao, data_atualizacao FROM (SELECT produto.*, setweight(to_tsvector('portuguese', unaccent(nome)), 'A') || setweight(to_tsvector('portuguese', unaccent(descricao)), 'B') || setweight(to_tsvector('portuguese', unaccent(array_to_string(ingredientes, ' '))), 'C') AS documento, websearch_to_tsquery('portuguese', unaccent($1)) AS consulta, unaccent(lower($1)) AS termo FROM (SELECT produto.id, produto.nome, produto.foto, produto.descricao, produto.categoria_id, COALESCE(produto_loja.preco, produto.preco) AS preco, produto.ingredientes, produto.loja_id, produto.versao, produto.data_criacao, produto.data_atualizacao FROM produto LEFT JOIN produto_loja ON produto_loja.produto_id = produto.id AND produto_loja.loja_id = $2 WHERE (produto.loja_id IS NULL OR produto.loja_id = $2) AND COALESCE(produto_loja.disponivel, TRUE)) produto) busca WHERE documento @@ consulta OR word_similarity(termo, unaccent(lower(nome || ' ' || descricao || ' ' || array_to_string(ingredientes, ' ')))) >= 0.5 ORDER BY ts_rank(documento, consulta) + word_similarity(termo, unaccent(lower(nome))) DESC, nome";
+const QUERY_PRODUCTS_DA_LOJA: &str = "SELECT produto.id, produto.nome, produto.foto, produto.descricao, produto.categoria_id, COALESCE(produto_loja.preco, produto.preco) AS preco, produto.ingredientes, produto.loja_id, produto.versao, produto.data_criacao, produto.data_atualizacao FROM produto LEFT JOIN produto_loja ON produto_loja.produto_id = produto.id AND produto_loja.loja_id = $1 WHERE (produto.loja_id IS NULL OR produto.loja_id = $1) AND COALESCE(produto_loja.disponivel, TRUE) ORDER BY produto.id";
+const QUERY_PRODUTOS_LOJA: &str = "SELECT loja_id, produto_id, preco, disponivel FROM produto_loja WHERE loja_id = $1";
+const UPSERT_PRODUTO_LOJA: &str = "WITH atualizado AS (UPDATE produto_loja SET preco = $3, disponivel = $4 WHERE loja_id = $1 AND produto_id = $2 RETURNING loja_id) INSERT INTO produto_loja (loja_id, produto_id, preco, disponivel) SELECT $1, $2, $3, $4 WHERE NOT EXISTS (SELECT 1 FROM atualizado)";
+const QUERY_TRADUCOES: &str = "SELECT produto_id, idioma, nome, descricao FROM produto_traducao WHERE idioma = $1";
+const QUERY_TRADUCOES_DO_PRODUTO: &str = "SELECT produto_id, idioma, nome, descricao FROM produto_traducao WHERE produto_id = $1 ORDER BY idioma";
+const UPSERT_TRADUCAO: &str = "WITH atualizado AS (UPDATE produto_traducao SET nome = $3, descricao = $4 WHERE produto_id = $1 AND idioma = $2 RETURNING produto_id) INSERT INTO produto_traducao (produto_id, idioma, nome, descricao) SELECT $1, $2, $3, $4 WHERE NOT EXISTS (SELECT 1 FROM atualizado)";
+const UPDATE_PRODUCT: &str = "UPDATE produto SET nome = $1, foto = $2, descricao = $3, categoria_id = $4, preco = $5, ingredientes = $6, loja_id = $7, versao = versao + 1, data_atualizacao = CURRENT_TIMESTAMP WHERE id = $8 AND versao = $9 RETURNING id, nome, foto, descricao, categoria_id, preco, ingredientes, loja_id, versao, data_criacao, data_atualizacao";
+// A importação sobrescreve o produto independente da versão, mas também a incrementa.
+const IMPORT_UPDATE_PRODUCT: &str = "UPDATE produto SET nome = $1, foto = $2, descricao = $3, categoria_id = $4, preco = $5, ingredientes = $6, loja_id = $7, versao = versao + 1, data_atualizacao = CURRENT_TIMESTAMP WHERE id = $8 RETURNING id, nome, foto, descricao, categoria_id, preco, ingredientes, loja_id, versao, data_criacao, data_atualizacao";
+const PRODUCT_EXISTS: &str = "SELECT EXISTS (SELECT 1 FROM produto WHERE id = $1)";
+const DELETE_PRODUCT: &str = "DELETE FROM produto WHERE id = $1 RETURNING id, nome, foto, descricao, categoria_id, preco, ingredientes, loja_id, versao, data_criacao, data_atualizacao";
 
 impl PostgresProdutoRepository {
-    pub async fn new(client: Arc<Client>, tables: Vec<Table>) -> Self {
-        let repo = PostgresProdutoRepository { client, tables };
-        repo.check_for_tables().await;
-        repo
+    pub fn new(pool: Pool) -> Self {
+        PostgresProdutoRepository { pool }
     }
 
-    async fn check_for_tables(&self) {
-        for table in self.tables.iter() {
-            let query = table.get_create_if_not_exists_query();
-            self.client.execute(query.as_str(), &[]).await.unwrap();
+
+    // Grava na transação aberta em importa_produtos.
+    async fn grava_importacao(transacao: &Transaction<'_>, novos: Vec<Produto>, atualizados: Vec<Produto>) -> Result<Vec<Produto>, DomainError> {
+        let mut importados = Vec::new();
+        for produto in novos {
+            let categoria_id = *produto.categoria_id() as i32;
+            let loja_id = produto.loja_id().map(|loja_id| *loja_id as i32);
+            let ingredientes_vec: Vec<String> = produto.ingredientes().to_vec_string();
+            let row = metricas::mede_consulta(
+                "create_product",
+                transacao.query_one(
+                    CREATE_PRODUCT,
+                    &[
+                        &produto.nome(),
+                        &produto.foto(),
+                        &produto.descricao(),
+                        &categoria_id,
+                        &produto.preco(),
+                        &ingredientes_vec,
+                        &loja_id,
+                    ],
+                ),
+            )
+            .await?;
+            importados.push(Produto::from_row(&row));
+        }
+        for produto in atualizados {
+            let id = *produto.id() as i32;
+            let categoria_id = *produto.categoria_id() as i32;
+            let loja_id = produto.loja_id().map(|loja_id| *loja_id as i32);
+            let ingredientes_vec: Vec<String> = produto.ingredientes().to_vec_string();
+            let row = metricas::mede_consulta(
+                "import_update_product",
+                transacao.query_opt(
+                    IMPORT_UPDATE_PRODUCT,
+                    &[
+                        &produto.nome(),
+                        &produto.foto(),
+                        &produto.descricao(),
+                        &categoria_id,
+                        &produto.preco(),
+                        &ingredientes_vec,
+                        &loja_id,
+                        &id,
+                    ],
+                ),
+            )
+            .await?
+            .ok_or(DomainError::NotFound)?;
+            importados.push(Produto::from_row(&row));
         }
+        Ok(importados)
     }
 }
 
 #[async_trait]
 impl ProdutoGateway for PostgresProdutoRepository {
     async fn get_produtos(&self) -> Result<Vec<Produto>, DomainError> {
-        let produtos = self.client.query(QUERY_PRODUCTS, &[]).await.unwrap();
+        let client = self.pool.get().await?;
+        let produtos = metricas::mede_consulta(
+            "query_products",
+            client.query(QUERY_PRODUCTS, &[]),
+        )
+        .await?;
         let mut produtos_vec = Vec::new();
         for produto in produtos {
             produtos_vec.push(Produto::from_row(&produto));
@@ -99,33 +106,79 @@ impl ProdutoGateway for PostgresProdutoRepository {
         Ok(produtos_vec)
     }
 
+    async fn get_produtos_da_loja(&self, loja_id: usize) -> Result<Vec<Produto>, DomainError> {
+        let client = self.pool.get().await?;
+        let loja_id = loja_id as i32;
+        let produtos = metricas::mede_consulta(
+            "query_products_da_loja",
+            client.query(QUERY_PRODUCTS_DA_LOJA, &[&loja_id]),
+        )
+        .await?;
+        Ok(produtos.iter().map(Produto::from_row).collect())
+    }
+
     async fn get_produto_by_id(&self, id: usize) -> Result<Produto, DomainError> {
+        let client = self.pool.get().await?;
         let id = id as i32;
-        let produto = self.client.query_one(QUERY_PRODUCT_BY_ID, &[&id]).await;
+        let produto = metricas::mede_consulta(
+            "query_product_by_id",
+            client.query_one(QUERY_PRODUCT_BY_ID, &[&id]),
+        )
+        .await;
         match produto {
             Ok(produto) => Ok(Produto::from_row(&produto)),
             Err(_) => Err(DomainError::NotFound),
         }
     }
 
-    async fn create_produto(&mut self, produto: Produto) -> Result<Produto, DomainError> {
+    async fn get_produtos_by_ids(&self, ids: Vec<usize>) -> Result<Vec<Produto>, DomainError> {
+        let client = self.pool.get().await?;
+        let ids: Vec<i32> = ids.into_iter().map(|id| id as i32).collect();
+        let produtos = metricas::mede_consulta(
+            "query_products_by_ids",
+            client.query(QUERY_PRODUCTS_BY_IDS, &[&ids]),
+        )
+        .await?;
+        Ok(produtos.iter().map(Produto::from_row).collect())
+    }
+
+    async fn busca_produtos(&self, termo: String, loja_id: usize) -> Result<Vec<Produto>, DomainError> {
+        let client = self.pool.get().await?;
+        let loja_id = loja_id as i32;
+        let produtos = metricas::mede_consulta(
+            "search_products",
+            client.query(SEARCH_PRODUCTS, &[&termo, &loja_id]),
+        )
+        .await?;
+        let mut produtos_vec = Vec::new();
+        for produto in produtos {
+            produtos_vec.push(Produto::from_row(&produto));
+        }
+        Ok(produtos_vec)
+    }
+
+    async fn create_produto(&self, produto: Produto) -> Result<Produto, DomainError> {
+        let client = self.pool.get().await?;
+        let categoria_id = *produto.categoria_id() as i32;
+        let loja_id = produto.loja_id().map(|loja_id| *loja_id as i32);
         let ingredientes = produto.ingredientes();
         let ingredientes_vec: Vec<String> = ingredientes.to_vec_string();
-        let new_produto = self
-            .client
-            .query(
+        let new_produto = metricas::mede_consulta(
+            "create_product",
+            client.query(
                 CREATE_PRODUCT,
                 &[
                     &produto.nome(),
                     &produto.foto(),
                     &produto.descricao(),
-                    &produto.categoria(),
+                    &categoria_id,
                     &produto.preco(),
                     &ingredientes_vec,
+                    &loja_id,
                 ],
-            )
-            .await
-            .unwrap();
+            ),
+        )
+        .await?;
         let new_produto = new_produto.get(0);
         match new_produto {
             Some(produto) => Ok(Produto::from_row(produto)),
@@ -133,40 +186,124 @@ impl ProdutoGateway for PostgresProdutoRepository {
         }
     }
 
-    async fn update_produto(&mut self, new_produto_data: Produto) -> Result<Produto, DomainError> {
+    async fn update_produto(&self, new_produto_data: Produto) -> Result<Produto, DomainError> {
+        let client = self.pool.get().await?;
         let id = new_produto_data.id().clone() as i32;
+        let categoria_id = *new_produto_data.categoria_id() as i32;
+        let loja_id = new_produto_data.loja_id().map(|loja_id| *loja_id as i32);
         let ingredientes = new_produto_data.ingredientes();
         let ingredientes_vec: Vec<String> = ingredientes.to_vec_string();
+        let versao = new_produto_data.versao() as i32;
 
-        let updated_produto = self
-            .client
-            .query(
+        let updated_produto = metricas::mede_consulta(
+            "update_product",
+            client.query(
                 UPDATE_PRODUCT,
                 &[
                     &new_produto_data.nome(),
                     &new_produto_data.foto(),
                     &new_produto_data.descricao(),
-                    &new_produto_data.categoria(),
+                    &categoria_id,
                     &new_produto_data.preco(),
                     &ingredientes_vec,
+                    &loja_id,
                     &id,
+                    &versao,
                 ],
-            )
-            .await
-            .unwrap();
-        let updated_produto = updated_produto.get(0);
-        match updated_produto {
+            ),
+        )
+        .await?;
+        match updated_produto.first() {
             Some(produto) => Ok(Produto::from_row(produto)),
-            None => Err(DomainError::NotFound),
+            // Nenhuma linha atualizada: o produto não existe ou outra requisição já o alterou.
+            None => {
+                let existe: bool = client.query_one(PRODUCT_EXISTS, &[&id]).await?.get(0);
+                Err(if existe { DomainError::Conflict } else { DomainError::NotFound })
+            }
         }
     }
 
-    async fn delete_produto(&mut self, id: usize) -> Result<(), DomainError> {
+    async fn delete_produto(&self, id: usize) -> Result<(), DomainError> {
+        let client = self.pool.get().await?;
         let id = id as i32;
-        let deleted_produto = self.client.query_one(DELETE_PRODUCT, &[&id]).await;
+        let deleted_produto = metricas::mede_consulta(
+            "delete_product",
+            client.query_one(DELETE_PRODUCT, &[&id]),
+        )
+        .await;
         match deleted_produto {
             Ok(_) => Ok(()),
             _ => Err(DomainError::NotFound),
         }
     }
+
+    async fn get_produtos_loja(&self, loja_id: usize) -> Result<Vec<ProdutoLoja>, DomainError> {
+        let client = self.pool.get().await?;
+        let loja_id = loja_id as i32;
+        let produtos_loja = metricas::mede_consulta(
+            "query_produtos_loja",
+            client.query(QUERY_PRODUTOS_LOJA, &[&loja_id]),
+        )
+        .await?;
+        Ok(produtos_loja.iter().map(ProdutoLoja::from_row).collect())
+    }
+
+    async fn salva_produto_loja(&self, produto_loja: ProdutoLoja) -> Result<ProdutoLoja, DomainError> {
+        let client = self.pool.get().await?;
+        let loja_id = *produto_loja.loja_id() as i32;
+        let produto_id = *produto_loja.produto_id() as i32;
+        metricas::mede_consulta(
+            "upsert_produto_loja",
+            client.execute(
+                UPSERT_PRODUTO_LOJA,
+                &[&loja_id, &produto_id, &produto_loja.preco(), &produto_loja.disponivel()],
+            ),
+        )
+        .await?;
+        Ok(produto_loja)
+    }
+
+    async fn get_traducoes(&self, idioma: Idioma) -> Result<Vec<TraducaoProduto>, DomainError> {
+        let client = self.pool.get().await?;
+        let traducoes = metricas::mede_consulta(
+            "query_traducoes",
+            client.query(QUERY_TRADUCOES, &[&idioma.tag()]),
+        )
+        .await?;
+        Ok(traducoes.iter().map(TraducaoProduto::from_row).collect())
+    }
+
+    async fn get_traducoes_do_produto(&self, produto_id: usize) -> Result<Vec<TraducaoProduto>, DomainError> {
+        let client = self.pool.get().await?;
+        let produto_id = produto_id as i32;
+        let traducoes = metricas::mede_consulta(
+            "query_traducoes_do_produto",
+            client.query(QUERY_TRADUCOES_DO_PRODUTO, &[&produto_id]),
+        )
+        .await?;
+        Ok(traducoes.iter().map(TraducaoProduto::from_row).collect())
+    }
+
+    async fn salva_traducao(&self, traducao: TraducaoProduto) -> Result<TraducaoProduto, DomainError> {
+        let client = self.pool.get().await?;
+        let produto_id = *traducao.produto_id() as i32;
+        metricas::mede_consulta(
+            "upsert_traducao",
+            client.execute(
+                UPSERT_TRADUCAO,
+                &[&produto_id, &traducao.idioma().tag(), &traducao.nome(), &traducao.descricao()],
+            ),
+        )
+        .await?;
+        Ok(traducao)
+    }
+
+    async fn importa_produtos(&self, novos: Vec<Produto>, atualizados: Vec<Produto>) -> Result<Vec<Produto>, DomainError> {
+        let mut client = self.pool.get().await?;
+        // Se a gravação falhar, a transação é desfeita ao ser descartada.
+        let transacao = client.transaction().await?;
+        let importados = Self::grava_importacao(&transacao, novos, atualizados).await?;
+        transacao.commit().await?;
+        Ok(importados)
+    }
 }
@@ -4,10 +4,10 @@
 
 pub mod api;
 pub mod rabbit;
-mod adapters;
+pub mod adapters;
 mod base;
 mod controllers;
-mod entities;
+pub mod entities;
 pub mod external;
 pub mod gateways;
 pub mod traits;
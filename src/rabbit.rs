@@ -1,43 +1,78 @@
 use crate::{
-    api::config::Config,
-    traits::{pedido_gateway::PedidoGateway, produto_gateway::ProdutoGateway},
-    use_cases::pedidos_e_pagamentos_use_case::{InfoPagamenmto, PedidosEPagamentosUseCase},
+    adapters::rabbitmq_dependencia::RabbitMQDependencia,
+    api::{config::Config, request_id::novo_request_id},
+    base::metricas,
+    traits::{
+        agenda_gateway::AgendaGateway, categoria_gateway::CategoriaGateway,
+        dependencia_adapter::DependenciaAdapter, pedido_gateway::PedidoGateway,
+        pontos_gateway::PontosGateway, produto_gateway::ProdutoGateway,
+    },
+    use_cases::{
+        pedidos_e_pagamentos_use_case::{InfoPagamenmto, PedidosEPagamentosUseCase},
+        programa_de_fidelidade_use_case::ProgramaDeFidelidadeUseCase,
+    },
 };
 use async_global_executor;
 use futures_lite::stream::StreamExt;
-use lapin::{options::*, types::FieldTable, Connection, ConnectionProperties, Result};
-use std::sync::Arc;
-use tokio::sync::Mutex;
-use tokio;
+use lapin::{
+    options::*,
+    types::{AMQPValue, FieldTable},
+    BasicProperties, Channel, Connection, ConnectionProperties, Result,
+};
+use std::sync::{Arc, RwLock};
+use tracing::Instrument;
+
+// Header AMQP com o mesmo identificador do X-Request-Id da API.
+pub const HEADER_REQUEST_ID: &str = "x-request-id";
 
 #[derive(Clone)]
 pub struct RabbitMQPagamentoUpdateSubscriber {
     config: Config,
     pedido_e_pagamento_use_case: PedidosEPagamentosUseCase,
+    programa_de_fidelidade_use_case: ProgramaDeFidelidadeUseCase,
+    canal: Arc<RwLock<Option<Channel>>>,
 }
 
-static MODULE_NAME: &str = "==RabbitMQ==";
-
 impl RabbitMQPagamentoUpdateSubscriber {
     pub fn new(
         config: Config,
-        produto_gateway: Arc<Mutex<dyn ProdutoGateway + Sync + Send>>,
-        pedido_gateway: Arc<Mutex<dyn PedidoGateway + Sync + Send>>,
+        produto_gateway: Arc<dyn ProdutoGateway + Sync + Send>,
+        pedido_gateway: Arc<dyn PedidoGateway + Sync + Send>,
+        categoria_gateway: Arc<dyn CategoriaGateway + Sync + Send>,
+        agenda_gateway: Arc<dyn AgendaGateway + Sync + Send>,
+        pontos_gateway: Arc<dyn PontosGateway + Sync + Send>,
     ) -> Self {
-        let pedido_e_pagamento_use_case =
-            PedidosEPagamentosUseCase::new(pedido_gateway, produto_gateway);
+        let pedido_e_pagamento_use_case = PedidosEPagamentosUseCase::new(
+            pedido_gateway.clone(),
+            produto_gateway,
+            categoria_gateway,
+            agenda_gateway,
+        );
+        let programa_de_fidelidade_use_case = ProgramaDeFidelidadeUseCase::new(
+            pontos_gateway,
+            pedido_gateway,
+            config.regras_de_fidelidade(),
+        );
         Self {
             config,
             pedido_e_pagamento_use_case,
+            programa_de_fidelidade_use_case,
+            canal: Arc::new(RwLock::new(None)),
         }
     }
 
+    // Estado do canal com o RabbitMQ para o /health/ready do listener.
+    pub fn dependencia(&self) -> Arc<dyn DependenciaAdapter + Sync + Send> {
+        Arc::new(RabbitMQDependencia::new(self.canal.clone()))
+    }
+
     pub async fn subscribe_pagamento_queue(
         self,
     ) -> Result<()> {
         let res: Result<()> = async_global_executor::block_on(async {
             let conn = Connection::connect(self.config.rabbitmq_addr.as_str(), ConnectionProperties::default()).await?;
             let channel = conn.create_channel().await?;
+            *self.canal.write().unwrap() = Some(channel.clone());
             let mut consumer = channel
                 .basic_consume(
                     self.config.queue_name.as_str(),
@@ -47,33 +82,98 @@ impl RabbitMQPagamentoUpdateSubscriber {
                 )
                 .await?;
 
-            println!("{} Conectado ao RabbitMQ", MODULE_NAME);
+            tracing::info!(fila = %self.config.queue_name, "Conectado ao RabbitMQ");
             while let Some(delivery) = consumer.next().await {
                 let delivery = delivery.expect("error in consumer");
                 delivery.ack(BasicAckOptions::default()).await?;
-                println!("{} Atualização de pagamento recebida", MODULE_NAME);
-                let json_string = std::str::from_utf8(&delivery.data).unwrap();
-                let update_input = serde_json::from_str::<InfoPagamenmto>(json_string);
-                match update_input {
-                    Ok(update_input) => {
-                        println!("{} {:?}", MODULE_NAME, update_input);
-                        match self.pedido_e_pagamento_use_case.atualiza_pagamento(update_input.clone()).await
-                        {
-                            Ok(pedido) => {
-                                println!("{} Pedido atualizado: {:?} | Status do Pagamento: {:?}", MODULE_NAME, pedido.id(), pedido.status());
-                            }
-                            Err(e) => {
-                                println!("{} Erro ao atualizar pedido: {:?}", MODULE_NAME, e);
-                            }
-                        }
+                let span = tracing::info_span!(
+                    "atualizacao_pagamento",
+                    request_id = %request_id_da_mensagem(&delivery.properties),
+                );
+                self.processa_atualizacao(&delivery.data).instrument(span).await;
+            }
+            Ok(())
+        });
+        res
+    }
+
+    async fn processa_atualizacao(&self, data: &[u8]) {
+        tracing::info!("Atualização de pagamento recebida");
+        let json_string = std::str::from_utf8(data).unwrap();
+        let update_input = serde_json::from_str::<InfoPagamenmto>(json_string);
+        match update_input {
+            Ok(update_input) => {
+                match self
+                    .pedido_e_pagamento_use_case
+                    .atualiza_pagamento(update_input.clone(), &self.programa_de_fidelidade_use_case)
+                    .await
+                {
+                    Ok(pedido) => {
+                        tracing::info!(pedido_id = *pedido.id(), status = ?pedido.status(), "Pedido atualizado");
+                        metricas::registra_mensagem_da_fila(true);
                     }
                     Err(e) => {
-                        println!("{} Erro ao desserializar mensagem: {:?}", MODULE_NAME, e);
+                        tracing::error!(pedido_id = update_input.pedido_id, erro = ?e, "Erro ao atualizar pedido");
+                        metricas::registra_mensagem_da_fila(false);
                     }
                 }
             }
-            Ok(())
-        });
-        res
+            Err(e) => {
+                tracing::error!(erro = %e, "Erro ao desserializar mensagem");
+                metricas::registra_mensagem_da_fila(false);
+            }
+        }
+    }
+}
+
+// Identificador de correlação da mensagem: o header x-request-id, o correlation_id
+// ou, na falta dos dois, um novo.
+pub fn request_id_da_mensagem(properties: &BasicProperties) -> String {
+    properties
+        .headers()
+        .as_ref()
+        .and_then(|headers| headers.inner().get(HEADER_REQUEST_ID))
+        .and_then(|valor| match valor {
+            AMQPValue::LongString(id) => Some(id.to_string()),
+            AMQPValue::ShortString(id) => Some(id.to_string()),
+            _ => None,
+        })
+        .or_else(|| properties.correlation_id().as_ref().map(|id| id.to_string()))
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .unwrap_or_else(novo_request_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_id_da_mensagem_usa_header() {
+        let mut headers = FieldTable::default();
+        headers.insert(HEADER_REQUEST_ID.into(), AMQPValue::LongString("abc-123".into()));
+        let properties = BasicProperties::default()
+            .with_headers(headers)
+            .with_correlation_id("outro".into());
+
+        assert_eq!(request_id_da_mensagem(&properties), "abc-123");
+    }
+
+    #[test]
+    fn test_request_id_da_mensagem_usa_correlation_id() {
+        let properties = BasicProperties::default().with_correlation_id("abc-123".into());
+
+        assert_eq!(request_id_da_mensagem(&properties), "abc-123");
+    }
+
+    #[test]
+    fn test_request_id_da_mensagem_gera_novo() {
+        let properties = BasicProperties::default();
+
+        let primeiro = request_id_da_mensagem(&properties);
+        let segundo = request_id_da_mensagem(&properties);
+
+        assert!(!primeiro.is_empty());
+        assert_ne!(primeiro, segundo);
     }
 }
@@ -1,4 +1,14 @@
 pub mod pedido_gateway;
 pub mod produto_gateway;
+pub mod categoria_gateway;
 pub mod api_key_validator_adapter;
-pub mod user_group_validator_adapter;
\ No newline at end of file
+pub mod user_group_validator_adapter;
+pub mod foto_storage_adapter;
+pub mod agenda_gateway;
+pub mod loja_gateway;
+pub mod token_validator_adapter;
+pub mod dependencia_adapter;
+pub mod idempotencia_gateway;
+pub mod cliente_gateway;
+pub mod pontos_gateway;
+pub mod auditoria_gateway;
@@ -0,0 +1,61 @@
+use mockall::*;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::agenda::{Agenda, AlvoAgenda};
+use std::fmt;
+use std::str::FromStr;
+
+impl FromStr for AlvoAgenda {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<AlvoAgenda, Self::Err> {
+        match input {
+            "Produto" => Ok(AlvoAgenda::Produto),
+            "Categoria" => Ok(AlvoAgenda::Categoria),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for AlvoAgenda {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                AlvoAgenda::Produto => "Produto",
+                AlvoAgenda::Categoria => "Categoria",
+            }
+        )
+    }
+}
+
+#[automock]
+#[async_trait]
+pub trait AgendaGateway {
+    async fn get_agendas(&self) -> Result<Vec<Agenda>, DomainError>;
+
+    async fn get_agenda_by_id(&self, id: usize) -> Result<Agenda, DomainError>;
+
+    async fn create_agenda(&self, agenda: Agenda) -> Result<Agenda, DomainError>;
+
+    async fn delete_agenda(&self, id: usize) -> Result<(), DomainError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alvo_agenda_from_string() {
+        assert_eq!(AlvoAgenda::from_str("Produto").unwrap(), AlvoAgenda::Produto);
+        assert_eq!(AlvoAgenda::from_str("Categoria").unwrap(), AlvoAgenda::Categoria);
+        assert!(AlvoAgenda::from_str("Loja").is_err());
+    }
+
+    #[test]
+    fn test_alvo_agenda_to_string() {
+        assert_eq!(AlvoAgenda::Produto.to_string(), "Produto");
+        assert_eq!(AlvoAgenda::Categoria.to_string(), "Categoria");
+    }
+}
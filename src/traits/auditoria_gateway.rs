@@ -0,0 +1,37 @@
+use mockall::*;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::auditoria::{AcaoDeAuditoria, RegistroDeAuditoria};
+use std::fmt;
+use std::str::FromStr;
+
+impl FromStr for AcaoDeAuditoria {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<AcaoDeAuditoria, Self::Err> {
+        match input {
+            "ExportacaoDeDados" => Ok(AcaoDeAuditoria::ExportacaoDeDados),
+            "AnonimizacaoDeDados" => Ok(AcaoDeAuditoria::AnonimizacaoDeDados),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for AcaoDeAuditoria {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                AcaoDeAuditoria::ExportacaoDeDados => "ExportacaoDeDados",
+                AcaoDeAuditoria::AnonimizacaoDeDados => "AnonimizacaoDeDados",
+            }
+        )
+    }
+}
+
+#[automock]
+#[async_trait]
+pub trait AuditoriaGateway {
+    async fn registra(&self, registro: RegistroDeAuditoria) -> Result<RegistroDeAuditoria, DomainError>;
+}
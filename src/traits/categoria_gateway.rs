@@ -0,0 +1,73 @@
+use mockall::*;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::categoria::{Categoria, ItemPedido};
+use std::fmt;
+use std::str::FromStr;
+
+impl FromStr for ItemPedido {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<ItemPedido, Self::Err> {
+        match input {
+            "Lanche" => Ok(ItemPedido::Lanche),
+            "Acompanhamento" => Ok(ItemPedido::Acompanhamento),
+            "Bebida" => Ok(ItemPedido::Bebida),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for ItemPedido {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ItemPedido::Lanche => "Lanche",
+                ItemPedido::Acompanhamento => "Acompanhamento",
+                ItemPedido::Bebida => "Bebida",
+            }
+        )
+    }
+}
+
+#[automock]
+#[async_trait]
+pub trait CategoriaGateway {
+    async fn get_categorias(&self) -> Result<Vec<Categoria>, DomainError>;
+
+    async fn get_categoria_by_id(&self, id: usize) -> Result<Categoria, DomainError>;
+
+    async fn create_categoria(&self, categoria: Categoria) -> Result<Categoria, DomainError>;
+
+    async fn update_categoria(&self, categoria: Categoria) -> Result<Categoria, DomainError>;
+
+    async fn delete_categoria(&self, id: usize) -> Result<(), DomainError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_item_pedido_from_string() {
+        let item = ItemPedido::from_str("Lanche").unwrap();
+        assert_eq!(item, ItemPedido::Lanche);
+
+        let item = ItemPedido::from_str("Acompanhamento").unwrap();
+        assert_eq!(item, ItemPedido::Acompanhamento);
+
+        let item = ItemPedido::from_str("Bebida").unwrap();
+        assert_eq!(item, ItemPedido::Bebida);
+
+        assert!(ItemPedido::from_str("Sobremesa").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_item_pedido_to_string() {
+        assert_eq!(ItemPedido::Lanche.to_string(), "Lanche");
+        assert_eq!(ItemPedido::Acompanhamento.to_string(), "Acompanhamento");
+        assert_eq!(ItemPedido::Bebida.to_string(), "Bebida");
+    }
+}
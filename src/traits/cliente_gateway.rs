@@ -0,0 +1,18 @@
+use mockall::*;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::{cliente::Cliente, cpf::Cpf};
+
+#[automock]
+#[async_trait]
+pub trait ClienteGateway {
+    async fn get_cliente_by_cpf(&self, cpf: &Cpf) -> Result<Cliente, DomainError>;
+
+    // AlreadyExists se o CPF já estiver cadastrado.
+    async fn create_cliente(&self, cliente: Cliente) -> Result<Cliente, DomainError>;
+
+    async fn update_cliente(&self, cliente: Cliente) -> Result<Cliente, DomainError>;
+
+    // NotFound se o CPF não estiver cadastrado.
+    async fn delete_cliente(&self, cpf: &Cpf) -> Result<(), DomainError>;
+}
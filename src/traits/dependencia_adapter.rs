@@ -0,0 +1,11 @@
+use mockall::*;
+
+#[automock]
+#[async_trait]
+pub trait DependenciaAdapter {
+    /// Nome da dependência no relatório de prontidão (ex.: postgres, rabbitmq).
+    fn nome(&self) -> String;
+
+    /// Verifica se a dependência responde; o erro descreve o motivo da falha.
+    async fn verifica(&self) -> Result<(), String>;
+}
@@ -0,0 +1,14 @@
+use mockall::*;
+
+use crate::base::domain_error::DomainError;
+
+#[automock]
+#[async_trait]
+pub trait FotoStorageAdapter {
+    /// Armazena a foto (e sua miniatura) e retorna a URL pública do arquivo.
+    async fn salva_foto(&self, nome_arquivo: String, conteudo: Vec<u8>) -> Result<String, DomainError>;
+
+    /// Apaga a foto (e sua miniatura) a partir da URL devolvida por `salva_foto`. URLs que não
+    /// são deste armazenamento (ex.: fotos do cadastro inicial) são ignoradas.
+    async fn remove_foto(&self, url: &str) -> Result<(), DomainError>;
+}
@@ -0,0 +1,19 @@
+use mockall::*;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::{idempotencia::RegistroDeIdempotencia, pedido::Pedido};
+
+// Registros das chaves Idempotency-Key; cada um vale pela janela configurada no gateway.
+#[automock]
+#[async_trait]
+pub trait IdempotenciaGateway {
+  // Reserva a chave para a requisição com esta impressão digital. Devolve None se a reserva
+  // foi feita agora, ou o registro de quem já usou a chave dentro da janela.
+  async fn reserva(&self, chave: String, impressao_digital: String) -> Result<Option<RegistroDeIdempotencia>, DomainError>;
+
+  // Guarda o pedido criado como resposta das repetições.
+  async fn conclui(&self, chave: String, pedido: Pedido) -> Result<(), DomainError>;
+
+  // Desfaz a reserva de uma requisição que falhou, para que possa ser repetida.
+  async fn libera(&self, chave: String) -> Result<(), DomainError>;
+}
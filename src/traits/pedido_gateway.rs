@@ -1,7 +1,11 @@
+use chrono::{DateTime, Utc};
 use mockall::*;
 
 use crate::base::domain_error::DomainError;
+use crate::base::paginacao::{Pagina, Paginacao};
+use crate::entities::cpf::Cpf;
 use crate::entities::pedido::{Pedido, Status};
+use crate::entities::transicao_de_status::TransicaoDeStatus;
 use std::fmt;
 use std::str::FromStr;
 
@@ -43,26 +47,82 @@ impl fmt::Display for Status {
 #[automock]
 #[async_trait]
 pub trait PedidoGateway {
-    async fn create_pedido(&mut self, pedido: Pedido) -> Result<Pedido, DomainError>;
+    async fn create_pedido(&self, pedido: Pedido) -> Result<Pedido, DomainError>;
 
-    async fn lista_pedidos(&mut self) -> Result<Vec<Pedido>, DomainError>;
+    async fn lista_pedidos(&self, loja_id: usize) -> Result<Vec<Pedido>, DomainError>;
 
-    async fn get_pedidos_novos(&self) -> Result<Vec<Pedido>, DomainError>;
+    async fn get_pedidos_novos(&self, loja_id: usize) -> Result<Vec<Pedido>, DomainError>;
 
+    // Pedido de qualquer loja, para os fluxos do cliente; a cozinha usa get_pedido_da_loja.
     async fn get_pedido_by_id(&self, pedido_id: usize) -> Result<Pedido, DomainError>;
 
+    // Pedido de outra loja é tratado como inexistente (NotFound).
+    async fn get_pedido_da_loja(&self, pedido_id: usize, loja_id: usize) -> Result<Pedido, DomainError>;
+
+    // Pedidos do cliente em todas as lojas, do mais recente ao mais antigo.
+    async fn lista_pedidos_do_cliente(
+        &self,
+        cpf: &Cpf,
+        paginacao: Paginacao,
+    ) -> Result<Pagina<Pedido>, DomainError>;
+
+    // Pedidos em que o CPF aparece, como cliente ou na nota fiscal, do mais recente ao mais
+    // antigo. É o que a exportação dos dados do titular (LGPD) entrega.
+    async fn pedidos_do_titular(&self, cpf: &Cpf) -> Result<Vec<Pedido>, DomainError>;
+
+    // Só atualiza se o pedido ainda estiver na `versao` informada; do contrário, Conflict.
     async fn atualiza_status(
-        &mut self,
+        &self,
         pedido_id: usize,
         status: Status,
+        versao: usize,
     ) -> Result<Pedido, DomainError>;
 
     async fn atualiza_pagamento_status(
-        &mut self,
+        &self,
         pedido_id: usize,
         pagamento_id: String,
         status: Status,
     ) -> Result<Pedido, DomainError>;
+
+    // Como atualiza_status: só grava o desconto se o pedido ainda estiver na `versao` informada.
+    async fn aplica_desconto(
+        &self,
+        pedido_id: usize,
+        desconto: f64,
+        versao: usize,
+    ) -> Result<Pedido, DomainError>;
+
+    // Troca o CPF dos pedidos do cliente pelo pseudônimo; os pedidos passam a ser do cliente
+    // anônimo. Também tira o CPF das notas fiscais em que ele aparece. Devolve quantos pedidos
+    // foram anonimizados.
+    async fn anonimiza_pedidos_do_cliente(
+        &self,
+        cpf: &Cpf,
+        pseudonimo: &str,
+    ) -> Result<usize, DomainError>;
+
+    async fn conta_pedidos_por_status(&self) -> Result<Vec<(Status, usize)>, DomainError>;
+
+    // Pedidos da loja criados a partir de `inicio` e antes de `fim`, em ordem de criação.
+    async fn pedidos_do_periodo(
+        &self,
+        loja_id: usize,
+        inicio: DateTime<Utc>,
+        fim: DateTime<Utc>,
+    ) -> Result<Vec<Pedido>, DomainError>;
+
+    // Cada criação e mudança de status do pedido fica registrada; um mesmo status pode
+    // aparecer mais de uma vez. As duas consultas vêm em ordem cronológica.
+    async fn transicoes_do_pedido(&self, pedido_id: usize) -> Result<Vec<TransicaoDeStatus>, DomainError>;
+
+    // Transições dos pedidos da loja criados no intervalo, como em pedidos_do_periodo.
+    async fn transicoes_do_periodo(
+        &self,
+        loja_id: usize,
+        inicio: DateTime<Utc>,
+        fim: DateTime<Utc>,
+    ) -> Result<Vec<TransicaoDeStatus>, DomainError>;
 }
 
 #[cfg(test)]
@@ -0,0 +1,61 @@
+use mockall::*;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::cpf::Cpf;
+use crate::entities::pontos::{MovimentoDePontos, TipoDeMovimento};
+use std::fmt;
+use std::str::FromStr;
+
+impl FromStr for TipoDeMovimento {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<TipoDeMovimento, Self::Err> {
+        match input {
+            "Acumulo" => Ok(TipoDeMovimento::Acumulo),
+            "Resgate" => Ok(TipoDeMovimento::Resgate),
+            "EstornoDeAcumulo" => Ok(TipoDeMovimento::EstornoDeAcumulo),
+            "EstornoDeResgate" => Ok(TipoDeMovimento::EstornoDeResgate),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for TipoDeMovimento {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                TipoDeMovimento::Acumulo => "Acumulo",
+                TipoDeMovimento::Resgate => "Resgate",
+                TipoDeMovimento::EstornoDeAcumulo => "EstornoDeAcumulo",
+                TipoDeMovimento::EstornoDeResgate => "EstornoDeResgate",
+            }
+        )
+    }
+}
+
+#[automock]
+#[async_trait]
+pub trait PontosGateway {
+    // AlreadyExists se o pedido já tiver um movimento do mesmo tipo.
+    async fn registra_movimento(
+        &self,
+        movimento: MovimentoDePontos,
+    ) -> Result<MovimentoDePontos, DomainError>;
+
+    // Extrato completo do cliente, do movimento mais antigo ao mais recente.
+    async fn movimentos_do_cliente(&self, cpf: &Cpf) -> Result<Vec<MovimentoDePontos>, DomainError>;
+
+    async fn movimentos_do_pedido(&self, pedido_id: usize) -> Result<Vec<MovimentoDePontos>, DomainError>;
+
+    // Registra o resgate só se o saldo do cliente o cobrir, conferindo e debitando de uma vez:
+    // resgates simultâneos não gastam os mesmos pontos. None se o saldo não cobrir.
+    async fn registra_resgate(
+        &self,
+        resgate: MovimentoDePontos,
+    ) -> Result<Option<MovimentoDePontos>, DomainError>;
+
+    // Apaga o extrato do cliente e devolve quantos movimentos foram removidos.
+    async fn remove_movimentos_do_cliente(&self, cpf: &Cpf) -> Result<usize, DomainError>;
+}
@@ -1,84 +1,46 @@
 use mockall::*;
 
 use crate::base::domain_error::DomainError;
-use crate::entities::produto::{Produto, Categoria};
-use std::fmt;
-use std::str::FromStr;
-
-impl FromStr for Categoria {
-  type Err = ();
-
-  fn from_str(input: &str) -> Result<Categoria, Self::Err> {
-      match input {
-          "Lanche" => Ok(Categoria::Lanche),
-          "Acompanhamento" => Ok(Categoria::Acompanhamento),
-          "Bebida" => Ok(Categoria::Bebida),
-          "Sobremesa" => Ok(Categoria::Sobremesa),
-          _ => Err(()),
-      }
-  }
-}
-
-impl fmt::Display for Categoria {
-  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-      write!(
-          f,
-          "{}",
-          match self {
-              Categoria::Lanche => "Lanche",
-              Categoria::Acompanhamento => "Acompanhamento",
-              Categoria::Bebida => "Bebida",
-              Categoria::Sobremesa => "Sobremesa",
-          }
-      )
-  }
-}
+use crate::entities::{
+  idioma::{Idioma, TraducaoProduto},
+  loja::ProdutoLoja,
+  produto::Produto,
+};
 
 #[automock]
 #[async_trait]
 pub trait ProdutoGateway {
   async fn get_produtos(&self) -> Result<Vec<Produto>, DomainError>;
 
-  async fn get_produto_by_id(&self, id: usize) -> Result<Produto, DomainError>;
+  // Catálogo da loja: produtos compartilhados e exclusivos dela, com os ajustes de preço e
+  // disponibilidade já aplicados.
+  async fn get_produtos_da_loja(&self, loja_id: usize) -> Result<Vec<Produto>, DomainError>;
 
-  async fn create_produto(&mut self, produto: Produto) -> Result<Produto, DomainError>;
+  async fn get_produto_by_id(&self, id: usize) -> Result<Produto, DomainError>;
 
-  async fn update_produto(&mut self, new_produto_data: Produto) -> Result<Produto, DomainError>;
+  // Busca vários produtos numa única consulta; ids inexistentes são ignorados.
+  async fn get_produtos_by_ids(&self, ids: Vec<usize>) -> Result<Vec<Produto>, DomainError>;
 
-  async fn delete_produto(&mut self, id: usize) -> Result<(), DomainError>;
-}
+  async fn busca_produtos(&self, termo: String, loja_id: usize) -> Result<Vec<Produto>, DomainError>;
 
-#[cfg(test)]
-mod tests {
-  use super::*;
+  async fn create_produto(&self, produto: Produto) -> Result<Produto, DomainError>;
 
-  #[tokio::test]
-  async fn test_categoria_from_string() {
-      let categoria = Categoria::from_str("Lanche").unwrap();
-      assert_eq!(categoria, Categoria::Lanche);
+  // Só grava se o produto ainda estiver na versão de `new_produto_data`; do contrário, Conflict.
+  async fn update_produto(&self, new_produto_data: Produto) -> Result<Produto, DomainError>;
 
-      let categoria = Categoria::from_str("Acompanhamento").unwrap();
-      assert_eq!(categoria, Categoria::Acompanhamento);
+  async fn delete_produto(&self, id: usize) -> Result<(), DomainError>;
 
-      let categoria = Categoria::from_str("Bebida").unwrap();
-      assert_eq!(categoria, Categoria::Bebida);
+  async fn get_produtos_loja(&self, loja_id: usize) -> Result<Vec<ProdutoLoja>, DomainError>;
 
-      let categoria = Categoria::from_str("Sobremesa").unwrap();
-      assert_eq!(categoria, Categoria::Sobremesa);
-  }
+  async fn salva_produto_loja(&self, produto_loja: ProdutoLoja) -> Result<ProdutoLoja, DomainError>;
 
-  #[tokio::test]
-  async fn test_categoria_to_string() {
-      let categoria = Categoria::Lanche;
-      assert_eq!(categoria.to_string(), "Lanche");
+  async fn get_traducoes(&self, idioma: Idioma) -> Result<Vec<TraducaoProduto>, DomainError>;
 
-      let categoria = Categoria::Acompanhamento;
-      assert_eq!(categoria.to_string(), "Acompanhamento");
+  async fn get_traducoes_do_produto(&self, produto_id: usize) -> Result<Vec<TraducaoProduto>, DomainError>;
 
-      let categoria = Categoria::Bebida;
-      assert_eq!(categoria.to_string(), "Bebida");
+  // Grava a tradução do produto no idioma, substituindo a anterior se houver.
+  async fn salva_traducao(&self, traducao: TraducaoProduto) -> Result<TraducaoProduto, DomainError>;
 
-      let categoria = Categoria::Sobremesa;
-      assert_eq!(categoria.to_string(), "Sobremesa");
-  }
-}
\ No newline at end of file
+  // Cria e atualiza os produtos numa única transação: se algum falhar nada é gravado.
+  async fn importa_produtos(&self, novos: Vec<Produto>, atualizados: Vec<Produto>) -> Result<Vec<Produto>, DomainError>;
+}
@@ -0,0 +1,13 @@
+use mockall::*;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::cpf::Cpf;
+
+#[automock]
+pub trait TokenValidatorAdapter {
+    // Lê a claim `loja_id` de um token assinado; tokens sem loja (ex.: de cliente) dão None.
+    fn loja_id(&self, token: String) -> Result<Option<usize>, DomainError>;
+
+    // Lê a claim `cpf` de um token de cliente assinado.
+    fn cpf(&self, token: String) -> Result<Cpf, DomainError>;
+}
@@ -1,3 +1,13 @@
 pub mod preparacao_e_entrega_use_case;
 pub mod pedidos_e_pagamentos_use_case;
 pub mod gerenciamento_de_produtos_use_case;
+pub mod gerenciamento_de_categorias_use_case;
+pub mod gerenciamento_de_agendas_use_case;
+pub mod importacao_de_catalogo_use_case;
+pub mod gerenciamento_de_lojas_use_case;
+pub mod gerenciamento_de_clientes_use_case;
+pub mod verificacao_de_saude_use_case;
+pub mod programa_de_fidelidade_use_case;
+pub mod protecao_de_dados_use_case;
+pub mod relatorios_de_vendas_use_case;
+pub mod desempenho_da_cozinha_use_case;
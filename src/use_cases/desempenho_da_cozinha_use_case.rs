@@ -0,0 +1,492 @@
+use chrono::{DateTime, Duration, Timelike, Utc};
+use chrono_tz::Tz;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::pedido::{Pedido, Status};
+use crate::entities::transicao_de_status::TransicaoDeStatus;
+use crate::traits::pedido_gateway::PedidoGateway;
+use crate::use_cases::relatorios_de_vendas_use_case::PeriodoDoRelatorio;
+
+const FORMATO_DATA: &str = "%Y-%m-%d %H:%M:%S%.3f%z";
+
+// A previsão usa o histórico das últimas quatro semanas da loja.
+pub const DIAS_DO_HISTORICO_DA_PREVISAO: i64 = 28;
+// Com menos pedidos que isso, a média de uma hora do dia ou de um produto não é confiável.
+const AMOSTRA_MINIMA: usize = 5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, JsonSchema)]
+pub enum Etapa {
+    // De Pago a EmPreparacao: o pedido esperando a cozinha.
+    Espera,
+    // De EmPreparacao a Pronto.
+    Preparo,
+    // De Pronto a Finalizado: o pedido esperando o cliente.
+    Retirada,
+}
+
+const ETAPAS: [Etapa; 3] = [Etapa::Espera, Etapa::Preparo, Etapa::Retirada];
+
+impl Etapa {
+    fn status(&self) -> (Status, Status) {
+        match self {
+            Etapa::Espera => (Status::Pago, Status::EmPreparacao),
+            Etapa::Preparo => (Status::EmPreparacao, Status::Pronto),
+            Etapa::Retirada => (Status::Pronto, Status::Finalizado),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema, PartialEq)]
+pub struct DuracaoDaEtapa {
+    pub etapa: Etapa,
+    // Pedidos que passaram pela etapa.
+    pub pedidos: usize,
+    pub media_em_segundos: f64,
+    pub p90_em_segundos: f64,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct DesempenhoPorHora {
+    // Hora do dia, no fuso do relatório, em que a etapa começou.
+    pub hora: u32,
+    pub etapas: Vec<DuracaoDaEtapa>,
+}
+
+// Cada produto conta com a duração do pedido inteiro em que apareceu.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct DesempenhoDoProduto {
+    pub produto_id: usize,
+    pub nome: String,
+    pub etapas: Vec<DuracaoDaEtapa>,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct DesempenhoDaCozinha {
+    pub inicio: String,
+    pub fim: String,
+    pub fuso: String,
+    pub etapas: Vec<DuracaoDaEtapa>,
+    pub por_hora: Vec<DesempenhoPorHora>,
+    pub por_produto: Vec<DesempenhoDoProduto>,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct PrevisaoDePreparo {
+    pub pedido_id: usize,
+    pub status: Status,
+    // Sem histórico suficiente, ou com o pedido cancelado, não há previsão.
+    pub pronto_em: Option<String>,
+    // Zero quando o pedido já está pronto ou passou do previsto.
+    pub segundos_restantes: Option<i64>,
+}
+
+fn instante(data: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_str(data, FORMATO_DATA)
+        .ok()
+        .map(|data| data.with_timezone(&Utc))
+}
+
+// Primeira vez em que o pedido entrou em cada status. Um mesmo status pode ter sido gravado
+// mais de uma vez (ex.: a notificação de pagamento repetida), e vale o primeiro registro.
+fn entradas(transicoes: &[TransicaoDeStatus]) -> HashMap<(usize, Status), DateTime<Utc>> {
+    let mut entradas: HashMap<(usize, Status), DateTime<Utc>> = HashMap::new();
+    for transicao in transicoes {
+        let Some(momento) = instante(transicao.data_criacao()) else {
+            continue;
+        };
+        entradas
+            .entry((*transicao.pedido_id(), transicao.status().clone()))
+            .and_modify(|entrada| *entrada = (*entrada).min(momento))
+            .or_insert(momento);
+    }
+    entradas
+}
+
+#[derive(Default)]
+struct Duracoes(HashMap<Etapa, Vec<f64>>);
+
+impl Duracoes {
+    fn soma(&mut self, etapa: Etapa, segundos: f64) {
+        self.0.entry(etapa).or_default().push(segundos);
+    }
+
+    fn pedidos(&self, etapa: Etapa) -> usize {
+        self.0.get(&etapa).map_or(0, Vec::len)
+    }
+
+    fn media(&self, etapa: Etapa) -> Option<f64> {
+        let duracoes = self.0.get(&etapa).filter(|duracoes| !duracoes.is_empty())?;
+        Some(duracoes.iter().sum::<f64>() / duracoes.len() as f64)
+    }
+
+    // Percentil 90 pelo método do posto mais próximo: a menor duração que cobre 90% dos pedidos.
+    fn p90(&self, etapa: Etapa) -> Option<f64> {
+        let mut duracoes = self.0.get(&etapa).filter(|duracoes| !duracoes.is_empty())?.clone();
+        duracoes.sort_by(f64::total_cmp);
+        let posicao = ((duracoes.len() as f64 * 0.9).ceil() as usize).max(1) - 1;
+        Some(duracoes[posicao])
+    }
+
+    fn etapas(&self) -> Vec<DuracaoDaEtapa> {
+        let decimos = |segundos: Option<f64>| (segundos.unwrap_or(0.0) * 10.0).round() / 10.0;
+        ETAPAS
+            .iter()
+            .map(|etapa| DuracaoDaEtapa {
+                etapa: *etapa,
+                pedidos: self.pedidos(*etapa),
+                media_em_segundos: decimos(self.media(*etapa)),
+                p90_em_segundos: decimos(self.p90(*etapa)),
+            })
+            .collect()
+    }
+}
+
+#[derive(Default)]
+struct Estatisticas {
+    geral: Duracoes,
+    por_hora: BTreeMap<u32, Duracoes>,
+    por_produto: BTreeMap<usize, (String, Duracoes)>,
+}
+
+impl Estatisticas {
+    // Etapas com fim anterior ao início (status gravados fora de ordem) são descartadas.
+    fn calcula(pedidos: &[Pedido], transicoes: &[TransicaoDeStatus], fuso: Tz) -> Self {
+        let entradas = entradas(transicoes);
+        let mut estatisticas = Estatisticas::default();
+        for pedido in pedidos {
+            let mut produtos: Vec<(usize, String)> = [pedido.lanche(), pedido.acompanhamento(), pedido.bebida()]
+                .into_iter()
+                .flatten()
+                .map(|produto| (*produto.id(), produto.nome().clone()))
+                .collect();
+            produtos.sort();
+            produtos.dedup();
+
+            for etapa in ETAPAS {
+                let (de, para) = etapa.status();
+                let (Some(inicio), Some(fim)) = (
+                    entradas.get(&(*pedido.id(), de)),
+                    entradas.get(&(*pedido.id(), para)),
+                ) else {
+                    continue;
+                };
+                if fim < inicio {
+                    continue;
+                }
+                let segundos = (*fim - *inicio).num_milliseconds() as f64 / 1000.0;
+                estatisticas.geral.soma(etapa, segundos);
+                estatisticas
+                    .por_hora
+                    .entry(inicio.with_timezone(&fuso).hour())
+                    .or_default()
+                    .soma(etapa, segundos);
+                for (produto_id, nome) in produtos.iter() {
+                    estatisticas
+                        .por_produto
+                        .entry(*produto_id)
+                        .or_insert_with(|| (nome.clone(), Duracoes::default()))
+                        .1
+                        .soma(etapa, segundos);
+                }
+            }
+        }
+        estatisticas
+    }
+
+    // O preparo usa o produto mais demorado do pedido, se todos tiverem histórico suficiente.
+    // Fora isso, vale a média da hora do dia e, na falta dela, a da loja toda.
+    fn estimativa(&self, etapa: Etapa, hora: u32, pedido: &Pedido) -> Option<f64> {
+        if etapa == Etapa::Preparo {
+            let produtos: Option<Vec<f64>> = [pedido.lanche(), pedido.acompanhamento(), pedido.bebida()]
+                .into_iter()
+                .flatten()
+                .map(|produto| {
+                    let (_, duracoes) = self.por_produto.get(produto.id())?;
+                    if duracoes.pedidos(etapa) < AMOSTRA_MINIMA {
+                        return None;
+                    }
+                    duracoes.media(etapa)
+                })
+                .collect();
+            if let Some(maior) = produtos.and_then(|medias| medias.into_iter().reduce(f64::max)) {
+                return Some(maior);
+            }
+        }
+        match self.por_hora.get(&hora) {
+            Some(duracoes) if duracoes.pedidos(etapa) >= AMOSTRA_MINIMA => duracoes.media(etapa),
+            _ => self.geral.media(etapa),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DesempenhoDaCozinhaUseCase {
+    pedido_repository: Arc<dyn PedidoGateway + Sync + Send>,
+}
+
+impl DesempenhoDaCozinhaUseCase {
+    pub fn new(pedido_repository: Arc<dyn PedidoGateway + Sync + Send>) -> Self {
+        DesempenhoDaCozinhaUseCase { pedido_repository }
+    }
+
+    async fn estatisticas(&self, loja_id: usize, periodo: &PeriodoDoRelatorio) -> Result<Estatisticas, DomainError> {
+        let (inicio, fim) = periodo.intervalo();
+        let pedidos = self
+            .pedido_repository
+            .pedidos_do_periodo(loja_id, inicio, fim)
+            .await?;
+        let transicoes = self
+            .pedido_repository
+            .transicoes_do_periodo(loja_id, inicio, fim)
+            .await?;
+        Ok(Estatisticas::calcula(&pedidos, &transicoes, periodo.fuso()))
+    }
+
+    // Duração média e p90 de cada etapa nos pedidos criados no período.
+    pub async fn desempenho(
+        &self,
+        loja_id: usize,
+        periodo: PeriodoDoRelatorio,
+    ) -> Result<DesempenhoDaCozinha, DomainError> {
+        let estatisticas = self.estatisticas(loja_id, &periodo).await?;
+        Ok(DesempenhoDaCozinha {
+            inicio: periodo.inicio().to_string(),
+            fim: periodo.fim().to_string(),
+            fuso: periodo.fuso().name().to_string(),
+            etapas: estatisticas.geral.etapas(),
+            por_hora: estatisticas
+                .por_hora
+                .iter()
+                .map(|(hora, duracoes)| DesempenhoPorHora {
+                    hora: *hora,
+                    etapas: duracoes.etapas(),
+                })
+                .collect(),
+            por_produto: estatisticas
+                .por_produto
+                .iter()
+                .map(|(produto_id, (nome, duracoes))| DesempenhoDoProduto {
+                    produto_id: *produto_id,
+                    nome: nome.clone(),
+                    etapas: duracoes.etapas(),
+                })
+                .collect(),
+        })
+    }
+
+    // Quando o pedido deve ficar pronto: o momento em que ele entrou no status atual mais a
+    // duração estimada das etapas que faltam. Um pedido pendente ainda nem foi pago, então a
+    // contagem começa agora.
+    pub async fn previsao(
+        &self,
+        pedido_id: usize,
+        loja_id: usize,
+        fuso: Tz,
+        agora: DateTime<Utc>,
+    ) -> Result<PrevisaoDePreparo, DomainError> {
+        let pedido = self.pedido_repository.get_pedido_da_loja(pedido_id, loja_id).await?;
+        let transicoes = self.pedido_repository.transicoes_do_pedido(pedido_id).await?;
+        let entradas = entradas(&transicoes);
+        let entrada = |status: Status| entradas.get(&(pedido_id, status)).copied();
+        let previsao = |pronto_em: Option<DateTime<Utc>>| PrevisaoDePreparo {
+            pedido_id,
+            status: pedido.status().clone(),
+            pronto_em: pronto_em.map(|pronto_em| pronto_em.format(FORMATO_DATA).to_string()),
+            segundos_restantes: pronto_em.map(|pronto_em| (pronto_em - agora).num_seconds().max(0)),
+        };
+
+        let (etapas, inicio) = match pedido.status() {
+            Status::Pronto | Status::Finalizado => {
+                let mut pronto = previsao(entrada(Status::Pronto));
+                pronto.segundos_restantes = Some(0);
+                return Ok(pronto);
+            }
+            Status::Cancelado | Status::Invalido => return Ok(previsao(None)),
+            Status::Pendente => (vec![Etapa::Espera, Etapa::Preparo], agora),
+            Status::Pago => (vec![Etapa::Espera, Etapa::Preparo], entrada(Status::Pago).unwrap_or(agora)),
+            Status::EmPreparacao => (vec![Etapa::Preparo], entrada(Status::EmPreparacao).unwrap_or(agora)),
+        };
+
+        let hoje = agora.with_timezone(&fuso).date_naive();
+        let historico = PeriodoDoRelatorio::new(
+            hoje - Duration::days(DIAS_DO_HISTORICO_DA_PREVISAO - 1),
+            hoje,
+            fuso,
+        )?;
+        let estatisticas = self.estatisticas(loja_id, &historico).await?;
+        let hora = inicio.with_timezone(&fuso).hour();
+        let segundos: Option<f64> = etapas
+            .into_iter()
+            .map(|etapa| estatisticas.estimativa(etapa, hora, &pedido))
+            .sum();
+        let pronto_em = segundos
+            .map(|segundos| (inicio + Duration::milliseconds((segundos * 1000.0) as i64)).max(agora));
+        Ok(previsao(pronto_em))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::ingredientes::Ingredientes;
+    use crate::entities::produto::Produto;
+    use crate::traits::pedido_gateway::MockPedidoGateway;
+
+    const SAO_PAULO: Tz = chrono_tz::America::Sao_Paulo;
+
+    fn data(data: &str) -> chrono::NaiveDate {
+        chrono::NaiveDate::parse_from_str(data, "%Y-%m-%d").unwrap()
+    }
+
+    fn produto(id: usize) -> Produto {
+        Produto::new(
+            id,
+            format!("Produto {}", id),
+            "foto.png".to_string(),
+            "Descrição".to_string(),
+            1,
+            10.0,
+            Ingredientes::new(vec!["Pão".to_string()]).unwrap(),
+            "2024-01-01 00:00:00.000+0000".to_string(),
+            "2024-01-01 00:00:00.000+0000".to_string(),
+        )
+    }
+
+    fn pedido(id: usize, status: Status, bebida: Option<Produto>) -> Pedido {
+        Pedido::new(
+            id,
+            None,
+            Some(produto(1)),
+            None,
+            bebida,
+            None,
+            status,
+            "2024-01-10 15:00:00.000+0000".to_string(),
+            "2024-01-10 15:00:00.000+0000".to_string(),
+        )
+    }
+
+    fn transicao(pedido_id: usize, status: Status, momento: &str) -> TransicaoDeStatus {
+        TransicaoDeStatus::new(pedido_id, status, format!("2024-01-10 {}.000+0000", momento))
+    }
+
+    // Dez pedidos pagos às 15h (12h em São Paulo); o pedido `n` espera `n` minutos e leva
+    // `n` minutos no preparo. Os pares ainda levam uma bebida e os ímpares, 2 minutos na
+    // retirada. O pagamento do pedido 1 foi notificado duas vezes.
+    fn historico() -> (Vec<Pedido>, Vec<TransicaoDeStatus>) {
+        let mut pedidos = Vec::new();
+        let mut transicoes = Vec::new();
+        for n in 1..=10 {
+            let bebida = if n % 2 == 0 { Some(produto(2)) } else { None };
+            let status = if n % 2 == 0 { Status::Pronto } else { Status::Finalizado };
+            pedidos.push(pedido(n, status, bebida));
+            transicoes.push(transicao(n, Status::Pago, "15:00:00"));
+            transicoes.push(transicao(n, Status::EmPreparacao, &format!("15:{:02}:00", n)));
+            transicoes.push(transicao(n, Status::Pronto, &format!("15:{:02}:00", 2 * n)));
+            if n % 2 == 1 {
+                transicoes.push(transicao(n, Status::Finalizado, &format!("15:{:02}:00", 2 * n + 2)));
+            }
+        }
+        transicoes.push(transicao(1, Status::Pago, "15:00:30"));
+        (pedidos, transicoes)
+    }
+
+    fn use_case(atual: Pedido, transicoes_do_pedido: Vec<TransicaoDeStatus>) -> DesempenhoDaCozinhaUseCase {
+        let mut mock = MockPedidoGateway::new();
+        mock.expect_pedidos_do_periodo().returning(|_, _, _| Ok(historico().0));
+        mock.expect_transicoes_do_periodo().returning(|_, _, _| Ok(historico().1));
+        mock.expect_get_pedido_da_loja()
+            .returning(move |_, loja_id| match *atual.loja_id() == loja_id {
+                true => Ok(atual.clone()),
+                false => Err(DomainError::NotFound),
+            });
+        mock.expect_transicoes_do_pedido()
+            .returning(move |_| Ok(transicoes_do_pedido.clone()));
+        DesempenhoDaCozinhaUseCase::new(Arc::new(mock))
+    }
+
+    #[tokio::test]
+    async fn test_desempenho_por_etapa_hora_e_produto() {
+        let periodo = PeriodoDoRelatorio::new(data("2024-01-10"), data("2024-01-10"), SAO_PAULO).unwrap();
+        let desempenho = use_case(pedido(1, Status::Pago, None), vec![])
+            .desempenho(1, periodo)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            desempenho.etapas,
+            vec![
+                DuracaoDaEtapa { etapa: Etapa::Espera, pedidos: 10, media_em_segundos: 330.0, p90_em_segundos: 540.0 },
+                DuracaoDaEtapa { etapa: Etapa::Preparo, pedidos: 10, media_em_segundos: 330.0, p90_em_segundos: 540.0 },
+                DuracaoDaEtapa { etapa: Etapa::Retirada, pedidos: 5, media_em_segundos: 120.0, p90_em_segundos: 120.0 },
+            ]
+        );
+        assert_eq!(desempenho.por_hora.len(), 1);
+        assert_eq!(desempenho.por_hora[0].hora, 12);
+
+        // A bebida só aparece nos pedidos pares, que são os que demoram mais.
+        assert_eq!(desempenho.por_produto.len(), 2);
+        assert_eq!(desempenho.por_produto[1].produto_id, 2);
+        assert_eq!(desempenho.por_produto[1].etapas[1].pedidos, 5);
+        assert_eq!(desempenho.por_produto[1].etapas[1].media_em_segundos, 360.0);
+    }
+
+    #[tokio::test]
+    async fn test_previsao_de_preparo() {
+        let agora = instante("2024-01-10 16:02:00.000+0000").unwrap();
+        let em_preparacao = vec![
+            transicao(20, Status::Pago, "15:58:00"),
+            transicao(20, Status::EmPreparacao, "16:00:00"),
+        ];
+
+        // Com bebida, vale a média dos pedidos com bebida: 6 minutos de preparo.
+        let previsao = use_case(pedido(20, Status::EmPreparacao, Some(produto(2))), em_preparacao.clone())
+            .previsao(20, 1, SAO_PAULO, agora)
+            .await
+            .unwrap();
+        assert_eq!(previsao.pronto_em, Some("2024-01-10 16:06:00.000+0000".to_string()));
+        assert_eq!(previsao.segundos_restantes, Some(240));
+
+        // Pago às 15h58: espera e preparo médios de 5,5 minutos cada, a partir do pagamento.
+        let previsao = use_case(pedido(20, Status::Pago, None), em_preparacao)
+            .previsao(20, 1, SAO_PAULO, agora)
+            .await
+            .unwrap();
+        assert_eq!(previsao.pronto_em, Some("2024-01-10 16:09:00.000+0000".to_string()));
+
+        let previsao = use_case(pedido(20, Status::Cancelado, None), vec![])
+            .previsao(20, 1, SAO_PAULO, agora)
+            .await
+            .unwrap();
+        assert_eq!(previsao.pronto_em, None);
+
+        assert!(matches!(
+            use_case(pedido(20, Status::Pago, None), vec![])
+                .previsao(20, 2, SAO_PAULO, agora)
+                .await,
+            Err(DomainError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_previsao_sem_historico() {
+        let mut mock = MockPedidoGateway::new();
+        mock.expect_pedidos_do_periodo().returning(|_, _, _| Ok(vec![]));
+        mock.expect_transicoes_do_periodo().returning(|_, _, _| Ok(vec![]));
+        mock.expect_get_pedido_da_loja()
+            .returning(|_, _| Ok(pedido(1, Status::Pendente, None)));
+        mock.expect_transicoes_do_pedido().returning(|_| Ok(vec![]));
+
+        let previsao = DesempenhoDaCozinhaUseCase::new(Arc::new(mock))
+            .previsao(1, 1, SAO_PAULO, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(previsao.status, Status::Pendente);
+        assert_eq!(previsao.pronto_em, None);
+        assert_eq!(previsao.segundos_restantes, None);
+    }
+}
@@ -0,0 +1,218 @@
+use chrono::Utc;
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::agenda::{Agenda, AlvoAgenda, DadosDaAgenda};
+use crate::traits::{
+    agenda_gateway::AgendaGateway, categoria_gateway::CategoriaGateway,
+    produto_gateway::ProdutoGateway,
+};
+
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
+pub struct CreateAgendaInput {
+    pub alvo: AlvoAgenda,
+    pub alvo_id: usize,
+    pub dias_semana: Vec<u32>,
+    pub hora_inicio: String,
+    pub hora_fim: String,
+    pub preco: Option<f64>,
+}
+
+#[derive(Clone)]
+pub struct AgendaUseCase {
+    agenda_repository: Arc<dyn AgendaGateway + Sync + Send>,
+    produto_repository: Arc<dyn ProdutoGateway + Sync + Send>,
+    categoria_repository: Arc<dyn CategoriaGateway + Sync + Send>,
+}
+
+impl AgendaUseCase {
+    pub fn new(
+        agenda_repository: Arc<dyn AgendaGateway + Sync + Send>,
+        produto_repository: Arc<dyn ProdutoGateway + Sync + Send>,
+        categoria_repository: Arc<dyn CategoriaGateway + Sync + Send>,
+    ) -> Self {
+        AgendaUseCase {
+            agenda_repository,
+            produto_repository,
+            categoria_repository,
+        }
+    }
+
+    pub async fn get_agendas(&self) -> Result<Vec<Agenda>, DomainError> {
+        self.agenda_repository.get_agendas().await
+    }
+
+    pub async fn create_agenda(&self, agenda: CreateAgendaInput) -> Result<Agenda, DomainError> {
+        let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
+        let agenda = Agenda::new(
+            0,
+            DadosDaAgenda {
+                alvo: agenda.alvo,
+                alvo_id: agenda.alvo_id,
+                dias_semana: agenda.dias_semana,
+                hora_inicio: agenda.hora_inicio,
+                hora_fim: agenda.hora_fim,
+                preco: agenda.preco,
+            },
+            _now.clone(),
+            _now,
+        );
+        agenda.validate_entity()?;
+
+        let alvo_existe = match agenda.alvo() {
+            AlvoAgenda::Produto => {
+                self.produto_repository.get_produto_by_id(*agenda.alvo_id()).await.map(|_| ())
+            }
+            AlvoAgenda::Categoria => {
+                self.categoria_repository.get_categoria_by_id(*agenda.alvo_id()).await.map(|_| ())
+            }
+        };
+        match alvo_existe {
+            Ok(()) => (),
+            Err(DomainError::NotFound) => return Err(DomainError::Invalid(agenda.alvo().to_string())),
+            Err(e) => return Err(e),
+        }
+
+        self.agenda_repository.create_agenda(agenda).await
+    }
+
+    pub async fn delete_agenda(&self, id: usize) -> Result<(), DomainError> {
+        self.agenda_repository.delete_agenda(id).await
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::categoria::{Categoria, DadosDaCategoria};
+    use crate::traits::agenda_gateway::MockAgendaGateway;
+    use crate::traits::categoria_gateway::MockCategoriaGateway;
+    use crate::traits::produto_gateway::MockProdutoGateway;
+
+    fn create_input(alvo: AlvoAgenda, preco: Option<f64>) -> CreateAgendaInput {
+        CreateAgendaInput {
+            alvo,
+            alvo_id: 2,
+            dias_semana: vec![1, 2, 3, 4, 5],
+            hora_inicio: "17:00".to_string(),
+            hora_fim: "19:00".to_string(),
+            preco,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_agenda() {
+        let mut mock = MockAgendaGateway::new();
+        mock.expect_create_agenda().times(1).returning(Ok);
+
+        let mut mock_categoria = MockCategoriaGateway::new();
+        mock_categoria
+            .expect_get_categoria_by_id()
+            .times(1)
+            .returning(|id| {
+                Ok(Categoria::new(
+                    id,
+                    DadosDaCategoria {
+                        nome: "Bebida".to_string(),
+                        icone: None,
+                        ordem: 2,
+                        ativa: true,
+                        item_pedido: None,
+                    },
+                    "2021-10-10 00:00:00.000+0000".to_string(),
+                    "2021-10-10 00:00:00.000+0000".to_string(),
+                ))
+            });
+
+        let use_case = AgendaUseCase::new(
+            Arc::new(mock),
+            Arc::new(MockProdutoGateway::new()),
+            Arc::new(mock_categoria),
+        );
+
+        let result = use_case
+            .create_agenda(create_input(AlvoAgenda::Categoria, None))
+            .await
+            .unwrap();
+        assert_eq!(result.alvo(), &AlvoAgenda::Categoria);
+        assert_eq!(result.dias_semana().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_create_agenda_alvo_inexistente() {
+        let mut mock = MockAgendaGateway::new();
+        mock.expect_create_agenda().times(0);
+
+        let mut mock_produto = MockProdutoGateway::new();
+        mock_produto
+            .expect_get_produto_by_id()
+            .times(1)
+            .returning(|_| Err(DomainError::NotFound));
+
+        let use_case = AgendaUseCase::new(
+            Arc::new(mock),
+            Arc::new(mock_produto),
+            Arc::new(MockCategoriaGateway::new()),
+        );
+
+        let result = use_case
+            .create_agenda(create_input(AlvoAgenda::Produto, Some(4.0)))
+            .await;
+        assert!(matches!(result, Err(DomainError::Invalid(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_agenda_preco_em_categoria() {
+        let mut mock = MockAgendaGateway::new();
+        mock.expect_create_agenda().times(1).returning(Ok);
+
+        let mut mock_categoria = MockCategoriaGateway::new();
+        mock_categoria
+            .expect_get_categoria_by_id()
+            .times(1)
+            .returning(|id| {
+                Ok(Categoria::new(
+                    id,
+                    DadosDaCategoria {
+                        nome: "Bebida".to_string(),
+                        icone: None,
+                        ordem: 2,
+                        ativa: true,
+                        item_pedido: None,
+                    },
+                    "2021-10-10 00:00:00.000+0000".to_string(),
+                    "2021-10-10 00:00:00.000+0000".to_string(),
+                ))
+            });
+
+        let use_case = AgendaUseCase::new(
+            Arc::new(mock),
+            Arc::new(MockProdutoGateway::new()),
+            Arc::new(mock_categoria),
+        );
+
+        let result = use_case
+            .create_agenda(create_input(AlvoAgenda::Categoria, Some(4.0)))
+            .await
+            .unwrap();
+        assert_eq!(result.preco(), Some(4.0));
+    }
+
+    #[tokio::test]
+    async fn test_delete_agenda() {
+        let mut mock = MockAgendaGateway::new();
+        mock.expect_delete_agenda().times(1).returning(|_| Ok(()));
+
+        let use_case = AgendaUseCase::new(
+            Arc::new(mock),
+            Arc::new(MockProdutoGateway::new()),
+            Arc::new(MockCategoriaGateway::new()),
+        );
+
+        assert!(use_case.delete_agenda(1).await.is_ok());
+    }
+}
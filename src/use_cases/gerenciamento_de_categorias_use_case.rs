@@ -0,0 +1,313 @@
+use chrono::Utc;
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::categoria::{Categoria, DadosDaCategoria, ItemPedido};
+use crate::traits::{categoria_gateway::CategoriaGateway, produto_gateway::ProdutoGateway};
+
+fn ativa_por_padrao() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
+pub struct CreateCategoriaInput {
+    pub nome: String,
+    pub icone: Option<String>,
+    pub ordem: usize,
+    #[serde(default = "ativa_por_padrao")]
+    pub ativa: bool,
+    pub item_pedido: Option<ItemPedido>,
+}
+
+// Campos ausentes ficam como estão; `icone` e `item_pedido` enviados como null são apagados.
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
+pub struct UpdateCategoriaInput {
+    pub nome: Option<String>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    #[schemars(with = "Option<String>")]
+    pub icone: Option<Option<String>>,
+    pub ordem: Option<usize>,
+    pub ativa: Option<bool>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    #[schemars(with = "Option<ItemPedido>")]
+    pub item_pedido: Option<Option<ItemPedido>>,
+}
+
+#[derive(Clone)]
+pub struct CategoriaUseCase {
+    categoria_repository: Arc<dyn CategoriaGateway + Sync + Send>,
+    produto_repository: Arc<dyn ProdutoGateway + Sync + Send>,
+}
+
+impl CategoriaUseCase {
+    pub fn new(
+        categoria_repository: Arc<dyn CategoriaGateway + Sync + Send>,
+        produto_repository: Arc<dyn ProdutoGateway + Sync + Send>,
+    ) -> Self {
+        CategoriaUseCase {
+            categoria_repository,
+            produto_repository,
+        }
+    }
+
+    pub async fn get_categorias(&self) -> Result<Vec<Categoria>, DomainError> {
+        self.categoria_repository.get_categorias().await
+    }
+
+    pub async fn get_categoria_by_id(&self, id: usize) -> Result<Categoria, DomainError> {
+        self.categoria_repository.get_categoria_by_id(id).await
+    }
+
+    pub async fn create_categoria(
+        &self,
+        categoria: CreateCategoriaInput,
+    ) -> Result<Categoria, DomainError> {
+        let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
+        let categoria = Categoria::new(
+            0,
+            DadosDaCategoria {
+                nome: categoria.nome,
+                icone: categoria.icone,
+                ordem: categoria.ordem,
+                ativa: categoria.ativa,
+                item_pedido: categoria.item_pedido,
+            },
+            _now.clone(),
+            _now,
+        );
+        categoria.validate_entity()?;
+
+        self.categoria_repository.create_categoria(categoria).await
+    }
+
+    pub async fn update_categoria(
+        &self,
+        id: usize,
+        fields_to_update: UpdateCategoriaInput,
+    ) -> Result<Categoria, DomainError> {
+
+        let mut current_categoria = self.categoria_repository.get_categoria_by_id(id).await?;
+
+        if let Some(nome) = fields_to_update.nome {
+            current_categoria.set_nome(nome)?;
+        }
+        if let Some(icone) = fields_to_update.icone {
+            current_categoria.set_icone(icone);
+        }
+        if let Some(ordem) = fields_to_update.ordem {
+            current_categoria.set_ordem(ordem);
+        }
+        if let Some(ativa) = fields_to_update.ativa {
+            current_categoria.set_ativa(ativa);
+        }
+        if let Some(item_pedido) = fields_to_update.item_pedido {
+            current_categoria.set_item_pedido(item_pedido);
+        }
+
+        self.categoria_repository.update_categoria(current_categoria).await
+    }
+
+    pub async fn delete_categoria(&self, id: usize) -> Result<(), DomainError> {
+        let produtos = self.produto_repository.get_produtos().await?;
+        if produtos.iter().any(|produto| *produto.categoria_id() == id) {
+            return Err(DomainError::Invalid(
+                "Categoria possui produtos cadastrados".to_string(),
+            ));
+        }
+
+        self.categoria_repository.delete_categoria(id).await
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{ingredientes::Ingredientes, produto::Produto};
+    use crate::traits::categoria_gateway::MockCategoriaGateway;
+    use crate::traits::produto_gateway::MockProdutoGateway;
+    use mockall::predicate::*;
+
+    fn create_valid_categoria() -> Categoria {
+        Categoria::new(
+            1,
+            DadosDaCategoria {
+                nome: "Lanche".to_string(),
+                icone: None,
+                ordem: 1,
+                ativa: true,
+                item_pedido: Some(ItemPedido::Lanche),
+            },
+            "2021-10-10 00:00:00.000+0000".to_string(),
+            "2021-10-10 00:00:00.000+0000".to_string(),
+        )
+    }
+
+    fn create_valid_produto(categoria_id: usize) -> Produto {
+        Produto::new(
+            1,
+            "nome".to_string(),
+            "foto".to_string(),
+            "descricao".to_string(),
+            categoria_id,
+            10.0,
+            Ingredientes::new(vec!["ingrediente1".to_string()]).unwrap(),
+            "2021-10-10".to_string(),
+            "2021-10-10".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_get_categorias() {
+        let mut mock = MockCategoriaGateway::new();
+        mock.expect_get_categorias()
+            .times(1)
+            .returning(|| Ok(vec![create_valid_categoria()]));
+
+        let use_case = CategoriaUseCase::new(
+            Arc::new(mock),
+            Arc::new(MockProdutoGateway::new()),
+        );
+        let result = use_case.get_categorias().await;
+        assert_eq!(result.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_create_categoria() {
+        let mut mock = MockCategoriaGateway::new();
+        mock.expect_create_categoria()
+            .times(1)
+            .withf(|categoria| categoria.nome() == "Vegano" && categoria.ativa())
+            .returning(Ok);
+
+        let use_case = CategoriaUseCase::new(
+            Arc::new(mock),
+            Arc::new(MockProdutoGateway::new()),
+        );
+        let result = use_case
+            .create_categoria(CreateCategoriaInput {
+                nome: "Vegano".to_string(),
+                icone: Some("folha.svg".to_string()),
+                ordem: 5,
+                ativa: true,
+                item_pedido: Some(ItemPedido::Lanche),
+            })
+            .await;
+        assert_eq!(result.unwrap().nome(), "Vegano");
+    }
+
+    #[tokio::test]
+    async fn test_create_categoria_nome_vazio() {
+        let mut mock = MockCategoriaGateway::new();
+        mock.expect_create_categoria().times(0);
+
+        let use_case = CategoriaUseCase::new(
+            Arc::new(mock),
+            Arc::new(MockProdutoGateway::new()),
+        );
+        let result = use_case
+            .create_categoria(CreateCategoriaInput {
+                nome: "".to_string(),
+                icone: None,
+                ordem: 5,
+                ativa: true,
+                item_pedido: None,
+            })
+            .await;
+        assert!(matches!(result, Err(DomainError::Empty)));
+    }
+
+    #[tokio::test]
+    async fn test_update_categoria() {
+        let mut mock = MockCategoriaGateway::new();
+        mock.expect_get_categoria_by_id()
+            .times(1)
+            .with(eq(1))
+            .returning(|_| Ok(create_valid_categoria()));
+        mock.expect_update_categoria().times(1).returning(Ok);
+
+        let use_case = CategoriaUseCase::new(
+            Arc::new(mock),
+            Arc::new(MockProdutoGateway::new()),
+        );
+        let result = use_case
+            .update_categoria(
+                1,
+                UpdateCategoriaInput {
+                    nome: None,
+                    icone: None,
+                    ordem: Some(9),
+                    ativa: Some(false),
+                    item_pedido: None,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.ordem(), 9);
+        assert!(!result.ativa());
+        assert_eq!(result.nome(), "Lanche");
+    }
+
+    #[tokio::test]
+    async fn test_update_categoria_apaga_campos_enviados_como_null() {
+        let mut categoria = create_valid_categoria();
+        categoria.set_icone(Some("lanche.svg".to_string()));
+        let mut mock = MockCategoriaGateway::new();
+        mock.expect_get_categoria_by_id()
+            .returning(move |_| Ok(categoria.clone()));
+        mock.expect_update_categoria().returning(Ok);
+        let use_case = CategoriaUseCase::new(Arc::new(mock), Arc::new(MockProdutoGateway::new()));
+
+        let sem_campos: UpdateCategoriaInput = serde_json::from_str(r#"{"ordem": 2}"#).unwrap();
+        let result = use_case.update_categoria(1, sem_campos).await.unwrap();
+        assert_eq!(result.icone(), Some(&"lanche.svg".to_string()));
+        assert_eq!(result.item_pedido(), Some(&ItemPedido::Lanche));
+
+        let com_null: UpdateCategoriaInput =
+            serde_json::from_str(r#"{"icone": null, "item_pedido": null}"#).unwrap();
+        let result = use_case.update_categoria(1, com_null).await.unwrap();
+        assert_eq!(result.icone(), None);
+        assert_eq!(result.item_pedido(), None);
+    }
+
+    #[tokio::test]
+    async fn test_delete_categoria() {
+        let mut mock = MockCategoriaGateway::new();
+        mock.expect_delete_categoria()
+            .times(1)
+            .with(eq(2))
+            .returning(|_| Ok(()));
+
+        let mut mock_produto = MockProdutoGateway::new();
+        mock_produto
+            .expect_get_produtos()
+            .returning(|| Ok(vec![create_valid_produto(1)]));
+
+        let use_case = CategoriaUseCase::new(
+            Arc::new(mock),
+            Arc::new(mock_produto),
+        );
+        assert!(use_case.delete_categoria(2).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_delete_categoria_com_produtos() {
+        let mut mock = MockCategoriaGateway::new();
+        mock.expect_delete_categoria().times(0);
+
+        let mut mock_produto = MockProdutoGateway::new();
+        mock_produto
+            .expect_get_produtos()
+            .returning(|| Ok(vec![create_valid_produto(1)]));
+
+        let use_case = CategoriaUseCase::new(
+            Arc::new(mock),
+            Arc::new(mock_produto),
+        );
+        let result = use_case.delete_categoria(1).await;
+        assert!(matches!(result, Err(DomainError::Invalid(_))));
+    }
+}
@@ -0,0 +1,219 @@
+use chrono::Utc;
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::base::domain_error::DomainError;
+use crate::base::paginacao::{Pagina, Paginacao};
+use crate::entities::{cliente::Cliente, cpf::Cpf, pedido::Pedido};
+use crate::traits::{cliente_gateway::ClienteGateway, pedido_gateway::PedidoGateway};
+
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
+pub struct CreateClienteInput {
+    pub cpf: Cpf,
+    pub nome: String,
+    pub email: Option<String>,
+    #[serde(default)]
+    pub aceita_marketing: bool,
+    #[serde(default)]
+    pub aceita_compartilhamento: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
+pub struct UpdateClienteInput {
+    pub nome: Option<String>,
+    pub email: Option<String>,
+    pub aceita_marketing: Option<bool>,
+    pub aceita_compartilhamento: Option<bool>,
+}
+
+#[derive(Clone)]
+pub struct ClienteUseCase {
+    cliente_repository: Arc<dyn ClienteGateway + Sync + Send>,
+    pedido_repository: Arc<dyn PedidoGateway + Sync + Send>,
+}
+
+impl ClienteUseCase {
+    pub fn new(
+        cliente_repository: Arc<dyn ClienteGateway + Sync + Send>,
+        pedido_repository: Arc<dyn PedidoGateway + Sync + Send>,
+    ) -> Self {
+        ClienteUseCase {
+            cliente_repository,
+            pedido_repository,
+        }
+    }
+
+    pub async fn get_cliente_by_cpf(&self, cpf: String) -> Result<Cliente, DomainError> {
+        let cpf = Cpf::new(cpf)?;
+        self.cliente_repository.get_cliente_by_cpf(&cpf).await
+    }
+
+    pub async fn create_cliente(&self, cliente: CreateClienteInput) -> Result<Cliente, DomainError> {
+        let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
+        // O CPF chega já deserializado; passa de novo pela validação.
+        let cpf = Cpf::new(cliente.cpf.get_string())?;
+        let cliente = Cliente::new(
+            cpf,
+            cliente.nome,
+            cliente.email,
+            cliente.aceita_marketing,
+            cliente.aceita_compartilhamento,
+            _now.clone(),
+            _now,
+        );
+        cliente.validate_entity()?;
+
+        self.cliente_repository.create_cliente(cliente).await
+    }
+
+    pub async fn update_cliente(
+        &self,
+        cpf: String,
+        fields_to_update: UpdateClienteInput,
+    ) -> Result<Cliente, DomainError> {
+        let mut current_cliente = self.get_cliente_by_cpf(cpf).await?;
+
+        if let Some(nome) = fields_to_update.nome {
+            current_cliente.set_nome(nome)?;
+        }
+        if let Some(email) = fields_to_update.email {
+            current_cliente.set_email(Some(email))?;
+        }
+        if let Some(aceita_marketing) = fields_to_update.aceita_marketing {
+            current_cliente.set_aceita_marketing(aceita_marketing);
+        }
+        if let Some(aceita_compartilhamento) = fields_to_update.aceita_compartilhamento {
+            current_cliente.set_aceita_compartilhamento(aceita_compartilhamento);
+        }
+
+        self.cliente_repository.update_cliente(current_cliente).await
+    }
+
+    // Cada cliente só vê o próprio histórico.
+    pub async fn historico_de_pedidos(
+        &self,
+        cpf: String,
+        cliente_autenticado: &Cpf,
+        pagina: Option<usize>,
+        tamanho: Option<usize>,
+    ) -> Result<Pagina<Pedido>, DomainError> {
+        let cpf = Cpf::new(cpf)?;
+        if cpf != *cliente_autenticado {
+            return Err(DomainError::Forbidden);
+        }
+        let paginacao = Paginacao::new(pagina, tamanho)?;
+        self.pedido_repository
+            .lista_pedidos_do_cliente(&cpf, paginacao)
+            .await
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::{cliente_gateway::MockClienteGateway, pedido_gateway::MockPedidoGateway};
+
+    fn create_valid_cliente() -> Cliente {
+        Cliente::new(
+            Cpf::new("123.456.789-09".to_string()).unwrap(),
+            "Maria".to_string(),
+            None,
+            false,
+            false,
+            "2021-10-10 00:00:00.000+0000".to_string(),
+            "2021-10-10 00:00:00.000+0000".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_create_cliente() {
+        let mut mock = MockClienteGateway::new();
+        mock.expect_create_cliente()
+            .times(1)
+            .withf(|cliente| cliente.cpf().get_string() == "12345678909" && cliente.aceita_marketing())
+            .returning(Ok);
+
+        let use_case = ClienteUseCase::new(Arc::new(mock), Arc::new(MockPedidoGateway::new()));
+        let result = use_case
+            .create_cliente(CreateClienteInput {
+                cpf: Cpf("123.456.789-09".to_string()),
+                nome: "Maria".to_string(),
+                email: Some("maria@email.com".to_string()),
+                aceita_marketing: true,
+                aceita_compartilhamento: false,
+            })
+            .await;
+        assert_eq!(result.unwrap().nome(), "Maria");
+    }
+
+    #[tokio::test]
+    async fn test_create_cliente_cpf_invalido() {
+        let mut mock = MockClienteGateway::new();
+        mock.expect_create_cliente().times(0);
+
+        let use_case = ClienteUseCase::new(Arc::new(mock), Arc::new(MockPedidoGateway::new()));
+        let result = use_case
+            .create_cliente(CreateClienteInput {
+                cpf: Cpf("000.000.000-00".to_string()),
+                nome: "Maria".to_string(),
+                email: None,
+                aceita_marketing: false,
+                aceita_compartilhamento: false,
+            })
+            .await;
+        assert!(matches!(result, Err(DomainError::InvalidField { ref field, .. }) if field == "cpf"));
+    }
+
+    #[tokio::test]
+    async fn test_update_cliente() {
+        let mut mock = MockClienteGateway::new();
+        mock.expect_get_cliente_by_cpf()
+            .times(1)
+            .withf(|cpf| cpf.get_string() == "12345678909")
+            .returning(|_| Ok(create_valid_cliente()));
+        mock.expect_update_cliente()
+            .times(1)
+            .withf(|cliente| cliente.aceita_compartilhamento())
+            .returning(Ok);
+
+        let use_case = ClienteUseCase::new(Arc::new(mock), Arc::new(MockPedidoGateway::new()));
+        let result = use_case
+            .update_cliente(
+                "12345678909".to_string(),
+                UpdateClienteInput {
+                    nome: None,
+                    email: None,
+                    aceita_marketing: None,
+                    aceita_compartilhamento: Some(true),
+                },
+            )
+            .await;
+        assert!(result.unwrap().aceita_compartilhamento());
+    }
+
+    #[tokio::test]
+    async fn test_historico_de_pedidos() {
+        let mut mock_pedido_gateway = MockPedidoGateway::new();
+        mock_pedido_gateway
+            .expect_lista_pedidos_do_cliente()
+            .times(1)
+            .withf(|cpf, paginacao| cpf.get_string() == "12345678909" && paginacao.pagina() == 2)
+            .returning(|_, paginacao| Ok(paginacao.aplica(vec![])));
+
+        let use_case = ClienteUseCase::new(Arc::new(MockClienteGateway::new()), Arc::new(mock_pedido_gateway));
+        let cliente = Cpf::new("123.456.789-09".to_string()).unwrap();
+
+        let result = use_case
+            .historico_de_pedidos("123.456.789-09".to_string(), &cliente, Some(2), None)
+            .await;
+        assert_eq!(result.unwrap().total(), 0);
+
+        let result = use_case
+            .historico_de_pedidos("097.855.456-60".to_string(), &cliente, None, None)
+            .await;
+        assert!(matches!(result, Err(DomainError::Forbidden)));
+    }
+}
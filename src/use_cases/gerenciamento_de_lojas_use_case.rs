@@ -0,0 +1,147 @@
+use chrono::Utc;
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::loja::Loja;
+use crate::traits::loja_gateway::LojaGateway;
+
+fn ativa_por_padrao() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
+pub struct CreateLojaInput {
+    pub nome: String,
+    #[serde(default = "ativa_por_padrao")]
+    pub ativa: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
+pub struct UpdateLojaInput {
+    pub nome: Option<String>,
+    pub ativa: Option<bool>,
+}
+
+#[derive(Clone)]
+pub struct LojaUseCase {
+    loja_repository: Arc<dyn LojaGateway + Sync + Send>,
+}
+
+impl LojaUseCase {
+    pub fn new(loja_repository: Arc<dyn LojaGateway + Sync + Send>) -> Self {
+        LojaUseCase { loja_repository }
+    }
+
+    pub async fn get_lojas(&self) -> Result<Vec<Loja>, DomainError> {
+        self.loja_repository.get_lojas().await
+    }
+
+    pub async fn get_loja_by_id(&self, id: usize) -> Result<Loja, DomainError> {
+        self.loja_repository.get_loja_by_id(id).await
+    }
+
+    pub async fn create_loja(&self, loja: CreateLojaInput) -> Result<Loja, DomainError> {
+        let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
+        let loja = Loja::new(0, loja.nome, loja.ativa, _now.clone(), _now);
+        loja.validate_entity()?;
+
+        self.loja_repository.create_loja(loja).await
+    }
+
+    pub async fn update_loja(
+        &self,
+        id: usize,
+        fields_to_update: UpdateLojaInput,
+    ) -> Result<Loja, DomainError> {
+
+        let mut current_loja = self.loja_repository.get_loja_by_id(id).await?;
+
+        if let Some(nome) = fields_to_update.nome {
+            current_loja.set_nome(nome)?;
+        }
+        if let Some(ativa) = fields_to_update.ativa {
+            current_loja.set_ativa(ativa);
+        }
+
+        self.loja_repository.update_loja(current_loja).await
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::loja_gateway::MockLojaGateway;
+    use mockall::predicate::*;
+
+    fn create_valid_loja() -> Loja {
+        Loja::new(
+            2,
+            "Shopping".to_string(),
+            true,
+            "2021-10-10 00:00:00.000+0000".to_string(),
+            "2021-10-10 00:00:00.000+0000".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_create_loja() {
+        let mut mock = MockLojaGateway::new();
+        mock.expect_create_loja()
+            .times(1)
+            .withf(|loja| loja.nome() == "Shopping" && loja.ativa())
+            .returning(Ok);
+
+        let use_case = LojaUseCase::new(Arc::new(mock));
+        let result = use_case
+            .create_loja(CreateLojaInput {
+                nome: "Shopping".to_string(),
+                ativa: true,
+            })
+            .await;
+        assert_eq!(result.unwrap().nome(), "Shopping");
+    }
+
+    #[tokio::test]
+    async fn test_create_loja_nome_vazio() {
+        let mut mock = MockLojaGateway::new();
+        mock.expect_create_loja().times(0);
+
+        let use_case = LojaUseCase::new(Arc::new(mock));
+        let result = use_case
+            .create_loja(CreateLojaInput {
+                nome: "".to_string(),
+                ativa: true,
+            })
+            .await;
+        assert!(matches!(result, Err(DomainError::Empty)));
+    }
+
+    #[tokio::test]
+    async fn test_update_loja() {
+        let mut mock = MockLojaGateway::new();
+        mock.expect_get_loja_by_id()
+            .times(1)
+            .with(eq(2))
+            .returning(|_| Ok(create_valid_loja()));
+        mock.expect_update_loja()
+            .times(1)
+            .withf(|loja| !loja.ativa())
+            .returning(Ok);
+
+        let use_case = LojaUseCase::new(Arc::new(mock));
+        let result = use_case
+            .update_loja(
+                2,
+                UpdateLojaInput {
+                    nome: None,
+                    ativa: Some(false),
+                },
+            )
+            .await;
+        assert!(!result.unwrap().ativa());
+    }
+}
@@ -1,25 +1,33 @@
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 
-use tokio::sync::Mutex;
 use schemars::JsonSchema;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-use crate::base::domain_error::DomainError;
+use crate::base::{assertion_concern, domain_error::DomainError};
 use crate::entities::{
-    ingredientes::Ingredientes,
-    produto::{Categoria, Produto},
+    agenda::Agenda, categoria::Categoria, idioma::{Idioma, TraducaoProduto}, ingredientes::Ingredientes,
+    loja::ProdutoLoja, produto::Produto,
 };
-use crate::traits::produto_gateway::ProdutoGateway;
+use crate::traits::{
+    agenda_gateway::AgendaGateway, categoria_gateway::CategoriaGateway, foto_storage_adapter::FotoStorageAdapter,
+    produto_gateway::ProdutoGateway,
+};
+
+pub const TAMANHO_MAXIMO_FOTO: usize = 5 * 1024 * 1024;
 
 #[derive(Clone, Debug, Deserialize, JsonSchema)]
 pub struct CreateProdutoInput {
     pub nome: String,
     pub foto: String,
     pub descricao: String,
-    pub categoria: Categoria,
+    pub categoria_id: usize,
     pub preco: f64,
     pub ingredientes: Ingredientes,
+    // Ausente para produtos vendidos em todas as lojas.
+    #[serde(default)]
+    pub loja_id: Option<usize>,
 }
 
 impl CreateProdutoInput {
@@ -27,7 +35,7 @@ impl CreateProdutoInput {
         nome: String,
         foto: String,
         descricao: String,
-        categoria: Categoria,
+        categoria_id: usize,
         preco: f64,
         ingredientes: Ingredientes,
     ) -> Self {
@@ -35,9 +43,10 @@ impl CreateProdutoInput {
             nome,
             foto,
             descricao,
-            categoria,
+            categoria_id,
             preco,
             ingredientes,
+            loja_id: None,
         }
     }
 }
@@ -47,52 +56,195 @@ pub struct UpdateProdutoInput {
     pub nome: Option<String>,
     pub foto: Option<String>,
     pub descricao: Option<String>,
-    pub categoria: Option<Categoria>,
+    pub categoria_id: Option<usize>,
     pub preco: Option<f64>,
     pub ingredientes: Option<Ingredientes>,
+    #[serde(default)]
+    pub loja_id: Option<usize>,
+}
+
+fn disponivel_por_padrao() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
+pub struct ProdutoLojaInput {
+    // Preço cobrado na loja; ausente mantém o preço do produto.
+    pub preco: Option<f64>,
+    #[serde(default = "disponivel_por_padrao")]
+    pub disponivel: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
+pub struct TraducaoInput {
+    pub nome: Option<String>,
+    pub descricao: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct FotoInput {
+    pub content_type: String,
+    pub conteudo: Vec<u8>,
+}
+
+impl FotoInput {
+    fn extensao(&self) -> Result<&'static str, DomainError> {
+        let (extensao, assinatura_valida) = match self.content_type.as_str() {
+            "image/png" => ("png", self.conteudo.starts_with(b"\x89PNG\r\n\x1a\n")),
+            "image/jpeg" => ("jpg", self.conteudo.starts_with(&[0xFF, 0xD8, 0xFF])),
+            "image/webp" => (
+                "webp",
+                self.conteudo.len() >= 12
+                    && self.conteudo.starts_with(b"RIFF")
+                    && &self.conteudo[8..12] == b"WEBP",
+            ),
+            _ => return Err(DomainError::Invalid("Tipo de foto não suportado".to_string())),
+        };
+        match assinatura_valida {
+            true => Ok(extensao),
+            false => Err(DomainError::Invalid("Conteúdo da foto não corresponde ao tipo".to_string())),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct SecaoCardapio {
+    pub categoria: Categoria,
+    pub produtos: Vec<Produto>,
 }
 
 #[derive(Clone)]
 pub struct ProdutoUseCase {
-    produto_repository: Arc<Mutex<dyn ProdutoGateway + Sync + Send>>,
+    produto_repository: Arc<dyn ProdutoGateway + Sync + Send>,
+    categoria_repository: Arc<dyn CategoriaGateway + Sync + Send>,
+    agenda_repository: Arc<dyn AgendaGateway + Sync + Send>,
 }
 
 impl ProdutoUseCase {
-    pub fn new(produto_repository: Arc<Mutex<dyn ProdutoGateway + Sync + Send>>) -> Self {
-        ProdutoUseCase { produto_repository }
+    pub fn new(
+        produto_repository: Arc<dyn ProdutoGateway + Sync + Send>,
+        categoria_repository: Arc<dyn CategoriaGateway + Sync + Send>,
+        agenda_repository: Arc<dyn AgendaGateway + Sync + Send>,
+    ) -> Self {
+        ProdutoUseCase {
+            produto_repository,
+            categoria_repository,
+            agenda_repository,
+        }
+    }
+
+    async fn aplica_agendas(
+        &self,
+        produtos: Vec<Produto>,
+        momento: &DateTime<Tz>,
+    ) -> Result<Vec<Produto>, DomainError> {
+        let agendas = self.agenda_repository.get_agendas().await?;
+        Ok(produtos
+            .iter()
+            .filter_map(|produto| Agenda::aplica(produto, &agendas, momento))
+            .collect())
+    }
+
+    async fn traduz(&self, produtos: Vec<Produto>, idioma: Idioma) -> Result<Vec<Produto>, DomainError> {
+        if idioma == Idioma::PtBr {
+            return Ok(produtos);
+        }
+        let traducoes = self.produto_repository.get_traducoes(idioma).await?;
+        produtos
+            .iter()
+            .map(|produto| TraducaoProduto::aplica(produto, idioma, &traducoes))
+            .collect()
+    }
+
+    async fn valida_categoria(&self, categoria_id: usize) -> Result<(), DomainError> {
+        match self.categoria_repository.get_categoria_by_id(categoria_id).await {
+            Ok(_) => Ok(()),
+            Err(DomainError::NotFound) => Err(DomainError::InvalidField {
+                field: "categoria_id".to_string(),
+                message: "Categoria não encontrada".to_string(),
+            }),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub async fn get_produtos(
+        &self,
+        loja_id: usize,
+        idioma: Idioma,
+        momento: DateTime<Tz>,
+    ) -> Result<Vec<Produto>, DomainError> {
+        let produtos = self.produto_repository.get_produtos_da_loja(loja_id).await?;
+        let produtos = self.aplica_agendas(produtos, &momento).await?;
+        self.traduz(produtos, idioma).await
+    }
+
+    pub async fn get_produto_by_id(&self, id: usize, loja_id: usize, idioma: Idioma) -> Result<Produto, DomainError> {
+        let produto = {
+            let produto = self.produto_repository.get_produto_by_id(id).await?;
+            let ajustes = self.produto_repository.get_produtos_loja(loja_id).await?;
+            ProdutoLoja::aplica(&produto, loja_id, &ajustes).ok_or(DomainError::NotFound)?
+        };
+        let mut produtos = self.traduz(vec![produto], idioma).await?;
+        produtos.pop().ok_or(DomainError::NotFound)
     }
 
-    pub async fn get_produtos(&self) -> Result<Vec<Produto>, DomainError> {
-        let produto_repository = self.produto_repository.lock().await;
-        produto_repository.get_produtos().await
+    pub async fn get_cardapio(
+        &self,
+        loja_id: usize,
+        idioma: Idioma,
+        momento: DateTime<Tz>,
+    ) -> Result<Vec<SecaoCardapio>, DomainError> {
+        let mut categorias = self.categoria_repository.get_categorias().await?;
+        categorias.retain(|categoria| categoria.ativa());
+        categorias.sort_by_key(|categoria| categoria.ordem());
+
+        let produtos = self.produto_repository.get_produtos_da_loja(loja_id).await?;
+        let produtos = self.aplica_agendas(produtos, &momento).await?;
+        let produtos = self.traduz(produtos, idioma).await?;
+
+        Ok(categorias
+            .into_iter()
+            .map(|categoria| SecaoCardapio {
+                produtos: produtos
+                    .iter()
+                    .filter(|produto| produto.categoria_id() == categoria.id())
+                    .cloned()
+                    .collect(),
+                categoria,
+            })
+            .collect())
     }
 
-    pub async fn get_produto_by_id(&self, id: usize) -> Result<Produto, DomainError> {
-        let produto_repository = self.produto_repository.lock().await;
-        produto_repository.get_produto_by_id(id).await
+    pub async fn busca_produtos(&self, termo: String, loja_id: usize, idioma: Idioma) -> Result<Vec<Produto>, DomainError> {
+        let termo = termo.trim().to_string();
+        assertion_concern::assert_argument_not_empty(termo.clone())?;
+        let produtos = self.produto_repository.busca_produtos(termo, loja_id).await?;
+        self.traduz(produtos, idioma).await
     }
 
     pub async fn create_produto(
         &self,
         produto: CreateProdutoInput,
     ) -> Result<Produto, DomainError> {
-        let mut produto_repository = self.produto_repository.lock().await;
+        self.valida_categoria(produto.categoria_id).await?;
+
 
         let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
 
-        let produto = produto_repository
-            .create_produto(Produto::new(
-                0,
-                produto.nome,
-                produto.foto,
-                produto.descricao,
-                produto.categoria,
-                produto.preco,
-                produto.ingredientes.clone(),
-                _now.clone(),
-                _now,
-            ))
-            .await?;
+        let mut novo_produto = Produto::new(
+            0,
+            produto.nome,
+            produto.foto,
+            produto.descricao,
+            produto.categoria_id,
+            produto.preco,
+            produto.ingredientes.clone(),
+            _now.clone(),
+            _now,
+        );
+        novo_produto.set_loja_id(produto.loja_id);
+
+        let produto = self.produto_repository.create_produto(novo_produto).await?;
 
         Ok(produto.clone())
     }
@@ -101,52 +253,122 @@ impl ProdutoUseCase {
         &self,
         id: usize,
         fields_to_update: UpdateProdutoInput,
+        versao: Option<usize>,
     ) -> Result<Produto, DomainError> {
-        let mut produto_repository = self.produto_repository.lock().await;
+        if let Some(categoria_id) = fields_to_update.categoria_id {
+            self.valida_categoria(categoria_id).await?;
+        }
 
-        let mut current_produto = produto_repository.get_produto_by_id(id).await?;
+
+        let mut current_produto = self.produto_repository.get_produto_by_id(id).await?;
+        // `versao` é a que o cliente leu (If-Match); sem ela, vale a lida agora.
+        if versao.is_some_and(|versao| versao != current_produto.versao()) {
+            return Err(DomainError::Conflict);
+        }
 
         match fields_to_update.nome {
-            Some(nome) => current_produto.set_nome(nome),
+            Some(nome) => current_produto.set_nome(nome).map_err(|erro| erro.no_campo("nome")),
             None => Ok(()),
         }?;
 
         match fields_to_update.descricao {
-            Some(descricao) => current_produto.set_descricao(descricao),
+            Some(descricao) => current_produto.set_descricao(descricao).map_err(|erro| erro.no_campo("descricao")),
             None => Ok(()),
         }?;
 
-        match fields_to_update.foto {
-            Some(foto) => current_produto.set_foto(foto),
-            None => (),
-        };
+        if let Some(foto) = fields_to_update.foto {
+            current_produto.set_foto(foto);
+        }
 
-        match fields_to_update.categoria {
-            Some(categoria) => current_produto.set_categoria(categoria),
-            None => (),
-        };
+        if let Some(categoria_id) = fields_to_update.categoria_id {
+            current_produto.set_categoria_id(categoria_id);
+        }
 
         match fields_to_update.preco {
-            Some(preco) => current_produto.set_preco(preco),
+            Some(preco) => current_produto.set_preco(preco).map_err(|erro| erro.no_campo("preco")),
             None => Ok(()),
         }?;
 
-        match fields_to_update.ingredientes {
-            Some(ingredientes) => current_produto.set_ingredientes(ingredientes),
-            None => (),
-        };
+        if let Some(ingredientes) = fields_to_update.ingredientes {
+            current_produto.set_ingredientes(ingredientes);
+        }
+
+        if let Some(loja_id) = fields_to_update.loja_id {
+            current_produto.set_loja_id(Some(loja_id));
+        }
 
         let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
-        let produto_atualizado = produto_repository
-            .update_produto(current_produto)
-            .await?;
+        let produto_atualizado = self.produto_repository.update_produto(current_produto).await?;
 
         Ok(produto_atualizado.clone())
     }
 
+    pub async fn atualiza_foto(
+        &self,
+        id: usize,
+        foto: FotoInput,
+        foto_storage: Arc<dyn FotoStorageAdapter + Sync + Send>,
+    ) -> Result<Produto, DomainError> {
+        if foto.conteudo.is_empty() {
+            return Err(DomainError::Empty);
+        }
+        if foto.conteudo.len() > TAMANHO_MAXIMO_FOTO {
+            return Err(DomainError::Invalid("Foto excede o tamanho máximo".to_string()));
+        }
+        let extensao = foto.extensao()?;
+
+        let mut current_produto = self.produto_repository.get_produto_by_id(id).await?;
+
+        let nome_arquivo = format!("produto-{}-{}.{}", id, Utc::now().timestamp_millis(), extensao);
+        let url = foto_storage.salva_foto(nome_arquivo, foto.conteudo).await?;
+        let foto_anterior = current_produto.foto().clone();
+        current_produto.set_foto(url);
+
+        let produto = self.produto_repository.update_produto(current_produto).await?;
+        // A foto antiga só sai depois que o produto já aponta para a nova; se não der para
+        // apagá-la, o arquivo fica órfão, mas a troca vale.
+        if let Err(erro) = foto_storage.remove_foto(&foto_anterior).await {
+            tracing::warn!(erro = ?erro, foto = %foto_anterior, "Foto anterior não foi apagada");
+        }
+        Ok(produto)
+    }
+
+    pub async fn define_produto_loja(
+        &self,
+        loja_id: usize,
+        produto_id: usize,
+        ajuste: ProdutoLojaInput,
+    ) -> Result<ProdutoLoja, DomainError> {
+        let produto_loja = ProdutoLoja::new(loja_id, produto_id, ajuste.preco, ajuste.disponivel);
+        produto_loja.validate_entity()?;
+
+        let produto = self.produto_repository.get_produto_by_id(produto_id).await?;
+        if !produto.visivel_na_loja(loja_id) {
+            return Err(DomainError::Invalid("Produto exclusivo de outra loja".to_string()));
+        }
+        self.produto_repository.salva_produto_loja(produto_loja).await
+    }
+
+    pub async fn get_traducoes(&self, produto_id: usize) -> Result<Vec<TraducaoProduto>, DomainError> {
+        self.produto_repository.get_produto_by_id(produto_id).await?;
+        self.produto_repository.get_traducoes_do_produto(produto_id).await
+    }
+
+    pub async fn define_traducao(
+        &self,
+        produto_id: usize,
+        idioma: Idioma,
+        traducao: TraducaoInput,
+    ) -> Result<TraducaoProduto, DomainError> {
+        let traducao = TraducaoProduto::new(produto_id, idioma, traducao.nome, traducao.descricao);
+        traducao.validate_entity()?;
+
+        self.produto_repository.get_produto_by_id(produto_id).await?;
+        self.produto_repository.salva_traducao(traducao).await
+    }
+
     pub async fn delete_produto(&self, id: usize) -> Result<(), DomainError> {
-        let mut produto_repository = self.produto_repository.lock().await;
-        produto_repository.delete_produto(id).await?;
+        self.produto_repository.delete_produto(id).await?;
         Ok(())
     }
 }
@@ -157,11 +379,29 @@ unsafe impl Sync for ProdutoUseCase {}
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::entities::{ingredientes::Ingredientes, produto::Produto};
+    use crate::entities::{categoria::{DadosDaCategoria, ItemPedido}, ingredientes::Ingredientes, produto::Produto};
+    use crate::entities::agenda::{AlvoAgenda, DadosDaAgenda};
+    use crate::traits::agenda_gateway::MockAgendaGateway;
+    use crate::traits::categoria_gateway::MockCategoriaGateway;
+    use crate::traits::foto_storage_adapter::MockFotoStorageAdapter;
     use crate::traits::produto_gateway::MockProdutoGateway;
     use mockall::predicate::*;
     use tokio;
 
+    fn momento(valor: &str) -> DateTime<Tz> {
+        DateTime::parse_from_rfc3339(valor)
+            .unwrap()
+            .with_timezone(&chrono_tz::America::Sao_Paulo)
+    }
+
+    fn agenda_repository(agendas: Vec<Agenda>) -> Arc<MockAgendaGateway> {
+        let mut mock = MockAgendaGateway::new();
+        mock.expect_get_agendas()
+            .times(1)
+            .returning(move || Ok(agendas.clone()));
+        Arc::new(mock)
+    }
+
     #[tokio::test]
     async fn test_get_produtos() {
         let mut mock = MockProdutoGateway::new();
@@ -171,7 +411,7 @@ mod tests {
             "nome".to_string(),
             "foto".to_string(),
             "descricao".to_string(),
-            Categoria::Lanche,
+            1,
             10.0,
             Ingredientes::new(vec!["ingrediente1".to_string(), "ingrediente2".to_string()])
                 .unwrap(),
@@ -181,15 +421,80 @@ mod tests {
 
         let expected_produto = returned_produto.clone();
 
-        mock.expect_get_produtos()
+        mock.expect_get_produtos_da_loja()
             .times(1)
-            .returning(move || Ok(vec![returned_produto.clone()]));
+            .with(eq(1))
+            .returning(move |_| Ok(vec![returned_produto.clone()]));
 
-        let use_case = ProdutoUseCase::new(Arc::new(Mutex::new(mock)));
-        let result = use_case.get_produtos().await;
+        let use_case = ProdutoUseCase::new(
+            Arc::new(mock),
+            Arc::new(MockCategoriaGateway::new()),
+            agenda_repository(vec![]),
+        );
+        let result = use_case.get_produtos(1, Idioma::PtBr, momento("2024-05-06T12:00:00-03:00")).await;
         assert_eq!(result.unwrap()[0].id(), expected_produto.id());
     }
 
+    #[tokio::test]
+    async fn test_get_produtos_com_agendas() {
+        let mut mock = MockProdutoGateway::new();
+        mock.expect_get_produtos_da_loja().times(2).returning(|_| {
+            Ok(vec![
+                create_produto(1, "Pão de queijo", 1),
+                create_produto(2, "Refrigerante", 2),
+            ])
+        });
+
+        let agendas = vec![
+            Agenda::new(
+                1,
+                DadosDaAgenda {
+                    alvo: AlvoAgenda::Categoria,
+                    alvo_id: 1,
+                    dias_semana: vec![1, 2, 3, 4, 5],
+                    hora_inicio: "06:00".to_string(),
+                    hora_fim: "11:00".to_string(),
+                    preco: None,
+                },
+                "2021-10-10 00:00:00.000+0000".to_string(),
+                "2021-10-10 00:00:00.000+0000".to_string(),
+            ),
+            Agenda::new(
+                2,
+                DadosDaAgenda {
+                    alvo: AlvoAgenda::Produto,
+                    alvo_id: 2,
+                    dias_semana: vec![1, 2, 3, 4, 5],
+                    hora_inicio: "17:00".to_string(),
+                    hora_fim: "19:00".to_string(),
+                    preco: Some(5.0),
+                },
+                "2021-10-10 00:00:00.000+0000".to_string(),
+                "2021-10-10 00:00:00.000+0000".to_string(),
+            ),
+        ];
+        let mut mock_agenda = MockAgendaGateway::new();
+        mock_agenda
+            .expect_get_agendas()
+            .times(2)
+            .returning(move || Ok(agendas.clone()));
+
+        let use_case = ProdutoUseCase::new(
+            Arc::new(mock),
+            Arc::new(MockCategoriaGateway::new()),
+            Arc::new(mock_agenda),
+        );
+
+        let manha = use_case.get_produtos(1, Idioma::PtBr, momento("2024-05-06T08:00:00-03:00")).await.unwrap();
+        assert_eq!(manha.len(), 2);
+        assert_eq!(manha[1].preco(), 10.0);
+
+        let happy_hour = use_case.get_produtos(1, Idioma::PtBr, momento("2024-05-06T18:00:00-03:00")).await.unwrap();
+        assert_eq!(happy_hour.len(), 1);
+        assert_eq!(happy_hour[0].nome(), "Refrigerante");
+        assert_eq!(happy_hour[0].preco(), 5.0);
+    }
+
     #[tokio::test]
     async fn test_get_produto_by_id() {
         let mut mock = MockProdutoGateway::new();
@@ -199,7 +504,7 @@ mod tests {
             "nome".to_string(),
             "foto".to_string(),
             "descricao".to_string(),
-            Categoria::Lanche,
+            1,
             10.0,
             Ingredientes::new(vec!["ingrediente1".to_string(), "ingrediente2".to_string()])
                 .unwrap(),
@@ -210,13 +515,179 @@ mod tests {
         let expected_produto = returned_produto.clone();
 
         mock.expect_get_produto_by_id()
-            .times(1)
+            .times(2)
             .with(eq(1))
             .returning(move |_| Ok(returned_produto.clone()));
+        mock.expect_get_produtos_loja()
+            .times(2)
+            .returning(|loja_id| Ok(vec![ProdutoLoja::new(2, 1, Some(8.0), loja_id == 2)]));
+
+        let use_case = ProdutoUseCase::new(
+            Arc::new(mock),
+            Arc::new(MockCategoriaGateway::new()),
+            Arc::new(MockAgendaGateway::new()),
+        );
+        let result = use_case.get_produto_by_id(1, 1, Idioma::PtBr).await.unwrap();
+        assert_eq!(result.id(), expected_produto.id());
+        assert_eq!(result.preco(), 10.0);
 
-        let use_case = ProdutoUseCase::new(Arc::new(Mutex::new(mock)));
-        let result = use_case.get_produto_by_id(1).await;
-        assert_eq!(result.unwrap().id(), expected_produto.id());
+        let result = use_case.get_produto_by_id(1, 2, Idioma::PtBr).await.unwrap();
+        assert_eq!(result.preco(), 8.0);
+    }
+
+    #[tokio::test]
+    async fn test_get_produto_by_id_indisponivel_na_loja() {
+        let mut mock = MockProdutoGateway::new();
+        mock.expect_get_produto_by_id()
+            .times(1)
+            .returning(|id| Ok(create_produto(id, "Pudim", 3)));
+        mock.expect_get_produtos_loja()
+            .times(1)
+            .with(eq(2))
+            .returning(|_| Ok(vec![ProdutoLoja::new(2, 1, None, false)]));
+
+        let use_case = ProdutoUseCase::new(
+            Arc::new(mock),
+            Arc::new(MockCategoriaGateway::new()),
+            Arc::new(MockAgendaGateway::new()),
+        );
+        let result = use_case.get_produto_by_id(1, 2, Idioma::PtBr).await;
+        assert!(matches!(result, Err(DomainError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_define_produto_loja() {
+        let mut mock = MockProdutoGateway::new();
+        mock.expect_get_produto_by_id().times(2).returning(|id| {
+            let mut produto = create_produto(id, "Pudim", 3);
+            if id == 2 {
+                produto.set_loja_id(Some(3));
+            }
+            Ok(produto)
+        });
+        mock.expect_salva_produto_loja()
+            .times(1)
+            .withf(|ajuste| *ajuste.loja_id() == 2 && ajuste.preco() == Some(7.0))
+            .returning(Ok);
+
+        let use_case = ProdutoUseCase::new(
+            Arc::new(mock),
+            Arc::new(MockCategoriaGateway::new()),
+            Arc::new(MockAgendaGateway::new()),
+        );
+        let ajuste = ProdutoLojaInput {
+            preco: Some(7.0),
+            disponivel: true,
+        };
+        assert!(use_case.define_produto_loja(2, 1, ajuste.clone()).await.is_ok());
+        assert!(matches!(
+            use_case.define_produto_loja(2, 2, ajuste).await,
+            Err(DomainError::Invalid(_))
+        ));
+        assert!(matches!(
+            use_case
+                .define_produto_loja(2, 1, ProdutoLojaInput { preco: Some(-1.0), disponivel: true })
+                .await,
+            Err(DomainError::NonPositive)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_produtos_traduzidos() {
+        let mut mock = MockProdutoGateway::new();
+        mock.expect_get_produtos_da_loja()
+            .times(1)
+            .returning(|_| Ok(vec![create_produto(1, "Pão de queijo", 1), create_produto(2, "Refrigerante", 2)]));
+        mock.expect_get_traducoes()
+            .times(1)
+            .with(eq(Idioma::En))
+            .returning(|idioma| Ok(vec![TraducaoProduto::new(1, idioma, Some("Cheese bread".to_string()), None)]));
+
+        let use_case = ProdutoUseCase::new(
+            Arc::new(mock),
+            Arc::new(MockCategoriaGateway::new()),
+            agenda_repository(vec![]),
+        );
+        let produtos = use_case.get_produtos(1, Idioma::En, momento("2024-05-06T12:00:00-03:00")).await.unwrap();
+        assert_eq!(produtos[0].nome(), "Cheese bread");
+        assert_eq!(produtos[1].nome(), "Refrigerante");
+    }
+
+    #[tokio::test]
+    async fn test_define_traducao() {
+        let mut mock = MockProdutoGateway::new();
+        mock.expect_get_produto_by_id().times(2).returning(|id| match id {
+            1 => Ok(create_produto(id, "Pudim", 3)),
+            _ => Err(DomainError::NotFound),
+        });
+        mock.expect_salva_traducao()
+            .times(1)
+            .withf(|traducao| traducao.idioma() == Idioma::Es && traducao.nome().unwrap() == "Flan")
+            .returning(Ok);
+
+        let use_case = ProdutoUseCase::new(
+            Arc::new(mock),
+            Arc::new(MockCategoriaGateway::new()),
+            Arc::new(MockAgendaGateway::new()),
+        );
+        let traducao = TraducaoInput {
+            nome: Some("Flan".to_string()),
+            descricao: None,
+        };
+        assert!(use_case.define_traducao(1, Idioma::Es, traducao.clone()).await.is_ok());
+        assert!(matches!(
+            use_case.define_traducao(2, Idioma::Es, traducao.clone()).await,
+            Err(DomainError::NotFound)
+        ));
+        assert!(matches!(
+            use_case.define_traducao(1, Idioma::PtBr, traducao).await,
+            Err(DomainError::Invalid(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_busca_produtos() {
+        let mut mock = MockProdutoGateway::new();
+
+        let returned_produto = Produto::new(
+            1,
+            "Hambúrguer".to_string(),
+            "foto".to_string(),
+            "descricao".to_string(),
+            1,
+            10.0,
+            Ingredientes::new(vec!["ingrediente1".to_string(), "ingrediente2".to_string()])
+                .unwrap(),
+            "2021-10-10".to_string(),
+            "2021-10-10".to_string(),
+        );
+
+        mock.expect_busca_produtos()
+            .times(1)
+            .with(eq("hamburguer".to_string()), eq(1))
+            .returning(move |_, _| Ok(vec![returned_produto.clone()]));
+
+        let use_case = ProdutoUseCase::new(
+            Arc::new(mock),
+            Arc::new(MockCategoriaGateway::new()),
+            Arc::new(MockAgendaGateway::new()),
+        );
+        let result = use_case.busca_produtos("  hamburguer ".to_string(), 1, Idioma::PtBr).await;
+        assert_eq!(result.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_busca_produtos_termo_vazio() {
+        let mut mock = MockProdutoGateway::new();
+        mock.expect_busca_produtos().times(0);
+
+        let use_case = ProdutoUseCase::new(
+            Arc::new(mock),
+            Arc::new(MockCategoriaGateway::new()),
+            Arc::new(MockAgendaGateway::new()),
+        );
+        let result = use_case.busca_produtos("   ".to_string(), 1, Idioma::PtBr).await;
+        assert!(matches!(result, Err(DomainError::Empty)));
     }
 
     #[tokio::test]
@@ -228,7 +699,7 @@ mod tests {
             "nome".to_string(),
             "foto".to_string(),
             "descricao".to_string(),
-            Categoria::Lanche,
+            1,
             10.0,
             Ingredientes::new(vec!["ingrediente1".to_string(), "ingrediente2".to_string()])
                 .unwrap(),
@@ -242,13 +713,24 @@ mod tests {
             .times(1)
             .returning(move |_| Ok(returned_produto.clone()));
 
-        let use_case = ProdutoUseCase::new(Arc::new(Mutex::new(mock)));
+        let mut mock_categoria = MockCategoriaGateway::new();
+        mock_categoria
+            .expect_get_categoria_by_id()
+            .times(1)
+            .with(eq(1))
+            .returning(|id| Ok(create_categoria(id, 1, true)));
+
+        let use_case = ProdutoUseCase::new(
+            Arc::new(mock),
+            Arc::new(mock_categoria),
+            Arc::new(MockAgendaGateway::new()),
+        );
         let result = use_case
             .create_produto(CreateProdutoInput::new(
                 "nome".to_string(),
                 "foto".to_string(),
                 "descricao".to_string(),
-                Categoria::Lanche,
+                1,
                 10.0,
                 Ingredientes::new(vec!["ingrediente1".to_string(), "ingrediente2".to_string()])
                     .unwrap(),
@@ -257,6 +739,98 @@ mod tests {
         assert_eq!(result.unwrap().id(), expected_produto.id());
     }
 
+    #[tokio::test]
+    async fn test_create_produto_categoria_inexistente() {
+        let mut mock = MockProdutoGateway::new();
+        mock.expect_create_produto().times(0);
+
+        let mut mock_categoria = MockCategoriaGateway::new();
+        mock_categoria
+            .expect_get_categoria_by_id()
+            .times(1)
+            .returning(|_| Err(DomainError::NotFound));
+
+        let use_case = ProdutoUseCase::new(
+            Arc::new(mock),
+            Arc::new(mock_categoria),
+            Arc::new(MockAgendaGateway::new()),
+        );
+        let result = use_case
+            .create_produto(CreateProdutoInput::new(
+                "nome".to_string(),
+                "foto".to_string(),
+                "descricao".to_string(),
+                42,
+                10.0,
+                Ingredientes::new(vec!["ingrediente1".to_string()]).unwrap(),
+            ))
+            .await;
+        assert!(matches!(result, Err(DomainError::InvalidField { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_get_cardapio() {
+        let mut mock = MockProdutoGateway::new();
+        mock.expect_get_produtos_da_loja().times(1).returning(|_| {
+            Ok(vec![
+                create_produto(1, "X-Burguer", 1),
+                create_produto(2, "Refrigerante", 2),
+                create_produto(3, "Pudim", 3),
+            ])
+        });
+
+        let mut mock_categoria = MockCategoriaGateway::new();
+        mock_categoria.expect_get_categorias().times(1).returning(|| {
+            Ok(vec![
+                create_categoria(1, 2, true),
+                create_categoria(2, 1, true),
+                create_categoria(3, 3, false),
+            ])
+        });
+
+        let use_case = ProdutoUseCase::new(
+            Arc::new(mock),
+            Arc::new(mock_categoria),
+            agenda_repository(vec![]),
+        );
+        let cardapio = use_case.get_cardapio(1, Idioma::PtBr, momento("2024-05-06T12:00:00-03:00")).await.unwrap();
+
+        assert_eq!(cardapio.len(), 2);
+        assert_eq!(cardapio[0].categoria.id(), &2);
+        assert_eq!(cardapio[0].produtos[0].nome(), "Refrigerante");
+        assert_eq!(cardapio[1].categoria.id(), &1);
+        assert_eq!(cardapio[1].produtos.len(), 1);
+    }
+
+    fn create_categoria(id: usize, ordem: usize, ativa: bool) -> Categoria {
+        Categoria::new(
+            id,
+            DadosDaCategoria {
+                nome: format!("Categoria {}", id),
+                icone: None,
+                ordem,
+                ativa,
+                item_pedido: Some(ItemPedido::Lanche),
+            },
+            "2021-10-10 00:00:00.000+0000".to_string(),
+            "2021-10-10 00:00:00.000+0000".to_string(),
+        )
+    }
+
+    fn create_produto(id: usize, nome: &str, categoria_id: usize) -> Produto {
+        Produto::new(
+            id,
+            nome.to_string(),
+            "foto".to_string(),
+            "descricao".to_string(),
+            categoria_id,
+            10.0,
+            Ingredientes::new(vec!["ingrediente1".to_string()]).unwrap(),
+            "2021-10-10".to_string(),
+            "2021-10-10".to_string(),
+        )
+    }
+
     // #[tokio::test]
     // async fn test_update_produto() {
     //     let mut mock = MockProdutoGateway::new();
@@ -266,7 +840,7 @@ mod tests {
     //         "nome".to_string(),
     //         "foto".to_string(),
     //         "descricao".to_string(),
-    //         Categoria::Lanche,
+    //         1,
     //         10.0,
     //         Ingredientes::new(vec!["ingrediente1".to_string(), "ingrediente2".to_string()])
     //             .unwrap(),
@@ -280,7 +854,7 @@ mod tests {
     //         .times(1)
     //         .returning(move |_| Ok(returned_produto.clone()));
 
-    //     let use_case = ProdutoUseCase::new(Arc::new(Mutex::new(mock)));
+    //     let use_case = ProdutoUseCase::new(Arc::new(mock));
     //     let result = use_case
     //         .update_produto(
     //             1,
@@ -288,7 +862,7 @@ mod tests {
     //                 "nome".to_string(),
     //                 "foto".to_string(),
     //                 "descricao".to_string(),
-    //                 Categoria::Lanche,
+    //                 1,
     //                 10.0,
     //                 Ingredientes::new(vec!["ingrediente1".to_string(), "ingrediente2".to_string()])
     //                     .unwrap(),
@@ -298,6 +872,133 @@ mod tests {
     //     assert_eq!(result.unwrap().id(), expected_produto.id());
     // }
 
+    #[tokio::test]
+    async fn test_update_produto_com_versao_desatualizada() {
+        let mut mock = MockProdutoGateway::new();
+        let mut produto_atual = create_produto(1, "nome", 1);
+        produto_atual.set_versao(3);
+        mock.expect_get_produto_by_id()
+            .times(1)
+            .with(eq(1))
+            .returning(move |_| Ok(produto_atual.clone()));
+        mock.expect_update_produto().never();
+
+        let use_case = ProdutoUseCase::new(
+            Arc::new(mock),
+            Arc::new(MockCategoriaGateway::new()),
+            Arc::new(MockAgendaGateway::new()),
+        );
+        let fields_to_update = UpdateProdutoInput {
+            nome: Some("novo nome".to_string()),
+            foto: None,
+            descricao: None,
+            categoria_id: None,
+            preco: None,
+            ingredientes: None,
+            loja_id: None,
+        };
+        let result = use_case.update_produto(1, fields_to_update, Some(2)).await;
+
+        assert!(matches!(result, Err(DomainError::Conflict)));
+    }
+
+    fn create_png_input() -> FotoInput {
+        let mut conteudo = b"\x89PNG\r\n\x1a\n".to_vec();
+        conteudo.extend_from_slice(&[0; 16]);
+        FotoInput {
+            content_type: "image/png".to_string(),
+            conteudo,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_atualiza_foto() {
+        let mut mock = MockProdutoGateway::new();
+
+        let returned_produto = Produto::new(
+            1,
+            "nome".to_string(),
+            "foto".to_string(),
+            "descricao".to_string(),
+            1,
+            10.0,
+            Ingredientes::new(vec!["ingrediente1".to_string(), "ingrediente2".to_string()])
+                .unwrap(),
+            "2021-10-10".to_string(),
+            "2021-10-10".to_string(),
+        );
+
+        mock.expect_get_produto_by_id()
+            .times(1)
+            .with(eq(1))
+            .returning(move |_| Ok(returned_produto.clone()));
+        mock.expect_update_produto()
+            .times(1)
+            .returning(Ok);
+
+        let mut foto_storage = MockFotoStorageAdapter::new();
+        foto_storage
+            .expect_salva_foto()
+            .times(1)
+            .withf(|nome_arquivo, _| nome_arquivo.starts_with("produto-1-") && nome_arquivo.ends_with(".png"))
+            .returning(|nome_arquivo, _| Ok(format!("/fotos/{}", nome_arquivo)));
+        foto_storage
+            .expect_remove_foto()
+            .times(1)
+            .withf(|url| url == "foto")
+            .returning(|_| Ok(()));
+
+        let use_case = ProdutoUseCase::new(
+            Arc::new(mock),
+            Arc::new(MockCategoriaGateway::new()),
+            Arc::new(MockAgendaGateway::new()),
+        );
+        let result = use_case
+            .atualiza_foto(1, create_png_input(), Arc::new(foto_storage))
+            .await
+            .unwrap();
+        assert!(result.foto().starts_with("/fotos/produto-1-"));
+    }
+
+    #[tokio::test]
+    async fn test_atualiza_foto_tipo_invalido() {
+        let use_case = ProdutoUseCase::new(
+            Arc::new(MockProdutoGateway::new()),
+            Arc::new(MockCategoriaGateway::new()),
+            Arc::new(MockAgendaGateway::new()),
+        );
+
+        let mut foto = create_png_input();
+        foto.content_type = "application/pdf".to_string();
+        let result = use_case
+            .atualiza_foto(1, foto, Arc::new(MockFotoStorageAdapter::new()))
+            .await;
+        assert!(matches!(result, Err(DomainError::Invalid(_))));
+
+        let mut foto = create_png_input();
+        foto.content_type = "image/jpeg".to_string();
+        let result = use_case
+            .atualiza_foto(1, foto, Arc::new(MockFotoStorageAdapter::new()))
+            .await;
+        assert!(matches!(result, Err(DomainError::Invalid(_))));
+    }
+
+    #[tokio::test]
+    async fn test_atualiza_foto_tamanho_invalido() {
+        let use_case = ProdutoUseCase::new(
+            Arc::new(MockProdutoGateway::new()),
+            Arc::new(MockCategoriaGateway::new()),
+            Arc::new(MockAgendaGateway::new()),
+        );
+
+        let mut foto = create_png_input();
+        foto.conteudo.resize(TAMANHO_MAXIMO_FOTO + 1, 0);
+        let result = use_case
+            .atualiza_foto(1, foto, Arc::new(MockFotoStorageAdapter::new()))
+            .await;
+        assert!(matches!(result, Err(DomainError::Invalid(_))));
+    }
+
     #[tokio::test]
     async fn test_delete_produto() {
         let mut mock = MockProdutoGateway::new();
@@ -306,7 +1007,11 @@ mod tests {
             .times(1)
             .returning(move |_| Ok(()));
 
-        let use_case = ProdutoUseCase::new(Arc::new(Mutex::new(mock)));
+        let use_case = ProdutoUseCase::new(
+            Arc::new(mock),
+            Arc::new(MockCategoriaGateway::new()),
+            Arc::new(MockAgendaGateway::new()),
+        );
         let result = use_case.delete_produto(1).await;
         assert_eq!(result.unwrap(), ());
     }
@@ -0,0 +1,512 @@
+use chrono::Utc;
+
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::base::{assertion_concern, domain_error::DomainError};
+use crate::entities::{ingredientes::Ingredientes, produto::Produto};
+use crate::traits::{categoria_gateway::CategoriaGateway, produto_gateway::ProdutoGateway};
+
+pub const TAMANHO_MAXIMO_CATALOGO: usize = 2 * 1024 * 1024;
+
+// No CSV os ingredientes ficam numa única coluna separados por este caractere.
+const SEPARADOR_INGREDIENTES: &str = "|";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FormatoCatalogo {
+    Csv,
+    Json,
+}
+
+impl FromStr for FormatoCatalogo {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(FormatoCatalogo::Csv),
+            "json" => Ok(FormatoCatalogo::Json),
+            _ => Err(DomainError::Invalid(format!("Formato de catálogo inválido: {}", s))),
+        }
+    }
+}
+
+// Linha do catálogo: sem id o produto é criado, com id o produto existente é atualizado.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct LinhaCatalogo {
+    #[serde(default)]
+    pub id: Option<usize>,
+    pub nome: String,
+    #[serde(default)]
+    pub foto: String,
+    pub descricao: String,
+    pub categoria_id: usize,
+    pub preco: f64,
+    #[serde(default)]
+    pub ingredientes: Vec<String>,
+    // Ausente para produtos vendidos em todas as lojas.
+    #[serde(default)]
+    pub loja_id: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LinhaCatalogoCsv {
+    id: Option<usize>,
+    nome: String,
+    foto: String,
+    descricao: String,
+    categoria_id: usize,
+    preco: f64,
+    ingredientes: String,
+    #[serde(default)]
+    loja_id: Option<usize>,
+}
+
+// Campo de uma linha do catálogo em JSON; ausente ou null vira None.
+fn campo_json<T: DeserializeOwned>(campos: &mut Map<String, Value>, nome: &str) -> Result<Option<T>, DomainError> {
+    match campos.remove(nome) {
+        None | Some(Value::Null) => Ok(None),
+        Some(valor) => serde_json::from_value(valor).map(Some).map_err(|erro| DomainError::InvalidField {
+            field: nome.to_string(),
+            message: erro.to_string(),
+        }),
+    }
+}
+
+fn obrigatorio<T>(valor: Option<T>, nome: &str) -> Result<T, DomainError> {
+    valor.ok_or_else(|| DomainError::InvalidField {
+        field: nome.to_string(),
+        message: "Campo obrigatório".to_string(),
+    })
+}
+
+impl LinhaCatalogo {
+    // Lida campo a campo para que o erro aponte o campo recusado e não derrube o arquivo.
+    fn de_json(valor: Value) -> Result<LinhaCatalogo, DomainError> {
+        let Value::Object(mut campos) = valor else {
+            return Err(DomainError::Invalid("A linha deve ser um objeto".to_string()));
+        };
+        Ok(LinhaCatalogo {
+            id: campo_json(&mut campos, "id")?,
+            nome: obrigatorio(campo_json(&mut campos, "nome")?, "nome")?,
+            foto: campo_json(&mut campos, "foto")?.unwrap_or_default(),
+            descricao: obrigatorio(campo_json(&mut campos, "descricao")?, "descricao")?,
+            categoria_id: obrigatorio(campo_json(&mut campos, "categoria_id")?, "categoria_id")?,
+            preco: obrigatorio(campo_json(&mut campos, "preco")?, "preco")?,
+            ingredientes: campo_json(&mut campos, "ingredientes")?.unwrap_or_default(),
+            loja_id: campo_json(&mut campos, "loja_id")?,
+        })
+    }
+}
+
+// Erro de desserialização do CSV associado à coluna, pelo cabeçalho.
+fn erro_csv(cabecalho: &csv::StringRecord, erro: csv::Error) -> DomainError {
+    match erro.kind() {
+        csv::ErrorKind::Deserialize { err, .. } => {
+            match err.field().and_then(|indice| cabecalho.get(indice as usize)) {
+                Some(campo) => DomainError::InvalidField {
+                    field: campo.to_string(),
+                    message: err.kind().to_string(),
+                },
+                None => DomainError::Invalid(err.kind().to_string()),
+            }
+        }
+        _ => DomainError::Invalid(erro.to_string()),
+    }
+}
+
+impl From<LinhaCatalogoCsv> for LinhaCatalogo {
+    fn from(linha: LinhaCatalogoCsv) -> Self {
+        LinhaCatalogo {
+            id: linha.id,
+            nome: linha.nome,
+            foto: linha.foto,
+            descricao: linha.descricao,
+            categoria_id: linha.categoria_id,
+            preco: linha.preco,
+            ingredientes: linha
+                .ingredientes
+                .split(SEPARADOR_INGREDIENTES)
+                .map(|ingrediente| ingrediente.trim().to_string())
+                .filter(|ingrediente| !ingrediente.is_empty())
+                .collect(),
+            loja_id: linha.loja_id,
+        }
+    }
+}
+
+impl From<LinhaCatalogo> for LinhaCatalogoCsv {
+    fn from(linha: LinhaCatalogo) -> Self {
+        LinhaCatalogoCsv {
+            id: linha.id,
+            nome: linha.nome,
+            foto: linha.foto,
+            descricao: linha.descricao,
+            categoria_id: linha.categoria_id,
+            preco: linha.preco,
+            ingredientes: linha.ingredientes.join(SEPARADOR_INGREDIENTES),
+            loja_id: linha.loja_id,
+        }
+    }
+}
+
+impl From<&Produto> for LinhaCatalogo {
+    fn from(produto: &Produto) -> Self {
+        LinhaCatalogo {
+            id: Some(*produto.id()),
+            nome: produto.nome().clone(),
+            foto: produto.foto().clone(),
+            descricao: produto.descricao().clone(),
+            categoria_id: *produto.categoria_id(),
+            preco: produto.preco(),
+            ingredientes: produto.ingredientes().to_vec_string(),
+            loja_id: produto.loja_id().copied(),
+        }
+    }
+}
+
+// Erro de uma linha do catálogo, com o campo recusado quando ele é conhecido.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct ErroImportacao {
+    pub linha: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub campo: Option<String>,
+    pub erro: String,
+}
+
+impl ErroImportacao {
+    fn new(linha: usize, erro: DomainError) -> Self {
+        let (campo, erro) = match erro {
+            DomainError::InvalidField { field, message } => (Some(field), message),
+            DomainError::Invalid(message) => (None, message),
+            DomainError::Empty => (None, "Campo obrigatório".to_string()),
+            DomainError::NonPositive => (None, "Valor não pode ser negativo".to_string()),
+            erro => (None, format!("{:?}", erro)),
+        };
+        ErroImportacao { linha, campo, erro }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct RelatorioImportacao {
+    pub dry_run: bool,
+    pub aplicado: bool,
+    pub criados: usize,
+    pub atualizados: usize,
+    pub erros: Vec<ErroImportacao>,
+    pub produtos: Vec<Produto>,
+}
+
+#[derive(Clone)]
+pub struct CatalogoUseCase {
+    produto_repository: Arc<dyn ProdutoGateway + Sync + Send>,
+    categoria_repository: Arc<dyn CategoriaGateway + Sync + Send>,
+}
+
+impl CatalogoUseCase {
+    pub fn new(
+        produto_repository: Arc<dyn ProdutoGateway + Sync + Send>,
+        categoria_repository: Arc<dyn CategoriaGateway + Sync + Send>,
+    ) -> Self {
+        CatalogoUseCase {
+            produto_repository,
+            categoria_repository,
+        }
+    }
+
+    fn le_linhas(
+        conteudo: &str,
+        formato: FormatoCatalogo,
+    ) -> Result<Vec<Result<LinhaCatalogo, DomainError>>, DomainError> {
+        match formato {
+            FormatoCatalogo::Json => {
+                let linhas: Vec<Value> = serde_json::from_str(conteudo)
+                    .map_err(|erro| DomainError::Invalid(format!("Catálogo: {}", erro)))?;
+                Ok(linhas.into_iter().map(LinhaCatalogo::de_json).collect())
+            }
+            FormatoCatalogo::Csv => {
+                let mut leitor = csv::Reader::from_reader(conteudo.as_bytes());
+                let cabecalho = leitor
+                    .headers()
+                    .map_err(|erro| DomainError::Invalid(format!("Catálogo: {}", erro)))?
+                    .clone();
+                Ok(leitor
+                    .deserialize::<LinhaCatalogoCsv>()
+                    .map(|linha| linha.map(LinhaCatalogo::from).map_err(|erro| erro_csv(&cabecalho, erro)))
+                    .collect())
+            }
+        }
+    }
+
+    pub async fn exporta_catalogo(&self, formato: FormatoCatalogo) -> Result<String, DomainError> {
+        let produtos = self.produto_repository.get_produtos().await?;
+        let linhas: Vec<LinhaCatalogo> = produtos.iter().map(LinhaCatalogo::from).collect();
+
+        match formato {
+            FormatoCatalogo::Json => serde_json::to_string(&linhas)
+                .map_err(|erro| DomainError::Invalid(erro.to_string())),
+            FormatoCatalogo::Csv => {
+                let mut escritor = csv::Writer::from_writer(vec![]);
+                for linha in linhas {
+                    escritor
+                        .serialize(LinhaCatalogoCsv::from(linha))
+                        .map_err(|erro| DomainError::Invalid(erro.to_string()))?;
+                }
+                let conteudo = escritor
+                    .into_inner()
+                    .map_err(|erro| DomainError::Invalid(erro.to_string()))?;
+                String::from_utf8(conteudo).map_err(|erro| DomainError::Invalid(erro.to_string()))
+            }
+        }
+    }
+
+    // Valida todas as linhas antes de gravar qualquer coisa; com erros (ou em dry run)
+    // o catálogo não é alterado e o relatório indica o que teria sido feito.
+    pub async fn importa_catalogo(
+        &self,
+        conteudo: String,
+        formato: FormatoCatalogo,
+        dry_run: bool,
+    ) -> Result<RelatorioImportacao, DomainError> {
+        let linhas = CatalogoUseCase::le_linhas(&conteudo, formato)?;
+        if linhas.is_empty() {
+            return Err(DomainError::Empty);
+        }
+
+        let categorias = self.categoria_repository.get_categorias().await?;
+        let existentes = self.produto_repository.get_produtos().await?;
+
+        let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
+        let mut novos = Vec::new();
+        let mut atualizados = Vec::new();
+        let mut erros = Vec::new();
+
+        for (indice, linha) in linhas.into_iter().enumerate() {
+            let resultado = linha.and_then(|linha| {
+                if !categorias.iter().any(|categoria| *categoria.id() == linha.categoria_id) {
+                    return Err(DomainError::InvalidField {
+                        field: "categoria_id".to_string(),
+                        message: "Categoria não encontrada".to_string(),
+                    });
+                }
+                let data_criacao = match linha.id {
+                    Some(id) => existentes
+                        .iter()
+                        .find(|produto| *produto.id() == id)
+                        .map(|produto| produto.data_criacao().clone())
+                        .ok_or_else(|| DomainError::InvalidField {
+                            field: "id".to_string(),
+                            message: "Produto não encontrado".to_string(),
+                        })?,
+                    None => _now.clone(),
+                };
+                assertion_concern::assert_argument_not_empty(linha.nome.clone())
+                    .map_err(|erro| erro.no_campo("nome"))?;
+                assertion_concern::assert_argument_not_empty(linha.descricao.clone())
+                    .map_err(|erro| erro.no_campo("descricao"))?;
+                assertion_concern::assert_argument_not_negative(linha.preco)
+                    .map_err(|erro| erro.no_campo("preco"))?;
+                let mut produto = Produto::new(
+                    linha.id.unwrap_or(0),
+                    linha.nome,
+                    linha.foto,
+                    linha.descricao,
+                    linha.categoria_id,
+                    linha.preco,
+                    Ingredientes::new(linha.ingredientes)?,
+                    data_criacao,
+                    _now.clone(),
+                );
+                produto.set_loja_id(linha.loja_id);
+                produto.validate_entity()?;
+                Ok((linha.id.is_some(), produto))
+            });
+
+            match resultado {
+                Ok((true, produto)) => atualizados.push(produto),
+                Ok((false, produto)) => novos.push(produto),
+                Err(erro) => erros.push(ErroImportacao::new(indice + 1, erro)),
+            }
+        }
+
+        let mut relatorio = RelatorioImportacao {
+            dry_run,
+            aplicado: false,
+            criados: novos.len(),
+            atualizados: atualizados.len(),
+            erros,
+            produtos: vec![],
+        };
+        if dry_run || !relatorio.erros.is_empty() {
+            relatorio.produtos = novos.into_iter().chain(atualizados).collect();
+            return Ok(relatorio);
+        }
+
+        relatorio.produtos = self.produto_repository.importa_produtos(novos, atualizados).await?;
+        relatorio.aplicado = true;
+        Ok(relatorio)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::categoria::{Categoria, DadosDaCategoria, ItemPedido};
+    use crate::traits::categoria_gateway::MockCategoriaGateway;
+    use crate::traits::produto_gateway::MockProdutoGateway;
+
+    const AGORA: &str = "2021-10-10 00:00:00.000+0000";
+
+    fn categoria_repository() -> Arc<MockCategoriaGateway> {
+        let mut mock = MockCategoriaGateway::new();
+        mock.expect_get_categorias().returning(|| {
+            Ok(vec![Categoria::new(
+                1,
+                DadosDaCategoria {
+                    nome: "Lanche".to_string(),
+                    icone: None,
+                    ordem: 1,
+                    ativa: true,
+                    item_pedido: Some(ItemPedido::Lanche),
+                },
+                AGORA.to_string(),
+                AGORA.to_string(),
+            )])
+        });
+        Arc::new(mock)
+    }
+
+    fn create_valid_produto() -> Produto {
+        Produto::new(
+            7,
+            "X-Salada".to_string(),
+            "x-salada.png".to_string(),
+            "Pão, carne e salada".to_string(),
+            1,
+            18.0,
+            Ingredientes::new(vec!["Pão".to_string(), "Carne".to_string()]).unwrap(),
+            AGORA.to_string(),
+            AGORA.to_string(),
+        )
+    }
+
+    #[test]
+    fn test_formato_catalogo_from_str() {
+        assert_eq!("CSV".parse::<FormatoCatalogo>().unwrap(), FormatoCatalogo::Csv);
+        assert_eq!("json".parse::<FormatoCatalogo>().unwrap(), FormatoCatalogo::Json);
+        assert!(matches!("xml".parse::<FormatoCatalogo>(), Err(DomainError::Invalid(_))));
+    }
+
+    #[tokio::test]
+    async fn test_exporta_catalogo_csv() {
+        let mut mock = MockProdutoGateway::new();
+        mock.expect_get_produtos()
+            .times(1)
+            .returning(|| Ok(vec![create_valid_produto()]));
+
+        let use_case = CatalogoUseCase::new(Arc::new(mock), categoria_repository());
+        let csv = use_case.exporta_catalogo(FormatoCatalogo::Csv).await.unwrap();
+
+        assert_eq!(
+            csv,
+            "id,nome,foto,descricao,categoria_id,preco,ingredientes,loja_id\n7,X-Salada,x-salada.png,\"Pão, carne e salada\",1,18.0,Pão|Carne,\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_importa_catalogo_csv() {
+        let mut mock = MockProdutoGateway::new();
+        mock.expect_get_produtos()
+            .returning(|| Ok(vec![create_valid_produto()]));
+        mock.expect_importa_produtos()
+            .times(1)
+            .withf(|novos, atualizados| {
+                novos.len() == 1 && atualizados.len() == 1 && atualizados[0].preco() == 20.0
+            })
+            .returning(|novos, atualizados| Ok(novos.into_iter().chain(atualizados).collect()));
+
+        let use_case = CatalogoUseCase::new(Arc::new(mock), categoria_repository());
+        let conteudo = "id,nome,foto,descricao,categoria_id,preco,ingredientes\n\
+            ,X-Bacon,,Pão e bacon,1,22.5,Pão|Bacon\n\
+            7,X-Salada,x-salada.png,Pão e salada,1,20.0,Pão|Carne|Alface\n";
+
+        let relatorio = use_case
+            .importa_catalogo(conteudo.to_string(), FormatoCatalogo::Csv, false)
+            .await
+            .unwrap();
+
+        assert!(relatorio.aplicado);
+        assert_eq!(relatorio.criados, 1);
+        assert_eq!(relatorio.atualizados, 1);
+        assert!(relatorio.erros.is_empty());
+        assert_eq!(relatorio.produtos[0].ingredientes().to_vec_string(), vec!["Pão", "Bacon"]);
+    }
+
+    #[tokio::test]
+    async fn test_importa_catalogo_dry_run_reporta_erros() {
+        let mut mock = MockProdutoGateway::new();
+        mock.expect_get_produtos()
+            .returning(|| Ok(vec![create_valid_produto()]));
+        mock.expect_importa_produtos().times(0);
+
+        let use_case = CatalogoUseCase::new(Arc::new(mock), categoria_repository());
+        let conteudo = r#"[
+            {"nome": "", "descricao": "Sem nome", "categoria_id": 1, "preco": 10.0},
+            {"nome": "Suco", "descricao": "Suco de laranja", "categoria_id": 1, "preco": -1.0},
+            {"nome": "Sorvete", "descricao": "Casquinha", "categoria_id": 9, "preco": 5.0},
+            {"id": 99, "nome": "Água", "descricao": "Garrafa", "categoria_id": 1, "preco": 3.0},
+            {"nome": "Batata", "descricao": "Porção", "categoria_id": 1, "preco": 9.0},
+            {"nome": "Refrigerante", "descricao": "Lata", "categoria_id": 1, "preco": "caro"},
+            {"nome": "Café", "categoria_id": 1, "preco": 4.0}
+        ]"#;
+
+        let relatorio = use_case
+            .importa_catalogo(conteudo.to_string(), FormatoCatalogo::Json, true)
+            .await
+            .unwrap();
+
+        assert!(!relatorio.aplicado);
+        assert_eq!(relatorio.criados, 1);
+        let erros: Vec<(usize, Option<&str>, &str)> = relatorio
+            .erros
+            .iter()
+            .map(|erro| (erro.linha, erro.campo.as_deref(), erro.erro.as_str()))
+            .collect();
+        assert_eq!(
+            erros,
+            vec![
+                (1, Some("nome"), "Campo obrigatório"),
+                (2, Some("preco"), "Valor não pode ser negativo"),
+                (3, Some("categoria_id"), "Categoria não encontrada"),
+                (4, Some("id"), "Produto não encontrado"),
+                (6, Some("preco"), r#"invalid type: string "caro", expected f64"#),
+                (7, Some("descricao"), "Campo obrigatório"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_importa_catalogo_com_erros_nao_grava() {
+        let mut mock = MockProdutoGateway::new();
+        mock.expect_get_produtos().returning(|| Ok(vec![]));
+        mock.expect_importa_produtos().times(0);
+
+        let use_case = CatalogoUseCase::new(Arc::new(mock), categoria_repository());
+        let conteudo = "id,nome,foto,descricao,categoria_id,preco,ingredientes\n\
+            ,X-Bacon,,Pão e bacon,1,22.5,Pão|Bacon\n\
+            ,X-Tudo,,Tudo,1,caro,\n";
+
+        let relatorio = use_case
+            .importa_catalogo(conteudo.to_string(), FormatoCatalogo::Csv, false)
+            .await
+            .unwrap();
+
+        assert!(!relatorio.aplicado);
+        assert_eq!(relatorio.erros.len(), 1);
+        assert_eq!(relatorio.erros[0].linha, 2);
+        assert_eq!(relatorio.erros[0].campo.as_deref(), Some("preco"));
+    }
+}
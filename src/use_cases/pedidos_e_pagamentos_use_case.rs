@@ -1,21 +1,34 @@
 use crate::base::domain_error::DomainError;
+use crate::base::metricas;
 use crate::entities::{
+    agenda::Agenda,
+    categoria::ItemPedido,
     pedido::{Pedido, Status},
+    produto::Produto,
     cpf::Cpf,
+    documento_fiscal::DocumentoFiscal,
 };
 use crate::traits::{
+    agenda_gateway::AgendaGateway, categoria_gateway::CategoriaGateway,
+    cliente_gateway::ClienteGateway, idempotencia_gateway::IdempotenciaGateway,
     pedido_gateway::PedidoGateway, produto_gateway::ProdutoGateway,
 };
+use crate::use_cases::programa_de_fidelidade_use_case::ProgramaDeFidelidadeUseCase;
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use schemars::JsonSchema;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::sync::Arc;
-use tokio::sync::Mutex;
 
-#[derive(Clone, Debug, Deserialize, JsonSchema)]
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
 pub struct CreatePedidoInput {
     pub cliente_id: Option<Cpf>,
+    // CPF ou CNPJ para a nota fiscal. Fora da impressão digital quando ausente, para que
+    // pedidos de antes do campo continuem com a mesma.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub documento_fiscal: Option<DocumentoFiscal>,
     pub lanche_id: Option<usize>,
     pub acompanhamento_id: Option<usize>,
     pub bebida_id: Option<usize>,
@@ -34,62 +47,192 @@ pub struct InfoPagamenmto {
     pub status: StatusPagamento,
 }
 
+// Item do pedido original que ficou de fora ao repetir o pedido.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct ItemIndisponivel {
+    pub item: ItemPedido,
+    pub produto_id: usize,
+    pub nome: String,
+    pub motivo: String,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct PedidoRepetido {
+    pub pedido: Pedido,
+    pub indisponiveis: Vec<ItemIndisponivel>,
+}
+
+// Identifica o corpo de um POST /pedidos: a mesma Idempotency-Key só pode repetir o mesmo pedido.
+fn impressao_digital(pedido_input: &CreatePedidoInput, loja_id: usize) -> Result<String, DomainError> {
+    let corpo = serde_json::to_vec(&(loja_id, pedido_input))
+        .map_err(|erro| DomainError::Invalid(erro.to_string()))?;
+    Ok(Sha256::digest(corpo)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+// Pedido com CPF fica com o do cliente cadastrado, se houver; um CPF válido sem cadastro
+// continua valendo só como CPF. Sem CPF, é do cliente anônimo.
+async fn identifica_cliente(
+    cliente_id: Option<&Cpf>,
+    cliente_repository: &Arc<dyn ClienteGateway + Sync + Send>,
+) -> Result<Option<Cpf>, DomainError> {
+    let Some(cliente_id) = cliente_id else {
+        return Ok(None);
+    };
+    let cpf = Cpf::new(cliente_id.get_string())?;
+    match cliente_repository.get_cliente_by_cpf(&cpf).await {
+        Ok(cliente) => Ok(Some(cliente.cpf().clone())),
+        Err(DomainError::NotFound) => Ok(Some(cpf)),
+        Err(erro) => Err(erro),
+    }
+}
+
 #[derive(Clone)]
 pub struct PedidosEPagamentosUseCase {
-    pedido_repository: Arc<Mutex<dyn PedidoGateway + Sync + Send>>,
-    produto_repository: Arc<Mutex<dyn ProdutoGateway + Sync + Send>>,
+    pedido_repository: Arc<dyn PedidoGateway + Sync + Send>,
+    produto_repository: Arc<dyn ProdutoGateway + Sync + Send>,
+    categoria_repository: Arc<dyn CategoriaGateway + Sync + Send>,
+    agenda_repository: Arc<dyn AgendaGateway + Sync + Send>,
 }
 
 impl PedidosEPagamentosUseCase {
     pub fn new(
-        pedido_repository: Arc<Mutex<dyn PedidoGateway + Sync + Send>>,
-        produto_repository: Arc<Mutex<dyn ProdutoGateway + Sync + Send>>,
+        pedido_repository: Arc<dyn PedidoGateway + Sync + Send>,
+        produto_repository: Arc<dyn ProdutoGateway + Sync + Send>,
+        categoria_repository: Arc<dyn CategoriaGateway + Sync + Send>,
+        agenda_repository: Arc<dyn AgendaGateway + Sync + Send>,
     ) -> Self {
         PedidosEPagamentosUseCase {
             pedido_repository,
             produto_repository,
+            categoria_repository,
+            agenda_repository,
+        }
+    }
+
+    async fn seleciona_item(
+        &self,
+        produto_id: Option<usize>,
+        item: ItemPedido,
+        catalogo: &[Produto],
+        agendas: &[Agenda],
+        momento: &DateTime<Tz>,
+    ) -> Result<Option<Produto>, DomainError> {
+        let produto = match produto_id {
+            Some(produto_id) => catalogo
+                .iter()
+                .find(|produto| *produto.id() == produto_id)
+                .ok_or(DomainError::NotFound)?,
+            None => return Ok(None),
+        };
+
+        let categoria = self.categoria_repository.get_categoria_by_id(*produto.categoria_id()).await?;
+        if !categoria.permite_item(&item) {
+            return Err(DomainError::Invalid(format!(
+                "Produto {} não pode ser pedido como {}",
+                produto.nome(),
+                item
+            )));
+        }
+
+        match Agenda::aplica(produto, agendas, momento) {
+            Some(produto) => Ok(Some(produto)),
+            None => Err(DomainError::Invalid(format!(
+                "Produto {} indisponível no momento",
+                produto.nome()
+            ))),
         }
     }
 
-    pub async fn lista_pedidos(&self) -> Result<Vec<Pedido>, DomainError> {
-        let mut pedido_repository = self.pedido_repository.lock().await;
-        pedido_repository.lista_pedidos().await
+    // Item do pedido original com o preço de agora, ou o motivo de não poder ser pedido de novo.
+    async fn seleciona_item_repetido(
+        &self,
+        original: Option<&Produto>,
+        item: ItemPedido,
+        catalogo: &[Produto],
+        agendas: &[Agenda],
+        momento: &DateTime<Tz>,
+        indisponiveis: &mut Vec<ItemIndisponivel>,
+    ) -> Result<Option<Produto>, DomainError> {
+        let Some(original) = original else {
+            return Ok(None);
+        };
+        let motivo = match self
+            .seleciona_item(Some(*original.id()), item.clone(), catalogo, agendas, momento)
+            .await
+        {
+            Ok(produto) => return Ok(produto),
+            Err(DomainError::NotFound) => "Produto fora do cardápio da loja".to_string(),
+            Err(DomainError::Invalid(motivo)) => motivo,
+            Err(erro) => return Err(erro),
+        };
+        indisponiveis.push(ItemIndisponivel {
+            item,
+            produto_id: *original.id(),
+            nome: original.nome().clone(),
+            motivo,
+        });
+        Ok(None)
     }
 
-    pub async fn seleciona_pedido_por_id(&self, id: usize) -> Result<Pedido, DomainError> {
-        let pedido_repository = self.pedido_repository.lock().await;
-        pedido_repository.get_pedido_by_id(id).await
+    pub async fn lista_pedidos(&self, loja_id: usize) -> Result<Vec<Pedido>, DomainError> {
+        self.pedido_repository.lista_pedidos(loja_id).await
     }
 
+    pub async fn seleciona_pedido_por_id(&self, id: usize, loja_id: usize) -> Result<Pedido, DomainError> {
+        self.pedido_repository.get_pedido_da_loja(id, loja_id).await
+    }
+
+    #[tracing::instrument(skip(self, pedido_input, cliente_repository))]
     pub async fn novo_pedido(
         &self,
         pedido_input: CreatePedidoInput,
+        loja_id: usize,
+        cliente_repository: Arc<dyn ClienteGateway + Sync + Send>,
+        momento: DateTime<Tz>,
     ) -> Result<Pedido, DomainError> {
-        let lanche = if let Some(lanche_id) = pedido_input.lanche_id {
-            let produto_repo = self.produto_repository.lock().await;
-            Some(produto_repo.get_produto_by_id(lanche_id).await?)
-        } else {
-            None
-        };
-
-        let bebida = if let Some(bebida_id) = pedido_input.bebida_id {
-            let produto_repo = self.produto_repository.lock().await;
-            Some(produto_repo.get_produto_by_id(bebida_id).await?)
-        } else {
-            None
+        let cliente = identifica_cliente(pedido_input.cliente_id.as_ref(), &cliente_repository).await?;
+        let documento_fiscal = pedido_input
+            .documento_fiscal
+            .as_ref()
+            .map(|documento| DocumentoFiscal::new(documento.get_string()))
+            .transpose()?;
+        let agendas = self.agenda_repository.get_agendas().await?;
+        let itens = [
+            pedido_input.lanche_id,
+            pedido_input.bebida_id,
+            pedido_input.acompanhamento_id,
+        ];
+        // Só é possível pedir o que está no cardápio da loja, já com os preços dela.
+        let catalogo = match itens.iter().any(Option::is_some) {
+            true => {
+                self.produto_repository.get_produtos_da_loja(loja_id).await?
+            }
+            false => vec![],
         };
 
-        let acompanhamento = if let Some(acompanhamento_id) = pedido_input.acompanhamento_id {
-            let produto_repo = self.produto_repository.lock().await;
-            Some(produto_repo.get_produto_by_id(acompanhamento_id).await?)
-        } else {
-            None
-        };
+        let lanche = self
+            .seleciona_item(pedido_input.lanche_id, ItemPedido::Lanche, &catalogo, &agendas, &momento)
+            .await?;
+        let bebida = self
+            .seleciona_item(pedido_input.bebida_id, ItemPedido::Bebida, &catalogo, &agendas, &momento)
+            .await?;
+        let acompanhamento = self
+            .seleciona_item(
+                pedido_input.acompanhamento_id,
+                ItemPedido::Acompanhamento,
+                &catalogo,
+                &agendas,
+                &momento,
+            )
+            .await?;
 
         let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
-        let pedido = Pedido::new(
+        let mut pedido = Pedido::new(
             0,
-            pedido_input.cliente_id.clone(),
+            cliente,
             lanche,
             acompanhamento,
             bebida,
@@ -98,24 +241,142 @@ impl PedidosEPagamentosUseCase {
             _now.clone(),
             _now.clone(),
         );
+        pedido.set_loja_id(loja_id);
+        pedido.set_documento_fiscal(documento_fiscal);
 
-        let mut pedido_repository = self.pedido_repository.lock().await;
 
-        pedido_repository.create_pedido(pedido).await
+        let pedido = self.pedido_repository.create_pedido(pedido).await?;
+        tracing::info!(pedido_id = *pedido.id(), "Pedido criado");
+        metricas::registra_pedido_criado();
+        Ok(pedido)
     }
 
-    pub async fn atualiza_pagamento(&self,  info_pagamento : InfoPagamenmto) -> Result<Pedido, DomainError> {
-        let pedido_repository = self.pedido_repository.try_lock();
-        if pedido_repository.is_err() {
-            return Err(DomainError::Invalid("Erro ao acessar o banco de dados".to_string()));
+    // Novo pedido Pendente, na mesma loja, com os itens de um pedido anterior do cliente que
+    // continuam disponíveis; os demais são devolvidos com o motivo.
+    #[tracing::instrument(skip(self, cliente))]
+    pub async fn repete_pedido(
+        &self,
+        pedido_id: usize,
+        cliente: &Cpf,
+        momento: DateTime<Tz>,
+    ) -> Result<PedidoRepetido, DomainError> {
+        let original = self.pedido_repository.get_pedido_by_id(pedido_id).await?;
+        // Pedido de outro cliente é tratado como inexistente, como o de outra loja.
+        if original.cliente() != Some(cliente) {
+            return Err(DomainError::NotFound);
         }
-        
+
+        let loja_id = *original.loja_id();
+        let agendas = self.agenda_repository.get_agendas().await?;
+        let catalogo = self.produto_repository.get_produtos_da_loja(loja_id).await?;
+
+        let mut indisponiveis = vec![];
+        let lanche = self
+            .seleciona_item_repetido(original.lanche(), ItemPedido::Lanche, &catalogo, &agendas, &momento, &mut indisponiveis)
+            .await?;
+        let bebida = self
+            .seleciona_item_repetido(original.bebida(), ItemPedido::Bebida, &catalogo, &agendas, &momento, &mut indisponiveis)
+            .await?;
+        let acompanhamento = self
+            .seleciona_item_repetido(
+                original.acompanhamento(),
+                ItemPedido::Acompanhamento,
+                &catalogo,
+                &agendas,
+                &momento,
+                &mut indisponiveis,
+            )
+            .await?;
+
+        if lanche.is_none() && bebida.is_none() && acompanhamento.is_none() {
+            return Err(DomainError::Invalid("Nenhum item do pedido está disponível".to_string()));
+        }
+
+        let _now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f%z").to_string();
+        let mut pedido = Pedido::new(
+            0,
+            Some(cliente.clone()),
+            lanche,
+            acompanhamento,
+            bebida,
+            None,
+            Status::Pendente,
+            _now.clone(),
+            _now.clone(),
+        );
+        pedido.set_loja_id(loja_id);
+        pedido.set_documento_fiscal(original.documento_fiscal().cloned());
+
+        let pedido = self.pedido_repository.create_pedido(pedido).await?;
+        tracing::info!(pedido_id = *pedido.id(), "Pedido repetido");
+        metricas::registra_pedido_criado();
+        Ok(PedidoRepetido { pedido, indisponiveis })
+    }
+
+    // Cria o pedido uma única vez por chave: repetições da mesma requisição recebem o pedido
+    // criado pela primeira, mesmo que cheguem enquanto ela ainda está em andamento (Conflict).
+    #[tracing::instrument(skip(self, pedido_input, cliente_repository, idempotencia_repository))]
+    pub async fn novo_pedido_idempotente(
+        &self,
+        pedido_input: CreatePedidoInput,
+        loja_id: usize,
+        chave: String,
+        cliente_repository: Arc<dyn ClienteGateway + Sync + Send>,
+        idempotencia_repository: Arc<dyn IdempotenciaGateway + Sync + Send>,
+        momento: DateTime<Tz>,
+    ) -> Result<Pedido, DomainError> {
+        let impressao_digital = impressao_digital(&pedido_input, loja_id)?;
+        let registro = idempotencia_repository
+            .reserva(chave.clone(), impressao_digital.clone())
+            .await?;
+
+        match registro {
+            Some(registro) if *registro.impressao_digital() != impressao_digital => {
+                Err(DomainError::InvalidField {
+                    field: "Idempotency-Key".to_string(),
+                    message: "Chave já usada em uma requisição diferente".to_string(),
+                })
+            }
+            Some(registro) => match registro.pedido() {
+                Some(pedido) => {
+                    tracing::info!(pedido_id = *pedido.id(), "Requisição repetida, devolvendo o pedido original");
+                    Ok(pedido.clone())
+                }
+                None => Err(DomainError::Conflict),
+            },
+            None => match self.novo_pedido(pedido_input, loja_id, cliente_repository, momento).await {
+                Ok(pedido) => {
+                    idempotencia_repository.conclui(chave, pedido.clone()).await?;
+                    Ok(pedido)
+                }
+                Err(erro) => {
+                    idempotencia_repository.libera(chave).await?;
+                    Err(erro)
+                }
+            },
+        }
+    }
+
+    // Pagamento recusado cancela o pedido, devolvendo os pontos resgatados nele.
+    #[tracing::instrument(skip_all, fields(pedido_id = info_pagamento.pedido_id, status = ?info_pagamento.status))]
+    pub async fn atualiza_pagamento(
+        &self,
+        info_pagamento: InfoPagamenmto,
+        fidelidade: &ProgramaDeFidelidadeUseCase,
+    ) -> Result<Pedido, DomainError> {
         let status = match info_pagamento.status {
             StatusPagamento::Aprovado => Status::Pago,
             StatusPagamento::Recusado => Status::Cancelado
         };
         
-        pedido_repository.unwrap().atualiza_pagamento_status(info_pagamento.pedido_id, info_pagamento.pagamento_id, status).await
+        let pedido = self.pedido_repository.atualiza_pagamento_status(info_pagamento.pedido_id, info_pagamento.pagamento_id, status).await?;
+        metricas::registra_pagamento(matches!(info_pagamento.status, StatusPagamento::Aprovado));
+        metricas::registra_transicao_de_status(&pedido.status().to_string());
+        // O pagamento já foi gravado: uma falha nos pontos fica no log, sem desfazer a mudança.
+        if let Err(erro) = fidelidade.registra_status(&pedido).await {
+            tracing::error!(pedido_id = *pedido.id(), erro = ?erro, "Erro ao registrar os pontos do pedido");
+        }
+        Ok(pedido)
     }
 }
 
@@ -125,13 +386,27 @@ unsafe impl Sync for PedidosEPagamentosUseCase {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::entities::{
+        agenda::{AlvoAgenda, DadosDaAgenda}, categoria::{Categoria, DadosDaCategoria}, cliente::Cliente,
+        idempotencia::RegistroDeIdempotencia, ingredientes::Ingredientes,
+        pontos::RegrasDeFidelidade,
+    };
+    use crate::gateways::{
+        in_memory_cliente_gateway::InMemoryClienteRepository,
+        in_memory_idempotencia_gateway::InMemoryIdempotenciaRepository,
+    };
     use crate::traits::{
+        agenda_gateway::MockAgendaGateway,
+        categoria_gateway::MockCategoriaGateway,
+        cliente_gateway::MockClienteGateway,
+        idempotencia_gateway::MockIdempotenciaGateway,
         pedido_gateway::MockPedidoGateway,
+        pontos_gateway::MockPontosGateway,
         produto_gateway::MockProdutoGateway,
     };
     use std::sync::Arc;
+    use std::time::Duration;
     use tokio;
-    use tokio::sync::Mutex;
 
     #[tokio::test]
     async fn test_lista_pedidos() {
@@ -153,13 +428,16 @@ mod tests {
 
         mock.expect_lista_pedidos()
             .times(1)
-            .returning(move || Ok(vec![returned_pedido.clone()]));
+            .withf(|loja_id| *loja_id == 1)
+            .returning(move |_| Ok(vec![returned_pedido.clone()]));
 
         let use_case = PedidosEPagamentosUseCase::new(
-            Arc::new(Mutex::new(mock)),
-            Arc::new(Mutex::new(MockProdutoGateway::new())),
+            Arc::new(mock),
+            Arc::new(MockProdutoGateway::new()),
+            Arc::new(MockCategoriaGateway::new()),
+            Arc::new(MockAgendaGateway::new()),
         );
-        let result = use_case.lista_pedidos().await;
+        let result = use_case.lista_pedidos(1).await;
         assert_eq!(result.unwrap()[0].id(), expected_pedido.id());
     }
 
@@ -181,16 +459,24 @@ mod tests {
 
         let expected_pedido = returned_pedido.clone();
 
-        mock.expect_get_pedido_by_id()
-            .times(1)
-            .returning(move |_| Ok(returned_pedido.clone()));
+        mock.expect_get_pedido_da_loja()
+            .times(2)
+            .returning(move |_, loja_id| match loja_id {
+                1 => Ok(returned_pedido.clone()),
+                _ => Err(DomainError::NotFound),
+            });
 
         let use_case = PedidosEPagamentosUseCase::new(
-            Arc::new(Mutex::new(mock)),
-            Arc::new(Mutex::new(MockProdutoGateway::new())),
+            Arc::new(mock),
+            Arc::new(MockProdutoGateway::new()),
+            Arc::new(MockCategoriaGateway::new()),
+            Arc::new(MockAgendaGateway::new()),
         );
-        let result = use_case.seleciona_pedido_por_id(1).await;
+        let result = use_case.seleciona_pedido_por_id(1, 1).await;
         assert_eq!(result.unwrap().id(), expected_pedido.id());
+
+        let result = use_case.seleciona_pedido_por_id(1, 2).await;
+        assert!(matches!(result, Err(DomainError::NotFound)));
     }
 
     #[tokio::test]
@@ -214,24 +500,585 @@ mod tests {
         mock_pedido_gateway
             .expect_create_pedido()
             .times(1)
+            .withf(|pedido| *pedido.loja_id() == 2)
             .returning(move |_| Ok(returned_pedido.clone()));
 
+        let mut mock_agenda_gateway = MockAgendaGateway::new();
+        mock_agenda_gateway
+            .expect_get_agendas()
+            .times(1)
+            .returning(|| Ok(vec![]));
+
         let use_case = PedidosEPagamentosUseCase::new(
-            Arc::new(Mutex::new(mock_pedido_gateway)),
-            Arc::new(Mutex::new(MockProdutoGateway::new())),
+            Arc::new(mock_pedido_gateway),
+            Arc::new(MockProdutoGateway::new()),
+            Arc::new(MockCategoriaGateway::new()),
+            Arc::new(mock_agenda_gateway),
         );
 
+        let mut mock_cliente_gateway = MockClienteGateway::new();
+        mock_cliente_gateway
+            .expect_get_cliente_by_cpf()
+            .times(1)
+            .withf(|cpf| cpf.get_string() == "12345678909")
+            .returning(|cpf| {
+                Ok(Cliente::new(
+                    cpf.clone(),
+                    "Maria".to_string(),
+                    None,
+                    false,
+                    false,
+                    "2021-10-10".to_string(),
+                    "2021-10-10".to_string(),
+                ))
+            });
+
         let result = use_case
             .novo_pedido(CreatePedidoInput {
-                cliente_id: Cpf::new("123.456.789-09".to_string()).ok(),
+                cliente_id: Some(Cpf("123.456.789-09".to_string())),
+                documento_fiscal: None,
                 lanche_id: None,
                 acompanhamento_id: None,
                 bebida_id: None,
-            })
+            }, 2, Arc::new(mock_cliente_gateway), agora())
             .await;
         assert_eq!(result.unwrap().id(), expected_pedido.id());
     }
 
+    #[tokio::test]
+    async fn test_novo_pedido_cliente_nao_cadastrado() {
+        let mut mock_pedido_gateway = MockPedidoGateway::new();
+        mock_pedido_gateway
+            .expect_create_pedido()
+            .withf(|pedido| pedido.cliente().map(Cpf::get_string) == Some("12345678909".to_string()))
+            .times(1)
+            .returning(Ok);
+        let use_case = use_case_sem_itens(mock_pedido_gateway);
+
+        let result = use_case
+            .novo_pedido(CreatePedidoInput {
+                cliente_id: Some(Cpf("123.456.789-09".to_string())),
+                ..pedido_input(None)
+            }, 1, sem_clientes(), agora())
+            .await;
+        assert!(result.is_ok());
+
+        let result = use_case
+            .novo_pedido(CreatePedidoInput {
+                cliente_id: Some(Cpf("123.456.789-00".to_string())),
+                ..pedido_input(None)
+            }, 1, sem_clientes(), agora())
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_novo_pedido_com_documento_fiscal() {
+        let mut mock_pedido_gateway = MockPedidoGateway::new();
+        mock_pedido_gateway
+            .expect_create_pedido()
+            .withf(|pedido| {
+                pedido.documento_fiscal().map(DocumentoFiscal::get_string) == Some("12ABC34501DE35".to_string())
+            })
+            .times(1)
+            .returning(Ok);
+        let use_case = use_case_sem_itens(mock_pedido_gateway);
+
+        let pedido = use_case
+            .novo_pedido(CreatePedidoInput {
+                documento_fiscal: Some(DocumentoFiscal("12.abc.345/01de-35".to_string())),
+                ..pedido_input(None)
+            }, 1, sem_clientes(), agora())
+            .await
+            .unwrap();
+        assert!(pedido.cliente().is_none());
+
+        let result = use_case
+            .novo_pedido(CreatePedidoInput {
+                documento_fiscal: Some(DocumentoFiscal("12.ABC.345/01DE-36".to_string())),
+                ..pedido_input(None)
+            }, 1, sem_clientes(), agora())
+            .await;
+        assert!(matches!(result, Err(DomainError::InvalidField { ref field, .. }) if field == "documento_fiscal"));
+    }
+
+    fn sem_pontos() -> ProgramaDeFidelidadeUseCase {
+        ProgramaDeFidelidadeUseCase::new(
+            Arc::new(MockPontosGateway::new()),
+            Arc::new(MockPedidoGateway::new()),
+            RegrasDeFidelidade::new(1.0, 0.05, 365),
+        )
+    }
+
+    // Segunda-feira, 6 de maio de 2024, ao meio-dia em São Paulo.
+    fn agora() -> DateTime<Tz> {
+        DateTime::parse_from_rfc3339("2024-05-06T12:00:00-03:00")
+            .unwrap()
+            .with_timezone(&chrono_tz::America::Sao_Paulo)
+    }
+
+    fn sem_clientes() -> Arc<dyn ClienteGateway + Sync + Send> {
+        Arc::new(InMemoryClienteRepository::new())
+    }
+
+    fn pedido_input(lanche_id: Option<usize>) -> CreatePedidoInput {
+        CreatePedidoInput {
+            cliente_id: None,
+            documento_fiscal: None,
+            lanche_id,
+            acompanhamento_id: None,
+            bebida_id: None,
+        }
+    }
+
+    fn use_case_sem_itens(mock_pedido_gateway: MockPedidoGateway) -> PedidosEPagamentosUseCase {
+        let mut mock_agenda_gateway = MockAgendaGateway::new();
+        mock_agenda_gateway.expect_get_agendas().returning(|| Ok(vec![]));
+        PedidosEPagamentosUseCase::new(
+            Arc::new(mock_pedido_gateway),
+            Arc::new(MockProdutoGateway::new()),
+            Arc::new(MockCategoriaGateway::new()),
+            Arc::new(mock_agenda_gateway),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_novo_pedido_idempotente_repete_o_pedido_original() {
+        let mut mock_pedido_gateway = MockPedidoGateway::new();
+        mock_pedido_gateway
+            .expect_create_pedido()
+            .times(1)
+            .returning(|_| {
+                Ok(Pedido::new(
+                    42,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    Status::Pendente,
+                    "2021-10-10".to_string(),
+                    "2021-10-10".to_string(),
+                ))
+            });
+        let use_case = use_case_sem_itens(mock_pedido_gateway);
+        let idempotencia: Arc<dyn IdempotenciaGateway + Sync + Send> =
+            Arc::new(InMemoryIdempotenciaRepository::new(Duration::from_secs(60)));
+
+        let original = use_case
+            .novo_pedido_idempotente(pedido_input(None), 1, "chave".to_string(), sem_clientes(), idempotencia.clone(), agora())
+            .await
+            .unwrap();
+        let repetido = use_case
+            .novo_pedido_idempotente(pedido_input(None), 1, "chave".to_string(), sem_clientes(), idempotencia.clone(), agora())
+            .await
+            .unwrap();
+        assert_eq!(*original.id(), 42);
+        assert_eq!(original.id(), repetido.id());
+
+        let result = use_case
+            .novo_pedido_idempotente(pedido_input(None), 2, "chave".to_string(), sem_clientes(), idempotencia, agora())
+            .await;
+        assert!(matches!(result, Err(DomainError::InvalidField { ref field, .. }) if field == "Idempotency-Key"));
+    }
+
+    #[tokio::test]
+    async fn test_novo_pedido_idempotente_libera_a_chave_se_falhar() {
+        let mut mock_pedido_gateway = MockPedidoGateway::new();
+        let mut sequencia = mockall::Sequence::new();
+        mock_pedido_gateway
+            .expect_create_pedido()
+            .times(1)
+            .in_sequence(&mut sequencia)
+            .returning(|_| Err(DomainError::Database("timeout".to_string())));
+        mock_pedido_gateway
+            .expect_create_pedido()
+            .times(1)
+            .in_sequence(&mut sequencia)
+            .returning(Ok);
+        let use_case = use_case_sem_itens(mock_pedido_gateway);
+        let idempotencia: Arc<dyn IdempotenciaGateway + Sync + Send> =
+            Arc::new(InMemoryIdempotenciaRepository::new(Duration::from_secs(60)));
+
+        let result = use_case
+            .novo_pedido_idempotente(pedido_input(None), 1, "chave".to_string(), sem_clientes(), idempotencia.clone(), agora())
+            .await;
+        assert!(matches!(result, Err(DomainError::Database(_))));
+
+        let result = use_case
+            .novo_pedido_idempotente(pedido_input(None), 1, "chave".to_string(), sem_clientes(), idempotencia, agora())
+            .await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_novo_pedido_idempotente_em_andamento() {
+        let mut mock_pedido_gateway = MockPedidoGateway::new();
+        mock_pedido_gateway.expect_create_pedido().never();
+        let use_case = use_case_sem_itens(mock_pedido_gateway);
+
+        let impressao = impressao_digital(&pedido_input(Some(1)), 1).unwrap();
+        let mut mock_idempotencia = MockIdempotenciaGateway::new();
+        mock_idempotencia
+            .expect_reserva()
+            .times(1)
+            .returning(move |chave, _| Ok(Some(RegistroDeIdempotencia::new(chave, impressao.clone(), None))));
+
+        let result = use_case
+            .novo_pedido_idempotente(pedido_input(Some(1)), 1, "chave".to_string(), sem_clientes(), Arc::new(mock_idempotencia), agora())
+            .await;
+        assert!(matches!(result, Err(DomainError::Conflict)));
+    }
+
+    #[tokio::test]
+    async fn test_novo_pedido_item_fora_da_categoria() {
+        let mut mock_pedido_gateway = MockPedidoGateway::new();
+        mock_pedido_gateway.expect_create_pedido().times(0);
+
+        let mut mock_produto_gateway = MockProdutoGateway::new();
+        mock_produto_gateway
+            .expect_get_produtos_da_loja()
+            .times(1)
+            .returning(|_| {
+                Ok(vec![Produto::new(
+                    5,
+                    "Refrigerante".to_string(),
+                    "refrigerante.png".to_string(),
+                    "Lata 350ml".to_string(),
+                    2,
+                    5.0,
+                    Ingredientes::new(vec!["Refrigerante".to_string()]).unwrap(),
+                    "2021-10-10".to_string(),
+                    "2021-10-10".to_string(),
+                )])
+            });
+
+        let mut mock_agenda_gateway = MockAgendaGateway::new();
+        mock_agenda_gateway
+            .expect_get_agendas()
+            .returning(|| Ok(vec![]));
+
+        let mut mock_categoria_gateway = MockCategoriaGateway::new();
+        mock_categoria_gateway
+            .expect_get_categoria_by_id()
+            .times(1)
+            .returning(|id| {
+                Ok(Categoria::new(
+                    id,
+                    DadosDaCategoria {
+                        nome: "Bebida".to_string(),
+                        icone: None,
+                        ordem: 2,
+                        ativa: true,
+                        item_pedido: Some(ItemPedido::Bebida),
+                    },
+                    "2021-10-10".to_string(),
+                    "2021-10-10".to_string(),
+                ))
+            });
+
+        let use_case = PedidosEPagamentosUseCase::new(
+            Arc::new(mock_pedido_gateway),
+            Arc::new(mock_produto_gateway),
+            Arc::new(mock_categoria_gateway),
+            Arc::new(mock_agenda_gateway),
+        );
+
+        let result = use_case
+            .novo_pedido(CreatePedidoInput {
+                cliente_id: None,
+                documento_fiscal: None,
+                lanche_id: Some(5),
+                acompanhamento_id: None,
+                bebida_id: None,
+            }, 1, sem_clientes(), agora())
+            .await;
+        assert!(matches!(result, Err(DomainError::Invalid(_))));
+    }
+
+    fn create_bebida() -> Produto {
+        Produto::new(
+            7,
+            "Refrigerante".to_string(),
+            "refrigerante.png".to_string(),
+            "Lata 350ml".to_string(),
+            2,
+            6.0,
+            Ingredientes::new(vec!["Refrigerante".to_string()]).unwrap(),
+            "2021-10-10".to_string(),
+            "2021-10-10".to_string(),
+        )
+    }
+
+    fn create_categoria_bebida() -> Categoria {
+        Categoria::new(
+            2,
+            DadosDaCategoria {
+                nome: "Bebida".to_string(),
+                icone: None,
+                ordem: 2,
+                ativa: true,
+                item_pedido: Some(ItemPedido::Bebida),
+            },
+            "2021-10-10".to_string(),
+            "2021-10-10".to_string(),
+        )
+    }
+
+    fn create_agenda_dia_inteiro(dias_semana: Vec<u32>, preco: Option<f64>) -> Agenda {
+        Agenda::new(
+            1,
+            DadosDaAgenda {
+                alvo: AlvoAgenda::Produto,
+                alvo_id: 7,
+                dias_semana,
+                hora_inicio: "00:00".to_string(),
+                hora_fim: "00:00".to_string(),
+                preco,
+            },
+            "2021-10-10".to_string(),
+            "2021-10-10".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_novo_pedido_aplica_preco_programado() {
+        let mut mock_pedido_gateway = MockPedidoGateway::new();
+        mock_pedido_gateway
+            .expect_create_pedido()
+            .times(1)
+            .returning(Ok);
+
+        let mut mock_produto_gateway = MockProdutoGateway::new();
+        mock_produto_gateway
+            .expect_get_produtos_da_loja()
+            .returning(|_| Ok(vec![create_bebida()]));
+
+        let mut mock_categoria_gateway = MockCategoriaGateway::new();
+        mock_categoria_gateway
+            .expect_get_categoria_by_id()
+            .returning(|_| Ok(create_categoria_bebida()));
+
+        let mut mock_agenda_gateway = MockAgendaGateway::new();
+        mock_agenda_gateway
+            .expect_get_agendas()
+            .returning(|| Ok(vec![create_agenda_dia_inteiro(vec![0, 1, 2, 3, 4, 5, 6], Some(4.5))]));
+
+        let use_case = PedidosEPagamentosUseCase::new(
+            Arc::new(mock_pedido_gateway),
+            Arc::new(mock_produto_gateway),
+            Arc::new(mock_categoria_gateway),
+            Arc::new(mock_agenda_gateway),
+        );
+
+        let pedido = use_case
+            .novo_pedido(CreatePedidoInput {
+                cliente_id: None,
+                documento_fiscal: None,
+                lanche_id: None,
+                acompanhamento_id: None,
+                bebida_id: Some(7),
+            }, 1, sem_clientes(), agora())
+            .await
+            .unwrap();
+        assert_eq!(pedido.bebida().unwrap().preco(), 4.5);
+        assert_eq!(pedido.valor_total(), 4.5);
+    }
+
+    #[tokio::test]
+    async fn test_novo_pedido_produto_indisponivel() {
+        let mut mock_pedido_gateway = MockPedidoGateway::new();
+        mock_pedido_gateway.expect_create_pedido().times(0);
+
+        let mut mock_produto_gateway = MockProdutoGateway::new();
+        mock_produto_gateway
+            .expect_get_produtos_da_loja()
+            .returning(|_| Ok(vec![create_bebida()]));
+
+        let mut mock_categoria_gateway = MockCategoriaGateway::new();
+        mock_categoria_gateway
+            .expect_get_categoria_by_id()
+            .returning(|_| Ok(create_categoria_bebida()));
+
+        let mut mock_agenda_gateway = MockAgendaGateway::new();
+        mock_agenda_gateway
+            .expect_get_agendas()
+            .returning(|| Ok(vec![create_agenda_dia_inteiro(vec![], None)]));
+
+        let use_case = PedidosEPagamentosUseCase::new(
+            Arc::new(mock_pedido_gateway),
+            Arc::new(mock_produto_gateway),
+            Arc::new(mock_categoria_gateway),
+            Arc::new(mock_agenda_gateway),
+        );
+
+        let result = use_case
+            .novo_pedido(CreatePedidoInput {
+                cliente_id: None,
+                documento_fiscal: None,
+                lanche_id: None,
+                acompanhamento_id: None,
+                bebida_id: Some(7),
+            }, 1, sem_clientes(), agora())
+            .await;
+        assert!(matches!(result, Err(DomainError::Invalid(_))));
+    }
+
+    #[tokio::test]
+    async fn test_novo_pedido_produto_fora_da_loja() {
+        let mut mock_pedido_gateway = MockPedidoGateway::new();
+        mock_pedido_gateway.expect_create_pedido().times(0);
+
+        let mut mock_produto_gateway = MockProdutoGateway::new();
+        mock_produto_gateway
+            .expect_get_produtos_da_loja()
+            .withf(|loja_id| *loja_id == 2)
+            .returning(|_| Ok(vec![]));
+
+        let mut mock_agenda_gateway = MockAgendaGateway::new();
+        mock_agenda_gateway
+            .expect_get_agendas()
+            .returning(|| Ok(vec![]));
+
+        let use_case = PedidosEPagamentosUseCase::new(
+            Arc::new(mock_pedido_gateway),
+            Arc::new(mock_produto_gateway),
+            Arc::new(MockCategoriaGateway::new()),
+            Arc::new(mock_agenda_gateway),
+        );
+
+        let result = use_case
+            .novo_pedido(CreatePedidoInput {
+                cliente_id: None,
+                documento_fiscal: None,
+                lanche_id: None,
+                acompanhamento_id: None,
+                bebida_id: Some(7),
+            }, 2, sem_clientes(), agora())
+            .await;
+        assert!(matches!(result, Err(DomainError::NotFound)));
+    }
+
+    fn create_lanche() -> Produto {
+        Produto::new(
+            3,
+            "X-Bacon".to_string(),
+            "x-bacon.png".to_string(),
+            "Pão, hambúrguer e bacon".to_string(),
+            1,
+            20.0,
+            Ingredientes::new(vec!["Pão".to_string(), "Bacon".to_string()]).unwrap(),
+            "2021-10-10".to_string(),
+            "2021-10-10".to_string(),
+        )
+    }
+
+    fn pedido_para_repetir() -> Pedido {
+        let mut pedido = Pedido::new(
+            9,
+            Some(Cpf::new("123.456.789-09".to_string()).unwrap()),
+            Some(create_lanche()),
+            None,
+            Some(create_bebida()),
+            Some("id_pagamento".to_string()),
+            Status::Finalizado,
+            "2021-10-10".to_string(),
+            "2021-10-10".to_string(),
+        );
+        pedido.set_loja_id(2);
+        pedido
+    }
+
+    #[tokio::test]
+    async fn test_repete_pedido() {
+        let mut mock_pedido_gateway = MockPedidoGateway::new();
+        mock_pedido_gateway
+            .expect_get_pedido_by_id()
+            .returning(|_| Ok(pedido_para_repetir()));
+        mock_pedido_gateway
+            .expect_create_pedido()
+            .times(1)
+            .withf(|pedido| {
+                *pedido.loja_id() == 2
+                    && *pedido.status() == Status::Pendente
+                    && pedido.pagamento().is_none()
+                    && pedido.lanche().is_none()
+                    && pedido.bebida().is_some()
+            })
+            .returning(Ok);
+
+        // O lanche saiu do cardápio da loja; a bebida continua.
+        let mut mock_produto_gateway = MockProdutoGateway::new();
+        mock_produto_gateway
+            .expect_get_produtos_da_loja()
+            .withf(|loja_id| *loja_id == 2)
+            .returning(|_| Ok(vec![create_bebida()]));
+
+        let mut mock_categoria_gateway = MockCategoriaGateway::new();
+        mock_categoria_gateway
+            .expect_get_categoria_by_id()
+            .returning(|_| Ok(create_categoria_bebida()));
+
+        let mut mock_agenda_gateway = MockAgendaGateway::new();
+        mock_agenda_gateway
+            .expect_get_agendas()
+            .returning(|| Ok(vec![]));
+
+        let use_case = PedidosEPagamentosUseCase::new(
+            Arc::new(mock_pedido_gateway),
+            Arc::new(mock_produto_gateway),
+            Arc::new(mock_categoria_gateway),
+            Arc::new(mock_agenda_gateway),
+        );
+
+        let cliente = Cpf::new("123.456.789-09".to_string()).unwrap();
+        let repetido = use_case.repete_pedido(9, &cliente, agora()).await.unwrap();
+        assert_eq!(repetido.pedido.cliente(), Some(&cliente));
+        assert_eq!(repetido.indisponiveis.len(), 1);
+        assert_eq!(repetido.indisponiveis[0].produto_id, 3);
+        assert_eq!(repetido.indisponiveis[0].item, ItemPedido::Lanche);
+
+        let outro_cliente = Cpf::new("097.855.456-60".to_string()).unwrap();
+        let result = use_case.repete_pedido(9, &outro_cliente, agora()).await;
+        assert!(matches!(result, Err(DomainError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_repete_pedido_sem_itens_disponiveis() {
+        let mut mock_pedido_gateway = MockPedidoGateway::new();
+        mock_pedido_gateway
+            .expect_get_pedido_by_id()
+            .returning(|_| Ok(pedido_para_repetir()));
+        mock_pedido_gateway.expect_create_pedido().times(0);
+
+        let mut mock_produto_gateway = MockProdutoGateway::new();
+        mock_produto_gateway
+            .expect_get_produtos_da_loja()
+            .returning(|_| Ok(vec![create_bebida()]));
+
+        let mut mock_categoria_gateway = MockCategoriaGateway::new();
+        mock_categoria_gateway
+            .expect_get_categoria_by_id()
+            .returning(|_| Ok(create_categoria_bebida()));
+
+        // A bebida está fora do horário de venda.
+        let mut mock_agenda_gateway = MockAgendaGateway::new();
+        mock_agenda_gateway
+            .expect_get_agendas()
+            .returning(|| Ok(vec![create_agenda_dia_inteiro(vec![], None)]));
+
+        let use_case = PedidosEPagamentosUseCase::new(
+            Arc::new(mock_pedido_gateway),
+            Arc::new(mock_produto_gateway),
+            Arc::new(mock_categoria_gateway),
+            Arc::new(mock_agenda_gateway),
+        );
+
+        let cliente = Cpf::new("123.456.789-09".to_string()).unwrap();
+        let result = use_case.repete_pedido(9, &cliente, agora()).await;
+        assert!(matches!(result, Err(DomainError::Invalid(_))));
+    }
+
     #[tokio::test]
     async fn test_atualiza_pagamento_pago() {
         let mut mock_pedido_gateway = MockPedidoGateway::new();
@@ -258,8 +1105,10 @@ mod tests {
             .returning(move |_, _, _| Ok(returned_pedido.clone()));
 
         let use_case = PedidosEPagamentosUseCase::new(
-            Arc::new(Mutex::new(mock_pedido_gateway)),
-            Arc::new(Mutex::new(MockProdutoGateway::new())),
+            Arc::new(mock_pedido_gateway),
+            Arc::new(MockProdutoGateway::new()),
+            Arc::new(MockCategoriaGateway::new()),
+            Arc::new(MockAgendaGateway::new()),
         );
 
         let result = use_case
@@ -269,6 +1118,7 @@ mod tests {
                     pagamento_id: "id_pagamento".to_string(),
                     status: StatusPagamento::Aprovado,
                 },
+                &sem_pontos(),
             )
             .await;
         assert_eq!(result.unwrap().id(), expected_pedido.id());
@@ -300,8 +1150,10 @@ mod tests {
             .returning(move |_, _, _| Ok(returned_pedido.clone()));
 
         let use_case = PedidosEPagamentosUseCase::new(
-            Arc::new(Mutex::new(mock_pedido_gateway)),
-            Arc::new(Mutex::new(MockProdutoGateway::new())),
+            Arc::new(mock_pedido_gateway),
+            Arc::new(MockProdutoGateway::new()),
+            Arc::new(MockCategoriaGateway::new()),
+            Arc::new(MockAgendaGateway::new()),
         );
 
         let result = use_case
@@ -311,6 +1163,7 @@ mod tests {
                     pagamento_id: "id_pagamento".to_string(),
                     status: StatusPagamento::Recusado,
                 },
+                &sem_pontos(),
             )
             .await;
         assert_eq!(result.unwrap().id(), expected_pedido.id());
@@ -1,30 +1,49 @@
 use std::sync::Arc;
 
-use tokio::sync::Mutex;
 
 use crate::base::domain_error::DomainError;
+use crate::base::metricas;
 use crate::entities::pedido::{Pedido, Status};
 
 use crate::traits::pedido_gateway::PedidoGateway;
+use crate::use_cases::programa_de_fidelidade_use_case::ProgramaDeFidelidadeUseCase;
 
 #[derive(Clone)]
 pub struct PreparacaoeEntregaUseCase {
-    pedido_repository: Arc<Mutex<dyn PedidoGateway + Sync + Send>>,
+    pedido_repository: Arc<dyn PedidoGateway + Sync + Send>,
 }
 
 impl PreparacaoeEntregaUseCase {
-    pub fn new(pedido_repository: Arc<Mutex<dyn PedidoGateway + Sync + Send>>) -> Self {
+    pub fn new(pedido_repository: Arc<dyn PedidoGateway + Sync + Send>) -> Self {
         PreparacaoeEntregaUseCase { pedido_repository }
     }
 
-    pub async fn get_pedidos_novos(&self) -> Result<Vec<Pedido>, DomainError> {
-        let pedido_repository = self.pedido_repository.lock().await;
-        pedido_repository.get_pedidos_novos().await
+    pub async fn get_pedidos_novos(&self, loja_id: usize) -> Result<Vec<Pedido>, DomainError> {
+        self.pedido_repository.get_pedidos_novos(loja_id).await
     }
 
-    pub async fn atualiza_status(&self,  id: usize, status : Status) -> Result<Pedido, DomainError> {
-        let mut pedido_repository = self.pedido_repository.lock().await;
-        pedido_repository.atualiza_status(id, status).await
+    #[tracing::instrument(skip(self, fidelidade))]
+    pub async fn atualiza_status(
+        &self,
+        id: usize,
+        status: Status,
+        loja_id: usize,
+        versao: Option<usize>,
+        fidelidade: &ProgramaDeFidelidadeUseCase,
+    ) -> Result<Pedido, DomainError> {
+        // A cozinha só altera pedidos da própria loja.
+        let pedido = self.pedido_repository.get_pedido_da_loja(id, loja_id).await?;
+        // `versao` é a que o cliente leu (If-Match); sem ela, vale a lida agora.
+        if versao.is_some_and(|versao| versao != pedido.versao()) {
+            return Err(DomainError::Conflict);
+        }
+        let pedido = self.pedido_repository.atualiza_status(id, status, pedido.versao()).await?;
+        metricas::registra_transicao_de_status(&pedido.status().to_string());
+        // O status já foi gravado: uma falha nos pontos fica no log, sem desfazer a mudança.
+        if let Err(erro) = fidelidade.registra_status(&pedido).await {
+            tracing::error!(pedido_id = *pedido.id(), erro = ?erro, "Erro ao registrar os pontos do pedido");
+        }
+        Ok(pedido)
     }
 }
 
@@ -36,11 +55,23 @@ mod tests {
     use super::*;
     use tokio;
     use crate::entities::pedido::Pedido;
+    use crate::entities::{
+        cpf::Cpf, ingredientes::Ingredientes, pontos::RegrasDeFidelidade, produto::Produto,
+    };
+    use crate::gateways::in_memory_pontos_gateway::InMemoryPontosRepository;
     use crate::traits::pedido_gateway::MockPedidoGateway;
-    use tokio::sync::Mutex;
+    use crate::traits::pontos_gateway::MockPontosGateway;
     use std::sync::Arc;
     use mockall::predicate::*;
 
+    fn sem_pontos() -> ProgramaDeFidelidadeUseCase {
+        ProgramaDeFidelidadeUseCase::new(
+            Arc::new(MockPontosGateway::new()),
+            Arc::new(MockPedidoGateway::new()),
+            RegrasDeFidelidade::new(1.0, 0.05, 365),
+        )
+    }
+
     #[tokio::test]
     async fn test_get_pedidos_novos() {
         let mut mock = MockPedidoGateway::new();
@@ -61,10 +92,11 @@ mod tests {
 
         mock.expect_get_pedidos_novos()
             .times(1)
-            .returning(move || Ok(vec![returned_pedido.clone()]));
+            .with(eq(1))
+            .returning(move |_| Ok(vec![returned_pedido.clone()]));
 
-        let use_case = PreparacaoeEntregaUseCase::new(Arc::new(Mutex::new(mock)));
-        let result = use_case.get_pedidos_novos().await;
+        let use_case = PreparacaoeEntregaUseCase::new(Arc::new(mock));
+        let result = use_case.get_pedidos_novos(1).await;
         assert_eq!(result.unwrap()[0].id(), expected_pedido.id());
     }
 
@@ -84,13 +116,150 @@ mod tests {
             "2021-10-10".to_string()
         );
 
+        let pedido_atual = returned_pedido.clone();
+        mock.expect_get_pedido_da_loja()
+            .times(2)
+            .returning(move |_, loja_id| match loja_id {
+                1 => Ok(pedido_atual.clone()),
+                _ => Err(DomainError::NotFound),
+            });
         mock.expect_atualiza_status()
             .times(1)
-            .with(eq(1), eq(Status::EmPreparacao))
-            .returning(move |_, _| Ok(returned_pedido.clone()));
+            .with(eq(1), eq(Status::EmPreparacao), eq(1))
+            .returning(move |_, _, _| Ok(returned_pedido.clone()));
 
-        let use_case = PreparacaoeEntregaUseCase::new(Arc::new(Mutex::new(mock)));
-        let result = use_case.atualiza_status(1, Status::EmPreparacao).await;
+        let use_case = PreparacaoeEntregaUseCase::new(Arc::new(mock));
+        let result = use_case.atualiza_status(1, Status::EmPreparacao, 1, None, &sem_pontos()).await;
         assert_eq!(result.unwrap().status().to_owned(), Status::EmPreparacao);
+
+        let result = use_case.atualiza_status(1, Status::Pronto, 2, None, &sem_pontos()).await;
+        assert!(matches!(result, Err(DomainError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_atualiza_status_com_versao_desatualizada() {
+        let mut mock = MockPedidoGateway::new();
+
+        let mut pedido_atual = Pedido::new(
+            1,
+            None,
+            None,
+            None,
+            None,
+            Some("id_pagamento".to_string()),
+            Status::EmPreparacao,
+            "2021-10-10".to_string(),
+            "2021-10-10".to_string()
+        );
+        pedido_atual.set_versao(3);
+        mock.expect_get_pedido_da_loja()
+            .times(1)
+            .returning(move |_, _| Ok(pedido_atual.clone()));
+        mock.expect_atualiza_status().never();
+
+        let use_case = PreparacaoeEntregaUseCase::new(Arc::new(mock));
+        let result = use_case.atualiza_status(1, Status::Pronto, 1, Some(2), &sem_pontos()).await;
+        assert!(matches!(result, Err(DomainError::Conflict)));
+    }
+
+    #[tokio::test]
+    async fn test_pedido_finalizado_rende_pontos() {
+        let cliente = Cpf::new("123.456.789-09".to_string()).unwrap();
+        let mut mock = MockPedidoGateway::new();
+        let lanche = Produto::new(
+            1,
+            "Cheeseburger".to_string(),
+            "cheeseburger.png".to_string(),
+            "O clássico pão, carne e queijo!".to_string(),
+            1,
+            10.0,
+            Ingredientes::new(vec!["Pão".to_string()]).unwrap(),
+            "2021-10-10".to_string(),
+            "2021-10-10".to_string(),
+        );
+        let pedido = Pedido::new(
+            1,
+            Some(cliente.clone()),
+            Some(lanche),
+            None,
+            None,
+            Some("id_pagamento".to_string()),
+            Status::Pronto,
+            "2021-10-10".to_string(),
+            "2021-10-10".to_string()
+        );
+        let pedido_atual = pedido.clone();
+        mock.expect_get_pedido_da_loja()
+            .returning(move |_, _| Ok(pedido_atual.clone()));
+        mock.expect_atualiza_status()
+            .returning(move |_, status, _| {
+                let mut pedido = pedido.clone();
+                pedido.set_status(status);
+                Ok(pedido)
+            });
+
+        let pontos_repository = Arc::new(InMemoryPontosRepository::new());
+        // 2 pontos por real: o pedido de R$ 10,00 rende 20.
+        let fidelidade = ProgramaDeFidelidadeUseCase::new(
+            pontos_repository.clone(),
+            Arc::new(MockPedidoGateway::new()),
+            RegrasDeFidelidade::new(2.0, 0.05, 365),
+        );
+        let use_case = PreparacaoeEntregaUseCase::new(Arc::new(mock));
+        use_case.atualiza_status(1, Status::Finalizado, 1, None, &fidelidade).await.unwrap();
+
+        let saldo = fidelidade.saldo("12345678909".to_string(), &cliente).await.unwrap();
+        assert_eq!(saldo.saldo, 20);
+    }
+
+    #[tokio::test]
+    async fn test_falha_nos_pontos_nao_desfaz_o_status() {
+        let lanche = Produto::new(
+            1,
+            "Cheeseburger".to_string(),
+            "cheeseburger.png".to_string(),
+            "O clássico pão, carne e queijo!".to_string(),
+            1,
+            10.0,
+            Ingredientes::new(vec!["Pão".to_string()]).unwrap(),
+            "2021-10-10".to_string(),
+            "2021-10-10".to_string(),
+        );
+        let pedido = Pedido::new(
+            1,
+            Some(Cpf::new("123.456.789-09".to_string()).unwrap()),
+            Some(lanche),
+            None,
+            None,
+            Some("id_pagamento".to_string()),
+            Status::Pronto,
+            "2021-10-10".to_string(),
+            "2021-10-10".to_string()
+        );
+        let mut mock = MockPedidoGateway::new();
+        let pedido_atual = pedido.clone();
+        mock.expect_get_pedido_da_loja()
+            .returning(move |_, _| Ok(pedido_atual.clone()));
+        mock.expect_atualiza_status()
+            .times(1)
+            .returning(move |_, status, _| {
+                let mut pedido = pedido.clone();
+                pedido.set_status(status);
+                Ok(pedido)
+            });
+        let mut mock_pontos = MockPontosGateway::new();
+        mock_pontos
+            .expect_registra_movimento()
+            .times(1)
+            .returning(|_| Err(DomainError::Database("fora do ar".to_string())));
+        let fidelidade = ProgramaDeFidelidadeUseCase::new(
+            Arc::new(mock_pontos),
+            Arc::new(MockPedidoGateway::new()),
+            RegrasDeFidelidade::new(1.0, 0.05, 365),
+        );
+
+        let use_case = PreparacaoeEntregaUseCase::new(Arc::new(mock));
+        let result = use_case.atualiza_status(1, Status::Finalizado, 1, None, &fidelidade).await;
+        assert_eq!(result.unwrap().status().to_owned(), Status::Finalizado);
     }
 }
@@ -0,0 +1,384 @@
+use chrono::Utc;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::base::domain_error::DomainError;
+use crate::base::paginacao::{Pagina, Paginacao};
+use crate::entities::{
+    cpf::Cpf,
+    pedido::{Pedido, Status},
+    pontos::{MovimentoDePontos, RegrasDeFidelidade, SaldoDePontos, TipoDeMovimento},
+};
+use crate::traits::{pedido_gateway::PedidoGateway, pontos_gateway::PontosGateway};
+
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
+pub struct ResgateInput {
+    pub pedido_id: usize,
+    pub pontos: i64,
+}
+
+#[derive(Clone)]
+pub struct ProgramaDeFidelidadeUseCase {
+    pontos_repository: Arc<dyn PontosGateway + Sync + Send>,
+    pedido_repository: Arc<dyn PedidoGateway + Sync + Send>,
+    regras: RegrasDeFidelidade,
+}
+
+// Cada cliente só vê e usa os próprios pontos.
+fn cliente_autorizado(cpf: String, cliente_autenticado: &Cpf) -> Result<Cpf, DomainError> {
+    let cpf = Cpf::new(cpf)?;
+    match cpf == *cliente_autenticado {
+        true => Ok(cpf),
+        false => Err(DomainError::Forbidden),
+    }
+}
+
+impl ProgramaDeFidelidadeUseCase {
+    pub fn new(
+        pontos_repository: Arc<dyn PontosGateway + Sync + Send>,
+        pedido_repository: Arc<dyn PedidoGateway + Sync + Send>,
+        regras: RegrasDeFidelidade,
+    ) -> Self {
+        ProgramaDeFidelidadeUseCase {
+            pontos_repository,
+            pedido_repository,
+            regras,
+        }
+    }
+
+    async fn saldo_do_cliente(&self, cpf: &Cpf) -> Result<SaldoDePontos, DomainError> {
+        let movimentos = self.pontos_repository.movimentos_do_cliente(cpf).await?;
+        Ok(SaldoDePontos::calcula(&movimentos, &Utc::now().fixed_offset()))
+    }
+
+    // Um movimento repetido (ex.: o pedido finalizado duas vezes) já foi registrado.
+    async fn registra(&self, movimento: MovimentoDePontos) -> Result<(), DomainError> {
+        match self.pontos_repository.registra_movimento(movimento).await {
+            Ok(_) | Err(DomainError::AlreadyExists) => Ok(()),
+            Err(erro) => Err(erro),
+        }
+    }
+
+    pub async fn saldo(&self, cpf: String, cliente_autenticado: &Cpf) -> Result<SaldoDePontos, DomainError> {
+        let cpf = cliente_autorizado(cpf, cliente_autenticado)?;
+        self.saldo_do_cliente(&cpf).await
+    }
+
+    // Extrato do movimento mais recente ao mais antigo.
+    pub async fn extrato(
+        &self,
+        cpf: String,
+        cliente_autenticado: &Cpf,
+        pagina: Option<usize>,
+        tamanho: Option<usize>,
+    ) -> Result<Pagina<MovimentoDePontos>, DomainError> {
+        let cpf = cliente_autorizado(cpf, cliente_autenticado)?;
+        let paginacao = Paginacao::new(pagina, tamanho)?;
+        let mut movimentos = self.pontos_repository.movimentos_do_cliente(&cpf).await?;
+        movimentos.reverse();
+        Ok(paginacao.aplica(movimentos))
+    }
+
+    // Troca pontos por desconto num pedido pendente do cliente, uma vez por pedido.
+    #[tracing::instrument(skip(self, cpf, cliente_autenticado))]
+    pub async fn resgata(
+        &self,
+        cpf: String,
+        cliente_autenticado: &Cpf,
+        resgate: ResgateInput,
+    ) -> Result<Pedido, DomainError> {
+        let cpf = cliente_autorizado(cpf, cliente_autenticado)?;
+        if resgate.pontos <= 0 {
+            return Err(DomainError::InvalidField {
+                field: "pontos".to_string(),
+                message: "Informe uma quantidade positiva de pontos".to_string(),
+            });
+        }
+
+        let pedido = self.pedido_repository.get_pedido_by_id(resgate.pedido_id).await?;
+        if pedido.cliente() != Some(&cpf) {
+            return Err(DomainError::NotFound);
+        }
+        if *pedido.status() != Status::Pendente {
+            return Err(DomainError::Invalid(
+                "Só pedidos pendentes de pagamento recebem desconto".to_string(),
+            ));
+        }
+        let desconto = self.regras.desconto(resgate.pontos);
+        if desconto > pedido.valor_total() {
+            return Err(DomainError::InvalidField {
+                field: "pontos".to_string(),
+                message: "Desconto maior que o valor do pedido".to_string(),
+            });
+        }
+
+        let debito = self
+            .pontos_repository
+            .registra_resgate(MovimentoDePontos::new(
+                0,
+                cpf.clone(),
+                *pedido.id(),
+                TipoDeMovimento::Resgate,
+                -resgate.pontos,
+                None,
+                String::new(),
+            ))
+            .await?;
+        if debito.is_none() {
+            return Err(DomainError::InvalidField {
+                field: "pontos".to_string(),
+                message: "Saldo de pontos insuficiente".to_string(),
+            });
+        }
+        let desconto = pedido.desconto() + desconto;
+        match self
+            .pedido_repository
+            .aplica_desconto(*pedido.id(), desconto, pedido.versao())
+            .await
+        {
+            Ok(pedido) => {
+                tracing::info!(pedido_id = *pedido.id(), pontos = resgate.pontos, "Pontos resgatados");
+                Ok(pedido)
+            }
+            Err(erro) => {
+                // Devolve os pontos: o desconto não chegou ao pedido.
+                self.estorna(&cpf, *pedido.id(), TipoDeMovimento::EstornoDeResgate, resgate.pontos)
+                    .await?;
+                Err(erro)
+            }
+        }
+    }
+
+    async fn estorna(
+        &self,
+        cliente: &Cpf,
+        pedido_id: usize,
+        tipo: TipoDeMovimento,
+        pontos: i64,
+    ) -> Result<(), DomainError> {
+        // Pontos devolvidos valem como um novo crédito, com validade a partir de agora.
+        let validade = match pontos > 0 {
+            true => Some(self.regras.validade(&Utc::now().fixed_offset())),
+            false => None,
+        };
+        self.registra(MovimentoDePontos::new(
+            0,
+            cliente.clone(),
+            pedido_id,
+            tipo,
+            pontos,
+            validade,
+            String::new(),
+        ))
+        .await
+    }
+
+    // Acompanha a mudança de status de um pedido: o pedido finalizado rende pontos ao
+    // cliente; o cancelado devolve os pontos resgatados e retira os ganhos com ele.
+    #[tracing::instrument(skip_all, fields(pedido_id = *pedido.id(), status = %pedido.status()))]
+    pub async fn registra_status(&self, pedido: &Pedido) -> Result<(), DomainError> {
+        let Some(cliente) = pedido.cliente() else {
+            return Ok(());
+        };
+
+        match pedido.status() {
+            Status::Finalizado => {
+                let pontos = self.regras.pontos_do_pedido(pedido);
+                if pontos <= 0 {
+                    return Ok(());
+                }
+                self.registra(MovimentoDePontos::new(
+                    0,
+                    cliente.clone(),
+                    *pedido.id(),
+                    TipoDeMovimento::Acumulo,
+                    pontos,
+                    Some(self.regras.validade(&Utc::now().fixed_offset())),
+                    String::new(),
+                ))
+                .await
+            }
+            Status::Cancelado => {
+                let movimentos = self.pontos_repository.movimentos_do_pedido(*pedido.id()).await?;
+                for movimento in movimentos {
+                    let estorno = match movimento.tipo() {
+                        TipoDeMovimento::Acumulo => TipoDeMovimento::EstornoDeAcumulo,
+                        TipoDeMovimento::Resgate => TipoDeMovimento::EstornoDeResgate,
+                        _ => continue,
+                    };
+                    self.estorna(cliente, *pedido.id(), estorno, -movimento.pontos())
+                        .await?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{ingredientes::Ingredientes, produto::Produto};
+    use crate::gateways::in_memory_pontos_gateway::InMemoryPontosRepository;
+    use crate::traits::{pedido_gateway::MockPedidoGateway, pontos_gateway::MockPontosGateway};
+
+    fn cliente() -> Cpf {
+        Cpf::new("123.456.789-09".to_string()).unwrap()
+    }
+
+    fn pedido(id: usize, status: Status) -> Pedido {
+        let lanche = Produto::new(
+            1,
+            "Cheeseburger".to_string(),
+            "cheeseburger.png".to_string(),
+            "O clássico pão, carne e queijo!".to_string(),
+            1,
+            25.5,
+            Ingredientes::new(vec!["Pão".to_string()]).unwrap(),
+            "2024-01-17".to_string(),
+            "2024-01-17".to_string(),
+        );
+        Pedido::new(
+            id,
+            Some(cliente()),
+            Some(lanche),
+            None,
+            None,
+            None,
+            status,
+            "2024-01-17".to_string(),
+            "2024-01-17".to_string(),
+        )
+    }
+
+    fn regras() -> RegrasDeFidelidade {
+        RegrasDeFidelidade::new(1.0, 0.1, 365)
+    }
+
+    #[tokio::test]
+    async fn test_pedido_finalizado_rende_pontos_uma_vez() {
+        let use_case = ProgramaDeFidelidadeUseCase::new(
+            Arc::new(InMemoryPontosRepository::new()),
+            Arc::new(MockPedidoGateway::new()),
+            regras(),
+        );
+
+        use_case.registra_status(&pedido(7, Status::Finalizado)).await.unwrap();
+        use_case.registra_status(&pedido(7, Status::Finalizado)).await.unwrap();
+
+        let saldo = use_case.saldo("123.456.789-09".to_string(), &cliente()).await.unwrap();
+        assert_eq!(saldo.saldo, 25);
+
+        let outro_cliente = Cpf::new("097.855.456-60".to_string()).unwrap();
+        let result = use_case.saldo("123.456.789-09".to_string(), &outro_cliente).await;
+        assert!(matches!(result, Err(DomainError::Forbidden)));
+    }
+
+    #[tokio::test]
+    async fn test_resgate_e_estorno_do_pedido_cancelado() {
+        let mut mock_pedido_gateway = MockPedidoGateway::new();
+        mock_pedido_gateway
+            .expect_get_pedido_by_id()
+            .returning(|id| Ok(pedido(id, Status::Pendente)));
+        mock_pedido_gateway
+            .expect_aplica_desconto()
+            .times(1)
+            .withf(|id, desconto, versao| *id == 8 && *desconto == 2.0 && *versao == 1)
+            .returning(|id, desconto, _| {
+                let mut pendente = pedido(id, Status::Pendente);
+                pendente.set_desconto(desconto);
+                Ok(pendente)
+            });
+
+        let use_case = ProgramaDeFidelidadeUseCase::new(
+            Arc::new(InMemoryPontosRepository::new()),
+            Arc::new(mock_pedido_gateway),
+            regras(),
+        );
+        use_case.registra_status(&pedido(7, Status::Finalizado)).await.unwrap();
+
+        let resgate = |pontos| ResgateInput { pedido_id: 8, pontos };
+        let result = use_case.resgata("12345678909".to_string(), &cliente(), resgate(30)).await;
+        assert!(matches!(result, Err(DomainError::InvalidField { ref field, .. }) if field == "pontos"));
+
+        let pedido_com_desconto = use_case
+            .resgata("12345678909".to_string(), &cliente(), resgate(20))
+            .await
+            .unwrap();
+        assert_eq!(pedido_com_desconto.valor_total(), 23.5);
+        let saldo = use_case.saldo("12345678909".to_string(), &cliente()).await.unwrap();
+        assert_eq!(saldo.saldo, 5);
+
+        // O pedido que recebeu o desconto foi cancelado: os 20 pontos voltam.
+        use_case.registra_status(&pedido(8, Status::Cancelado)).await.unwrap();
+        let saldo = use_case.saldo("12345678909".to_string(), &cliente()).await.unwrap();
+        assert_eq!(saldo.saldo, 25);
+
+        let extrato = use_case
+            .extrato("12345678909".to_string(), &cliente(), None, None)
+            .await
+            .unwrap();
+        assert_eq!(extrato.total(), 3);
+        assert_eq!(extrato.itens()[0].tipo(), &TipoDeMovimento::EstornoDeResgate);
+    }
+
+    #[tokio::test]
+    async fn test_resgates_simultaneos_nao_gastam_os_mesmos_pontos() {
+        let mut mock_pedido_gateway = MockPedidoGateway::new();
+        mock_pedido_gateway
+            .expect_get_pedido_by_id()
+            .returning(|id| Ok(pedido(id, Status::Pendente)));
+        mock_pedido_gateway
+            .expect_aplica_desconto()
+            .times(1)
+            .returning(|id, desconto, _| {
+                let mut pendente = pedido(id, Status::Pendente);
+                pendente.set_desconto(desconto);
+                Ok(pendente)
+            });
+
+        let use_case = ProgramaDeFidelidadeUseCase::new(
+            Arc::new(InMemoryPontosRepository::new()),
+            Arc::new(mock_pedido_gateway),
+            regras(),
+        );
+        use_case.registra_status(&pedido(7, Status::Finalizado)).await.unwrap();
+
+        // 25 pontos de saldo, 20 pedidos em cada resgate: só um deles cabe.
+        let cliente = cliente();
+        let (primeiro, segundo) = tokio::join!(
+            use_case.resgata("12345678909".to_string(), &cliente, ResgateInput { pedido_id: 8, pontos: 20 }),
+            use_case.resgata("12345678909".to_string(), &cliente, ResgateInput { pedido_id: 9, pontos: 20 }),
+        );
+        assert!(primeiro.is_ok() != segundo.is_ok());
+        let saldo = use_case.saldo("12345678909".to_string(), &cliente).await.unwrap();
+        assert_eq!(saldo.saldo, 5);
+    }
+
+    #[tokio::test]
+    async fn test_pedido_anonimo_nao_rende_pontos() {
+        let mut mock_pontos_gateway = MockPontosGateway::new();
+        mock_pontos_gateway.expect_registra_movimento().never();
+
+        let use_case = ProgramaDeFidelidadeUseCase::new(
+            Arc::new(mock_pontos_gateway),
+            Arc::new(MockPedidoGateway::new()),
+            regras(),
+        );
+        let anonimo = Pedido::new(
+            1,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Status::Finalizado,
+            "2024-01-17".to_string(),
+            "2024-01-17".to_string(),
+        );
+        assert!(use_case.registra_status(&anonimo).await.is_ok());
+    }
+}
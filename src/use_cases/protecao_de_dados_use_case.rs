@@ -0,0 +1,268 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::base::domain_error::DomainError;
+use crate::base::pseudonimo::Pseudonimizador;
+use crate::entities::{
+    auditoria::{AcaoDeAuditoria, RegistroDeAuditoria},
+    cliente::Cliente,
+    cpf::Cpf,
+    pedido::Pedido,
+    pontos::MovimentoDePontos,
+};
+use crate::traits::{
+    auditoria_gateway::AuditoriaGateway, cliente_gateway::ClienteGateway,
+    pedido_gateway::PedidoGateway, pontos_gateway::PontosGateway,
+};
+
+// Tudo o que está ligado a um CPF: cadastro (se houver), pedidos e extrato de pontos.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct DadosDoTitular {
+    pub cpf: Cpf,
+    pub cliente: Option<Cliente>,
+    pub pedidos: Vec<Pedido>,
+    pub movimentos_de_pontos: Vec<MovimentoDePontos>,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct Anonimizacao {
+    // Pseudônimo que passou a identificar os pedidos do titular.
+    pub titular: String,
+    pub pedidos_anonimizados: usize,
+    pub movimentos_de_pontos_removidos: usize,
+    pub cadastro_removido: bool,
+}
+
+#[derive(Clone)]
+pub struct ProtecaoDeDadosUseCase {
+    cliente_repository: Arc<dyn ClienteGateway + Sync + Send>,
+    pedido_repository: Arc<dyn PedidoGateway + Sync + Send>,
+    pontos_repository: Arc<dyn PontosGateway + Sync + Send>,
+    auditoria_repository: Arc<dyn AuditoriaGateway + Sync + Send>,
+    pseudonimizador: Pseudonimizador,
+}
+
+impl ProtecaoDeDadosUseCase {
+    pub fn new(
+        cliente_repository: Arc<dyn ClienteGateway + Sync + Send>,
+        pedido_repository: Arc<dyn PedidoGateway + Sync + Send>,
+        pontos_repository: Arc<dyn PontosGateway + Sync + Send>,
+        auditoria_repository: Arc<dyn AuditoriaGateway + Sync + Send>,
+        pseudonimizador: Pseudonimizador,
+    ) -> Self {
+        ProtecaoDeDadosUseCase {
+            cliente_repository,
+            pedido_repository,
+            pontos_repository,
+            auditoria_repository,
+            pseudonimizador,
+        }
+    }
+
+    // A operação só acontece depois de registrada: sem auditoria, nada é exportado nem apagado.
+    async fn audita(
+        &self,
+        acao: AcaoDeAuditoria,
+        cpf: &Cpf,
+        request_id: String,
+    ) -> Result<String, DomainError> {
+        let titular = self.pseudonimizador.pseudonimo(cpf);
+        self.auditoria_repository
+            .registra(RegistroDeAuditoria::new(0, acao, titular.clone(), request_id, String::new()))
+            .await?;
+        Ok(titular)
+    }
+
+    pub async fn exporta_dados(&self, cpf: String, request_id: String) -> Result<DadosDoTitular, DomainError> {
+        let cpf = Cpf::new(cpf)?;
+        self.audita(AcaoDeAuditoria::ExportacaoDeDados, &cpf, request_id).await?;
+
+        let cliente = match self.cliente_repository.get_cliente_by_cpf(&cpf).await {
+            Ok(cliente) => Some(cliente),
+            Err(DomainError::NotFound) => None,
+            Err(erro) => return Err(erro),
+        };
+
+        // Também os pedidos em que o CPF só aparece na nota fiscal.
+        let pedidos = self.pedido_repository.pedidos_do_titular(&cpf).await?;
+
+        let movimentos_de_pontos = self.pontos_repository.movimentos_do_cliente(&cpf).await?;
+
+        Ok(DadosDoTitular {
+            cpf,
+            cliente,
+            pedidos,
+            movimentos_de_pontos,
+        })
+    }
+
+    // Os pedidos continuam valendo para os relatórios de vendas, só que sem o CPF; cadastro e
+    // extrato de pontos são apagados. Cada passo pode ser repetido, então uma anonimização
+    // interrompida termina ao ser pedida de novo.
+    pub async fn anonimiza(&self, cpf: String, request_id: String) -> Result<Anonimizacao, DomainError> {
+        let cpf = Cpf::new(cpf)?;
+        let titular = self.audita(AcaoDeAuditoria::AnonimizacaoDeDados, &cpf, request_id).await?;
+
+        let pedidos_anonimizados = self
+            .pedido_repository
+            .anonimiza_pedidos_do_cliente(&cpf, &titular)
+            .await?;
+        let movimentos_de_pontos_removidos = self
+            .pontos_repository
+            .remove_movimentos_do_cliente(&cpf)
+            .await?;
+        let cadastro_removido = match self.cliente_repository.delete_cliente(&cpf).await {
+            Ok(()) => true,
+            Err(DomainError::NotFound) => false,
+            Err(erro) => return Err(erro),
+        };
+
+        tracing::info!(
+            titular = %titular,
+            pedidos_anonimizados,
+            movimentos_de_pontos_removidos,
+            cadastro_removido,
+            "Dados do titular anonimizados"
+        );
+        Ok(Anonimizacao {
+            titular,
+            pedidos_anonimizados,
+            movimentos_de_pontos_removidos,
+            cadastro_removido,
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{documento_fiscal::DocumentoFiscal, pedido::Status};
+    use crate::traits::{
+        auditoria_gateway::MockAuditoriaGateway, cliente_gateway::MockClienteGateway,
+        pedido_gateway::MockPedidoGateway, pontos_gateway::MockPontosGateway,
+    };
+
+    const CPF: &str = "123.456.789-09";
+
+    fn pseudonimizador() -> Pseudonimizador {
+        Pseudonimizador::new("chave".to_string())
+    }
+
+    fn auditoria(acao: AcaoDeAuditoria) -> MockAuditoriaGateway {
+        let titular = pseudonimizador().pseudonimo(&Cpf::new(CPF.to_string()).unwrap());
+        let mut auditoria = MockAuditoriaGateway::new();
+        auditoria
+            .expect_registra()
+            .times(1)
+            .withf(move |registro| {
+                *registro.acao() == acao && *registro.titular() == titular && registro.request_id() == "req-1"
+            })
+            .returning(Ok);
+        auditoria
+    }
+
+    #[tokio::test]
+    async fn test_exporta_dados() {
+        let mut cliente_repository = MockClienteGateway::new();
+        cliente_repository
+            .expect_get_cliente_by_cpf()
+            .returning(|_| Err(DomainError::NotFound));
+        let mut pedido_repository = MockPedidoGateway::new();
+        pedido_repository
+            .expect_pedidos_do_titular()
+            .times(1)
+            .withf(|cpf| cpf.get_string() == "12345678909")
+            .returning(|cpf| {
+                let mut na_nota = Pedido::new(
+                    1,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    Status::Finalizado,
+                    "2024-01-17".to_string(),
+                    "2024-01-17".to_string(),
+                );
+                na_nota.set_documento_fiscal(DocumentoFiscal::new(cpf.get_string()).ok());
+                Ok(vec![na_nota])
+            });
+        let mut pontos_repository = MockPontosGateway::new();
+        pontos_repository
+            .expect_movimentos_do_cliente()
+            .returning(|_| Ok(vec![]));
+
+        let use_case = ProtecaoDeDadosUseCase::new(
+            Arc::new(cliente_repository),
+            Arc::new(pedido_repository),
+            Arc::new(pontos_repository),
+            Arc::new(auditoria(AcaoDeAuditoria::ExportacaoDeDados)),
+            pseudonimizador(),
+        );
+
+        let dados = use_case
+            .exporta_dados(CPF.to_string(), "req-1".to_string())
+            .await
+            .unwrap();
+        assert_eq!(dados.cpf.get_string(), "12345678909");
+        assert!(dados.cliente.is_none());
+        assert_eq!(dados.pedidos.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_anonimiza() {
+        let titular = pseudonimizador().pseudonimo(&Cpf::new(CPF.to_string()).unwrap());
+        let mut pedido_repository = MockPedidoGateway::new();
+        pedido_repository
+            .expect_anonimiza_pedidos_do_cliente()
+            .times(1)
+            .withf(move |cpf, pseudonimo| cpf.get_string() == "12345678909" && pseudonimo == titular)
+            .returning(|_, _| Ok(3));
+        let mut pontos_repository = MockPontosGateway::new();
+        pontos_repository
+            .expect_remove_movimentos_do_cliente()
+            .times(1)
+            .returning(|_| Ok(2));
+        let mut cliente_repository = MockClienteGateway::new();
+        cliente_repository
+            .expect_delete_cliente()
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let use_case = ProtecaoDeDadosUseCase::new(
+            Arc::new(cliente_repository),
+            Arc::new(pedido_repository),
+            Arc::new(pontos_repository),
+            Arc::new(auditoria(AcaoDeAuditoria::AnonimizacaoDeDados)),
+            pseudonimizador(),
+        );
+
+        let anonimizacao = use_case.anonimiza(CPF.to_string(), "req-1".to_string()).await.unwrap();
+        assert!(anonimizacao.titular.starts_with("anon_"));
+        assert_eq!(anonimizacao.pedidos_anonimizados, 3);
+        assert_eq!(anonimizacao.movimentos_de_pontos_removidos, 2);
+        assert!(anonimizacao.cadastro_removido);
+    }
+
+    #[tokio::test]
+    async fn test_sem_auditoria_nao_anonimiza() {
+        let mut auditoria = MockAuditoriaGateway::new();
+        auditoria
+            .expect_registra()
+            .returning(|_| Err(DomainError::Database("indisponível".to_string())));
+        let mut pedido_repository = MockPedidoGateway::new();
+        pedido_repository.expect_anonimiza_pedidos_do_cliente().times(0);
+
+        let use_case = ProtecaoDeDadosUseCase::new(
+            Arc::new(MockClienteGateway::new()),
+            Arc::new(pedido_repository),
+            Arc::new(MockPontosGateway::new()),
+            Arc::new(auditoria),
+            pseudonimizador(),
+        );
+
+        assert!(use_case.anonimiza(CPF.to_string(), "req-1".to_string()).await.is_err());
+    }
+}
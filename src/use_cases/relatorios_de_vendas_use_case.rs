@@ -0,0 +1,603 @@
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::base::domain_error::DomainError;
+use crate::entities::pedido::{Pedido, Status};
+use crate::entities::produto::Produto;
+use crate::traits::{categoria_gateway::CategoriaGateway, pedido_gateway::PedidoGateway};
+
+// Um relatório cobre no máximo um ano, para não carregar pedidos demais de uma vez.
+pub const DIAS_MAXIMOS_DO_RELATORIO: i64 = 366;
+
+// Sem fuso informado, as datas dos relatórios são as de Brasília.
+pub const FUSO_PADRAO: Tz = chrono_tz::America::Sao_Paulo;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, JsonSchema)]
+pub enum Agrupamento {
+    Dia,
+    Semana,
+    Mes,
+}
+
+impl FromStr for Agrupamento {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dia" => Ok(Agrupamento::Dia),
+            "semana" => Ok(Agrupamento::Semana),
+            "mes" | "mês" => Ok(Agrupamento::Mes),
+            _ => Err(DomainError::InvalidField {
+                field: "agrupamento".to_string(),
+                message: format!("Agrupamento inválido: {}", s),
+            }),
+        }
+    }
+}
+
+impl Agrupamento {
+    // Primeiro dia do período que contém a data; a semana começa na segunda-feira.
+    fn inicio_do_periodo(&self, data: NaiveDate) -> NaiveDate {
+        match self {
+            Agrupamento::Dia => data,
+            Agrupamento::Semana => data - Duration::days(data.weekday().num_days_from_monday() as i64),
+            Agrupamento::Mes => data.with_day(1).unwrap(),
+        }
+    }
+
+    fn proximo_periodo(&self, inicio: NaiveDate) -> NaiveDate {
+        match self {
+            Agrupamento::Dia => inicio + Duration::days(1),
+            Agrupamento::Semana => inicio + Duration::days(7),
+            Agrupamento::Mes => inicio + Months::new(1),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FormatoDeRelatorio {
+    Csv,
+    Json,
+}
+
+impl FromStr for FormatoDeRelatorio {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(FormatoDeRelatorio::Csv),
+            "json" => Ok(FormatoDeRelatorio::Json),
+            _ => Err(DomainError::InvalidField {
+                field: "formato".to_string(),
+                message: format!("Formato de relatório inválido: {}", s),
+            }),
+        }
+    }
+}
+
+// Intervalo de datas do relatório, com início e fim inclusivos, no fuso horário informado:
+// um pedido das 23h de um dia em São Paulo já é do dia seguinte em UTC.
+#[derive(Clone, Debug)]
+pub struct PeriodoDoRelatorio {
+    inicio: NaiveDate,
+    fim: NaiveDate,
+    fuso: Tz,
+}
+
+impl PeriodoDoRelatorio {
+    pub fn new(inicio: NaiveDate, fim: NaiveDate, fuso: Tz) -> Result<Self, DomainError> {
+        if fim < inicio {
+            return Err(DomainError::InvalidField {
+                field: "fim".to_string(),
+                message: "A data final é anterior à inicial".to_string(),
+            });
+        }
+        if (fim - inicio).num_days() >= DIAS_MAXIMOS_DO_RELATORIO {
+            return Err(DomainError::InvalidField {
+                field: "fim".to_string(),
+                message: format!("O período tem no máximo {} dias", DIAS_MAXIMOS_DO_RELATORIO),
+            });
+        }
+        Ok(PeriodoDoRelatorio { inicio, fim, fuso })
+    }
+
+    pub fn inicio(&self) -> NaiveDate {
+        self.inicio
+    }
+
+    pub fn fim(&self) -> NaiveDate {
+        self.fim
+    }
+
+    pub fn fuso(&self) -> Tz {
+        self.fuso
+    }
+
+    // Instantes de início (inclusivo) e de fim (exclusivo) do período.
+    pub fn intervalo(&self) -> (DateTime<Utc>, DateTime<Utc>) {
+        (
+            self.comeco_do_dia(self.inicio),
+            self.comeco_do_dia(self.fim + Duration::days(1)),
+        )
+    }
+
+    // Instante em que o dia começa no fuso. Onde o horário de verão pula a meia-noite, o dia
+    // começa no primeiro horário que existe.
+    fn comeco_do_dia(&self, data: NaiveDate) -> DateTime<Utc> {
+        let meia_noite = data.and_hms_opt(0, 0, 0).unwrap();
+        (0..=48)
+            .find_map(|meias_horas| {
+                self.fuso
+                    .from_local_datetime(&(meia_noite + Duration::minutes(30 * meias_horas)))
+                    .earliest()
+            })
+            .expect("nenhum fuso pula mais de um dia")
+            .with_timezone(&Utc)
+    }
+
+    fn data_local(&self, momento: &DateTime<Utc>) -> NaiveDate {
+        momento.with_timezone(&self.fuso).date_naive()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema, PartialEq)]
+pub struct ResumoDeVendas {
+    // Primeiro dia do período (AAAA-MM-DD), ou "total".
+    pub periodo: String,
+    // Pedidos pagos que não foram cancelados.
+    pub pedidos: usize,
+    // Valor pago pelos pedidos, já sem os descontos do programa de fidelidade.
+    pub receita: f64,
+    pub descontos: f64,
+    pub ticket_medio: f64,
+    pub cancelados: usize,
+    // Cancelados sobre pedidos pagos mais cancelados, de 0 a 1.
+    pub taxa_de_cancelamento: f64,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct VendasPorPeriodo {
+    pub inicio: String,
+    pub fim: String,
+    pub fuso: String,
+    pub agrupamento: Agrupamento,
+    pub total: ResumoDeVendas,
+    pub periodos: Vec<ResumoDeVendas>,
+}
+
+// A receita por categoria e por produto soma os preços dos itens: o desconto vale para o
+// pedido inteiro e não é rateado entre eles.
+#[derive(Clone, Debug, Serialize, JsonSchema, PartialEq)]
+pub struct VendasDaCategoria {
+    pub categoria_id: usize,
+    pub nome: String,
+    pub itens: usize,
+    pub receita: f64,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema, PartialEq)]
+pub struct VendasDoProduto {
+    pub produto_id: usize,
+    pub nome: String,
+    pub categoria_id: usize,
+    pub quantidade: usize,
+    pub receita: f64,
+}
+
+fn centavos(valor: f64) -> f64 {
+    (valor * 100.0).round() / 100.0
+}
+
+// Pedidos pendentes ainda não foram pagos e não contam como venda nem como cancelamento.
+fn vendido(pedido: &Pedido) -> bool {
+    matches!(
+        pedido.status(),
+        Status::Pago | Status::EmPreparacao | Status::Pronto | Status::Finalizado
+    )
+}
+
+fn criacao(pedido: &Pedido) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_str(pedido.data_criacao(), "%Y-%m-%d %H:%M:%S%.3f%z")
+        .ok()
+        .map(|criacao| criacao.with_timezone(&Utc))
+}
+
+fn itens_vendidos(pedidos: &[Pedido]) -> impl Iterator<Item = &Produto> {
+    pedidos
+        .iter()
+        .filter(|pedido| vendido(pedido))
+        .flat_map(|pedido| [pedido.lanche(), pedido.acompanhamento(), pedido.bebida()])
+        .flatten()
+}
+
+#[derive(Default)]
+struct Acumulado {
+    pedidos: usize,
+    cancelados: usize,
+    receita: f64,
+    descontos: f64,
+}
+
+impl Acumulado {
+    fn soma(&mut self, pedido: &Pedido) {
+        if vendido(pedido) {
+            self.pedidos += 1;
+            self.receita += pedido.valor_total();
+            self.descontos += pedido.subtotal() - pedido.valor_total();
+        } else if *pedido.status() == Status::Cancelado {
+            self.cancelados += 1;
+        }
+    }
+
+    fn resumo(&self, periodo: String) -> ResumoDeVendas {
+        let ticket_medio = match self.pedidos {
+            0 => 0.0,
+            pedidos => self.receita / pedidos as f64,
+        };
+        let taxa_de_cancelamento = match self.pedidos + self.cancelados {
+            0 => 0.0,
+            total => ((self.cancelados as f64 / total as f64) * 10000.0).round() / 10000.0,
+        };
+        ResumoDeVendas {
+            periodo,
+            pedidos: self.pedidos,
+            receita: centavos(self.receita),
+            descontos: centavos(self.descontos),
+            ticket_medio: centavos(ticket_medio),
+            cancelados: self.cancelados,
+            taxa_de_cancelamento,
+        }
+    }
+}
+
+pub fn para_csv<T: Serialize>(linhas: &[T]) -> Result<String, DomainError> {
+    let mut escritor = csv::Writer::from_writer(vec![]);
+    for linha in linhas {
+        escritor
+            .serialize(linha)
+            .map_err(|erro| DomainError::Invalid(erro.to_string()))?;
+    }
+    let conteudo = escritor
+        .into_inner()
+        .map_err(|erro| DomainError::Invalid(erro.to_string()))?;
+    String::from_utf8(conteudo).map_err(|erro| DomainError::Invalid(erro.to_string()))
+}
+
+#[derive(Clone)]
+pub struct RelatoriosDeVendasUseCase {
+    pedido_repository: Arc<dyn PedidoGateway + Sync + Send>,
+    categoria_repository: Arc<dyn CategoriaGateway + Sync + Send>,
+}
+
+impl RelatoriosDeVendasUseCase {
+    pub fn new(
+        pedido_repository: Arc<dyn PedidoGateway + Sync + Send>,
+        categoria_repository: Arc<dyn CategoriaGateway + Sync + Send>,
+    ) -> Self {
+        RelatoriosDeVendasUseCase {
+            pedido_repository,
+            categoria_repository,
+        }
+    }
+
+    async fn pedidos(&self, loja_id: usize, periodo: &PeriodoDoRelatorio) -> Result<Vec<Pedido>, DomainError> {
+        let (inicio, fim) = periodo.intervalo();
+        self.pedido_repository
+            .pedidos_do_periodo(loja_id, inicio, fim)
+            .await
+    }
+
+    // Todos os períodos do intervalo aparecem, mesmo sem vendas. O primeiro e o último podem
+    // começar antes ou terminar depois do intervalo, mas só contam os pedidos de dentro dele.
+    pub async fn vendas_por_periodo(
+        &self,
+        loja_id: usize,
+        periodo: PeriodoDoRelatorio,
+        agrupamento: Agrupamento,
+    ) -> Result<VendasPorPeriodo, DomainError> {
+        let pedidos = self.pedidos(loja_id, &periodo).await?;
+
+        let mut periodos: Vec<(NaiveDate, Acumulado)> = Vec::new();
+        let mut inicio = agrupamento.inicio_do_periodo(periodo.inicio);
+        while inicio <= periodo.fim {
+            periodos.push((inicio, Acumulado::default()));
+            inicio = agrupamento.proximo_periodo(inicio);
+        }
+
+        let mut total = Acumulado::default();
+        for pedido in pedidos.iter() {
+            let Some(criacao) = criacao(pedido) else {
+                continue;
+            };
+            let inicio = agrupamento.inicio_do_periodo(periodo.data_local(&criacao));
+            if let Some((_, acumulado)) = periodos.iter_mut().find(|(data, _)| *data == inicio) {
+                acumulado.soma(pedido);
+            }
+            total.soma(pedido);
+        }
+
+        Ok(VendasPorPeriodo {
+            inicio: periodo.inicio.to_string(),
+            fim: periodo.fim.to_string(),
+            fuso: periodo.fuso.name().to_string(),
+            agrupamento,
+            total: total.resumo("total".to_string()),
+            periodos: periodos
+                .iter()
+                .map(|(inicio, acumulado)| acumulado.resumo(inicio.to_string()))
+                .collect(),
+        })
+    }
+
+    // Da categoria com maior receita para a menor.
+    pub async fn vendas_por_categoria(
+        &self,
+        loja_id: usize,
+        periodo: PeriodoDoRelatorio,
+    ) -> Result<Vec<VendasDaCategoria>, DomainError> {
+        let pedidos = self.pedidos(loja_id, &periodo).await?;
+        let nomes: HashMap<usize, String> = self
+            .categoria_repository
+            .get_categorias()
+            .await?
+            .into_iter()
+            .map(|categoria| (*categoria.id(), categoria.nome().clone()))
+            .collect();
+
+        let mut categorias: Vec<VendasDaCategoria> = Vec::new();
+        for produto in itens_vendidos(&pedidos) {
+            let categoria_id = *produto.categoria_id();
+            let posicao = match categorias.iter().position(|vendas| vendas.categoria_id == categoria_id) {
+                Some(posicao) => posicao,
+                None => {
+                    categorias.push(VendasDaCategoria {
+                        categoria_id,
+                        nome: nomes.get(&categoria_id).cloned().unwrap_or_default(),
+                        itens: 0,
+                        receita: 0.0,
+                    });
+                    categorias.len() - 1
+                }
+            };
+            categorias[posicao].itens += 1;
+            categorias[posicao].receita += produto.preco();
+        }
+
+        for vendas in categorias.iter_mut() {
+            vendas.receita = centavos(vendas.receita);
+        }
+        categorias.sort_by(|a, b| {
+            b.receita
+                .total_cmp(&a.receita)
+                .then(a.categoria_id.cmp(&b.categoria_id))
+        });
+        Ok(categorias)
+    }
+
+    // Dos mais vendidos (em quantidade) para os menos; com `limite`, só os primeiros.
+    pub async fn vendas_por_produto(
+        &self,
+        loja_id: usize,
+        periodo: PeriodoDoRelatorio,
+        limite: Option<usize>,
+    ) -> Result<Vec<VendasDoProduto>, DomainError> {
+        let pedidos = self.pedidos(loja_id, &periodo).await?;
+
+        let mut produtos: Vec<VendasDoProduto> = Vec::new();
+        for produto in itens_vendidos(&pedidos) {
+            let produto_id = *produto.id();
+            let posicao = match produtos.iter().position(|vendas| vendas.produto_id == produto_id) {
+                Some(posicao) => posicao,
+                None => {
+                    produtos.push(VendasDoProduto {
+                        produto_id,
+                        nome: produto.nome().clone(),
+                        categoria_id: *produto.categoria_id(),
+                        quantidade: 0,
+                        receita: 0.0,
+                    });
+                    produtos.len() - 1
+                }
+            };
+            produtos[posicao].quantidade += 1;
+            produtos[posicao].receita += produto.preco();
+        }
+
+        for vendas in produtos.iter_mut() {
+            vendas.receita = centavos(vendas.receita);
+        }
+        produtos.sort_by(|a, b| {
+            b.quantidade
+                .cmp(&a.quantidade)
+                .then(b.receita.total_cmp(&a.receita))
+                .then(a.produto_id.cmp(&b.produto_id))
+        });
+        if let Some(limite) = limite {
+            produtos.truncate(limite);
+        }
+        Ok(produtos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::categoria::{Categoria, DadosDaCategoria};
+    use crate::entities::ingredientes::Ingredientes;
+    use crate::traits::categoria_gateway::MockCategoriaGateway;
+    use crate::traits::pedido_gateway::MockPedidoGateway;
+
+    const SAO_PAULO: Tz = chrono_tz::America::Sao_Paulo;
+
+    fn data(data: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(data, "%Y-%m-%d").unwrap()
+    }
+
+    fn produto(id: usize, categoria_id: usize, preco: f64) -> Produto {
+        Produto::new(
+            id,
+            format!("Produto {}", id),
+            "foto.png".to_string(),
+            "Descrição".to_string(),
+            categoria_id,
+            preco,
+            Ingredientes::new(vec!["Pão".to_string()]).unwrap(),
+            "2024-01-01 00:00:00.000+0000".to_string(),
+            "2024-01-01 00:00:00.000+0000".to_string(),
+        )
+    }
+
+    fn pedido(id: usize, status: Status, criacao: &str, lanche: Produto, bebida: Option<Produto>) -> Pedido {
+        Pedido::new(
+            id,
+            None,
+            Some(lanche),
+            None,
+            bebida,
+            None,
+            status,
+            criacao.to_string(),
+            criacao.to_string(),
+        )
+    }
+
+    // Janeiro de 2024 em São Paulo (UTC-3):
+    // - pedido 1: dia 1 às 10h;
+    // - pedido 2: dia 1 às 23h30, que em UTC já é dia 2;
+    // - pedido 3: dia 8, cancelado;
+    // - pedido 4: dia 8, pendente;
+    // - pedido 5: dia 9, com desconto.
+    fn pedidos() -> Vec<Pedido> {
+        let mut com_desconto = pedido(5, Status::Pronto, "2024-01-09 15:00:00.000+0000", produto(1, 1, 20.0), None);
+        com_desconto.set_desconto(5.0);
+        vec![
+            pedido(1, Status::Finalizado, "2024-01-01 13:00:00.000+0000", produto(1, 1, 20.0), Some(produto(2, 2, 5.0))),
+            pedido(2, Status::Pago, "2024-01-02 02:30:00.000+0000", produto(1, 1, 20.0), None),
+            pedido(3, Status::Cancelado, "2024-01-08 15:00:00.000+0000", produto(3, 1, 30.0), None),
+            pedido(4, Status::Pendente, "2024-01-08 16:00:00.000+0000", produto(3, 1, 30.0), None),
+            com_desconto,
+        ]
+    }
+
+    fn use_case() -> RelatoriosDeVendasUseCase {
+        let mut pedido_gateway = MockPedidoGateway::new();
+        pedido_gateway
+            .expect_pedidos_do_periodo()
+            .returning(|loja_id, inicio, fim| {
+                assert_eq!(loja_id, 1);
+                Ok(pedidos()
+                    .into_iter()
+                    .filter(|pedido| criacao(pedido).is_some_and(|criacao| criacao >= inicio && criacao < fim))
+                    .collect())
+            });
+        let mut categoria_gateway = MockCategoriaGateway::new();
+        categoria_gateway.expect_get_categorias().returning(|| {
+            Ok(vec![Categoria::new(
+                1,
+                DadosDaCategoria {
+                    nome: "Lanches".to_string(),
+                    icone: None,
+                    ordem: 1,
+                    ativa: true,
+                    item_pedido: None,
+                },
+                "2024-01-01 00:00:00.000+0000".to_string(),
+                "2024-01-01 00:00:00.000+0000".to_string(),
+            )])
+        });
+        RelatoriosDeVendasUseCase::new(Arc::new(pedido_gateway), Arc::new(categoria_gateway))
+    }
+
+    #[test]
+    fn test_periodo_do_relatorio() {
+        let periodo = PeriodoDoRelatorio::new(data("2024-01-01"), data("2024-01-31"), SAO_PAULO).unwrap();
+        assert_eq!(
+            periodo.comeco_do_dia(data("2024-01-01")).to_rfc3339(),
+            "2024-01-01T03:00:00+00:00"
+        );
+
+        assert!(matches!(
+            PeriodoDoRelatorio::new(data("2024-01-02"), data("2024-01-01"), SAO_PAULO),
+            Err(DomainError::InvalidField { ref field, .. }) if field == "fim"
+        ));
+        assert!(PeriodoDoRelatorio::new(data("2024-01-01"), data("2025-01-01"), SAO_PAULO).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_vendas_por_dia_no_fuso_da_loja() {
+        let periodo = PeriodoDoRelatorio::new(data("2024-01-01"), data("2024-01-02"), SAO_PAULO).unwrap();
+        let vendas = use_case().vendas_por_periodo(1, periodo, Agrupamento::Dia).await.unwrap();
+
+        assert_eq!(vendas.periodos.len(), 2);
+        assert_eq!(vendas.periodos[0].periodo, "2024-01-01");
+        assert_eq!(vendas.periodos[0].pedidos, 2);
+        assert_eq!(vendas.periodos[0].receita, 45.0);
+        assert_eq!(vendas.periodos[0].ticket_medio, 22.5);
+        assert_eq!(vendas.periodos[1].pedidos, 0);
+
+        // Em UTC o pedido 2 cairia no dia 2.
+        let periodo = PeriodoDoRelatorio::new(data("2024-01-01"), data("2024-01-01"), chrono_tz::UTC).unwrap();
+        let vendas = use_case().vendas_por_periodo(1, periodo, Agrupamento::Dia).await.unwrap();
+        assert_eq!(vendas.total.pedidos, 1);
+    }
+
+    #[tokio::test]
+    async fn test_vendas_por_semana_e_por_mes() {
+        let periodo = PeriodoDoRelatorio::new(data("2024-01-01"), data("2024-01-31"), SAO_PAULO).unwrap();
+        let vendas = use_case()
+            .vendas_por_periodo(1, periodo.clone(), Agrupamento::Semana)
+            .await
+            .unwrap();
+
+        let semanas: Vec<&str> = vendas.periodos.iter().map(|resumo| resumo.periodo.as_str()).collect();
+        assert_eq!(semanas, vec!["2024-01-01", "2024-01-08", "2024-01-15", "2024-01-22", "2024-01-29"]);
+        assert_eq!(vendas.periodos[1].pedidos, 1);
+        assert_eq!(vendas.periodos[1].cancelados, 1);
+        assert_eq!(vendas.periodos[1].descontos, 5.0);
+
+        let vendas = use_case().vendas_por_periodo(1, periodo, Agrupamento::Mes).await.unwrap();
+        assert_eq!(vendas.periodos.len(), 1);
+        assert_eq!(
+            vendas.total,
+            ResumoDeVendas {
+                periodo: "total".to_string(),
+                pedidos: 3,
+                receita: 60.0,
+                descontos: 5.0,
+                ticket_medio: 20.0,
+                cancelados: 1,
+                taxa_de_cancelamento: 0.25,
+            }
+        );
+        assert_eq!(vendas.periodos[0].receita, vendas.total.receita);
+    }
+
+    #[tokio::test]
+    async fn test_vendas_por_categoria_e_por_produto() {
+        let periodo = PeriodoDoRelatorio::new(data("2024-01-01"), data("2024-01-31"), SAO_PAULO).unwrap();
+
+        let categorias = use_case().vendas_por_categoria(1, periodo.clone()).await.unwrap();
+        assert_eq!(
+            categorias,
+            vec![
+                VendasDaCategoria { categoria_id: 1, nome: "Lanches".to_string(), itens: 3, receita: 60.0 },
+                VendasDaCategoria { categoria_id: 2, nome: "".to_string(), itens: 1, receita: 5.0 },
+            ]
+        );
+
+        let produtos = use_case().vendas_por_produto(1, periodo, Some(1)).await.unwrap();
+        assert_eq!(produtos.len(), 1);
+        assert_eq!(produtos[0].produto_id, 1);
+        assert_eq!(produtos[0].quantidade, 3);
+
+        assert_eq!(
+            para_csv(&produtos).unwrap(),
+            "produto_id,nome,categoria_id,quantidade,receita\n1,Produto 1,1,3,60.0\n"
+        );
+    }
+}
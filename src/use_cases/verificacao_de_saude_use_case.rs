@@ -0,0 +1,97 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::traits::dependencia_adapter::DependenciaAdapter;
+
+#[derive(Clone, Debug, Serialize, JsonSchema, PartialEq)]
+pub struct StatusDependencia {
+    pub nome: String,
+    pub pronta: bool,
+    pub latencia_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub erro: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema, PartialEq)]
+pub struct Prontidao {
+    pub pronta: bool,
+    pub dependencias: Vec<StatusDependencia>,
+}
+
+#[derive(Clone)]
+pub struct VerificacaoDeSaudeUseCase {
+    dependencias: Vec<Arc<dyn DependenciaAdapter + Sync + Send>>,
+}
+
+impl VerificacaoDeSaudeUseCase {
+    pub fn new(dependencias: Vec<Arc<dyn DependenciaAdapter + Sync + Send>>) -> Self {
+        VerificacaoDeSaudeUseCase { dependencias }
+    }
+
+    pub async fn verifica_prontidao(&self) -> Prontidao {
+        let mut dependencias = Vec::new();
+        for dependencia in self.dependencias.iter() {
+            let inicio = Instant::now();
+            let resultado = dependencia.verifica().await;
+            dependencias.push(StatusDependencia {
+                nome: dependencia.nome(),
+                pronta: resultado.is_ok(),
+                latencia_ms: inicio.elapsed().as_secs_f64() * 1000.0,
+                erro: resultado.err(),
+            });
+        }
+        Prontidao {
+            pronta: dependencias.iter().all(|dependencia| dependencia.pronta),
+            dependencias,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::dependencia_adapter::MockDependenciaAdapter;
+
+    fn dependencia(nome: &str, resultado: Result<(), String>) -> Arc<dyn DependenciaAdapter + Sync + Send> {
+        let mut mock = MockDependenciaAdapter::new();
+        let nome = nome.to_string();
+        mock.expect_nome().returning(move || nome.clone());
+        mock.expect_verifica().returning(move || resultado.clone());
+        Arc::new(mock)
+    }
+
+    #[tokio::test]
+    async fn test_pronta_quando_todas_as_dependencias_respondem() {
+        let use_case = VerificacaoDeSaudeUseCase::new(vec![
+            dependencia("postgres", Ok(())),
+            dependencia("rabbitmq", Ok(())),
+        ]);
+
+        let prontidao = use_case.verifica_prontidao().await;
+
+        assert!(prontidao.pronta);
+        assert_eq!(prontidao.dependencias.len(), 2);
+        assert_eq!(prontidao.dependencias[0].nome, "postgres");
+        assert_eq!(prontidao.dependencias[0].erro, None);
+    }
+
+    #[tokio::test]
+    async fn test_nao_pronta_quando_uma_dependencia_falha() {
+        let use_case = VerificacaoDeSaudeUseCase::new(vec![
+            dependencia("postgres", Ok(())),
+            dependencia("rabbitmq", Err("Canal não conectado".to_string())),
+        ]);
+
+        let prontidao = use_case.verifica_prontidao().await;
+
+        assert!(!prontidao.pronta);
+        assert!(prontidao.dependencias[0].pronta);
+        assert!(!prontidao.dependencias[1].pronta);
+        assert_eq!(
+            prontidao.dependencias[1].erro,
+            Some("Canal não conectado".to_string())
+        );
+    }
+}